@@ -42,10 +42,12 @@ fn build_views(
     let mut stream_a = Series::line("stream-A").with_kind(SeriesKind::Line(LineStyle {
         color: Color::new(0.2, 0.82, 0.95, 1.0),
         width: 2.0,
+        dash: None,
     }));
     let mut stream_b = Series::line("stream-B").with_kind(SeriesKind::Line(LineStyle {
         color: Color::new(0.95, 0.64, 0.28, 1.0),
         width: 2.0,
+        dash: None,
     }));
 
     for i in 0..1_000 {
@@ -76,6 +78,7 @@ fn build_views(
         SeriesKind::Line(LineStyle {
             color: Color::new(0.45, 0.45, 0.5, 0.8),
             width: 1.0,
+            dash: None,
         }),
     );
 
@@ -114,6 +117,7 @@ fn build_views(
         link_cursor: true,
         link_brush: true,
         link_reset: true,
+        align_y_axis_width: true,
     };
 
     let top = cx.new(|_| {
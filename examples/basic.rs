@@ -25,6 +25,7 @@ fn main() {
                 SeriesKind::Line(LineStyle {
                     color: Color::new(0.2, 0.75, 0.95, 1.0),
                     width: 2.0,
+                    dash: None,
                 }),
             );
 
@@ -0,0 +1,168 @@
+//! Stress test: 10 series x 10M points with a performance HUD.
+//!
+//! The viewport sweeps through pan and zoom levels automatically so the HUD
+//! numbers can be compared between builds. Set `LIVEPLOT_STRESS_POINTS` to
+//! change the number of points per series.
+
+use std::time::Duration;
+
+use gpui::prelude::*;
+use gpui::{
+    AppContext, Application, AsyncWindowContext, Bounds, Timer, WindowBounds, WindowOptions, div,
+    px, rgb, rgba, size,
+};
+
+use gpui_liveplot::testdata::Generator;
+use gpui_liveplot::{
+    AxisConfig, Color, GpuiPlotView, LineStyle, Plot, PlotHandle, PlotViewConfig, Range,
+    SeriesKind, Theme, Viewport,
+};
+
+const SERIES_COUNT: usize = 10;
+const DEFAULT_POINTS_PER_SERIES: usize = 10_000_000;
+
+struct StressDemo {
+    plot: gpui::Entity<GpuiPlotView>,
+}
+
+impl gpui::Render for StressDemo {
+    fn render(
+        &mut self,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<Self>,
+    ) -> impl gpui::IntoElement {
+        let stats = self.plot.read(cx).perf_stats();
+        let lines = [
+            format!("FPS: {:.1}", stats.fps),
+            format!(
+                "frame build: {:.2} ms",
+                stats.frame_build.as_secs_f64() * 1e3
+            ),
+            format!("decimation: {:.2} ms", stats.decimation.as_secs_f64() * 1e3),
+            format!("commands: {}", stats.command_count),
+            format!("segments: {}", stats.segment_count),
+            format!("points: {}", stats.point_count),
+        ];
+
+        div().size_full().relative().child(self.plot.clone()).child(
+            div()
+                .absolute()
+                .top(px(12.0))
+                .left(px(72.0))
+                .p_2()
+                .flex()
+                .flex_col()
+                .bg(rgba(0x000000b0))
+                .text_color(rgb(0xe6e6e6))
+                .text_xs()
+                .children(lines.into_iter().map(|line| div().child(line))),
+        )
+    }
+}
+
+fn points_per_series() -> usize {
+    std::env::var("LIVEPLOT_STRESS_POINTS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_POINTS_PER_SERIES)
+}
+
+fn build_plot(points: usize) -> Plot {
+    let mut plot = Plot::builder()
+        .theme(Theme::dark())
+        .x_axis(AxisConfig::builder().title("Sample").build())
+        .y_axis(AxisConfig::builder().title("Value").build())
+        .build();
+
+    for index in 0..SERIES_COUNT {
+        let hue = index as f32 / SERIES_COUNT as f32;
+        let mut generator = match index % 3 {
+            0 => Generator::sine(1.0, 1.0 / 5_000.0 * (index + 1) as f64),
+            1 => Generator::random_walk(0.01),
+            _ => Generator::square(0.8, 20_000.0),
+        }
+        .with_noise(0.05)
+        .with_offset(index as f64 * 0.3 - 1.5)
+        .with_seed(index as u64);
+        let series = generator.series(
+            format!("series-{index}"),
+            points,
+            SeriesKind::Line(LineStyle {
+                color: Color::new(0.3 + 0.7 * hue, 0.9 - 0.5 * hue, 0.95, 1.0),
+                width: 1.0,
+                dash: None,
+            }),
+        );
+        plot.add_series(&series);
+    }
+    plot
+}
+
+fn sweep_viewport(step: u64, points: usize) -> Viewport {
+    let t = step as f64 * 0.016;
+    let total = points.max(2) as f64;
+    let zoom = 0.5 * (1.0 + (t * 0.35).sin());
+    let span = total * (0.0005 + 0.9995 * zoom * zoom);
+    let center = total * (0.5 + 0.45 * (t * 0.11).sin());
+    Viewport::new(
+        Range::new(center - span * 0.5, center + span * 0.5),
+        Range::new(-4.0, 4.0),
+    )
+}
+
+fn spawn_sweep(
+    window: &mut gpui::Window,
+    cx: &mut gpui::App,
+    demo: gpui::Entity<StressDemo>,
+    handle: PlotHandle,
+    points: usize,
+) {
+    window
+        .spawn(cx, move |cx: &mut AsyncWindowContext| {
+            let mut cx = cx.clone();
+            async move {
+                let mut step = 0_u64;
+                loop {
+                    Timer::after(Duration::from_millis(16)).await;
+                    step += 1;
+                    let viewport = sweep_viewport(step, points);
+                    // The plot view redraws on its own after handle writes.
+                    handle.write(|plot| plot.set_manual_view(viewport));
+
+                    let _ = cx.update(|_, cx| {
+                        demo.update(cx, |_demo, demo_cx| demo_cx.notify());
+                    });
+                }
+            }
+        })
+        .detach();
+}
+
+fn main() {
+    let points = points_per_series();
+    Application::new().run(move |cx| {
+        let options = WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                None,
+                size(px(1_200.0), px(760.0)),
+                cx,
+            ))),
+            ..Default::default()
+        };
+
+        cx.open_window(options, move |window, cx| {
+            let config = PlotViewConfig {
+                show_legend: true,
+                show_hover: true,
+                ..Default::default()
+            };
+            let view = GpuiPlotView::with_config(build_plot(points), config);
+            let handle = view.plot_handle();
+            let plot = cx.new(|_| view);
+            let demo = cx.new(|_| StressDemo { plot: plot.clone() });
+            spawn_sweep(window, cx, demo.clone(), handle, points);
+            demo
+        })
+        .unwrap();
+    });
+}
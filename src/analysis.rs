@@ -0,0 +1,859 @@
+//! Incremental analysis utilities over append-only series.
+//!
+//! Analyzers hold a shared handle to their source series and only scan the
+//! points appended since their previous update. Comparison helpers such as
+//! [`overlay_runs`] produce derived copies instead.
+
+use std::collections::VecDeque;
+
+use crate::geom::Point;
+use crate::interaction::Pin;
+use crate::plot::Plot;
+use crate::render::{Color, Colormap, LineStyle, MarkerStyle};
+use crate::series::{Series, SeriesKind};
+use crate::view::View;
+
+/// Direction of a level crossing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// The signal moved from below the level to at or above it.
+    Rising,
+    /// The signal moved from above the level to at or below it.
+    Falling,
+}
+
+/// Which crossings a [`CrossingDetector`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrossingDirection {
+    /// Report rising edges only.
+    Rising,
+    /// Report falling edges only.
+    Falling,
+    /// Report both edges.
+    #[default]
+    Both,
+}
+
+impl CrossingDirection {
+    fn accepts(self, edge: Edge) -> bool {
+        match self {
+            Self::Rising => edge == Edge::Rising,
+            Self::Falling => edge == Edge::Falling,
+            Self::Both => true,
+        }
+    }
+}
+
+/// A detected level crossing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Crossing {
+    /// Interpolated crossing point in data space.
+    pub point: Point,
+    /// Crossing direction.
+    pub edge: Edge,
+    /// Index of the crossing within the event series.
+    pub event_index: usize,
+}
+
+/// Detect rising and falling crossings of a level on a series.
+///
+/// Each call to [`CrossingDetector::update`] scans only newly appended points
+/// and appends the interpolated crossing points to an event series, which can
+/// be added to a plot to render the crossings as markers.
+#[derive(Debug)]
+pub struct CrossingDetector {
+    source: Series,
+    events: Series,
+    level: f64,
+    direction: CrossingDirection,
+    auto_pin: bool,
+    cursor: usize,
+    trimmed: u64,
+    last: Option<Point>,
+}
+
+impl CrossingDetector {
+    /// Create a detector for `level` crossings on `source`.
+    pub fn new(source: &Series, level: f64, direction: CrossingDirection) -> Self {
+        let events = Series::from_iter_points(
+            format!("{} crossings", source.name()),
+            std::iter::empty(),
+            SeriesKind::Scatter(MarkerStyle::default()),
+        );
+        Self {
+            source: source.share(),
+            events,
+            level,
+            direction,
+            auto_pin: false,
+            cursor: 0,
+            trimmed: source.trimmed_count(),
+            last: None,
+        }
+    }
+
+    /// Set the marker style used by the event series.
+    pub fn with_marker(mut self, marker: MarkerStyle) -> Self {
+        self.events = self.events.with_kind(SeriesKind::Scatter(marker));
+        self
+    }
+
+    /// Pin new crossings when updating through [`CrossingDetector::update_plot`].
+    pub fn with_auto_pin(mut self, auto_pin: bool) -> Self {
+        self.auto_pin = auto_pin;
+        self
+    }
+
+    /// Access the level being monitored.
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// Access the event series holding all detected crossings.
+    pub fn events(&self) -> &Series {
+        &self.events
+    }
+
+    /// Scan newly appended points and return the crossings found.
+    pub fn update(&mut self) -> Vec<Crossing> {
+        let removed = take_trimmed(&self.source, &mut self.trimmed);
+        if removed > 0 && removed >= self.cursor {
+            // The last scanned point is gone, e.g. after `clear()`, so the
+            // next point must not be paired with it.
+            self.last = None;
+        }
+        self.cursor = self.cursor.saturating_sub(removed);
+        let mut found = Vec::new();
+        self.source.with_store(|store| {
+            let points = store.data().points();
+            for point in points.iter().skip(self.cursor) {
+                if !point.x.is_finite() || !point.y.is_finite() {
+                    self.last = None;
+                    continue;
+                }
+                if let Some(previous) = self.last
+                    && let Some(edge) = crossing_edge(previous.y, point.y, self.level)
+                    && self.direction.accepts(edge)
+                {
+                    let t = (self.level - previous.y) / (point.y - previous.y);
+                    let x = previous.x + (point.x - previous.x) * t;
+                    found.push((Point::new(x, self.level), edge));
+                }
+                self.last = Some(*point);
+            }
+            self.cursor = points.len();
+        });
+
+        let start = self.events.with_store(|store| store.data().len());
+        let _ = self
+            .events
+            .extend_points(found.iter().map(|(point, _)| *point));
+        found
+            .into_iter()
+            .enumerate()
+            .map(|(offset, (point, edge))| Crossing {
+                point,
+                edge,
+                event_index: start + offset,
+            })
+            .collect()
+    }
+
+    /// Scan newly appended points and pin the new crossings in `plot`.
+    ///
+    /// Pins are only added when auto-pinning is enabled and the event series
+    /// has been added to `plot`.
+    pub fn update_plot(&mut self, plot: &mut Plot) -> Vec<Crossing> {
+        let crossings = self.update();
+        if !self.auto_pin || crossings.is_empty() {
+            return crossings;
+        }
+        let Some(series_id) = plot
+            .series()
+            .iter()
+            .find(|series| series.shares_data_with(&self.events))
+            .map(Series::id)
+        else {
+            return crossings;
+        };
+        let pins: Vec<Pin> = crossings
+            .iter()
+            .filter_map(|crossing| {
+                Some(Pin {
+                    series_id,
+                    point_id: self.events.point_id(crossing.event_index)?,
+                })
+            })
+            .collect();
+        plot.pins_mut().extend(pins);
+        crossings
+    }
+}
+
+/// Single-shot trigger capture, like an oscilloscope in single mode.
+///
+/// While armed, [`TriggerCapture::update`] scans newly appended points for a
+/// level crossing. Once enough points after the trigger have arrived, a window
+/// around it is copied into a frozen snapshot series and the capture disarms
+/// until [`TriggerCapture::arm`] is called again. Snapshot X values are
+/// relative to the interpolated trigger crossing.
+#[derive(Debug)]
+pub struct TriggerCapture {
+    source: Series,
+    snapshot: Series,
+    level: f64,
+    direction: CrossingDirection,
+    pre_trigger: usize,
+    post_trigger: usize,
+    pause_follow: bool,
+    armed: bool,
+    trigger: Option<(usize, Point)>,
+    captured: Option<Point>,
+    cursor: usize,
+    trimmed: u64,
+    last: Option<Point>,
+}
+
+impl TriggerCapture {
+    /// Create an armed capture of `window` points centered on `level`
+    /// crossings of `source`.
+    pub fn new(source: &Series, level: f64, direction: CrossingDirection, window: usize) -> Self {
+        let snapshot = Series::from_iter_points(
+            format!("{} capture", source.name()),
+            std::iter::empty(),
+            source.kind().clone(),
+        );
+        let pre_trigger = window / 2;
+        Self {
+            source: source.share(),
+            snapshot,
+            level,
+            direction,
+            pre_trigger,
+            post_trigger: window - pre_trigger,
+            pause_follow: false,
+            armed: true,
+            trigger: None,
+            captured: None,
+            cursor: 0,
+            trimmed: source.trimmed_count(),
+            last: None,
+        }
+    }
+
+    /// Set how many of the window points precede the trigger.
+    ///
+    /// Values larger than the window are clamped.
+    pub fn with_pre_trigger(mut self, points: usize) -> Self {
+        let window = self.pre_trigger + self.post_trigger;
+        self.pre_trigger = points.min(window);
+        self.post_trigger = window - self.pre_trigger;
+        self
+    }
+
+    /// Set the series kind used by the snapshot series.
+    pub fn with_kind(mut self, kind: SeriesKind) -> Self {
+        self.snapshot = self.snapshot.with_kind(kind);
+        self
+    }
+
+    /// Freeze follow views when a capture completes in
+    /// [`TriggerCapture::update_plot`].
+    pub fn with_pause_follow(mut self, pause_follow: bool) -> Self {
+        self.pause_follow = pause_follow;
+        self
+    }
+
+    /// Access the snapshot series holding the last capture.
+    pub fn snapshot(&self) -> &Series {
+        &self.snapshot
+    }
+
+    /// Check whether the capture is waiting for a trigger.
+    pub fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    /// Access the trigger crossing of the last completed capture.
+    pub fn captured(&self) -> Option<Point> {
+        self.captured
+    }
+
+    /// Clear the snapshot and wait for the next trigger.
+    ///
+    /// Only points appended after arming can fire the trigger.
+    pub fn arm(&mut self) {
+        self.snapshot.clear();
+        self.armed = true;
+        self.trigger = None;
+        self.captured = None;
+        self.last = None;
+    }
+
+    /// Scan newly appended points; returns `true` when a capture completed.
+    pub fn update(&mut self) -> bool {
+        let removed = take_trimmed(&self.source, &mut self.trimmed);
+        self.cursor = self.cursor.saturating_sub(removed);
+        if let Some((index, point)) = self.trigger {
+            self.trigger = index.checked_sub(removed).map(|index| (index, point));
+        }
+        if !self.armed {
+            self.cursor = self.source.with_store(|store| store.data().len());
+            return false;
+        }
+
+        let window = self.source.with_store(|store| {
+            let points = store.data().points();
+            if self.trigger.is_none() {
+                for (index, point) in points.iter().enumerate().skip(self.cursor) {
+                    if !point.x.is_finite() || !point.y.is_finite() {
+                        self.last = None;
+                        continue;
+                    }
+                    if let Some(previous) = self.last
+                        && let Some(edge) = crossing_edge(previous.y, point.y, self.level)
+                        && self.direction.accepts(edge)
+                    {
+                        let t = (self.level - previous.y) / (point.y - previous.y);
+                        let x = previous.x + (point.x - previous.x) * t;
+                        self.trigger = Some((index, Point::new(x, self.level)));
+                        self.last = Some(*point);
+                        break;
+                    }
+                    self.last = Some(*point);
+                }
+            }
+            self.cursor = points.len();
+            let (index, crossing) = self.trigger?;
+            if points.len() < index + self.post_trigger {
+                return None;
+            }
+            let start = index.saturating_sub(self.pre_trigger);
+            let window: Vec<Point> = points[start..index + self.post_trigger]
+                .iter()
+                .map(|point| Point::new(point.x - crossing.x, point.y))
+                .collect();
+            Some((crossing, window))
+        });
+
+        let Some((crossing, window)) = window else {
+            return false;
+        };
+        self.snapshot.clear();
+        let _ = self.snapshot.extend_points(window);
+        self.armed = false;
+        self.trigger = None;
+        self.captured = Some(crossing);
+        true
+    }
+
+    /// Scan newly appended points and pause follow views in `plot` when a
+    /// capture completes and pausing is enabled.
+    pub fn update_plot(&mut self, plot: &mut Plot) -> bool {
+        let completed = self.update();
+        if completed
+            && self.pause_follow
+            && matches!(
+                plot.view(),
+                View::FollowLastN { .. } | View::FollowLastNXY { .. }
+            )
+            && let Some(viewport) = plot.viewport()
+        {
+            plot.set_manual_view(viewport);
+        }
+        completed
+    }
+}
+
+/// Window used by [`RollingStats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RollingWindow {
+    /// The most recent `n` points.
+    Points(usize),
+    /// Points within this X span of the newest point.
+    Span(f64),
+}
+
+/// Statistic computed by [`RollingStats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RollingStat {
+    /// Window minimum.
+    Min,
+    /// Window maximum.
+    Max,
+    /// Window mean.
+    Mean,
+    /// Window percentile in `0.0..=100.0`, linearly interpolated.
+    Percentile(f64),
+}
+
+/// Rolling window statistic over a series, kept as a derived line series.
+///
+/// Each [`RollingStats::update`] call only processes newly appended points.
+/// Min and max use monotonic queues, the mean a running sum, and percentiles
+/// a sorted copy of the window. Non-finite points are skipped.
+#[derive(Debug)]
+pub struct RollingStats {
+    source: Series,
+    output: Series,
+    window: RollingWindow,
+    stat: RollingStat,
+    cursor: usize,
+    trimmed: u64,
+    next_seq: u64,
+    points: VecDeque<(u64, Point)>,
+    extremes: VecDeque<(u64, f64)>,
+    sorted: Vec<f64>,
+    sum: f64,
+}
+
+impl RollingStats {
+    /// Create a rolling statistic over `source`.
+    pub fn new(source: &Series, window: RollingWindow, stat: RollingStat) -> Self {
+        let label = match stat {
+            RollingStat::Min => "min".to_string(),
+            RollingStat::Max => "max".to_string(),
+            RollingStat::Mean => "mean".to_string(),
+            RollingStat::Percentile(percentile) => format!("p{percentile}"),
+        };
+        let output = Series::from_iter_points(
+            format!("{} rolling {label}", source.name()),
+            std::iter::empty(),
+            SeriesKind::Line(LineStyle::default()),
+        );
+        Self {
+            source: source.share(),
+            output,
+            window,
+            stat,
+            cursor: 0,
+            trimmed: source.trimmed_count(),
+            next_seq: 0,
+            points: VecDeque::new(),
+            extremes: VecDeque::new(),
+            sorted: Vec::new(),
+            sum: 0.0,
+        }
+    }
+
+    /// Set the line style of the output series.
+    pub fn with_style(mut self, style: LineStyle) -> Self {
+        self.output = self.output.with_kind(SeriesKind::Line(style));
+        self
+    }
+
+    /// Access the derived series.
+    pub fn series(&self) -> &Series {
+        &self.output
+    }
+
+    /// Process newly appended source points; returns the number of output
+    /// points added.
+    pub fn update(&mut self) -> usize {
+        let removed = take_trimmed(&self.source, &mut self.trimmed);
+        self.cursor = self.cursor.saturating_sub(removed);
+        let cursor = self.cursor;
+        let new_points = self.source.with_store(|store| {
+            let points = store.data().points();
+            points.get(cursor..).unwrap_or_default().to_vec()
+        });
+        self.cursor += new_points.len();
+        let mut output = Vec::new();
+        for point in new_points {
+            if point.x.is_finite() && point.y.is_finite() {
+                self.push(point);
+                output.push(Point::new(point.x, self.value()));
+            }
+        }
+        let added = output.len();
+        let _ = self.output.extend_points(output);
+        added
+    }
+
+    fn push(&mut self, point: Point) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.points.push_back((seq, point));
+        self.sum += point.y;
+        match self.stat {
+            RollingStat::Min | RollingStat::Max => {
+                let keep_min = self.stat == RollingStat::Min;
+                while let Some((_, back)) = self.extremes.back() {
+                    let dominated = if keep_min {
+                        *back >= point.y
+                    } else {
+                        *back <= point.y
+                    };
+                    if !dominated {
+                        break;
+                    }
+                    self.extremes.pop_back();
+                }
+                self.extremes.push_back((seq, point.y));
+            }
+            RollingStat::Percentile(_) => {
+                let index = self.sorted.partition_point(|value| *value < point.y);
+                self.sorted.insert(index, point.y);
+            }
+            RollingStat::Mean => {}
+        }
+
+        while let Some((front_seq, front)) = self.points.front().copied() {
+            let expired = match self.window {
+                RollingWindow::Points(count) => self.points.len() > count.max(1),
+                RollingWindow::Span(span) => front.x < point.x - span,
+            };
+            if !expired {
+                break;
+            }
+            self.points.pop_front();
+            self.sum -= front.y;
+            if self
+                .extremes
+                .front()
+                .is_some_and(|(seq, _)| *seq == front_seq)
+            {
+                self.extremes.pop_front();
+            }
+            if matches!(self.stat, RollingStat::Percentile(_)) {
+                let index = self.sorted.partition_point(|value| *value < front.y);
+                self.sorted.remove(index);
+            }
+        }
+    }
+
+    fn value(&self) -> f64 {
+        match self.stat {
+            RollingStat::Min | RollingStat::Max => {
+                self.extremes.front().map_or(f64::NAN, |(_, value)| *value)
+            }
+            RollingStat::Mean => self.sum / self.points.len().max(1) as f64,
+            RollingStat::Percentile(percentile) => {
+                let Some(last) = self.sorted.len().checked_sub(1) else {
+                    return f64::NAN;
+                };
+                let rank = (percentile / 100.0).clamp(0.0, 1.0) * last as f64;
+                let lower = rank.floor() as usize;
+                let upper = (lower + 1).min(last);
+                let t = rank - lower as f64;
+                self.sorted[lower] + (self.sorted[upper] - self.sorted[lower]) * t
+            }
+        }
+    }
+}
+
+/// Pair of rolling statistics drawn as the edges of a band around a trace.
+///
+/// Both edges use a translucent version of one color so the raw trace stays
+/// readable underneath.
+#[derive(Debug)]
+pub struct RollingBand {
+    lower: RollingStats,
+    upper: RollingStats,
+}
+
+impl RollingBand {
+    /// Create a band from `lower` and `upper` statistics over `source`.
+    pub fn new(
+        source: &Series,
+        window: RollingWindow,
+        lower: RollingStat,
+        upper: RollingStat,
+        color: Color,
+    ) -> Self {
+        let style = LineStyle {
+            color: Color::new(color.r, color.g, color.b, color.a * 0.5),
+            width: 1.0,
+            dash: None,
+        };
+        Self {
+            lower: RollingStats::new(source, window, lower).with_style(style),
+            upper: RollingStats::new(source, window, upper).with_style(style),
+        }
+    }
+
+    /// Access the lower edge series.
+    pub fn lower(&self) -> &Series {
+        self.lower.series()
+    }
+
+    /// Access the upper edge series.
+    pub fn upper(&self) -> &Series {
+        self.upper.series()
+    }
+
+    /// Process newly appended source points for both edges.
+    pub fn update(&mut self) -> usize {
+        self.lower.update();
+        self.upper.update()
+    }
+}
+
+/// Reference point used to align runs in [`overlay_runs`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RunAlignment {
+    /// Shift each run so its first finite point is at X = 0.
+    #[default]
+    FirstPoint,
+    /// Shift each run so its first crossing of `level` is at X = 0.
+    ///
+    /// Runs without a matching crossing fall back to their first point.
+    Trigger {
+        /// Trigger level.
+        level: f64,
+        /// Crossing direction that fires the trigger.
+        direction: CrossingDirection,
+    },
+}
+
+/// Overlay repeated runs on a common time origin for comparison.
+///
+/// Returns one shifted copy per run, colored along `colormap` from the first
+/// to the last run. Line, scatter, stem and area series are recolored; other kinds
+/// keep their style. Runs without finite points are returned unshifted.
+pub fn overlay_runs(runs: &[Series], alignment: RunAlignment, colormap: &Colormap) -> Vec<Series> {
+    let last = runs.len().saturating_sub(1).max(1) as f32;
+    runs.iter()
+        .enumerate()
+        .map(|(index, run)| {
+            let origin = run.with_store(|store| alignment_origin(store.data().points(), alignment));
+            let color = colormap.sample(index as f32 / last);
+            let shifted = run.shift_x(-origin.unwrap_or(0.0));
+            let kind = match shifted.kind().clone() {
+                SeriesKind::Line(mut style) => {
+                    style.color = color;
+                    SeriesKind::Line(style)
+                }
+                SeriesKind::Scatter(mut style) => {
+                    style.color = color;
+                    SeriesKind::Scatter(style)
+                }
+                SeriesKind::Stem(mut style) => {
+                    style.line.color = color;
+                    style.marker.color = color;
+                    SeriesKind::Stem(style)
+                }
+                SeriesKind::Area(mut style) => {
+                    style.line.color = color;
+                    style.fill = Color {
+                        a: style.fill.a,
+                        ..color
+                    };
+                    SeriesKind::Area(style)
+                }
+                kind => kind,
+            };
+            shifted.with_kind(kind)
+        })
+        .collect()
+}
+
+fn alignment_origin(points: &[Point], alignment: RunAlignment) -> Option<f64> {
+    let mut finite = points
+        .iter()
+        .filter(|point| point.x.is_finite() && point.y.is_finite());
+    let first = finite.next()?;
+    let RunAlignment::Trigger { level, direction } = alignment else {
+        return Some(first.x);
+    };
+    let mut previous = *first;
+    for point in finite {
+        if let Some(edge) = crossing_edge(previous.y, point.y, level)
+            && direction.accepts(edge)
+        {
+            let t = (level - previous.y) / (point.y - previous.y);
+            return Some(previous.x + (point.x - previous.x) * t);
+        }
+        previous = *point;
+    }
+    Some(first.x)
+}
+
+/// Consume the source trim counter, returning how many points were removed
+/// from its front since the last call.
+fn take_trimmed(source: &Series, seen: &mut u64) -> usize {
+    let trimmed = source.trimmed_count();
+    let removed = trimmed.wrapping_sub(*seen);
+    *seen = trimmed;
+    usize::try_from(removed).unwrap_or(usize::MAX)
+}
+
+fn crossing_edge(previous: f64, current: f64, level: f64) -> Option<Edge> {
+    if previous < level && current >= level {
+        Some(Edge::Rising)
+    } else if previous > level && current <= level {
+        Some(Edge::Falling)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{Color, LineStyle};
+
+    #[test]
+    fn trigger_capture_is_single_shot() {
+        let mut source = Series::line("signal");
+        let _ = source.extend_y([0.0, 0.0, 2.0]);
+        let mut capture = TriggerCapture::new(&source, 1.0, CrossingDirection::Rising, 4);
+        assert!(!capture.update());
+        assert!(capture.is_armed());
+
+        let _ = source.extend_y([2.0, 0.0, 2.0]);
+        assert!(capture.update());
+        assert_eq!(capture.captured(), Some(Point::new(1.5, 1.0)));
+        let xs: Vec<f64> = capture
+            .snapshot()
+            .with_store(|store| store.data().points().iter().map(|point| point.x).collect());
+        assert_eq!(xs, vec![-1.5, -0.5, 0.5, 1.5]);
+
+        let _ = source.extend_y([0.0, 2.0, 2.0, 2.0]);
+        assert!(!capture.update());
+        capture.arm();
+        assert_eq!(capture.snapshot().segment_count(), 0);
+    }
+
+    #[test]
+    fn rolling_stats_track_window() {
+        let mut source = Series::line("signal");
+        let _ = source.extend_y([3.0, 1.0, 4.0, 1.0, 5.0]);
+        let values = |stat| {
+            let mut rolling = RollingStats::new(&source, RollingWindow::Points(3), stat);
+            rolling.update();
+            rolling.series().with_store(|store| {
+                store
+                    .data()
+                    .points()
+                    .iter()
+                    .map(|p| p.y)
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(values(RollingStat::Min), vec![3.0, 1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(values(RollingStat::Max), vec![3.0, 3.0, 4.0, 4.0, 5.0]);
+        assert_eq!(
+            values(RollingStat::Percentile(50.0)),
+            vec![3.0, 2.0, 3.0, 1.0, 4.0]
+        );
+
+        let mut mean = RollingStats::new(&source, RollingWindow::Span(1.0), RollingStat::Mean);
+        assert_eq!(mean.update(), 5);
+        let _ = source.push_y(7.0);
+        assert_eq!(mean.update(), 1);
+        assert_eq!(
+            mean.series().with_store(|store| store.data().points()[5].y),
+            6.0
+        );
+    }
+
+    #[test]
+    fn overlay_aligns_runs_on_trigger() {
+        let kind = SeriesKind::Line(LineStyle::default());
+        let runs = [
+            Series::from_iter_points(
+                "run 1",
+                [Point::new(10.0, 0.0), Point::new(12.0, 2.0)],
+                kind.clone(),
+            ),
+            Series::from_iter_points(
+                "run 2",
+                [Point::new(50.0, 0.0), Point::new(51.0, 2.0)],
+                kind,
+            ),
+        ];
+        let ramp = Colormap::Stops(vec![Color::BLACK, Color::WHITE]);
+        let trigger = RunAlignment::Trigger {
+            level: 1.0,
+            direction: CrossingDirection::Rising,
+        };
+        let overlay = overlay_runs(&runs, trigger, &ramp);
+        let first_x = |series: &Series| series.with_store(|store| store.data().points()[0].x);
+        assert_eq!(first_x(&overlay[0]), -1.0);
+        assert_eq!(first_x(&overlay[1]), -0.5);
+        assert!(
+            matches!(overlay[1].kind(), SeriesKind::Line(style) if style.color == Color::WHITE)
+        );
+
+        let overlay = overlay_runs(&runs, RunAlignment::FirstPoint, &ramp);
+        assert_eq!(first_x(&overlay[1]), 0.0);
+    }
+
+    #[test]
+    fn detects_crossings_incrementally() {
+        let mut source = Series::from_iter_y(
+            "signal",
+            [-1.0, 1.0, -1.0],
+            SeriesKind::Line(LineStyle::default()),
+        );
+        let mut detector = CrossingDetector::new(&source, 0.0, CrossingDirection::Both);
+        let crossings = detector.update();
+        assert_eq!(crossings.len(), 2);
+        assert_eq!(crossings[0].edge, Edge::Rising);
+        assert_eq!(crossings[0].point, Point::new(0.5, 0.0));
+        assert_eq!(crossings[1].edge, Edge::Falling);
+
+        let _ = source.push_y(2.0);
+        let crossings = detector.update();
+        assert_eq!(crossings.len(), 1);
+        assert_eq!(crossings[0].event_index, 2);
+        assert!(detector.update().is_empty());
+    }
+
+    #[test]
+    fn crossing_detector_follows_cleared_source() {
+        let mut source = Series::line("signal");
+        let _ = source.extend_y([-1.0, 1.0, -1.0]);
+        let mut detector = CrossingDetector::new(&source, 0.0, CrossingDirection::Both);
+        assert_eq!(detector.update().len(), 2);
+
+        source.clear();
+        let _ = source.extend_y([-1.0, 1.0, -1.0]);
+        assert_eq!(detector.update().len(), 2);
+    }
+
+    #[test]
+    fn crossing_detector_follows_bounded_source() {
+        let mut source = Series::line("signal").with_max_points(100);
+        let mut detector = CrossingDetector::new(&source, 0.0, CrossingDirection::Both);
+        let mut crossings = 0;
+        for index in 0..1000 {
+            let _ = source.push_y(if index % 2 == 0 { -1.0 } else { 1.0 });
+            crossings += detector.update().len();
+        }
+        assert_eq!(crossings, 999);
+    }
+
+    #[test]
+    fn consumers_keep_up_with_retention() {
+        let mut source = Series::line("signal").with_max_points(64);
+        let mut detector = CrossingDetector::new(&source, 0.5, CrossingDirection::Rising);
+        let mut capture = TriggerCapture::new(&source, 0.5, CrossingDirection::Rising, 8);
+        let mut rolling = RollingStats::new(&source, RollingWindow::Points(4), RollingStat::Max);
+        let mut rising = 0;
+        let mut rolled = 0;
+        for index in 0..1000 {
+            let _ = source.push_y(if index % 100 == 99 { 1.0 } else { 0.0 });
+            rising += detector.update().len();
+            if capture.update() {
+                capture.arm();
+            }
+            rolled += rolling.update();
+        }
+        assert!(source.with_store(|store| store.data().len()) <= 64);
+        assert_eq!(rising, 10);
+        assert_eq!(rolled, 1000);
+        assert_eq!(
+            rolling
+                .series()
+                .with_store(|store| store.data().points()[999].y),
+            1.0
+        );
+
+        let _ = source.extend_y([0.0, 0.0, 0.0, 0.0]);
+        assert!(capture.update());
+        assert_eq!(capture.captured(), Some(Point::new(998.5, 0.5)));
+        assert_eq!(capture.snapshot().with_store(|store| store.data().len()), 8);
+    }
+}
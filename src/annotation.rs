@@ -0,0 +1,265 @@
+//! Plot-level annotations anchored in data space.
+//!
+//! Reference lines and threshold bands mark fixed X or Y values, such as
+//! alarm limits, behind the series without a placeholder series. See
+//! [`Plot::add_hline`](crate::plot::Plot::add_hline) and its siblings.
+//!
+//! [`Annotation`]s place text labels, arrows and callouts at data
+//! coordinates, so they pan and zoom with the viewport. They are drawn
+//! above the series; see
+//! [`Plot::add_annotation`](crate::plot::Plot::add_annotation).
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::geom::{Point, ScreenPoint, ScreenRect};
+use crate::render::{
+    Color, LineSegment, LineStyle, RectStyle, RenderCommand, RenderList, dash_segments,
+};
+use crate::transform::Transform;
+use crate::view::{Range, Viewport};
+
+static ANNOTATION_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+/// Unique identifier for an annotation added to a plot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnnotationId(u64);
+
+impl AnnotationId {
+    pub(crate) fn next() -> Self {
+        Self(ANNOTATION_ID_COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// How an [`Annotation`] is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnnotationKind {
+    /// Plain text next to the anchor.
+    Text,
+    /// Arrow from `tail` to the anchor, with the text at the tail.
+    Arrow {
+        /// Data position of the arrow tail.
+        tail: Point,
+    },
+    /// Boxed text placed near the anchor with a leader line to it.
+    Callout,
+}
+
+/// Text, arrow or callout anchored at a data position.
+///
+/// Text and callouts try the same positions around the anchor as pin
+/// labels and skip those that overlap annotations placed before them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// Data position the annotation points at.
+    pub anchor: Point,
+    /// Text drawn with the annotation; may be empty for arrows.
+    pub text: String,
+    /// How the annotation is drawn.
+    pub kind: AnnotationKind,
+    /// Color override; defaults to the theme's axis color.
+    pub color: Option<Color>,
+}
+
+impl Annotation {
+    /// Create a text label at `anchor`.
+    pub fn text(anchor: Point, text: impl Into<String>) -> Self {
+        Self {
+            anchor,
+            text: text.into(),
+            kind: AnnotationKind::Text,
+            color: None,
+        }
+    }
+
+    /// Create an arrow from `tail` to `anchor`, labeled at the tail.
+    pub fn arrow(tail: Point, anchor: Point, text: impl Into<String>) -> Self {
+        Self {
+            anchor,
+            text: text.into(),
+            kind: AnnotationKind::Arrow { tail },
+            color: None,
+        }
+    }
+
+    /// Create a boxed callout pointing at `anchor`.
+    pub fn callout(anchor: Point, text: impl Into<String>) -> Self {
+        Self {
+            anchor,
+            text: text.into(),
+            kind: AnnotationKind::Callout,
+            color: None,
+        }
+    }
+
+    /// Override the annotation color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Check whether the anchor or arrow tail lies inside `viewport`.
+    pub fn intersects(&self, viewport: Viewport) -> bool {
+        let inside = |point: Point| {
+            (viewport.x.min..=viewport.x.max).contains(&point.x)
+                && (viewport.y.min..=viewport.y.max).contains(&point.y)
+        };
+        inside(self.anchor) || matches!(self.kind, AnnotationKind::Arrow { tail } if inside(tail))
+    }
+}
+
+/// Value or range a [`Reference`] marks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReferenceKind {
+    /// Horizontal line across the plot at a Y value.
+    HLine {
+        /// Y value of the line.
+        y: f64,
+        /// Stroke styling.
+        style: LineStyle,
+    },
+    /// Vertical line across the plot at an X value.
+    VLine {
+        /// X value of the line.
+        x: f64,
+        /// Stroke styling.
+        style: LineStyle,
+    },
+    /// Horizontal band across the plot covering a Y range.
+    HBand {
+        /// Y range of the band.
+        y: Range,
+        /// Fill color, usually translucent.
+        fill: Color,
+    },
+    /// Vertical band across the plot covering an X range.
+    VBand {
+        /// X range of the band.
+        x: Range,
+        /// Fill color, usually translucent.
+        fill: Color,
+    },
+}
+
+/// Reference line or band drawn behind the series, with an optional label.
+///
+/// References do not count toward the data bounds, so auto-fit ignores
+/// them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    /// What the reference marks and how it is drawn.
+    pub kind: ReferenceKind,
+    /// Text drawn next to the line or inside the band.
+    pub label: Option<String>,
+}
+
+impl Reference {
+    /// Create a reference with an optional label.
+    pub fn new(kind: ReferenceKind, label: Option<&str>) -> Self {
+        Self {
+            kind,
+            label: label.map(str::to_string),
+        }
+    }
+
+    /// Opaque color for the label: the line color or the band fill.
+    pub(crate) fn label_color(&self) -> Color {
+        match self.kind {
+            ReferenceKind::HLine { style, .. } | ReferenceKind::VLine { style, .. } => style.color,
+            ReferenceKind::HBand { fill, .. } | ReferenceKind::VBand { fill, .. } => {
+                Color { a: 1.0, ..fill }
+            }
+        }
+    }
+
+    /// Screen area the reference covers inside `plot_rect`.
+    ///
+    /// Lines give a rectangle of zero height or width. Returns `None` when
+    /// the reference lies outside the plot.
+    pub(crate) fn screen_rect(
+        &self,
+        transform: &Transform,
+        plot_rect: ScreenRect,
+    ) -> Option<ScreenRect> {
+        let viewport = transform.viewport();
+        let screen_x = |x: f64| Some(transform.data_to_screen(Point::new(x, viewport.y.min))?.x);
+        let screen_y = |y: f64| Some(transform.data_to_screen(Point::new(viewport.x.min, y))?.y);
+        let (min, max) = match self.kind {
+            ReferenceKind::HLine { y, .. } => {
+                let y = screen_y(y)?;
+                ((plot_rect.min.x, y), (plot_rect.max.x, y))
+            }
+            ReferenceKind::VLine { x, .. } => {
+                let x = screen_x(x)?;
+                ((x, plot_rect.min.y), (x, plot_rect.max.y))
+            }
+            ReferenceKind::HBand { y, .. } => {
+                let (a, b) = (screen_y(y.min)?, screen_y(y.max)?);
+                let top = a.min(b).max(plot_rect.min.y);
+                let bottom = a.max(b).min(plot_rect.max.y);
+                ((plot_rect.min.x, top), (plot_rect.max.x, bottom))
+            }
+            ReferenceKind::VBand { x, .. } => {
+                let (a, b) = (screen_x(x.min)?, screen_x(x.max)?);
+                let left = a.min(b).max(plot_rect.min.x);
+                let right = a.max(b).min(plot_rect.max.x);
+                ((left, plot_rect.min.y), (right, plot_rect.max.y))
+            }
+        };
+        let inside = min.0 <= max.0
+            && min.1 <= max.1
+            && max.0 >= plot_rect.min.x
+            && min.0 <= plot_rect.max.x
+            && max.1 >= plot_rect.min.y
+            && min.1 <= plot_rect.max.y;
+        inside.then(|| {
+            ScreenRect::new(
+                ScreenPoint::new(min.0, min.1),
+                ScreenPoint::new(max.0, max.1),
+            )
+        })
+    }
+}
+
+/// Push draw commands for the lines and bands of `references`.
+///
+/// Bands are drawn before lines so lines stay visible on top of them.
+/// Labels are left to the caller, which knows how to measure text.
+pub(crate) fn push_references(
+    render: &mut RenderList,
+    references: &[Reference],
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    for reference in references {
+        let (ReferenceKind::HBand { fill, .. } | ReferenceKind::VBand { fill, .. }) =
+            reference.kind
+        else {
+            continue;
+        };
+        if let Some(rect) = reference.screen_rect(transform, plot_rect) {
+            render.push(RenderCommand::Rect {
+                rect,
+                style: RectStyle {
+                    fill,
+                    stroke: fill,
+                    stroke_width: 0.0,
+                },
+            });
+        }
+    }
+    for reference in references {
+        let (ReferenceKind::HLine { style, .. } | ReferenceKind::VLine { style, .. }) =
+            reference.kind
+        else {
+            continue;
+        };
+        let Some(rect) = reference.screen_rect(transform, plot_rect) else {
+            continue;
+        };
+        let mut segments = vec![LineSegment::new(rect.min, rect.max)];
+        if let Some(dash) = style.dash {
+            segments = dash_segments(&segments, dash);
+        }
+        render.push(RenderCommand::LineSegments { segments, style });
+    }
+}
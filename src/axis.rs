@@ -2,7 +2,7 @@
 //!
 //! Axes are configured at the plot level and shared across all series. This module provides:
 //! - scale types (linear),
-//! - formatting and tick generation,
+//! - formatting and tick generation, including calendar-aware time ticks,
 //! - layout metadata used by render backends.
 
 use std::sync::Arc;
@@ -33,6 +33,29 @@ impl AxisFormatter {
             Self::Custom(formatter) => formatter(value),
         }
     }
+
+    /// Format a tick label for ticks spaced `step` apart.
+    ///
+    /// The default formatter shows just enough decimals to resolve `step`,
+    /// so adjacent labels always differ however far the axis is zoomed in.
+    /// Custom formatters receive the value unchanged.
+    pub fn format_tick(&self, value: f64, step: f64) -> String {
+        match self {
+            Self::Default => {
+                if !step.is_finite() || step <= 0.0 {
+                    return self.format(value);
+                }
+                let decimals = (-step.log10().floor()).clamp(0.0, 17.0) as usize;
+                let value = if value.abs() < step * 1e-6 {
+                    0.0
+                } else {
+                    value
+                };
+                format!("{value:.decimals$}")
+            }
+            Self::Custom(formatter) => formatter(value),
+        }
+    }
 }
 
 impl std::fmt::Debug for AxisFormatter {
@@ -44,6 +67,54 @@ impl std::fmt::Debug for AxisFormatter {
     }
 }
 
+/// Units declared by the series on an axis.
+///
+/// Explicit [`AxisConfig`] units take precedence over inferred units.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AxisUnits {
+    /// No series declares a unit.
+    #[default]
+    Unknown,
+    /// Every series that declares a unit uses this one.
+    Uniform(String),
+    /// Series declare different units, in first-seen order.
+    Mixed(Vec<String>),
+}
+
+impl AxisUnits {
+    /// Infer axis units from the units declared by each series.
+    pub(crate) fn infer<'a>(units: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut seen: Vec<String> = Vec::new();
+        for unit in units {
+            if !seen.iter().any(|existing| existing == unit) {
+                seen.push(unit.to_string());
+            }
+        }
+        match seen.len() {
+            0 => Self::Unknown,
+            1 => Self::Uniform(seen.remove(0)),
+            _ => Self::Mixed(seen),
+        }
+    }
+
+    /// Check whether series on the axis declare conflicting units.
+    pub fn is_mixed(&self) -> bool {
+        matches!(self, Self::Mixed(_))
+    }
+}
+
+/// Where an axis shows its units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitPlacement {
+    /// In parentheses after the axis title.
+    #[default]
+    Title,
+    /// After the label of the highest major tick, as in `30 ms`.
+    LastTick,
+    /// After every major tick label, as in `10 ms`, `20 ms`.
+    EveryTick,
+}
+
 /// Axis configuration shared across all series in a plot.
 ///
 /// The axis configuration is owned by [`Plot`](crate::plot::Plot) and affects
@@ -53,6 +124,7 @@ impl std::fmt::Debug for AxisFormatter {
 pub struct AxisConfig {
     title: Option<String>,
     units: Option<String>,
+    unit_placement: UnitPlacement,
     formatter: AxisFormatter,
     tick_config: TickConfig,
     show_grid: bool,
@@ -60,6 +132,8 @@ pub struct AxisConfig {
     show_zero_line: bool,
     show_border: bool,
     label_size: f32,
+    date_boundaries: Option<DateBoundaries>,
+    time_ticks: Option<TimeTicks>,
 }
 
 impl AxisConfig {
@@ -70,6 +144,7 @@ impl AxisConfig {
         Self {
             title: None,
             units: None,
+            unit_placement: UnitPlacement::Title,
             formatter: AxisFormatter::default(),
             tick_config: TickConfig::default(),
             show_grid: true,
@@ -77,6 +152,20 @@ impl AxisConfig {
             show_zero_line: false,
             show_border: true,
             label_size: 12.0,
+            date_boundaries: None,
+            time_ticks: None,
+        }
+    }
+
+    /// Create an axis for Unix timestamps in seconds, in UTC.
+    ///
+    /// Ticks snap to calendar boundaries and labels show times or dates as
+    /// the zoom level requires. Use [`AxisConfigBuilder::time_ticks`] for a
+    /// local time zone.
+    pub fn time() -> Self {
+        Self {
+            time_ticks: Some(TimeTicks::new()),
+            ..Self::new()
         }
     }
 
@@ -95,14 +184,31 @@ impl AxisConfig {
         self.units.as_deref()
     }
 
+    /// Access where the axis units are shown.
+    pub fn unit_placement(&self) -> UnitPlacement {
+        self.unit_placement
+    }
+
     /// Access the formatter.
     pub fn formatter(&self) -> &AxisFormatter {
         &self.formatter
     }
 
     /// Format a value for display using the configured formatter.
+    ///
+    /// Time axes with the default formatter show `YYYY-MM-DD HH:MM:SS`.
     pub fn format_value(&self, value: f64) -> String {
-        self.formatter.format(value)
+        match (&self.formatter, self.time_ticks) {
+            (AxisFormatter::Default, Some(time)) => time.format_timestamp(value),
+            (formatter, _) => formatter.format(value),
+        }
+    }
+
+    /// Format a tick label for ticks spaced `step` apart.
+    ///
+    /// See [`AxisFormatter::format_tick`].
+    pub fn format_tick(&self, value: f64, step: f64) -> String {
+        self.formatter.format_tick(value, step)
     }
 
     /// Access the tick configuration.
@@ -134,6 +240,16 @@ impl AxisConfig {
     pub fn label_size(&self) -> f32 {
         self.label_size
     }
+
+    /// Access the calendar boundary markers.
+    pub fn date_boundaries(&self) -> Option<DateBoundaries> {
+        self.date_boundaries
+    }
+
+    /// Access the time tick configuration, if this is a time axis.
+    pub fn time_ticks(&self) -> Option<TimeTicks> {
+        self.time_ticks
+    }
 }
 
 /// Builder for [`AxisConfig`].
@@ -155,6 +271,15 @@ impl AxisConfigBuilder {
         self
     }
 
+    /// Show the axis units on tick labels instead of the title.
+    ///
+    /// Applies to units set with [`AxisConfigBuilder::units`]; units
+    /// inferred from series stay in the title.
+    pub fn unit_placement(mut self, placement: UnitPlacement) -> Self {
+        self.axis.unit_placement = placement;
+        self
+    }
+
     /// Set the axis formatter.
     ///
     /// Custom formatters override the default numeric formatting.
@@ -171,6 +296,13 @@ impl AxisConfigBuilder {
         self
     }
 
+    /// Override the number of minor ticks between major ticks on this axis,
+    /// keeping the rest of the tick configuration.
+    pub fn minor_count(mut self, minor_count: usize) -> Self {
+        self.axis.tick_config.minor_count = minor_count;
+        self
+    }
+
     /// Enable or disable major grid lines.
     pub fn grid(mut self, enabled: bool) -> Self {
         self.axis.show_grid = enabled;
@@ -201,6 +333,18 @@ impl AxisConfigBuilder {
         self
     }
 
+    /// Mark day or week boundaries on an axis holding Unix timestamps.
+    pub fn date_boundaries(mut self, boundaries: DateBoundaries) -> Self {
+        self.axis.date_boundaries = Some(boundaries);
+        self
+    }
+
+    /// Generate calendar-aware ticks for Unix timestamps in seconds.
+    pub fn time_ticks(mut self, ticks: TimeTicks) -> Self {
+        self.axis.time_ticks = Some(ticks);
+        self
+    }
+
     /// Build the axis configuration.
     pub fn build(self) -> AxisConfig {
         self.axis
@@ -213,27 +357,532 @@ impl Default for AxisConfig {
     }
 }
 
+/// Tick density presets for [`TickConfig::density`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TickDensity {
+    /// Few, widely spaced labels for small plots.
+    Sparse,
+    /// Default spacing.
+    #[default]
+    Normal,
+    /// Closely spaced labels for precise reading.
+    Dense,
+}
+
 /// Tick generation configuration.
 ///
 /// The tick generator uses `pixel_spacing` as a target distance between
-/// major ticks and inserts `minor_count` minor ticks in between.
+/// major ticks and inserts `minor_count` minor ticks in between. When
+/// `max_ticks` is set, the step grows until at most that many major ticks
+/// are visible. Minor ticks closer than `min_minor_spacing` pixels are
+/// thinned to a single midpoint tick, or dropped, so dense majors at deep
+/// zoom don't fill the grid.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TickConfig {
     /// Target pixel spacing between major ticks.
     pub pixel_spacing: f32,
     /// Number of minor ticks between major ticks.
     pub minor_count: usize,
+    /// Upper bound on the number of visible major ticks.
+    pub max_ticks: Option<usize>,
+    /// Smallest pixel distance between adjacent minor ticks.
+    pub min_minor_spacing: f32,
+}
+
+impl TickConfig {
+    /// Create a configuration from a density preset.
+    pub fn density(density: TickDensity) -> Self {
+        let (pixel_spacing, minor_count) = match density {
+            TickDensity::Sparse => (140.0, 1),
+            TickDensity::Normal => (80.0, 4),
+            TickDensity::Dense => (50.0, 4),
+        };
+        Self {
+            pixel_spacing,
+            minor_count,
+            max_ticks: None,
+            min_minor_spacing: 6.0,
+        }
+    }
+
+    /// Cap the number of visible major ticks.
+    pub fn with_max_ticks(mut self, max_ticks: usize) -> Self {
+        self.max_ticks = Some(max_ticks);
+        self
+    }
+
+    /// Set the number of minor ticks between major ticks.
+    pub fn with_minor_count(mut self, minor_count: usize) -> Self {
+        self.minor_count = minor_count;
+        self
+    }
+
+    /// Set the smallest pixel distance between minor ticks; `0.0` keeps
+    /// every minor tick.
+    pub fn with_min_minor_spacing(mut self, spacing: f32) -> Self {
+        self.min_minor_spacing = spacing.max(0.0);
+        self
+    }
+
+    /// Minor ticks to insert between majors `major_pixels` apart.
+    ///
+    /// Falls back to a single midpoint tick, then to none, when the
+    /// configured count would place minors closer than
+    /// `min_minor_spacing`.
+    pub(crate) fn adaptive_minor_count(&self, major_pixels: f32) -> usize {
+        let fits = |count: usize| major_pixels / (count + 1) as f32 >= self.min_minor_spacing;
+        if fits(self.minor_count) {
+            self.minor_count
+        } else if self.minor_count >= 1 && fits(1) {
+            1
+        } else {
+            0
+        }
+    }
 }
 
 impl Default for TickConfig {
     fn default() -> Self {
+        Self::density(TickDensity::Normal)
+    }
+}
+
+/// Calendar period marked by [`DateBoundaries`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DatePeriod {
+    /// Every midnight.
+    #[default]
+    Day,
+    /// Midnight at the start of every Monday.
+    Week,
+}
+
+impl DatePeriod {
+    fn seconds(self) -> f64 {
+        match self {
+            DatePeriod::Day => SECONDS_PER_DAY,
+            DatePeriod::Week => 7.0 * SECONDS_PER_DAY,
+        }
+    }
+
+    /// Offset of the first boundary after the Unix epoch, a Thursday.
+    fn phase(self) -> f64 {
+        match self {
+            DatePeriod::Day => 0.0,
+            DatePeriod::Week => 4.0 * SECONDS_PER_DAY,
+        }
+    }
+}
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Day or week separators for an axis holding Unix timestamps in seconds.
+///
+/// Boundaries are drawn independently of the regular ticks, as stronger
+/// grid lines with a small date label, and can shade every other period.
+/// They are hidden when zoomed out too far to tell them apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateBoundaries {
+    /// Period between boundaries.
+    pub period: DatePeriod,
+    /// Offset of local time from UTC in seconds, e.g. `3600` for UTC+1.
+    pub utc_offset_secs: i32,
+    /// Shade every other period.
+    pub shade: bool,
+    /// Label each boundary with its date.
+    pub labels: bool,
+}
+
+impl DateBoundaries {
+    /// Labeled, unshaded boundaries in UTC.
+    pub fn new(period: DatePeriod) -> Self {
         Self {
-            pixel_spacing: 80.0,
-            minor_count: 4,
+            period,
+            utc_offset_secs: 0,
+            shade: false,
+            labels: true,
+        }
+    }
+
+    /// Place boundaries at local midnight for a fixed UTC offset.
+    pub fn with_utc_offset(mut self, seconds: i32) -> Self {
+        self.utc_offset_secs = seconds;
+        self
+    }
+
+    /// Shade every other period.
+    pub fn with_shading(mut self, shade: bool) -> Self {
+        self.shade = shade;
+        self
+    }
+
+    /// Enable or disable date labels.
+    pub fn with_labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Boundary timestamps from the last one at or before `range.min`
+    /// through `range.max`.
+    ///
+    /// Returns nothing when more than `max_count` boundaries would be
+    /// visible.
+    pub fn boundaries(&self, range: Range, max_count: usize) -> Vec<f64> {
+        let period = self.period.seconds();
+        if !range.is_finite() || range.span() / period > max_count as f64 {
+            return Vec::new();
+        }
+        let origin = self.period.phase() - f64::from(self.utc_offset_secs);
+        let first = ((range.min - origin) / period).floor();
+        let last = ((range.max - origin) / period).floor();
+        (first as i64..=last as i64)
+            .map(|index| origin + index as f64 * period)
+            .collect()
+    }
+
+    /// Whether the period starting at `boundary` is shaded.
+    pub(crate) fn is_shaded(&self, boundary: f64) -> bool {
+        let origin = self.period.phase() - f64::from(self.utc_offset_secs);
+        let index = ((boundary - origin) / self.period.seconds()).round() as i64;
+        self.shade && index.rem_euclid(2) == 0
+    }
+
+    /// Period length in seconds.
+    pub(crate) fn period_seconds(&self) -> f64 {
+        self.period.seconds()
+    }
+
+    /// Format the local date at `timestamp` as `YYYY-MM-DD`.
+    ///
+    /// Timestamps outside the supported calendar range are shown as plain
+    /// numbers.
+    pub fn label(&self, timestamp: f64) -> String {
+        match CivilTime::new(timestamp + f64::from(self.utc_offset_secs)) {
+            Some(local) => format!("{:04}-{:02}-{:02}", local.year, local.month, local.day),
+            None => AxisFormatter::Default.format(timestamp),
         }
     }
 }
 
+/// Calendar-aware tick generation for an axis holding Unix timestamps in
+/// seconds.
+///
+/// Major ticks snap to whole seconds, minutes, hours, days, months or years
+/// in local time. Labels show `HH:MM:SS`, `HH:MM`, `Mar 14`, `Mar` or `2024`
+/// depending on the tick step, with the date or year on a second line
+/// wherever it changes. A custom [`AxisFormatter`] overrides the labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeTicks {
+    /// Offset of local time from UTC in seconds, e.g. `3600` for UTC+1.
+    pub utc_offset_secs: i32,
+}
+
+impl TimeTicks {
+    /// Time ticks in UTC.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place ticks in local time for a fixed UTC offset.
+    pub fn with_utc_offset(mut self, seconds: i32) -> Self {
+        self.utc_offset_secs = seconds;
+        self
+    }
+
+    /// Format a timestamp as local `YYYY-MM-DD HH:MM:SS`.
+    ///
+    /// Timestamps outside the supported calendar range are shown as plain
+    /// numbers.
+    pub fn format_timestamp(&self, timestamp: f64) -> String {
+        let Some(local) = CivilTime::new(timestamp + f64::from(self.utc_offset_secs)) else {
+            return AxisFormatter::Default.format(timestamp);
+        };
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            local.year, local.month, local.day, local.hour, local.minute, local.second
+        )
+    }
+
+    /// Whether both ends of `range` fall within the supported calendar range.
+    fn covers(&self, range: Range) -> bool {
+        let offset = f64::from(self.utc_offset_secs);
+        civil_days(range.min + offset).is_some() && civil_days(range.max + offset).is_some()
+    }
+}
+
+/// Days either side of the epoch that calendar conversion supports, about
+/// 27 million years, keeping the date arithmetic far from overflow.
+const MAX_CIVIL_DAYS: f64 = 1e10;
+
+/// Whole days since the epoch at `local` seconds, or `None` outside the
+/// supported calendar range.
+fn civil_days(local: f64) -> Option<f64> {
+    let days = (local / SECONDS_PER_DAY).floor();
+    (days.abs() <= MAX_CIVIL_DAYS).then_some(days)
+}
+
+/// Major tick spacing on a time axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimeStep {
+    Seconds(f64),
+    Months(i64),
+}
+
+/// Second-based steps from one second up to a week.
+const TIME_STEPS: [f64; 20] = [
+    1.0,
+    2.0,
+    5.0,
+    10.0,
+    15.0,
+    30.0,
+    60.0,
+    120.0,
+    300.0,
+    600.0,
+    900.0,
+    1800.0,
+    3600.0,
+    7200.0,
+    10_800.0,
+    21_600.0,
+    43_200.0,
+    SECONDS_PER_DAY,
+    2.0 * SECONDS_PER_DAY,
+    7.0 * SECONDS_PER_DAY,
+];
+
+const SECONDS_PER_MONTH: f64 = 30.436_875 * SECONDS_PER_DAY;
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+impl TimeStep {
+    /// Smallest calendar step of at least `seconds`.
+    fn at_least(seconds: f64) -> Self {
+        if seconds < 1.0 {
+            return TimeStep::Seconds(nice_step(seconds));
+        }
+        if let Some(step) = TIME_STEPS.iter().find(|step| **step >= seconds) {
+            return TimeStep::Seconds(*step);
+        }
+        let months = seconds / SECONDS_PER_MONTH;
+        match [1, 2, 3, 6].into_iter().find(|step| *step as f64 >= months) {
+            Some(step) => TimeStep::Months(step),
+            None => TimeStep::Months(12 * nice_step(months / 12.0).max(1.0) as i64),
+        }
+    }
+}
+
+/// Broken-down local time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CivilTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    fraction: f64,
+}
+
+impl CivilTime {
+    /// Break down `local` seconds, or `None` outside the supported calendar
+    /// range.
+    fn new(local: f64) -> Option<Self> {
+        let days = civil_days(local)?;
+        let (year, month, day) = civil_from_days(days as i64)?;
+        let time = local - days * SECONDS_PER_DAY;
+        let whole = time.floor();
+        let seconds = whole as u32;
+        Some(Self {
+            year,
+            month,
+            day,
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+            fraction: time - whole,
+        })
+    }
+
+    fn month_name(&self) -> &'static str {
+        MONTH_NAMES[self.month as usize - 1]
+    }
+}
+
+fn generate_time_ticks(
+    axis: &AxisConfig,
+    time: TimeTicks,
+    range: Range,
+    pixel_length: f32,
+) -> Vec<Tick> {
+    let target = (pixel_length / axis.tick_config().pixel_spacing).max(2.0);
+    let mut raw_step = range.span() / target as f64;
+    if let Some(max_ticks) = axis.tick_config().max_ticks {
+        raw_step = raw_step.max(range.span() / (max_ticks.max(2) - 1) as f64);
+    }
+    if !raw_step.is_finite() || raw_step <= 0.0 {
+        return Vec::new();
+    }
+    let step = TimeStep::at_least(raw_step);
+    let offset = f64::from(time.utc_offset_secs);
+
+    let mut majors = Vec::new();
+    match step {
+        TimeStep::Seconds(step) => {
+            // Weeks start on Monday; the epoch was a Thursday.
+            let phase = if step == 7.0 * SECONDS_PER_DAY {
+                4.0 * SECONDS_PER_DAY
+            } else {
+                0.0
+            };
+            let origin = phase - offset;
+            let first = ((range.min - origin) / step).floor();
+            for index in 0.. {
+                let value = origin + (first + index as f64) * step;
+                if value > range.max + step * 0.5 {
+                    break;
+                }
+                if value >= range.min - step * 0.5 {
+                    majors.push(value);
+                }
+            }
+        }
+        TimeStep::Months(step) => {
+            let Some(start) = CivilTime::new(range.min + offset) else {
+                return Vec::new();
+            };
+            let first = (start.year * 12 + i64::from(start.month) - 1).div_euclid(step) * step;
+            for index in 0.. {
+                let month = first + index * step;
+                let days =
+                    days_from_civil(month.div_euclid(12), month.rem_euclid(12) as u32 + 1, 1);
+                let value = days as f64 * SECONDS_PER_DAY - offset;
+                if value > range.max {
+                    break;
+                }
+                majors.push(value);
+            }
+        }
+    }
+
+    let minor_count = match step {
+        TimeStep::Seconds(step) => axis
+            .tick_config()
+            .adaptive_minor_count((step / range.span()) as f32 * pixel_length),
+        TimeStep::Months(_) => 0,
+    };
+    let mut ticks = Vec::new();
+    let mut previous = None;
+    for (index, value) in majors.iter().enumerate() {
+        let Some(local) = CivilTime::new(value + offset) else {
+            continue;
+        };
+        let label = match &axis.formatter {
+            AxisFormatter::Custom(formatter) => formatter(*value),
+            AxisFormatter::Default => time_label(step, local, previous),
+        };
+        previous = Some(local);
+        ticks.push(Tick {
+            value: *value,
+            label,
+            is_major: true,
+        });
+        let Some(next) = majors.get(index + 1) else {
+            continue;
+        };
+        let minor_step = (next - value) / (minor_count as f64 + 1.0);
+        for i in 1..=minor_count {
+            let minor = value + minor_step * i as f64;
+            if minor >= range.min && minor <= range.max {
+                ticks.push(Tick {
+                    value: minor,
+                    label: String::new(),
+                    is_major: false,
+                });
+            }
+        }
+    }
+    ticks
+}
+
+/// Label a major time tick, adding the date or year on a second line where
+/// it differs from the previous tick.
+fn time_label(step: TimeStep, local: CivilTime, previous: Option<CivilTime>) -> String {
+    let new_day = previous.is_none_or(|prev| {
+        (prev.year, prev.month, prev.day) != (local.year, local.month, local.day)
+    });
+    let new_year = previous.is_none_or(|prev| prev.year != local.year);
+    let date = || format!("{} {}", local.month_name(), local.day);
+    match step {
+        TimeStep::Seconds(step) if step < SECONDS_PER_DAY => {
+            let time = if step < 1.0 {
+                let decimals = (-step.log10().floor()).clamp(1.0, 9.0) as usize;
+                let seconds = f64::from(local.second) + local.fraction;
+                let width = decimals + 3;
+                format!(
+                    "{:02}:{:02}:{seconds:0width$.decimals$}",
+                    local.hour, local.minute
+                )
+            } else if step < 60.0 {
+                format!("{:02}:{:02}:{:02}", local.hour, local.minute, local.second)
+            } else {
+                format!("{:02}:{:02}", local.hour, local.minute)
+            };
+            if new_day {
+                format!("{time}\n{}", date())
+            } else {
+                time
+            }
+        }
+        TimeStep::Seconds(_) => {
+            if new_year {
+                format!("{}\n{}", date(), local.year)
+            } else {
+                date()
+            }
+        }
+        TimeStep::Months(step) if step < 12 => {
+            if new_year {
+                format!("{}\n{}", local.month_name(), local.year)
+            } else {
+                local.month_name().to_string()
+            }
+        }
+        TimeStep::Months(_) => local.year.to_string(),
+    }
+}
+
+/// Convert a proleptic Gregorian date to days since 1970-01-01.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Convert days since 1970-01-01 to a proleptic Gregorian date.
+///
+/// Returns `None` when `days` is too large to convert.
+fn civil_from_days(days: i64) -> Option<(i64, u32, u32)> {
+    let z = days.checked_add(719_468)?;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    Some((year, month, day))
+}
+
 /// Axis tick metadata.
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct Tick {
@@ -268,6 +917,9 @@ struct AxisLayoutKey {
     range: Range,
     pixels: u32,
     tick_config: TickConfig,
+    time_ticks: Option<TimeTicks>,
+    units: Option<String>,
+    unit_placement: UnitPlacement,
 }
 
 /// Cached layout for axis ticks and labels.
@@ -290,6 +942,9 @@ impl AxisLayoutCache {
             range,
             pixels,
             tick_config: axis.tick_config(),
+            time_ticks: axis.time_ticks(),
+            units: axis.units().map(str::to_string),
+            unit_placement: axis.unit_placement(),
         };
         if self.key.as_ref() == Some(&key) {
             return &self.layout;
@@ -301,7 +956,7 @@ impl AxisLayoutCache {
             if tick.label.is_empty() {
                 continue;
             }
-            let (w, h) = measurer.measure(&tick.label, axis.label_size());
+            let (w, h) = measure_lines(measurer, &tick.label, axis.label_size());
             max_size.0 = max_size.0.max(w);
             max_size.1 = max_size.1.max(h);
         }
@@ -313,6 +968,25 @@ impl AxisLayoutCache {
         self.key = Some(key);
         &self.layout
     }
+
+    /// Ticks from the most recent layout.
+    #[cfg(feature = "gpui")]
+    pub(crate) fn ticks(&self) -> &[Tick] {
+        &self.layout.ticks
+    }
+}
+
+/// Find the tick value closest to `value`, considering major and minor ticks.
+#[cfg(any(feature = "gpui", test))]
+pub(crate) fn nearest_tick(ticks: &[Tick], value: f64) -> Option<f64> {
+    if !value.is_finite() {
+        return None;
+    }
+    ticks
+        .iter()
+        .map(|tick| tick.value)
+        .filter(|tick| tick.is_finite())
+        .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
 }
 
 /// Text measurement interface for layout.
@@ -321,37 +995,89 @@ pub(crate) trait TextMeasurer {
     fn measure(&self, text: &str, size: f32) -> (f32, f32);
 }
 
+/// Measure a label that may span several lines: the widest line by the
+/// summed line heights.
+pub(crate) fn measure_lines(measurer: &impl TextMeasurer, text: &str, size: f32) -> (f32, f32) {
+    text.lines()
+        .fold((0.0_f32, 0.0_f32), |(width, height), line| {
+            let (w, h) = measurer.measure(line, size);
+            (width.max(w), height + h)
+        })
+}
+
 /// Generate axis ticks for a range and pixel length.
 fn generate_ticks(axis: &AxisConfig, range: Range, pixel_length: f32) -> Vec<Tick> {
     if !range.is_valid() || pixel_length <= 0.0 {
         return Vec::new();
     }
-    generate_linear_ticks(axis, range, pixel_length)
+    let mut ticks = match axis.time_ticks() {
+        Some(time) if time.covers(range) => generate_time_ticks(axis, time, range, pixel_length),
+        _ => generate_linear_ticks(axis, range, pixel_length),
+    };
+    append_units(axis, range, &mut ticks);
+    ticks
+}
+
+/// Suffix major tick labels with the axis units as its placement asks.
+fn append_units(axis: &AxisConfig, range: Range, ticks: &mut [Tick]) {
+    let Some(units) = axis.units() else {
+        return;
+    };
+    let labeled = |tick: &Tick| tick.is_major && !tick.label.is_empty();
+    let last = ticks
+        .iter()
+        .rposition(|tick| labeled(tick) && tick.value <= range.max);
+    for (index, tick) in ticks.iter_mut().enumerate() {
+        let suffixed = match axis.unit_placement() {
+            UnitPlacement::Title => false,
+            UnitPlacement::LastTick => Some(index) == last,
+            UnitPlacement::EveryTick => labeled(tick),
+        };
+        if suffixed {
+            tick.label = format!("{} {units}", tick.label);
+        }
+    }
 }
 
 fn generate_linear_ticks(axis: &AxisConfig, range: Range, pixel_length: f32) -> Vec<Tick> {
     let target = (pixel_length / axis.tick_config().pixel_spacing).max(2.0);
     let raw_step = range.span() / target as f64;
-    let step = nice_step(raw_step);
+    let mut step = nice_step(raw_step);
     if !step.is_finite() || step <= 0.0 {
         return Vec::new();
     }
+    if let Some(max_ticks) = axis.tick_config().max_ticks {
+        let max_ticks = max_ticks.max(2) as f64;
+        while ((range.max / step).floor() - (range.min / step).ceil() + 1.0) > max_ticks {
+            let next = nice_step(step * 1.5);
+            if !next.is_finite() || next <= step {
+                break;
+            }
+            step = next;
+        }
+    }
 
-    let minor_count = axis.tick_config().minor_count;
+    let minor_count = axis
+        .tick_config()
+        .adaptive_minor_count((step / range.span()) as f32 * pixel_length);
     let minor_step = step / (minor_count as f64 + 1.0);
 
     let mut ticks = Vec::new();
-    let mut value = (range.min / step).floor() * step;
-    if value == -0.0 {
-        value = 0.0;
-    }
+    let first = (range.min / step).floor();
     let max_value = range.max + step * 0.5;
 
-    while value <= max_value {
+    for index in 0.. {
+        let mut value = (first + index as f64) * step;
+        if value > max_value {
+            break;
+        }
+        if value == -0.0 {
+            value = 0.0;
+        }
         if value >= range.min - step * 0.5 {
             ticks.push(Tick {
                 value,
-                label: axis.format_value(value),
+                label: axis.format_tick(value, step),
                 is_major: true,
             });
         }
@@ -365,7 +1091,6 @@ fn generate_linear_ticks(axis: &AxisConfig, range: Range, pixel_length: f32) ->
                 });
             }
         }
-        value += step;
     }
 
     ticks
@@ -400,4 +1125,167 @@ mod tests {
         let ticks = generate_ticks(&axis, Range::new(0.0, 10.0), 400.0);
         assert!(ticks.iter().any(|tick| tick.is_major));
     }
+
+    #[test]
+    fn max_ticks_caps_major_count() {
+        let config = TickConfig::density(TickDensity::Dense).with_max_ticks(3);
+        let axis = AxisConfig::builder().tick_config(config).build();
+        let ticks = generate_ticks(&axis, Range::new(0.0, 100.0), 800.0);
+        let visible = ticks
+            .iter()
+            .filter(|tick| tick.is_major && tick.value >= 0.0 && tick.value <= 100.0)
+            .count();
+        assert!((2..=3).contains(&visible));
+    }
+
+    #[test]
+    fn minor_ticks_thin_out_when_majors_are_dense() {
+        let minors = |axis: &AxisConfig, pixels: f32| {
+            generate_ticks(axis, Range::new(0.0, 10.0), pixels)
+                .iter()
+                .filter(|tick| !tick.is_major)
+                .count()
+        };
+        let axis = AxisConfig::new();
+        // 80 px between majors leaves room for four minors each.
+        assert_eq!(minors(&axis, 800.0), 40);
+        let cramped = AxisConfig::builder()
+            .tick_config(TickConfig::default().with_min_minor_spacing(20.0))
+            .build();
+        assert_eq!(minors(&cramped, 800.0), 10);
+        let crowded = AxisConfig::builder()
+            .tick_config(TickConfig::default().with_min_minor_spacing(50.0))
+            .build();
+        assert_eq!(minors(&crowded, 800.0), 0);
+
+        let overridden = AxisConfig::builder().minor_count(1).build();
+        assert_eq!(overridden.tick_config().minor_count, 1);
+        assert_eq!(minors(&overridden, 800.0), 10);
+    }
+
+    #[test]
+    fn tick_labels_stay_distinct_at_deep_zoom() {
+        let axis = AxisConfig::new();
+        let ticks = generate_ticks(&axis, Range::new(1.0, 1.000_001), 400.0);
+        let labels: Vec<&str> = ticks
+            .iter()
+            .filter(|tick| tick.is_major)
+            .map(|tick| tick.label.as_str())
+            .collect();
+        assert!(labels.len() > 2);
+        assert!(labels.windows(2).all(|pair| pair[0] != pair[1]));
+        assert_eq!(axis.format_tick(20.0, 5.0), "20");
+        assert_eq!(axis.format_tick(-1e-17, 0.1), "0.0");
+    }
+
+    #[test]
+    fn units_suffix_last_or_every_tick_label() {
+        let axis = |placement| {
+            AxisConfig::builder()
+                .units("ms")
+                .unit_placement(placement)
+                .build()
+        };
+        let labels = |axis: &AxisConfig| -> Vec<String> {
+            generate_ticks(axis, Range::new(0.0, 30.0), 240.0)
+                .into_iter()
+                .filter(|tick| tick.is_major && tick.value <= 30.0)
+                .map(|tick| tick.label)
+                .collect()
+        };
+        assert_eq!(labels(&axis(UnitPlacement::Title)), ["0", "10", "20", "30"]);
+        assert_eq!(
+            labels(&axis(UnitPlacement::LastTick)),
+            ["0", "10", "20", "30 ms"]
+        );
+        assert_eq!(
+            labels(&axis(UnitPlacement::EveryTick)),
+            ["0 ms", "10 ms", "20 ms", "30 ms"]
+        );
+    }
+
+    #[test]
+    fn time_ticks_snap_to_calendar_boundaries() {
+        // 2023-11-14 22:13:20 UTC.
+        let now = 1_700_000_000.0;
+        let axis = AxisConfig::time();
+        let ticks = generate_ticks(&axis, Range::new(now, now + 600.0), 400.0);
+        let majors: Vec<&Tick> = ticks.iter().filter(|tick| tick.is_major).collect();
+        assert!(majors.iter().all(|tick| tick.value % 60.0 == 0.0));
+        assert_eq!(majors[0].label, "22:14\nNov 14");
+        assert!(!majors[1].label.contains('\n'));
+
+        let hours = generate_ticks(&axis, Range::new(now, now + 86_400.0), 400.0);
+        let midnight = hours
+            .iter()
+            .find(|tick| tick.is_major && tick.value == 1_700_006_400.0)
+            .expect("tick at midnight");
+        assert_eq!(midnight.label, "00:00\nNov 15");
+
+        let year = 365.0 * SECONDS_PER_DAY;
+        let months = generate_ticks(&axis, Range::new(now, now + year), 400.0);
+        let labels: Vec<&str> = months
+            .iter()
+            .filter(|tick| tick.is_major)
+            .map(|tick| tick.label.as_str())
+            .collect();
+        assert!(labels.contains(&"Jan\n2024"));
+        assert_eq!(days_from_civil(2024, 1, 1), 19_723);
+        assert_eq!(axis.format_value(now), "2023-11-14 22:13:20");
+    }
+
+    #[test]
+    fn time_axis_falls_back_to_numbers_outside_calendar_range() {
+        let axis = AxisConfig::time();
+        assert_eq!(
+            axis.format_value(1e300),
+            AxisFormatter::Default.format(1e300)
+        );
+        assert_eq!(
+            axis.format_value(-1e300),
+            AxisFormatter::Default.format(-1e300)
+        );
+        assert_eq!(axis.format_value(f64::NAN), "NaN");
+        assert_eq!(
+            DateBoundaries::new(DatePeriod::Day).label(1e300),
+            AxisFormatter::Default.format(1e300)
+        );
+        assert_eq!(civil_from_days(i64::MAX), None);
+
+        let ticks = generate_ticks(&axis, Range::new(0.0, 1e300), 400.0);
+        let linear = generate_ticks(&AxisConfig::new(), Range::new(0.0, 1e300), 400.0);
+        assert!(!ticks.is_empty());
+        assert_eq!(ticks, linear);
+    }
+
+    #[test]
+    fn date_boundaries_land_on_local_midnight() {
+        // 2023-11-14 22:13:20 UTC, a Tuesday.
+        let now = 1_700_000_000.0;
+        let days = DateBoundaries::new(DatePeriod::Day);
+        let range = Range::new(now, now + 2.0 * SECONDS_PER_DAY);
+        assert_eq!(
+            days.boundaries(range, 10),
+            vec![1_699_920_000.0, 1_700_006_400.0, 1_700_092_800.0]
+        );
+        assert_eq!(days.label(1_700_006_400.0), "2023-11-15");
+        assert!(days.boundaries(range, 1).is_empty());
+
+        let weeks = DateBoundaries::new(DatePeriod::Week).with_utc_offset(3600);
+        assert_eq!(
+            weeks.boundaries(Range::new(now, now), 10),
+            vec![1_699_833_600.0 - 3600.0]
+        );
+        assert_eq!(weeks.label(1_699_833_600.0 - 3600.0), "2023-11-13");
+        assert_eq!(civil_from_days(-1), Some((1969, 12, 31)));
+    }
+
+    #[test]
+    fn nearest_tick_picks_closest_value() {
+        let axis = AxisConfig::new();
+        let ticks = generate_ticks(&axis, Range::new(0.0, 10.0), 400.0);
+        let snapped = nearest_tick(&ticks, 4.1).expect("tick");
+        assert!((snapped - 4.0).abs() < 1e-9);
+        assert_eq!(nearest_tick(&ticks, f64::NAN), None);
+    }
 }
@@ -0,0 +1,243 @@
+//! Offscreen recording of plot frames.
+//!
+//! [`FrameRecorder`] renders full plot frames, laid out like the interactive
+//! view, with [`RenderList::rasterize`] at a fixed frame rate, without a
+//! window. The
+//! frames can be written as an animated GIF (`gif` feature) or as a numbered
+//! PNG sequence (`png` feature) for sharing short clips of live data. An
+//! [`ExportStyle`] restyles the frames, for example for print, without
+//! changing the plot's theme.
+//!
+//! The recorder takes elapsed time from the caller instead of reading a
+//! clock, so it also runs on `wasm32-unknown-unknown` and in tests.
+
+#[cfg(any(feature = "gif", feature = "png"))]
+use std::io;
+#[cfg(feature = "gif")]
+use std::io::Write;
+#[cfg(feature = "png")]
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::frame::{PlotUiState, PlotViewConfig, build_frame};
+use crate::geom::{ScreenPoint, ScreenRect};
+use crate::plot::Plot;
+use crate::render::{BitmapFont, RasterImage, RectStyle, RenderCommand, RenderList};
+use crate::style::ExportStyle;
+
+/// Highest frame rate a recorder accepts.
+const MAX_FPS: f64 = 100.0;
+
+/// Captures rasterized frames of a plot at a fixed rate.
+#[derive(Debug, Clone)]
+pub struct FrameRecorder {
+    width: usize,
+    height: usize,
+    interval: Duration,
+    next_frame: Option<Duration>,
+    style: Option<ExportStyle>,
+    frames: Vec<RasterImage>,
+}
+
+impl FrameRecorder {
+    /// Create a recorder for `width` x `height` frames at `fps` frames per
+    /// second.
+    ///
+    /// The rate is clamped to `0.1..=100`; non-finite rates use 10 fps.
+    pub fn new(width: usize, height: usize, fps: f64) -> Self {
+        let fps = if fps.is_finite() {
+            fps.clamp(0.1, MAX_FPS)
+        } else {
+            10.0
+        };
+        Self {
+            width: width.max(1),
+            height: height.max(1),
+            interval: Duration::from_secs_f64(1.0 / fps),
+            next_frame: None,
+            style: None,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Restyle captured frames with `style` instead of the plot's theme.
+    pub fn with_style(mut self, style: ExportStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Time between captured frames.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Capture a frame if `elapsed` has reached the next frame time.
+    ///
+    /// Call this from the update loop with the time since recording began.
+    /// The first call always captures; later calls keep the fixed cadence
+    /// and skip frames the loop fell behind on. Returns whether a frame was
+    /// captured.
+    pub fn capture_at(&mut self, plot: &Plot, elapsed: Duration) -> bool {
+        let due = self.next_frame.unwrap_or(elapsed);
+        if elapsed < due {
+            return false;
+        }
+        self.capture(plot);
+        let missed = ((elapsed - due).as_secs_f64() / self.interval.as_secs_f64()).floor();
+        self.next_frame = Some(due + self.interval.mul_f64(missed + 1.0));
+        true
+    }
+
+    /// Capture a frame now, regardless of the frame rate.
+    ///
+    /// The frame shows the plot with axes, legend and overlays as the
+    /// interactive view would draw it, restyled by the recorder's
+    /// [`ExportStyle`], if any.
+    pub fn capture(&mut self, plot: &Plot) {
+        if let Some(frame) = rasterize_plot(plot, self.width, self.height, self.style.as_ref()) {
+            self.frames.push(frame);
+        }
+    }
+
+    /// Captured frames in order.
+    pub fn frames(&self) -> &[RasterImage] {
+        &self.frames
+    }
+
+    /// Drop all frames and restart the cadence.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.next_frame = None;
+    }
+
+    /// Write the frames as a looping animated GIF.
+    ///
+    /// Frame delays follow the recorder's frame rate in the GIF's 10 ms
+    /// steps. Fails with [`io::ErrorKind::InvalidInput`] when a frame side
+    /// exceeds 65535 pixels.
+    #[cfg(feature = "gif")]
+    pub fn write_gif(&self, writer: impl Write) -> io::Result<()> {
+        let (Ok(width), Ok(height)) = (u16::try_from(self.width), u16::try_from(self.height))
+        else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "GIF frames are limited to 65535 pixels per side",
+            ));
+        };
+        let delay = (self.interval.as_secs_f64() * 100.0).round().max(1.0) as u16;
+        let mut encoder =
+            gif::Encoder::new(writer, width, height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        for frame in &self.frames {
+            let mut rgba = frame.to_rgba8();
+            let mut frame = gif::Frame::from_rgba_speed(width, height, &mut rgba, 10);
+            frame.delay = delay;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+
+    /// Write each frame as `<prefix>-00000.png`, `<prefix>-00001.png`, ...
+    /// into `directory`.
+    ///
+    /// Returns the written paths in frame order.
+    #[cfg(feature = "png")]
+    pub fn write_png_sequence(&self, directory: &Path, prefix: &str) -> io::Result<Vec<PathBuf>> {
+        let mut paths = Vec::with_capacity(self.frames.len());
+        for (index, frame) in self.frames.iter().enumerate() {
+            let path = directory.join(format!("{prefix}-{index:05}.png"));
+            frame.write_png(io::BufWriter::new(std::fs::File::create(&path)?))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// Rasterize a full frame of the plot, with axes, legend and overlays laid
+/// out like the interactive view, optionally restyled by `style`.
+///
+/// Text is measured and drawn with the built-in bitmap font. The plot is
+/// cloned for layout, so following views advance only in the copy.
+/// Returns `None` when either size is zero.
+pub(crate) fn rasterize_plot(
+    plot: &Plot,
+    width: usize,
+    height: usize,
+    style: Option<&ExportStyle>,
+) -> Option<RasterImage> {
+    let mut plot = plot.clone();
+    let bounds = ScreenRect::new(
+        ScreenPoint::new(0.0, 0.0),
+        ScreenPoint::new(width as f32, height as f32),
+    );
+    let background = plot.theme().background;
+    let mut list = RenderList::new();
+    list.push(RenderCommand::Rect {
+        rect: bounds,
+        style: RectStyle {
+            fill: background,
+            stroke: background,
+            stroke_width: 0.0,
+        },
+    });
+    let frame = build_frame(
+        &mut plot,
+        &mut PlotUiState::default(),
+        &PlotViewConfig::default(),
+        bounds,
+        &BitmapFont,
+        None,
+    );
+    for command in frame.render.commands() {
+        list.push(command.clone());
+    }
+    if let Some(style) = style {
+        list = style.apply(&list, plot.theme());
+    }
+    list.rasterize(width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::series::Series;
+
+    #[test]
+    fn capture_at_keeps_fixed_cadence() {
+        let mut plot = Plot::quick([Series::auto("trace", [0.0, 1.0, 0.5])]);
+        plot.refresh_viewport(0.05, 1e-6);
+        let mut recorder = FrameRecorder::new(16, 8, 10.0);
+        let captured: Vec<bool> = [0, 40, 100, 150, 390, 400]
+            .into_iter()
+            .map(|ms| recorder.capture_at(&plot, Duration::from_millis(ms)))
+            .collect();
+        assert_eq!(captured, [true, false, true, false, true, true]);
+
+        let frame = &recorder.frames()[0];
+        assert_eq!((frame.width(), frame.height()), (16, 8));
+        assert_eq!(frame.pixel(0, 0).map(|color| color.a), Some(1.0));
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn write_gif_encodes_every_frame() {
+        let plot = Plot::quick([Series::auto("trace", [0.0, 1.0])]);
+        let mut recorder = FrameRecorder::new(8, 8, 25.0);
+        recorder.capture(&plot);
+        recorder.capture(&plot);
+        let mut bytes = Vec::new();
+        recorder.write_gif(&mut bytes).expect("gif");
+        assert!(bytes.starts_with(b"GIF89a"));
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(bytes.as_slice())
+            .expect("decode");
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().expect("frame") {
+            assert_eq!(frame.delay, 4);
+            frames += 1;
+        }
+        assert_eq!(frames, 2);
+    }
+}
@@ -0,0 +1,241 @@
+//! Gridded 2D data for image-like series.
+
+use crate::datasource::AppendError;
+use crate::view::{Range, Viewport};
+
+/// Dense 2D array of samples stored row-major.
+///
+/// Row 0 is the top row (highest Y) when the grid is placed over a data-space
+/// extent, matching the orientation of camera frames and bitmaps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+}
+
+impl Grid {
+    /// Create a grid from row-major values.
+    ///
+    /// Returns `None` when the grid is empty or `values.len()` does not match
+    /// `width * height`.
+    pub fn new(width: usize, height: usize, values: Vec<f32>) -> Option<Self> {
+        if width == 0 || height == 0 || width.checked_mul(height)? != values.len() {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            values,
+        })
+    }
+
+    /// Build a grid by evaluating a function for each `(column, row)` cell.
+    ///
+    /// Zero dimensions are clamped to one.
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> f32) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        let mut values = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for column in 0..width {
+                values.push(f(column, row));
+            }
+        }
+        Self {
+            width,
+            height,
+            values,
+        }
+    }
+
+    /// Grid with `width` columns and no rows yet.
+    fn empty(width: usize) -> Self {
+        Self {
+            width: width.max(1),
+            height: 0,
+            values: Vec::new(),
+        }
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Access all values in row-major order.
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// Access a value by column and row.
+    pub fn value(&self, column: usize, row: usize) -> Option<f32> {
+        if column >= self.width || row >= self.height {
+            return None;
+        }
+        self.values.get(row * self.width + column).copied()
+    }
+
+    /// Compute the range of finite values.
+    pub fn value_range(&self) -> Option<Range> {
+        let mut range: Option<Range> = None;
+        for value in &self.values {
+            if !value.is_finite() {
+                continue;
+            }
+            let value = *value as f64;
+            match range.as_mut() {
+                None => range = Some(Range::new(value, value)),
+                Some(range) => range.expand_to_include(value),
+            }
+        }
+        range
+    }
+}
+
+/// Placement of a grid fed row by row, where each row covers one X step and
+/// its columns are Y bins from low to high.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RowLayout {
+    x_start: f64,
+    x_step: f64,
+    y: Range,
+    max_rows: Option<usize>,
+}
+
+/// Grid storage with placement and generation tracking.
+#[derive(Debug, Clone)]
+pub(crate) struct GridStore {
+    grid: Grid,
+    extent: Viewport,
+    rows: Option<RowLayout>,
+    generation: u64,
+}
+
+impl GridStore {
+    /// Create a store for a grid placed over a data-space extent.
+    pub fn new(grid: Grid, extent: Viewport) -> Self {
+        Self {
+            grid,
+            extent,
+            rows: None,
+            generation: 0,
+        }
+    }
+
+    /// Create an empty store fed by [`GridStore::push_row`], with `bins`
+    /// values per row spread over `y` and rows `x_step` apart from X = 0.
+    pub fn rows(bins: usize, y: Range, x_step: f64) -> Self {
+        let x_step = if x_step.is_finite() && x_step > 0.0 {
+            x_step
+        } else {
+            1.0
+        };
+        let mut store = Self::new(Grid::empty(bins), Viewport::new(Range::new(0.0, 0.0), y));
+        store.rows = Some(RowLayout {
+            x_start: 0.0,
+            x_step,
+            y,
+            max_rows: None,
+        });
+        store
+    }
+
+    /// Replace the grid and its placement.
+    ///
+    /// A store fed by rows stops accepting rows.
+    pub fn replace(&mut self, grid: Grid, extent: Viewport) {
+        self.grid = grid;
+        self.extent = extent;
+        self.rows = None;
+        self.mark_changed();
+    }
+
+    /// Advance the generation so render caches rebuild from this store.
+    pub fn mark_changed(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Move the X position of the first row.
+    pub fn set_x_origin(&mut self, x: f64) {
+        if let Some(layout) = &mut self.rows {
+            layout.x_start = x;
+            self.update_row_extent();
+        }
+    }
+
+    /// Keep at most `max_rows` rows, dropping the oldest.
+    pub fn set_max_rows(&mut self, max_rows: Option<usize>) {
+        if let Some(layout) = &mut self.rows {
+            layout.max_rows = max_rows.map(|max| max.max(1));
+            self.evict_rows();
+            self.update_row_extent();
+        }
+    }
+
+    /// Append a row with one value per Y bin.
+    pub fn push_row(&mut self, values: &[f32]) -> Result<(), AppendError> {
+        if self.rows.is_none() {
+            return Err(AppendError::WrongMode);
+        }
+        if values.len() != self.grid.width {
+            return Err(AppendError::RowLength {
+                expected: self.grid.width,
+                found: values.len(),
+            });
+        }
+        self.grid.values.extend_from_slice(values);
+        self.grid.height += 1;
+        self.evict_rows();
+        self.update_row_extent();
+        Ok(())
+    }
+
+    fn evict_rows(&mut self) {
+        let Some(layout) = &mut self.rows else {
+            return;
+        };
+        let Some(max_rows) = layout.max_rows else {
+            return;
+        };
+        let evicted = self.grid.height.saturating_sub(max_rows);
+        if evicted > 0 {
+            self.grid.values.drain(..evicted * self.grid.width);
+            self.grid.height -= evicted;
+            layout.x_start += evicted as f64 * layout.x_step;
+        }
+    }
+
+    fn update_row_extent(&mut self) {
+        if let Some(layout) = &self.rows {
+            let x_end = layout.x_start + self.grid.height as f64 * layout.x_step;
+            self.extent = Viewport::new(Range::new(layout.x_start, x_end), layout.y);
+            self.mark_changed();
+        }
+    }
+
+    /// Data-space bounds, or `None` while the grid has no rows.
+    pub fn bounds(&self) -> Option<Viewport> {
+        (self.grid.height > 0).then_some(self.extent)
+    }
+
+    /// Access the grid.
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Access the data-space extent.
+    pub fn extent(&self) -> Viewport {
+        self.extent
+    }
+
+    /// Access the grid generation (increments on replacement).
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
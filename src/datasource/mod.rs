@@ -3,11 +3,20 @@
 //! The data layer is optimized for append-only workloads and fast range
 //! queries. It underpins streaming plots and decimation logic.
 
+mod grid;
+mod stats;
 mod store;
 mod summary;
 
+pub use grid::Grid;
+pub(crate) use grid::GridStore;
+pub use stats::{BoxStats, Ohlc, SegmentStats};
 pub(crate) use store::SeriesStore;
 pub(crate) use summary::DecimationScratch;
+pub(crate) use summary::ScrollDecimation;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 
 use crate::geom::Point;
 use crate::view::{Range, Viewport};
@@ -31,8 +40,167 @@ pub enum AppendError {
     WrongMode,
     /// Explicit X values are not monotonic.
     ///
-    /// Non-monotonic X values disable fast range slicing.
+    /// The points were still stored, but non-monotonic X values disable fast
+    /// range slicing.
     NonMonotonicX,
+    /// Points would break X ordering and were rejected; nothing was stored.
+    OutOfOrder,
+    /// A correction targeted points that do not exist.
+    OutOfRange,
+    /// A heatmap row did not have one value per Y bin.
+    RowLength {
+        /// Number of Y bins.
+        expected: usize,
+        /// Number of values in the row.
+        found: usize,
+    },
+}
+
+impl std::fmt::Display for AppendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppendError::WrongMode => write!(f, "X mode does not match the series"),
+            AppendError::NonMonotonicX => write!(f, "X values are not monotonic"),
+            AppendError::OutOfOrder => write!(f, "X values out of order, points rejected"),
+            AppendError::OutOfRange => write!(f, "points out of range"),
+            AppendError::RowLength { expected, found } => {
+                write!(f, "row has {found} values, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AppendError {}
+
+/// How explicit-X appends treat points that break X ordering.
+///
+/// Indexed series are always ordered, so the policy only affects series with
+/// explicit X values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AppendPolicy {
+    /// Reject the whole batch with [`AppendError::OutOfOrder`] if any point
+    /// would break X ordering; nothing is appended.
+    Strict,
+    /// Append every point and report [`AppendError::NonMonotonicX`] once X
+    /// ordering breaks. Range queries then fall back to linear scans.
+    #[default]
+    Lenient,
+    /// Insert points at their sorted X position so the data stays monotonic.
+    ///
+    /// Appends in order stay cheap; out-of-order inserts rebuild the
+    /// decimation summaries.
+    SortOnInsert,
+}
+
+/// Stable identity of a point within a series.
+///
+/// Ids are assigned in append order and never reused, so an id keeps
+/// referring to the same point while older points are evicted or late
+/// points are merged in before it. Resolve it to the current index with
+/// [`Series::point_index`](crate::series::Series::point_index).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PointId(u64);
+
+/// Limits on how much history a series keeps.
+///
+/// Once an append exceeds a limit, the oldest points are evicted from the
+/// front. Eviction runs in batches of about 1/16 of the limit, so a series
+/// may briefly hold up to that much less history than the limit allows, and
+/// the summaries are rebuilt once per batch rather than per append.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Retention {
+    /// Maximum number of points to keep (at least one is always kept).
+    pub max_points: Option<usize>,
+    /// Maximum X distance between the oldest and newest point to keep.
+    pub max_x_span: Option<f64>,
+}
+
+impl Retention {
+    /// Check whether neither limit is set.
+    pub fn is_unbounded(&self) -> bool {
+        self.max_points.is_none() && self.max_x_span.is_none()
+    }
+}
+
+/// Error extents attached to a point, as distances below and above it.
+///
+/// X errors are optional; leave them at zero to draw vertical whiskers only.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ErrorBar {
+    /// Distance from the point down to its lower Y error.
+    pub y_minus: f64,
+    /// Distance from the point up to its upper Y error.
+    pub y_plus: f64,
+    /// Distance from the point left to its lower X error.
+    pub x_minus: f64,
+    /// Distance from the point right to its upper X error.
+    pub x_plus: f64,
+}
+
+impl ErrorBar {
+    /// Create a Y error of `error` on both sides.
+    pub fn symmetric(error: f64) -> Self {
+        Self::asymmetric(error, error)
+    }
+
+    /// Create a Y error of `minus` below and `plus` above the point.
+    pub fn asymmetric(minus: f64, plus: f64) -> Self {
+        Self {
+            y_minus: minus,
+            y_plus: plus,
+            ..Self::default()
+        }
+    }
+
+    /// Add an X error of `minus` left and `plus` right of the point.
+    pub fn with_x(self, minus: f64, plus: f64) -> Self {
+        Self {
+            x_minus: minus,
+            x_plus: plus,
+            ..self
+        }
+    }
+
+    /// X and Y ranges covered by the error around `point`.
+    ///
+    /// Distances are taken as magnitudes; non-finite ones count as zero.
+    pub(crate) fn extent(&self, point: Point) -> Viewport {
+        let distance = |value: f64| if value.is_finite() { value.abs() } else { 0.0 };
+        Viewport::new(
+            Range::new(
+                point.x - distance(self.x_minus),
+                point.x + distance(self.x_plus),
+            ),
+            Range::new(
+                point.y - distance(self.y_minus),
+                point.y + distance(self.y_plus),
+            ),
+        )
+    }
+}
+
+/// Data quality issue detected on a series.
+///
+/// Warnings do not reject data; they flag inputs that are skipped by bounds
+/// tracking or that disable fast paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataWarning {
+    /// Points with NaN or infinite coordinates were appended.
+    NonFinite {
+        /// Number of non-finite points.
+        count: usize,
+    },
+    /// Explicit X values are not monotonic, so range queries scan all points.
+    NonMonotonicX,
+}
+
+impl std::fmt::Display for DataWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NonFinite { count } => write!(f, "{count} non-finite values"),
+            Self::NonMonotonicX => write!(f, "non-monotonic X (slow range queries)"),
+        }
+    }
 }
 
 /// Append-only data storage with incremental bounds tracking.
@@ -41,6 +209,9 @@ pub(crate) struct AppendOnlyData {
     points: Vec<Point>,
     x_mode: XMode,
     monotonic: bool,
+    non_finite: usize,
+    policy: AppendPolicy,
+    index_offset: usize,
     bounds: Option<Viewport>,
 }
 
@@ -51,6 +222,9 @@ impl AppendOnlyData {
             points: Vec::new(),
             x_mode: XMode::Index,
             monotonic: true,
+            non_finite: 0,
+            policy: AppendPolicy::Lenient,
+            index_offset: 0,
             bounds: None,
         }
     }
@@ -61,6 +235,9 @@ impl AppendOnlyData {
             points: Vec::new(),
             x_mode: XMode::Explicit,
             monotonic: true,
+            non_finite: 0,
+            policy: AppendPolicy::Lenient,
+            index_offset: 0,
             bounds: None,
         }
     }
@@ -110,6 +287,87 @@ impl AppendOnlyData {
         data
     }
 
+    /// Build an explicit data set by sampling a callback adaptively.
+    ///
+    /// Sampling starts from a coarse uniform grid and repeatedly splits the
+    /// interval whose midpoint strays furthest from the straight line
+    /// between its ends, so samples concentrate where the function bends
+    /// or changes quickly. Splitting stops once every midpoint lies within
+    /// `tolerance` of its chord, as a fraction of the sampled Y span, or
+    /// once `max_points` samples are taken.
+    pub fn from_adaptive_callback(
+        function: impl Fn(f64) -> f64,
+        x_range: Range,
+        max_points: usize,
+        tolerance: f64,
+    ) -> Self {
+        let initial = (max_points / 8).clamp(2, 64).min(max_points);
+        let mut points: Vec<Point> = (0..initial)
+            .map(|i| {
+                let t = if initial > 1 {
+                    i as f64 / (initial - 1) as f64
+                } else {
+                    0.0
+                };
+                let x = x_range.min + x_range.span() * t;
+                Point::new(x, function(x))
+            })
+            .collect();
+        let (y_min, y_max) = points
+            .iter()
+            .filter(|point| point.y.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), point| {
+                (min.min(point.y), max.max(point.y))
+            });
+        let y_span = y_max - y_min;
+        let threshold = if y_span > 0.0 {
+            y_span * tolerance.max(0.0)
+        } else {
+            tolerance.max(0.0)
+        };
+        let min_width = x_range.span() * f64::EPSILON * 16.0;
+
+        let split = |left: Point, right: Point| {
+            let x = (left.x + right.x) * 0.5;
+            let mid = Point::new(x, function(x));
+            let error = (mid.y - (left.y + right.y) * 0.5).abs();
+            let error = if error.is_finite() { error } else { 0.0 };
+            AdaptiveInterval {
+                error,
+                left,
+                mid,
+                right,
+            }
+        };
+        let mut heap: BinaryHeap<AdaptiveInterval> = points
+            .windows(2)
+            .filter(|pair| pair[1].x - pair[0].x > min_width)
+            .map(|pair| split(pair[0], pair[1]))
+            .collect();
+        while points.len() < max_points {
+            let Some(interval) = heap.pop() else {
+                break;
+            };
+            if interval.error <= threshold {
+                break;
+            }
+            points.push(interval.mid);
+            for (left, right) in [
+                (interval.left, interval.mid),
+                (interval.mid, interval.right),
+            ] {
+                if right.x - left.x > min_width {
+                    heap.push(split(left, right));
+                }
+            }
+        }
+
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+        let mut data = Self::explicit();
+        let _ = data.extend_points(points);
+        data
+    }
+
     /// Append a Y value for indexed data.
     pub fn push_y(&mut self, y: f64) -> Result<usize, AppendError> {
         let index = self.points.len();
@@ -133,7 +391,7 @@ impl AppendOnlyData {
         let start_len = self.points.len();
         for value in values {
             let index = self.points.len();
-            let point = Point::new(index as f64, value.into());
+            let point = Point::new((self.index_offset + index) as f64, value.into());
             self.points.push(point);
             self.update_bounds(point);
         }
@@ -141,12 +399,16 @@ impl AppendOnlyData {
     }
 
     /// Append a point with explicit X value.
+    ///
+    /// Returns the index the point ended up at.
     pub fn push_point(&mut self, point: Point) -> Result<usize, AppendError> {
-        let index = self.points.len();
+        let index = self.insertion_index(point.x);
         self.extend_points([point]).map(|_| index)
     }
 
     /// Append multiple points with explicit X values.
+    ///
+    /// Out-of-order points are handled according to the [`AppendPolicy`].
     pub fn extend_points<I>(&mut self, points: I) -> Result<usize, AppendError>
     where
         I: IntoIterator<Item = Point>,
@@ -154,7 +416,208 @@ impl AppendOnlyData {
         if self.x_mode != XMode::Explicit {
             return Err(AppendError::WrongMode);
         }
+        match self.policy {
+            AppendPolicy::Lenient => self.append_lenient(points),
+            AppendPolicy::Strict => self.append_strict(points),
+            AppendPolicy::SortOnInsert => Ok(self.insert_sorted(points).len()),
+        }
+    }
+
+    /// Insert explicit points at their sorted X positions.
+    ///
+    /// Returns the final index of each input point, in input order. When the
+    /// existing data is already out of order the batch is sorted and appended
+    /// at the end.
+    pub fn insert_sorted<I>(&mut self, points: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let mut batch: Vec<(usize, Point)> = points.into_iter().enumerate().collect();
+        batch.sort_by(|a, b| a.1.x.total_cmp(&b.1.x));
+        let mut positions = vec![0; batch.len()];
+        let Some(&(_, first)) = batch.first() else {
+            return positions;
+        };
+
+        let start = if self.monotonic {
+            self.points
+                .partition_point(|point| point.x.total_cmp(&first.x).is_le())
+        } else {
+            self.points.len()
+        };
+        let mut tail = self.points.split_off(start).into_iter().peekable();
+        self.points.reserve(tail.len() + batch.len());
+        for (order, point) in batch {
+            while let Some(existing) =
+                tail.next_if(|existing| existing.x.total_cmp(&point.x).is_le())
+            {
+                self.points.push(existing);
+            }
+            positions[order] = self.points.len();
+            self.points.push(point);
+            self.update_bounds(point);
+        }
+        self.points.extend(tail);
+        positions
+    }
+
+    /// Index a point with the given X would occupy if appended now.
+    pub fn insertion_index(&self, x: f64) -> usize {
+        if self.x_mode == XMode::Explicit
+            && self.policy == AppendPolicy::SortOnInsert
+            && self.monotonic
+        {
+            self.points
+                .partition_point(|point| point.x.total_cmp(&x).is_le())
+        } else {
+            self.points.len()
+        }
+    }
+
+    /// Replace the last `points.len()` points in place.
+    ///
+    /// Indexed data keeps its implicit X values and only takes the new Y
+    /// values. Explicit replacements that break X ordering are rejected unless
+    /// the policy is [`AppendPolicy::Lenient`], which applies them and returns
+    /// [`AppendError::NonMonotonicX`]. Rejected replacements return
+    /// [`AppendError::OutOfOrder`].
+    pub fn replace_last_n(&mut self, mut points: Vec<Point>) -> Result<usize, AppendError> {
+        let count = points.len();
+        let Some(start) = self.points.len().checked_sub(count) else {
+            return Err(AppendError::OutOfRange);
+        };
+        let mut ordered = true;
+        match self.x_mode {
+            XMode::Index => {
+                for (offset, point) in points.iter_mut().enumerate() {
+                    point.x = (self.index_offset + start + offset) as f64;
+                }
+            }
+            XMode::Explicit => {
+                let mut last_x = start.checked_sub(1).map(|index| self.points[index].x);
+                for point in &points {
+                    if let Some(last_x) = last_x
+                        && point.x < last_x
+                    {
+                        ordered = false;
+                    }
+                    last_x = Some(point.x);
+                }
+                if !ordered {
+                    if self.policy != AppendPolicy::Lenient {
+                        return Err(AppendError::OutOfOrder);
+                    }
+                    self.monotonic = false;
+                }
+            }
+        }
+
+        let removed: Vec<Point> = self
+            .points
+            .splice(start.., points.iter().copied())
+            .collect();
+        self.repair_bounds(&removed, &points);
+        if ordered {
+            Ok(count)
+        } else {
+            Err(AppendError::NonMonotonicX)
+        }
+    }
+
+    /// Count the leading points with X below `x`.
+    ///
+    /// For non-monotonic data only the leading run is counted.
+    pub fn count_before_x(&self, x: f64) -> usize {
+        if self.monotonic {
+            self.points.partition_point(|point| point.x < x)
+        } else {
+            self.points
+                .iter()
+                .position(|point| point.x >= x || point.x.is_nan())
+                .unwrap_or(self.points.len())
+        }
+    }
+
+    /// Remove the first `count` points.
+    ///
+    /// Indexed data keeps the X values of the remaining points, so later
+    /// appends continue the same index sequence.
+    pub fn truncate_front(&mut self, count: usize) {
+        let count = count.min(self.points.len());
+        if count == 0 {
+            return;
+        }
+        self.points.drain(..count);
+        if self.x_mode == XMode::Index {
+            self.index_offset += count;
+        }
+        self.non_finite = 0;
+        self.bounds = None;
+        for index in 0..self.points.len() {
+            self.update_bounds(self.points[index]);
+        }
+        if !self.monotonic {
+            self.monotonic = self.points.windows(2).all(|pair| pair[1].x >= pair[0].x);
+        }
+    }
+
+    /// Remove all points and restart indexed X values at zero.
+    ///
+    /// The append policy is kept.
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.monotonic = true;
+        self.non_finite = 0;
+        self.index_offset = 0;
+        self.bounds = None;
+    }
+
+    /// Replace the Y value of an existing point.
+    pub fn update_point(&mut self, index: usize, y: f64) -> Result<(), AppendError> {
+        let Some(old) = self.points.get(index).copied() else {
+            return Err(AppendError::OutOfRange);
+        };
+        let new = Point::new(old.x, y);
+        self.points[index] = new;
+        self.repair_bounds(&[old], &[new]);
+        Ok(())
+    }
 
+    /// Access the append policy.
+    pub fn policy(&self) -> AppendPolicy {
+        self.policy
+    }
+
+    /// Replace the append policy used by later appends.
+    pub fn set_policy(&mut self, policy: AppendPolicy) {
+        self.policy = policy;
+    }
+
+    fn append_strict<I>(&mut self, points: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let batch: Vec<Point> = points.into_iter().collect();
+        let mut last_x = self.points.last().map(|point| point.x);
+        for point in &batch {
+            if let Some(last_x) = last_x
+                && point.x < last_x
+            {
+                return Err(AppendError::OutOfOrder);
+            }
+            last_x = Some(point.x);
+        }
+        self.points.extend_from_slice(&batch);
+        for point in &batch {
+            self.update_bounds(*point);
+        }
+        Ok(batch.len())
+    }
+
+    fn append_lenient<I>(&mut self, points: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = Point>,
+    {
         let points = points.into_iter();
         let (reserve, _) = points.size_hint();
         self.points.reserve(reserve);
@@ -216,13 +679,33 @@ impl AppendOnlyData {
         self.monotonic
     }
 
+    /// Collect data quality warnings for this data set.
+    pub fn warnings(&self) -> Vec<DataWarning> {
+        let mut warnings = Vec::new();
+        if self.non_finite > 0 {
+            warnings.push(DataWarning::NonFinite {
+                count: self.non_finite,
+            });
+        }
+        if !self.monotonic {
+            warnings.push(DataWarning::NonMonotonicX);
+        }
+        warnings
+    }
+
     /// Find the index range that intersects the X range.
     pub fn range_by_x(&self, range: Range) -> std::ops::Range<usize> {
         if self.points.is_empty() {
             return 0..0;
         }
         match self.x_mode {
-            XMode::Index => index_range(range, self.points.len()),
+            XMode::Index => {
+                let offset = self.index_offset as f64;
+                index_range(
+                    Range::new(range.min - offset, range.max - offset),
+                    self.points.len(),
+                )
+            }
             XMode::Explicit => {
                 if !self.monotonic {
                     return 0..self.points.len();
@@ -243,7 +726,7 @@ impl AppendOnlyData {
         match self.x_mode {
             XMode::Index => {
                 let max_index = self.points.len().saturating_sub(1) as f64;
-                let clamped = x.round().clamp(0.0, max_index);
+                let clamped = (x - self.index_offset as f64).round().clamp(0.0, max_index);
                 Some(clamped as usize)
             }
             XMode::Explicit => {
@@ -270,7 +753,48 @@ impl AppendOnlyData {
         }
     }
 
+    /// Update bounds and non-finite counts after points were replaced.
+    ///
+    /// Bounds only grow incrementally, so a full rescan happens only when a
+    /// removed point sat on the bounds edge.
+    fn repair_bounds(&mut self, removed: &[Point], added: &[Point]) {
+        let touches_edge = |bounds: Viewport, point: &Point| {
+            point.x.is_finite()
+                && point.y.is_finite()
+                && (point.x <= bounds.x.min
+                    || point.x >= bounds.x.max
+                    || point.y <= bounds.y.min
+                    || point.y >= bounds.y.max)
+        };
+        let rescan = match self.bounds {
+            Some(bounds) => removed.iter().any(|point| touches_edge(bounds, point)),
+            None => false,
+        };
+        if rescan {
+            self.non_finite = 0;
+            self.bounds = None;
+            for index in 0..self.points.len() {
+                self.update_bounds(self.points[index]);
+            }
+            return;
+        }
+        let removed_non_finite = removed
+            .iter()
+            .filter(|point| !point.x.is_finite() || !point.y.is_finite())
+            .count();
+        self.non_finite = self.non_finite.saturating_sub(removed_non_finite);
+        for point in added {
+            self.update_bounds(*point);
+        }
+    }
+
     fn update_bounds(&mut self, point: Point) {
+        if !point.x.is_finite() || !point.y.is_finite() {
+            // A point with one finite coordinate must not stretch the other
+            // axis either; the whole point is left out of the bounds.
+            self.non_finite += 1;
+            return;
+        }
         match self.bounds {
             None => {
                 self.bounds = Some(Viewport::new(
@@ -342,10 +866,58 @@ fn upper_bound(points: &[Point], target: f64) -> usize {
     left
 }
 
+/// Interval awaiting a split in [`AppendOnlyData::from_adaptive_callback`],
+/// ordered by how far its midpoint strays from the chord.
+#[derive(Debug, Clone, Copy)]
+struct AdaptiveInterval {
+    error: f64,
+    left: Point,
+    mid: Point,
+    right: Point,
+}
+
+impl PartialEq for AdaptiveInterval {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for AdaptiveInterval {}
+
+impl PartialOrd for AdaptiveInterval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AdaptiveInterval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.error.total_cmp(&other.error)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn adaptive_callback_refines_where_the_curve_bends() {
+        // A ramp with a sharp knee away from the initial grid.
+        let knee = |x: f64| (x - 0.4567).max(0.0) * 100.0;
+        let data = AppendOnlyData::from_adaptive_callback(knee, Range::new(0.0, 1.0), 64, 1e-3);
+        let points = data.points();
+        assert!(points.len() < 64);
+        assert!(points.windows(2).all(|pair| pair[0].x < pair[1].x));
+        let near_knee = points
+            .iter()
+            .filter(|point| (point.x - 0.4567).abs() < 0.1)
+            .count();
+        assert!(near_knee >= points.len() / 2);
+        // Straight parts need no extra samples.
+        let line = AppendOnlyData::from_adaptive_callback(|x| x, Range::new(0.0, 1.0), 64, 1e-3);
+        assert_eq!(line.len(), 8);
+    }
+
     #[test]
     fn indexed_range_matches_indices() {
         let data = AppendOnlyData::from_iter_y([1.0, 2.0, 3.0, 4.0]);
@@ -413,6 +985,75 @@ mod tests {
         assert!(!data.is_monotonic());
     }
 
+    #[test]
+    fn non_finite_values_are_counted_and_skip_bounds() {
+        let data = AppendOnlyData::from_iter_y([f64::NAN, 1.0, f64::INFINITY, 3.0]);
+        assert_eq!(data.warnings(), vec![DataWarning::NonFinite { count: 2 }]);
+        let bounds = data.bounds().expect("finite bounds");
+        assert_eq!((bounds.y.min, bounds.y.max), (1.0, 3.0));
+    }
+
+    #[test]
+    fn strict_policy_rejects_batch_atomically() {
+        let mut data = AppendOnlyData::explicit();
+        data.set_policy(AppendPolicy::Strict);
+        let _ = data.extend_points([Point::new(0.0, 0.0), Point::new(2.0, 1.0)]);
+        let result = data.extend_points([Point::new(3.0, 1.0), Point::new(1.0, 2.0)]);
+        assert_eq!(result, Err(AppendError::OutOfOrder));
+        assert_eq!(data.len(), 2);
+        assert!(data.is_monotonic());
+        assert_eq!(data.bounds().expect("bounds").x.max, 2.0);
+    }
+
+    #[test]
+    fn sort_on_insert_keeps_points_ordered() {
+        let mut data = AppendOnlyData::explicit();
+        data.set_policy(AppendPolicy::SortOnInsert);
+        let _ = data.extend_points([Point::new(0.0, 0.0), Point::new(2.0, 2.0)]);
+        let positions = data.insert_sorted([Point::new(3.0, 3.0), Point::new(1.0, 1.0)]);
+        assert_eq!(positions, vec![3, 1]);
+        assert_eq!(data.push_point(Point::new(0.5, 0.5)), Ok(1));
+        let xs: Vec<f64> = data.points().iter().map(|point| point.x).collect();
+        assert_eq!(xs, vec![0.0, 0.5, 1.0, 2.0, 3.0]);
+        assert!(data.is_monotonic());
+    }
+
+    #[test]
+    fn replacing_recent_points_repairs_bounds() {
+        let mut data = AppendOnlyData::from_iter_y([0.0, 1.0, 5.0, f64::NAN]);
+        assert_eq!(
+            data.replace_last_n(vec![Point::new(99.0, 2.0), Point::new(99.0, 3.0)]),
+            Ok(2)
+        );
+        assert_eq!(data.point(2), Some(Point::new(2.0, 2.0)));
+        assert_eq!(data.bounds().expect("bounds").y.max, 3.0);
+        assert!(data.warnings().is_empty());
+
+        assert_eq!(data.update_point(0, -4.0), Ok(()));
+        assert_eq!(data.bounds().expect("bounds").y.min, -4.0);
+        assert_eq!(data.update_point(9, 0.0), Err(AppendError::OutOfRange));
+        assert_eq!(
+            data.replace_last_n(vec![Point::new(0.0, 0.0); 5]),
+            Err(AppendError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn truncate_front_keeps_indexed_x_continuous() {
+        let mut data = AppendOnlyData::from_iter_y([5.0, 1.0, 2.0, 3.0]);
+        data.truncate_front(2);
+        assert_eq!(data.point(0), Some(Point::new(2.0, 2.0)));
+        assert_eq!(data.bounds().expect("bounds").y.min, 2.0);
+        assert_eq!(data.push_y(4.0), Ok(2));
+        assert_eq!(data.point(2), Some(Point::new(4.0, 4.0)));
+        assert_eq!(data.range_by_x(Range::new(3.0, 4.0)), 1..3);
+        assert_eq!(data.nearest_index_by_x(2.2), Some(0));
+
+        data.clear();
+        assert_eq!(data.push_y(1.0), Ok(0));
+        assert_eq!(data.point(0), Some(Point::new(0.0, 1.0)));
+    }
+
     #[test]
     fn extend_points_wrong_mode_does_not_append() {
         let mut data = AppendOnlyData::indexed();
@@ -421,6 +1062,14 @@ mod tests {
         assert!(data.is_empty());
     }
 
+    #[test]
+    fn grid_rejects_mismatched_values() {
+        assert!(Grid::new(2, 2, vec![0.0; 3]).is_none());
+        let grid = Grid::from_fn(3, 2, |column, row| (column + row * 3) as f32);
+        assert_eq!(grid.value(2, 1), Some(5.0));
+        assert_eq!(grid.value_range(), Some(Range::new(0.0, 5.0)));
+    }
+
     #[test]
     fn nearest_index_for_indexed_data_rounds() {
         let data = AppendOnlyData::from_iter_y([0.0, 1.0, 2.0, 3.0]);
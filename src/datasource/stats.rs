@@ -0,0 +1,208 @@
+//! Statistical summaries for box, violin and candlestick series and series
+//! segments.
+
+use crate::geom::Point;
+use crate::view::Range;
+
+const DENSITY_POINTS: usize = 32;
+
+/// Summary of one segment (run) of a series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentStats {
+    /// Point indices covered by the segment.
+    pub indices: std::ops::Range<usize>,
+    /// X range of the finite points, if any.
+    pub x: Option<Range>,
+    /// Y range of the finite points, if any.
+    pub y: Option<Range>,
+    /// Mean Y of the finite points, if any.
+    pub mean: Option<f64>,
+}
+
+impl SegmentStats {
+    pub(crate) fn from_points(indices: std::ops::Range<usize>, points: &[Point]) -> Self {
+        let mut x: Option<Range> = None;
+        let mut y: Option<Range> = None;
+        let mut sum = 0.0;
+        let mut count = 0_usize;
+        for point in points {
+            if !point.x.is_finite() || !point.y.is_finite() {
+                continue;
+            }
+            x.get_or_insert(Range::new(point.x, point.x))
+                .expand_to_include(point.x);
+            y.get_or_insert(Range::new(point.y, point.y))
+                .expand_to_include(point.y);
+            sum += point.y;
+            count += 1;
+        }
+        Self {
+            indices,
+            x,
+            y,
+            mean: (count > 0).then(|| sum / count as f64),
+        }
+    }
+}
+
+/// Five-number summary of a sample distribution, with an optional density.
+///
+/// Used by box-plot series; the density profile drives violin glyphs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxStats {
+    /// Lower whisker value.
+    pub min: f64,
+    /// First quartile.
+    pub q1: f64,
+    /// Median.
+    pub median: f64,
+    /// Third quartile.
+    pub q3: f64,
+    /// Upper whisker value.
+    pub max: f64,
+    /// Normalized density samples evenly spaced from `min` to `max`.
+    ///
+    /// Empty when the summary was built from provided quartiles.
+    pub density: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Create a summary from precomputed quartiles.
+    pub fn new(min: f64, q1: f64, median: f64, q3: f64, max: f64) -> Self {
+        Self {
+            min,
+            q1,
+            median,
+            q3,
+            max,
+            density: Vec::new(),
+        }
+    }
+
+    /// Compute a summary and density profile from raw samples.
+    ///
+    /// Non-finite samples are ignored. Whiskers span the full sample range.
+    /// Returns `None` when no finite samples remain.
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+        let mut stats = Self::new(
+            sorted[0],
+            quantile(&sorted, 0.25),
+            quantile(&sorted, 0.5),
+            quantile(&sorted, 0.75),
+            sorted[sorted.len() - 1],
+        );
+        stats.density = density(&sorted);
+        Some(stats)
+    }
+}
+
+/// Open, high, low and close values of one candlestick bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ohlc {
+    /// First value in the bucket.
+    pub open: f64,
+    /// Highest value in the bucket.
+    pub high: f64,
+    /// Lowest value in the bucket.
+    pub low: f64,
+    /// Last value in the bucket.
+    pub close: f64,
+}
+
+impl Ohlc {
+    /// Create a candle from its four values.
+    pub fn new(open: f64, high: f64, low: f64, close: f64) -> Self {
+        Self {
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    /// Whether the bucket closed at or above its open.
+    pub fn is_rising(&self) -> bool {
+        self.close >= self.open
+    }
+
+    /// Combine with the candle of the bucket that follows this one.
+    pub fn merge(self, next: Ohlc) -> Self {
+        Self {
+            open: self.open,
+            high: self.high.max(next.high),
+            low: self.low.min(next.low),
+            close: next.close,
+        }
+    }
+
+    pub(crate) fn is_finite(&self) -> bool {
+        [self.open, self.high, self.low, self.close]
+            .iter()
+            .all(|value| value.is_finite())
+    }
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let t = position - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * t
+}
+
+fn density(sorted: &[f64]) -> Vec<f64> {
+    let n = sorted.len() as f64;
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+    if n < 2.0 || max <= min {
+        return Vec::new();
+    }
+    let mean = sorted.iter().sum::<f64>() / n;
+    let variance = sorted.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / (n - 1.0);
+    // Silverman's rule of thumb, falling back to a fraction of the range.
+    let mut bandwidth = 1.06 * variance.sqrt() * n.powf(-0.2);
+    if !bandwidth.is_finite() || bandwidth <= 0.0 {
+        bandwidth = (max - min) * 0.1;
+    }
+    let mut values: Vec<f64> = (0..DENSITY_POINTS)
+        .map(|i| {
+            let y = min + (max - min) * i as f64 / (DENSITY_POINTS - 1) as f64;
+            sorted
+                .iter()
+                .map(|v| {
+                    let z = (y - v) / bandwidth;
+                    (-0.5 * z * z).exp()
+                })
+                .sum::<f64>()
+        })
+        .collect();
+    let peak = values.iter().copied().fold(0.0, f64::max);
+    if peak > 0.0 {
+        for value in &mut values {
+            *value /= peak;
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_samples_computes_quartiles() {
+        let stats = BoxStats::from_samples(&[5.0, 1.0, 3.0, 2.0, 4.0, f64::NAN]).unwrap();
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.q1, 2.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.q3, 4.0);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(stats.density.len(), DENSITY_POINTS);
+        assert!(BoxStats::from_samples(&[f64::NAN]).is_none());
+    }
+}
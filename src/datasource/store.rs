@@ -1,18 +1,48 @@
 //! Series storage combining raw data and summaries.
 
-use crate::datasource::summary::{DecimationScratch, SummaryLevels, decimate_minmax};
-use crate::datasource::{AppendError, AppendOnlyData, XMode};
-use crate::geom::Point;
-use crate::view::Range;
+use std::collections::HashMap;
+
+use crate::datasource::summary::{
+    DecimationScratch, ScrollDecimation, SummaryLevels, decimate_minmax,
+};
+use crate::datasource::{
+    AppendError, AppendOnlyData, AppendPolicy, BoxStats, ErrorBar, Ohlc, PointId, Retention,
+    SegmentStats, XMode,
+};
+use crate::geom::{Point, Vector};
+use crate::view::{Range, Viewport};
 
 const DEFAULT_BASE_CHUNK: usize = 64;
+/// Fraction of a retention limit evicted beyond it in one batch.
+const RETENTION_SLACK: usize = 16;
 
 /// Append-only series storage with summaries and generation tracking.
 #[derive(Debug, Clone)]
 pub(crate) struct SeriesStore {
     data: AppendOnlyData,
     summary: SummaryLevels,
+    vectors: Vec<Vector>,
+    boxes: Vec<BoxStats>,
+    box_bounds: Option<Viewport>,
+    bands: Vec<Range>,
+    band_bounds: Option<Viewport>,
+    candles: Vec<Ohlc>,
+    candle_bounds: Option<Viewport>,
+    errors: Vec<ErrorBar>,
+    error_bounds: Option<Viewport>,
+    pending: Vec<Point>,
+    segment_starts: Vec<usize>,
+    /// Id the next appended point receives.
+    next_id: u64,
+    /// Point ids by index once merges placed points out of append order;
+    /// empty while ids run contiguously up to `next_id`.
+    ids: Vec<u64>,
+    /// Index of every id in `ids`.
+    id_index: HashMap<u64, usize>,
+    retention: Retention,
+    trimmed: u64,
     generation: u64,
+    rewrites: u64,
 }
 
 impl SeriesStore {
@@ -28,22 +58,42 @@ impl SeriesStore {
             summary.push(*point);
         }
         Self {
+            next_id: data.len() as u64,
             data,
             summary,
+            vectors: Vec::new(),
+            boxes: Vec::new(),
+            box_bounds: None,
+            bands: Vec::new(),
+            band_bounds: None,
+            candles: Vec::new(),
+            candle_bounds: None,
+            errors: Vec::new(),
+            error_bounds: None,
+            pending: Vec::new(),
+            segment_starts: Vec::new(),
+            ids: Vec::new(),
+            id_index: HashMap::new(),
+            retention: Retention::default(),
+            trimmed: 0,
             generation: 0,
+            rewrites: 0,
         }
     }
 
     /// Append a Y value for indexed data.
     pub fn push_y(&mut self, y: f64) -> Result<usize, AppendError> {
+        let start_len = self.data.len();
         let result = self.data.push_y(y);
+        self.assign_ids(start_len, None);
         if let Ok(index) = result
             && let Some(point) = self.data.point(index)
         {
             self.summary.push(point);
             self.generation = self.generation.wrapping_add(1);
         }
-        result
+        let evicted = self.enforce_retention();
+        result.map(|index| index.saturating_sub(evicted))
     }
 
     /// Append multiple Y values for indexed data.
@@ -57,13 +107,40 @@ impl SeriesStore {
         if result.is_ok() {
             self.update_summary_from(start_len);
         }
+        self.assign_ids(start_len, None);
+        self.enforce_retention();
         result
     }
 
     /// Append an explicit point.
+    ///
+    /// Returns the index the point ended up at. Buffered late points are
+    /// merged first so the index stays valid.
     pub fn push_point(&mut self, point: Point) -> Result<usize, AppendError> {
-        let index = self.data.len();
-        self.extend_points([point]).map(|_| index)
+        self.flush_pending();
+        let index = self.data.insertion_index(point.x);
+        let result = self.insert_points([point]).0;
+        let evicted = self.enforce_retention();
+        result.map(|_| index.saturating_sub(evicted))
+    }
+
+    /// Append one sample at `x` with `append` and return its index.
+    ///
+    /// `append` is one of the `extend_*` methods called with that single
+    /// sample, so the point and its attached data are stored together. Like
+    /// [`SeriesStore::push_point`], the index accounts for points evicted by
+    /// retention.
+    pub(crate) fn push_sample(
+        &mut self,
+        x: f64,
+        append: impl FnOnce(&mut Self) -> Result<usize, AppendError>,
+    ) -> Result<usize, AppendError> {
+        self.flush_pending();
+        let index = self.data.insertion_index(x);
+        let trimmed = self.trimmed;
+        append(self)?;
+        let evicted = self.trimmed.wrapping_sub(trimmed) as usize;
+        Ok(index.saturating_sub(evicted))
     }
 
     /// Append multiple explicit points.
@@ -71,22 +148,558 @@ impl SeriesStore {
     where
         I: IntoIterator<Item = Point>,
     {
+        let result = self.insert_points(points).0;
+        self.enforce_retention();
+        result
+    }
+
+    /// Insert a late explicit point at its sorted X position.
+    ///
+    /// Points that arrive in order are appended directly. Late points are
+    /// buffered and merged in one pass by [`SeriesStore::flush_pending`], so
+    /// bursts of late samples cost a single summary rebuild from the earliest
+    /// affected chunk.
+    pub fn insert_point(&mut self, point: Point) -> Result<(), AppendError> {
+        if self.data.x_mode() != XMode::Explicit {
+            return Err(AppendError::WrongMode);
+        }
+        let in_order = self
+            .data
+            .points()
+            .last()
+            .is_none_or(|last| last.x <= point.x);
+        if self.pending.is_empty() && in_order {
+            return self.extend_points([point]).map(|_| ());
+        }
+        self.pending.push(point);
+        Ok(())
+    }
+
+    /// Check whether late points are waiting to be merged.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Merge buffered late points into the sorted data.
+    pub fn flush_pending(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let pending = std::mem::take(&mut self.pending);
+        self.merge_points(pending);
+        self.enforce_retention();
+    }
+
+    /// Replace the most recent points with corrected values.
+    ///
+    /// Only summary buckets covering the replaced points are rebuilt.
+    pub fn replace_last_n<I>(&mut self, points: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        self.flush_pending();
+        let points: Vec<Point> = points.into_iter().collect();
+        let count = points.len();
+        let start = self.data.len().saturating_sub(count);
+        let result = self.data.replace_last_n(points);
+        let applied = match result {
+            Ok(_) => true,
+            Err(AppendError::NonMonotonicX) => true,
+            Err(_) => false,
+        };
+        if applied {
+            self.resummarize_from(start, count);
+        }
+        result
+    }
+
+    /// Replace the Y value of an existing point.
+    pub fn update_point(&mut self, index: usize, y: f64) -> Result<(), AppendError> {
+        self.flush_pending();
+        self.data.update_point(index, y)?;
+        self.resummarize_from(index, 1);
+        Ok(())
+    }
+
+    /// Remove all data and summaries.
+    pub fn clear(&mut self) {
+        self.trimmed = self.trimmed.wrapping_add(self.data.len() as u64);
+        self.data.clear();
+        self.summary = SummaryLevels::new(self.summary.base_chunk());
+        self.vectors.clear();
+        self.boxes.clear();
+        self.box_bounds = None;
+        self.bands.clear();
+        self.band_bounds = None;
+        self.candles.clear();
+        self.candle_bounds = None;
+        self.errors.clear();
+        self.error_bounds = None;
+        self.pending.clear();
+        self.segment_starts.clear();
+        self.ids.clear();
+        self.id_index.clear();
+        self.generation = self.generation.wrapping_add(1);
+        self.rewrites = self.rewrites.wrapping_add(1);
+    }
+
+    /// Remove the leading points with X below `x`.
+    ///
+    /// Returns the number of removed points. Summaries, box, band, candle and
+    /// error bounds are rebuilt because bucket boundaries shift.
+    pub fn truncate_before_x(&mut self, x: f64) -> usize {
+        self.flush_pending();
+        let count = self.data.count_before_x(x);
+        self.truncate_front(count);
+        count
+    }
+
+    /// Replace the retention limits and evict points beyond them.
+    ///
+    /// Returns the number of evicted points.
+    pub fn set_retention(&mut self, retention: Retention) -> usize {
+        self.retention = retention;
+        self.flush_pending();
+        self.enforce_retention()
+    }
+
+    /// Access the retention limits.
+    pub fn retention(&self) -> Retention {
+        self.retention
+    }
+
+    /// Evict leading points once the data exceeds the retention limits.
+    ///
+    /// Evicts down to `1/RETENTION_SLACK` below a point limit, and keeps
+    /// exactly the allowed X span once the data reaches that much beyond it,
+    /// so summaries are rebuilt once per batch rather than per append.
+    fn enforce_retention(&mut self) -> usize {
+        if self.retention.is_unbounded() {
+            return 0;
+        }
+        let len = self.data.len();
+        let mut count = 0;
+        if let Some(max) = self.retention.max_points.map(|max| max.max(1))
+            && len > max
+        {
+            count = len - (max - max / RETENTION_SLACK);
+        }
+        if let Some(span) = self.retention.max_x_span.filter(|span| *span >= 0.0)
+            && let Some(last) = self.data.points().last().filter(|last| last.x.is_finite())
+        {
+            let slack = span / RETENTION_SLACK as f64;
+            if self.data.count_before_x(last.x - span - slack) > 0 {
+                count = count.max(self.data.count_before_x(last.x - span));
+            }
+        }
+        self.truncate_front(count);
+        count
+    }
+
+    /// Remove the first `count` points and rebuild everything derived from
+    /// their positions.
+    fn truncate_front(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.data.truncate_front(count);
+        if !self.ids.is_empty() {
+            self.ids.drain(..count.min(self.ids.len()));
+            self.reindex_ids();
+        }
+        self.segment_starts = self
+            .segment_starts
+            .iter()
+            .filter_map(|start| start.checked_sub(count))
+            .filter(|start| *start > 0)
+            .collect();
+        self.vectors.drain(..count.min(self.vectors.len()));
+        self.boxes.drain(..count.min(self.boxes.len()));
+        self.box_bounds = None;
+        for (point, stats) in self.data.points().iter().zip(&self.boxes) {
+            include_box(&mut self.box_bounds, point.x, stats);
+        }
+        self.bands.drain(..count.min(self.bands.len()));
+        self.band_bounds = None;
+        for (point, band) in self.data.points().iter().zip(&self.bands) {
+            include_band(&mut self.band_bounds, point.x, *band);
+        }
+        self.candles.drain(..count.min(self.candles.len()));
+        self.candle_bounds = None;
+        for (point, candle) in self.data.points().iter().zip(&self.candles) {
+            include_candle(&mut self.candle_bounds, point.x, candle);
+        }
+        self.errors.drain(..count.min(self.errors.len()));
+        self.error_bounds = None;
+        for (point, error) in self.data.points().iter().zip(&self.errors) {
+            include_error(&mut self.error_bounds, *point, error);
+        }
+        self.summary = SummaryLevels::new(self.summary.base_chunk());
+        for point in self.data.points() {
+            self.summary.push(*point);
+        }
+        self.trimmed = self.trimmed.wrapping_add(count as u64);
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Start a new segment at the next appended point.
+    ///
+    /// Consecutive calls without appends in between start a single segment.
+    pub fn begin_segment(&mut self) {
+        self.flush_pending();
+        let start = self.data.len();
+        if start > 0 && self.segment_starts.last() != Some(&start) {
+            self.segment_starts.push(start);
+            self.generation = self.generation.wrapping_add(1);
+            self.rewrites = self.rewrites.wrapping_add(1);
+        }
+    }
+
+    /// Check whether any segment breaks have been started.
+    pub fn has_breaks(&self) -> bool {
+        !self.segment_starts.is_empty()
+    }
+
+    /// Index ranges of all segments, in order.
+    pub fn segments(&self) -> Vec<std::ops::Range<usize>> {
+        let len = self.data.len();
+        if len == 0 {
+            return Vec::new();
+        }
+        let mut ranges = Vec::with_capacity(self.segment_starts.len() + 1);
+        let mut start = 0;
+        for next in self
+            .segment_starts
+            .iter()
+            .copied()
+            .filter(|next| *next < len)
+        {
+            ranges.push(start..next);
+            start = next;
+        }
+        ranges.push(start..len);
+        ranges
+    }
+
+    /// Summaries of all segments, in order.
+    pub fn segment_stats(&self) -> Vec<SegmentStats> {
+        self.segments()
+            .into_iter()
+            .map(|range| SegmentStats::from_points(range.clone(), &self.data.points()[range]))
+            .collect()
+    }
+
+    /// Stable id of the point at `index`.
+    pub fn point_id(&self, index: usize) -> Option<PointId> {
+        let len = self.data.len();
+        if index >= len {
+            return None;
+        }
+        if self.ids.is_empty() {
+            return Some(PointId(self.next_id - (len - index) as u64));
+        }
+        self.ids.get(index).copied().map(PointId)
+    }
+
+    /// Current index of the point with the given id, if it is still stored.
+    pub fn point_index(&self, id: PointId) -> Option<usize> {
+        if !self.ids.is_empty() {
+            return self.id_index.get(&id.0).copied();
+        }
+        let first = self.next_id - self.data.len() as u64;
+        (first..self.next_id)
+            .contains(&id.0)
+            .then(|| (id.0 - first) as usize)
+    }
+
+    /// Total number of points removed from the front by clears and
+    /// truncation, used to detect removed pinned points.
+    pub fn trimmed(&self) -> u64 {
+        self.trimmed
+    }
+
+    /// Replace the append policy used by later explicit appends.
+    pub fn set_append_policy(&mut self, policy: AppendPolicy) {
+        self.data.set_policy(policy);
+    }
+
+    /// Append explicit points with an attached vector each.
+    ///
+    /// Points appended without vectors (for example via
+    /// [`SeriesStore::extend_points`]) are treated as zero vectors.
+    pub fn extend_vectors<I>(&mut self, samples: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = (Point, Vector)>,
+    {
+        let samples: Vec<(Point, Vector)> = samples.into_iter().collect();
         let start_len = self.data.len();
-        let result = self.data.extend_points(points);
-        if matches!(result, Ok(_) | Err(AppendError::NonMonotonicX)) {
-            self.update_summary_from(start_len);
+        let (result, positions) = self.insert_points(samples.iter().map(|(point, _)| *point));
+        if self.data.len() > start_len {
+            match positions {
+                None => {
+                    self.vectors.resize(start_len, Vector::default());
+                    self.vectors
+                        .extend(samples.iter().map(|(_, vector)| *vector));
+                }
+                Some(positions) => {
+                    self.vectors.resize(self.data.len(), Vector::default());
+                    for (index, (_, vector)) in positions.into_iter().zip(samples) {
+                        self.vectors[index] = vector;
+                    }
+                }
+            }
+        }
+        self.enforce_retention();
+        result
+    }
+
+    /// Append explicit points with an attached error each.
+    ///
+    /// Points appended without errors have none.
+    pub fn extend_errors<I>(&mut self, samples: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = (Point, ErrorBar)>,
+    {
+        let samples: Vec<(Point, ErrorBar)> = samples.into_iter().collect();
+        let start_len = self.data.len();
+        let (result, positions) = self.insert_points(samples.iter().map(|(point, _)| *point));
+        if self.data.len() > start_len {
+            self.errors.resize(self.data.len(), ErrorBar::default());
+            for (order, (point, error)) in samples.into_iter().enumerate() {
+                let index = positions
+                    .as_ref()
+                    .map_or(start_len + order, |positions| positions[order]);
+                include_error(&mut self.error_bounds, point, &error);
+                self.errors[index] = error;
+            }
+        }
+        self.enforce_retention();
+        result
+    }
+
+    /// Attach an error to an existing point, replacing any previous one.
+    ///
+    /// Returns `false` when the index is out of range.
+    pub fn set_error(&mut self, index: usize, error: ErrorBar) -> bool {
+        self.flush_pending();
+        let Some(point) = self.data.point(index) else {
+            return false;
+        };
+        if self.errors.len() <= index {
+            self.errors.resize(self.data.len(), ErrorBar::default());
+        }
+        self.errors[index] = error;
+        include_error(&mut self.error_bounds, point, &error);
+        self.generation = self.generation.wrapping_add(1);
+        true
+    }
+
+    /// Access the error attached to a point index, if any.
+    pub fn error(&self, index: usize) -> Option<ErrorBar> {
+        self.errors
+            .get(index)
+            .copied()
+            .filter(|error| *error != ErrorBar::default())
+    }
+
+    /// Check whether any point has an error attached.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Access the vector attached to a point index.
+    pub fn vector(&self, index: usize) -> Option<Vector> {
+        if index >= self.data.len() {
+            return None;
         }
+        Some(self.vectors.get(index).copied().unwrap_or_default())
+    }
+
+    /// Append box summaries placed at explicit X positions.
+    ///
+    /// Each box is backed by a `(position, median)` point so hover, pins and
+    /// range queries work as for other series.
+    pub fn extend_boxes<I>(&mut self, boxes: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = (f64, BoxStats)>,
+    {
+        let boxes: Vec<(f64, BoxStats)> = boxes.into_iter().collect();
+        let start_len = self.data.len();
+        let (result, positions) = self.insert_points(
+            boxes
+                .iter()
+                .map(|(position, stats)| Point::new(*position, stats.median)),
+        );
+        if self.data.len() > start_len {
+            match &positions {
+                None => self.boxes.resize(start_len, empty_box()),
+                Some(_) => self.boxes.resize(self.data.len(), empty_box()),
+            }
+            for (order, (position, stats)) in boxes.into_iter().enumerate() {
+                include_box(&mut self.box_bounds, position, &stats);
+                match &positions {
+                    None => self.boxes.push(stats),
+                    Some(positions) => self.boxes[positions[order]] = stats,
+                }
+            }
+        }
+        self.enforce_retention();
         result
     }
 
+    /// Access the box summary attached to a point index.
+    pub fn box_stats(&self, index: usize) -> Option<&BoxStats> {
+        self.boxes.get(index)
+    }
+
+    /// Append low/high bands placed at explicit X positions.
+    ///
+    /// Each band is backed by a point at its center so hover, pins and range
+    /// queries work as for other series.
+    pub fn extend_bands<I>(&mut self, bands: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = (f64, Range)>,
+    {
+        let bands: Vec<(f64, Range)> = bands.into_iter().collect();
+        let start_len = self.data.len();
+        let (result, positions) = self.insert_points(
+            bands
+                .iter()
+                .map(|(position, band)| Point::new(*position, (band.min + band.max) * 0.5)),
+        );
+        if self.data.len() > start_len {
+            match &positions {
+                None => self.bands.resize(start_len, empty_band()),
+                Some(_) => self.bands.resize(self.data.len(), empty_band()),
+            }
+            for (order, (position, band)) in bands.into_iter().enumerate() {
+                include_band(&mut self.band_bounds, position, band);
+                match &positions {
+                    None => self.bands.push(band),
+                    Some(positions) => self.bands[positions[order]] = band,
+                }
+            }
+        }
+        self.enforce_retention();
+        result
+    }
+
+    /// Access the band attached to a point index.
+    pub fn band(&self, index: usize) -> Option<Range> {
+        self.bands.get(index).copied()
+    }
+
+    /// Append candles placed at explicit X positions.
+    ///
+    /// Each candle is backed by a `(position, close)` point so hover, pins
+    /// and range queries work as for other series.
+    pub fn extend_candles<I>(&mut self, candles: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = (f64, Ohlc)>,
+    {
+        let candles: Vec<(f64, Ohlc)> = candles.into_iter().collect();
+        let start_len = self.data.len();
+        let (result, positions) = self.insert_points(
+            candles
+                .iter()
+                .map(|(position, candle)| Point::new(*position, candle.close)),
+        );
+        if self.data.len() > start_len {
+            match &positions {
+                None => self.candles.resize(start_len, empty_candle()),
+                Some(_) => self.candles.resize(self.data.len(), empty_candle()),
+            }
+            for (order, (position, candle)) in candles.into_iter().enumerate() {
+                include_candle(&mut self.candle_bounds, position, &candle);
+                match &positions {
+                    None => self.candles.push(candle),
+                    Some(positions) => self.candles[positions[order]] = candle,
+                }
+            }
+        }
+        self.enforce_retention();
+        result
+    }
+
+    /// Access the candle attached to a point index.
+    pub fn candle(&self, index: usize) -> Option<Ohlc> {
+        self.candles.get(index).copied()
+    }
+
+    /// Candles around `x_range`, merged so that at most about `max_candles`
+    /// remain.
+    ///
+    /// Each entry holds the X extent of the merged candles and their
+    /// combined values. Groups are a power-of-two count of consecutive
+    /// candles aligned to the start of the data, so panning and eviction do
+    /// not reshuffle them. One candle past each side of the range is kept
+    /// so bodies overlapping the edges are drawn.
+    pub fn decimate_candles(&self, x_range: Range, max_candles: usize) -> Vec<(Range, Ohlc)> {
+        let visible = self.data.range_by_x(x_range);
+        let end = (visible.end + 1).min(self.candles.len());
+        let start = visible.start.saturating_sub(1);
+        if start >= end {
+            return Vec::new();
+        }
+        let group = (end - start)
+            .div_ceil(max_candles.max(1))
+            .next_power_of_two();
+        let offset = self.trimmed as usize;
+        let start = start - (offset.wrapping_add(start) % group).min(start);
+        let mut merged: Vec<(Range, Ohlc)> = Vec::new();
+        let mut last_group = None;
+        for index in start..end {
+            let (Some(point), Some(candle)) = (self.data.point(index), self.candles.get(index))
+            else {
+                continue;
+            };
+            if !point.x.is_finite() || !candle.is_finite() {
+                continue;
+            }
+            let key = offset.wrapping_add(index) / group;
+            if last_group == Some(key)
+                && let Some((extent, last)) = merged.last_mut()
+            {
+                extent.expand_to_include(point.x);
+                *last = last.merge(*candle);
+                continue;
+            }
+            last_group = Some(key);
+            merged.push((Range::new(point.x, point.x), *candle));
+        }
+        merged
+    }
+
     /// Access the underlying data.
     pub fn data(&self) -> &AppendOnlyData {
         &self.data
     }
 
     /// Access the series bounds.
-    pub fn bounds(&self) -> Option<crate::view::Viewport> {
-        self.data.bounds()
+    ///
+    /// Box summaries extend the bounds to cover their whiskers, bands and
+    /// candles to cover their low and high values, and error bars to cover
+    /// their extents.
+    pub fn bounds(&self) -> Option<Viewport> {
+        let mut bounds = self.data.bounds();
+        for extra in [
+            self.box_bounds,
+            self.band_bounds,
+            self.candle_bounds,
+            self.error_bounds,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            bounds = match bounds {
+                Some(bounds) => Some(Viewport::new(
+                    Range::union(bounds.x, extra.x)?,
+                    Range::union(bounds.y, extra.y)?,
+                )),
+                None => Some(extra),
+            };
+        }
+        bounds
     }
 
     /// Access the data generation (increments on append).
@@ -95,11 +708,136 @@ impl SeriesStore {
     }
 
     /// Decimate data for rendering within an X range and pixel width.
+    ///
+    /// For ordered data, a NaN point is inserted between segments so line
+    /// renderers draw a break.
     pub fn decimate<'a>(
         &self,
         x_range: Range,
         pixel_width: usize,
         scratch: &'a mut DecimationScratch,
+    ) -> &'a [Point] {
+        self.decimate_points(x_range, pixel_width, scratch);
+        if !self.segment_starts.is_empty()
+            && (self.data.x_mode() == XMode::Index || self.data.is_monotonic())
+        {
+            self.insert_segment_breaks(scratch.output_mut());
+        }
+        scratch.output()
+    }
+
+    /// Min/max envelope of the whole series with at most `max_points`
+    /// points.
+    ///
+    /// The X extent is split into `max_points / 2` equal buckets, keeping the
+    /// lowest and highest point of each. Series that already fit are copied
+    /// without their non-finite points.
+    pub fn envelope<'a>(
+        &self,
+        max_points: usize,
+        scratch: &'a mut DecimationScratch,
+    ) -> &'a [Point] {
+        scratch.clear();
+        let points = self.data.points();
+        if points.len() <= max_points {
+            scratch.output_mut().extend(
+                points
+                    .iter()
+                    .filter(|point| point.x.is_finite() && point.y.is_finite()),
+            );
+            return scratch.output();
+        }
+        let Some(bounds) = self.data.bounds() else {
+            return scratch.output();
+        };
+        decimate_minmax(points, bounds.x, (max_points / 2).max(1), scratch)
+    }
+
+    /// Decimate a view that scrolls at a fixed zoom, such as a follow view,
+    /// reusing buckets from the previous frame in `scroll`.
+    ///
+    /// Only buckets revealed at the edge, or still receiving appends, are
+    /// decimated again. Returns `None` when the data does not allow reuse
+    /// (non-monotonic X, segment breaks, or few enough points to draw
+    /// directly); use [`SeriesStore::decimate`] then.
+    pub(crate) fn decimate_scrolling<'a>(
+        &self,
+        x_range: Range,
+        pixel_width: usize,
+        scroll: &'a mut ScrollDecimation,
+    ) -> Option<&'a [Point]> {
+        let bucket_width = x_range.span() / pixel_width as f64;
+        if pixel_width == 0
+            || !x_range.is_finite()
+            || !bucket_width.is_finite()
+            || bucket_width <= 0.0
+            || self.has_breaks()
+            || (self.data.x_mode() == XMode::Explicit && !self.data.is_monotonic())
+            || self.data.range_by_x(x_range).len() <= pixel_width.saturating_mul(2)
+        {
+            return None;
+        }
+        Some(scroll.update(
+            &self.data,
+            self.rewrites,
+            self.trimmed,
+            x_range,
+            pixel_width,
+        ))
+    }
+
+    /// Coarse min/max points for an overview `pixel_width` wide, built from
+    /// summaries only.
+    ///
+    /// Series shorter than a few base chunks per pixel are copied directly.
+    pub fn overview(&self, pixel_width: usize, out: &mut Vec<Point>) {
+        out.clear();
+        let len = self.data.len();
+        if pixel_width == 0 || len == 0 {
+            return;
+        }
+        if len <= pixel_width.saturating_mul(2) {
+            out.extend_from_slice(self.data.points());
+            return;
+        }
+        let target = len.div_ceil(pixel_width);
+        self.summary.overview(target, out);
+    }
+
+    /// Insert NaN separators between output points that straddle a segment
+    /// boundary.
+    fn insert_segment_breaks(&self, out: &mut Vec<Point>) {
+        let points = self.data.points();
+        let gaps: Vec<(f64, f64)> = self
+            .segment_starts
+            .iter()
+            .filter_map(|start| Some((points.get(start - 1)?.x, points.get(*start)?.x)))
+            .collect();
+        let input = std::mem::take(out);
+        let mut gap = 0;
+        let mut previous: Option<Point> = None;
+        for point in input {
+            if let Some(previous) = previous {
+                while gap < gaps.len() && gaps[gap].0 < previous.x {
+                    gap += 1;
+                }
+                if gap < gaps.len() && gaps[gap].1 <= point.x {
+                    out.push(Point::new(f64::NAN, f64::NAN));
+                    while gap < gaps.len() && gaps[gap].1 <= point.x {
+                        gap += 1;
+                    }
+                }
+            }
+            out.push(point);
+            previous = Some(point);
+        }
+    }
+
+    fn decimate_points<'a>(
+        &self,
+        x_range: Range,
+        pixel_width: usize,
+        scratch: &'a mut DecimationScratch,
     ) -> &'a [Point] {
         scratch.clear();
         if pixel_width == 0 || self.data.is_empty() {
@@ -122,23 +860,146 @@ impl SeriesStore {
         if target_bucket < self.summary.base_chunk() {
             return decimate_minmax(points, x_range, pixel_width, scratch);
         }
-        if let Some(level) = self.summary.choose_level(target_bucket) {
-            for bucket in level.buckets() {
-                if bucket.x_range.max < x_range.min || bucket.x_range.min > x_range.max {
-                    continue;
+        if self.summary.choose_level(target_bucket).is_some() {
+            // Finer levels cover the tail not yet merged into the chosen one.
+            self.summary
+                .overview_in(target_bucket, x_range, scratch.output_mut());
+            return scratch.output();
+        }
+
+        decimate_minmax(points, x_range, pixel_width, scratch)
+    }
+
+    /// Append explicit points according to the data append policy.
+    ///
+    /// Returns the final index of each point when the batch was inserted in
+    /// sorted order, or `None` when it was appended at the end. Out-of-order
+    /// inserts rebuild the summaries and shift any vector or box columns.
+    fn insert_points<I>(&mut self, points: I) -> (Result<usize, AppendError>, Option<Vec<usize>>)
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let start_len = self.data.len();
+        if self.data.x_mode() != XMode::Explicit || self.data.policy() != AppendPolicy::SortOnInsert
+        {
+            let result = self.data.extend_points(points);
+            self.update_summary_from(start_len);
+            self.assign_ids(start_len, None);
+            return (result, None);
+        }
+
+        let positions = self.merge_points(points);
+        (Ok(positions.len()), Some(positions))
+    }
+
+    /// Give the points added since `start_len` fresh ids in input order.
+    ///
+    /// `positions` holds their final indices when they were merged rather
+    /// than appended; merges that place points before existing ones switch
+    /// to an explicit id column.
+    fn assign_ids(&mut self, start_len: usize, positions: Option<&[usize]>) {
+        let first_new = self.next_id;
+        self.next_id += self.data.len().saturating_sub(start_len) as u64;
+        let appended = positions.is_none_or(|positions| {
+            positions
+                .iter()
+                .enumerate()
+                .all(|(order, index)| *index == start_len + order)
+        });
+        if appended {
+            if !self.ids.is_empty() {
+                for (index, id) in (start_len..).zip(first_new..self.next_id) {
+                    self.ids.push(id);
+                    self.id_index.insert(id, index);
                 }
-                bucket.push_ordered(scratch.output_mut());
             }
-            if let Some(partial) = self.summary.partial_bucket()
-                && partial.x_range.max >= x_range.min
-                && partial.x_range.min <= x_range.max
-            {
-                partial.push_ordered(scratch.output_mut());
+            return;
+        }
+        let positions = positions.unwrap_or_default();
+        if self.ids.is_empty() {
+            self.ids = (first_new - start_len as u64..first_new).collect();
+        }
+        spread_column(&mut self.ids, start_len, positions, 0);
+        for (id, index) in (first_new..).zip(positions) {
+            self.ids[*index] = id;
+        }
+        self.reindex_ids();
+    }
+
+    /// Rebuild the id lookup, dropping the id column once ids are contiguous
+    /// again.
+    fn reindex_ids(&mut self) {
+        let first = self.next_id - self.ids.len() as u64;
+        if (first..)
+            .zip(&self.ids)
+            .all(|(expected, id)| expected == *id)
+        {
+            self.ids.clear();
+            self.id_index.clear();
+            return;
+        }
+        self.id_index = self
+            .ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+    }
+
+    /// Merge explicit points into sorted position and keep summaries and
+    /// columns aligned.
+    fn merge_points<I>(&mut self, points: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let start_len = self.data.len();
+        let positions = self.data.insert_sorted(points);
+        self.assign_ids(start_len, Some(&positions));
+        let first = positions.iter().copied().min().unwrap_or(start_len);
+        if first >= start_len {
+            self.update_summary_from(start_len);
+            return positions;
+        }
+
+        self.resummarize_from(first, positions.len());
+        if !self.segment_starts.is_empty() {
+            let mut inserted = positions.clone();
+            inserted.sort_unstable();
+            for start in &mut self.segment_starts {
+                for index in &inserted {
+                    if *index <= *start {
+                        *start += 1;
+                    }
+                }
             }
-            return scratch.output();
         }
+        if !self.vectors.is_empty() {
+            spread_column(&mut self.vectors, start_len, &positions, Vector::default());
+        }
+        if !self.boxes.is_empty() {
+            spread_column(&mut self.boxes, start_len, &positions, empty_box());
+        }
+        if !self.bands.is_empty() {
+            spread_column(&mut self.bands, start_len, &positions, empty_band());
+        }
+        if !self.candles.is_empty() {
+            spread_column(&mut self.candles, start_len, &positions, empty_candle());
+        }
+        if !self.errors.is_empty() {
+            spread_column(&mut self.errors, start_len, &positions, ErrorBar::default());
+        }
+        positions
+    }
 
-        decimate_minmax(points, x_range, pixel_width, scratch)
+    /// Rebuild summaries for points at or after `index` after `changed`
+    /// points were modified.
+    fn resummarize_from(&mut self, index: usize, changed: usize) {
+        let kept = self.summary.truncate(index);
+        for point in &self.data.points()[kept..] {
+            self.summary.push(*point);
+        }
+        self.generation = self.generation.wrapping_add(changed as u64);
+        self.rewrites = self.rewrites.wrapping_add(1);
     }
 
     fn update_summary_from(&mut self, start_len: usize) {
@@ -155,6 +1016,87 @@ impl SeriesStore {
     }
 }
 
+fn include_box(bounds: &mut Option<Viewport>, position: f64, stats: &BoxStats) {
+    if !position.is_finite() || !stats.median.is_finite() {
+        return;
+    }
+    let bounds = bounds.get_or_insert(Viewport::new(
+        Range::new(position, position),
+        Range::new(stats.median, stats.median),
+    ));
+    bounds.x.expand_to_include(position);
+    for value in [stats.min, stats.q1, stats.q3, stats.max] {
+        bounds.y.expand_to_include(value);
+    }
+}
+
+fn empty_box() -> BoxStats {
+    BoxStats::new(0.0, 0.0, 0.0, 0.0, 0.0)
+}
+
+fn include_band(bounds: &mut Option<Viewport>, position: f64, band: Range) {
+    if !position.is_finite() || !band.is_finite() {
+        return;
+    }
+    let bounds = bounds.get_or_insert(Viewport::new(Range::new(position, position), band));
+    bounds.x.expand_to_include(position);
+    bounds.y.expand_to_include(band.min);
+    bounds.y.expand_to_include(band.max);
+}
+
+fn empty_band() -> Range {
+    Range::new(f64::NAN, f64::NAN)
+}
+
+fn include_candle(bounds: &mut Option<Viewport>, position: f64, candle: &Ohlc) {
+    if !position.is_finite() || !candle.is_finite() {
+        return;
+    }
+    let bounds = bounds.get_or_insert(Viewport::new(
+        Range::new(position, position),
+        Range::new(candle.low, candle.low),
+    ));
+    bounds.x.expand_to_include(position);
+    bounds.y.expand_to_include(candle.low);
+    bounds.y.expand_to_include(candle.high);
+}
+
+fn empty_candle() -> Ohlc {
+    Ohlc::new(f64::NAN, f64::NAN, f64::NAN, f64::NAN)
+}
+
+fn include_error(bounds: &mut Option<Viewport>, point: Point, error: &ErrorBar) {
+    if !point.x.is_finite() || !point.y.is_finite() {
+        return;
+    }
+    let extent = error.extent(point);
+    let bounds = bounds.get_or_insert(extent);
+    bounds.x.expand_to_include(extent.x.min);
+    bounds.x.expand_to_include(extent.x.max);
+    bounds.y.expand_to_include(extent.y.min);
+    bounds.y.expand_to_include(extent.y.max);
+}
+
+/// Shift column entries so they follow points inserted at `positions`.
+///
+/// The column is padded to `old_len` first; inserted slots get `fill`.
+fn spread_column<T: Clone>(column: &mut Vec<T>, old_len: usize, positions: &[usize], fill: T) {
+    column.resize(old_len, fill.clone());
+    let total = old_len + positions.len();
+    let mut inserted = vec![false; total];
+    for index in positions {
+        inserted[*index] = true;
+    }
+    let mut old = std::mem::take(column).into_iter();
+    column.extend(inserted.into_iter().map(|is_new| {
+        if is_new {
+            fill.clone()
+        } else {
+            old.next().unwrap_or_else(|| fill.clone())
+        }
+    }));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,4 +1121,198 @@ mod tests {
         assert_eq!(store.data().len(), 3);
         assert_eq!(store.generation(), 3);
     }
+
+    #[test]
+    fn retention_evicts_in_batches_and_keeps_bounds() {
+        let mut store = SeriesStore::with_base_chunk(AppendOnlyData::indexed(), 4);
+        store.set_retention(Retention {
+            max_points: Some(32),
+            max_x_span: None,
+        });
+        let mut last = 0;
+        for value in 0..100 {
+            last = store.push_y(f64::from(value)).unwrap();
+            assert!(store.data().len() <= 32);
+        }
+        assert_eq!(store.data().point(last).map(|point| point.y), Some(99.0));
+        let len = store.data().len();
+        assert!(len >= 30);
+        assert_eq!(store.trimmed(), (100 - len) as u64);
+        let bounds = store.bounds().expect("bounds");
+        assert_eq!(bounds.x.min, (100 - len) as f64);
+        assert_eq!(bounds.y.max, 99.0);
+
+        let mut store = SeriesStore::with_base_chunk(AppendOnlyData::explicit(), 4);
+        store.set_retention(Retention {
+            max_points: None,
+            max_x_span: Some(16.0),
+        });
+        let _ = store.extend_bands((0..40).map(|x| (f64::from(x), Range::new(-1.0, 1.0))));
+        let first = store.data().points()[0].x;
+        assert!((39.0 - first - 16.0).abs() <= 1.0);
+        assert_eq!(store.band(0), Some(Range::new(-1.0, 1.0)));
+        assert_eq!(store.bounds().map(|bounds| bounds.x.min), Some(first));
+    }
+
+    #[test]
+    fn extend_vectors_stays_aligned_with_points() {
+        let mut store = SeriesStore::with_base_chunk(AppendOnlyData::explicit(), 4);
+        let _ = store.push_point(Point::new(0.0, 0.0));
+        let _ = store.extend_vectors([(Point::new(1.0, 1.0), Vector::new(2.0, 0.0))]);
+        assert_eq!(store.vector(0), Some(Vector::default()));
+        assert_eq!(store.vector(1), Some(Vector::new(2.0, 0.0)));
+        assert_eq!(store.vector(2), None);
+    }
+
+    #[test]
+    fn bands_extend_bounds_and_follow_truncation() {
+        let mut store = SeriesStore::with_base_chunk(AppendOnlyData::explicit(), 4);
+        let _ = store.extend_bands([
+            (0.0, Range::new(-5.0, 1.0)),
+            (1.0, Range::new(2.0, 0.0)),
+            (2.0, Range::new(0.5, 1.5)),
+        ]);
+        assert_eq!(store.data().point(1), Some(Point::new(1.0, 1.0)));
+        assert_eq!(store.bounds().map(|b| b.y), Some(Range::new(-5.0, 2.0)));
+
+        assert_eq!(store.truncate_before_x(1.0), 1);
+        assert_eq!(store.band(0), Some(Range::new(0.0, 2.0)));
+        assert_eq!(store.bounds().map(|b| b.y), Some(Range::new(0.0, 2.0)));
+    }
+
+    #[test]
+    fn candles_merge_in_groups_aligned_to_the_data() {
+        let mut store = SeriesStore::with_base_chunk(AppendOnlyData::explicit(), 4);
+        let _ = store.extend_candles((0..16).map(|i| {
+            let i = f64::from(i);
+            (i, Ohlc::new(i, i + 2.0, i - 1.0, i + 1.0))
+        }));
+        assert_eq!(store.data().point(2), Some(Point::new(2.0, 3.0)));
+        assert_eq!(store.bounds().map(|b| b.y), Some(Range::new(-1.0, 17.0)));
+
+        let merged = store.decimate_candles(Range::new(0.0, 15.0), 4);
+        assert_eq!(merged.len(), 4);
+        assert_eq!(
+            merged[0],
+            (Range::new(0.0, 3.0), Ohlc::new(0.0, 5.0, -1.0, 4.0))
+        );
+
+        // Evicting candles keeps the remaining groups in place.
+        assert_eq!(store.truncate_before_x(3.0), 3);
+        let merged = store.decimate_candles(Range::new(3.0, 15.0), 4);
+        assert_eq!(merged[0].0, Range::new(3.0, 3.0));
+        assert_eq!(
+            merged[1],
+            (Range::new(4.0, 7.0), Ohlc::new(4.0, 9.0, 3.0, 8.0))
+        );
+        assert_eq!(store.bounds().map(|b| b.y), Some(Range::new(2.0, 17.0)));
+    }
+
+    #[test]
+    fn sort_on_insert_keeps_vectors_and_summaries_aligned() {
+        let mut store = SeriesStore::with_base_chunk(AppendOnlyData::explicit(), 2);
+        store.set_append_policy(AppendPolicy::SortOnInsert);
+        let _ = store.extend_vectors([
+            (Point::new(0.0, 0.0), Vector::new(0.0, 1.0)),
+            (Point::new(2.0, 2.0), Vector::new(2.0, 1.0)),
+        ]);
+        let _ = store.extend_vectors([(Point::new(1.0, 1.0), Vector::new(1.0, 1.0))]);
+        assert_eq!(store.push_point(Point::new(0.5, 0.5)), Ok(1));
+        assert_eq!(store.vector(1), Some(Vector::default()));
+        assert_eq!(store.vector(2), Some(Vector::new(1.0, 1.0)));
+        assert_eq!(store.vector(3), Some(Vector::new(2.0, 1.0)));
+        assert_eq!(store.generation(), 4);
+
+        let mut scratch = DecimationScratch::new();
+        let decimated = store.decimate(Range::new(0.0, 2.0), 1, &mut scratch);
+        assert!(decimated.windows(2).all(|pair| pair[0].x <= pair[1].x));
+    }
+
+    #[test]
+    fn segments_break_decimated_lines() {
+        let mut store = SeriesStore::indexed();
+        let _ = store.extend_y([1.0, 2.0, 3.0]);
+        store.begin_segment();
+        store.begin_segment();
+        let _ = store.extend_y([4.0, 5.0]);
+        assert_eq!(store.segments(), vec![0..3, 3..5]);
+        assert_eq!(store.segment_stats()[1].mean, Some(4.5));
+        assert!(store.has_breaks());
+
+        let mut scratch = DecimationScratch::new();
+        let decimated = store.decimate(Range::new(0.0, 4.0), 100, &mut scratch);
+        assert_eq!(decimated.len(), 6);
+        assert!(decimated[3].x.is_nan());
+
+        store.truncate_before_x(3.0);
+        assert_eq!(store.segments(), vec![0..2]);
+        assert!(!store.has_breaks());
+    }
+
+    #[test]
+    fn late_points_merge_into_sorted_summaries() {
+        let mut store = SeriesStore::with_base_chunk(AppendOnlyData::explicit(), 2);
+        let _ = store.extend_points((0..8).map(|i| Point::new((i * 2) as f64, 0.0)));
+        store.insert_point(Point::new(5.0, 9.0)).unwrap();
+        store.insert_point(Point::new(1.0, -9.0)).unwrap();
+        assert!(store.has_pending());
+        assert_eq!(store.data().len(), 8);
+
+        store.flush_pending();
+        assert!(!store.has_pending());
+        assert!(store.data().is_monotonic());
+        assert_eq!(store.data().point(1), Some(Point::new(1.0, -9.0)));
+        assert_eq!(store.data().point(4), Some(Point::new(5.0, 9.0)));
+
+        let rebuilt = SeriesStore::with_base_chunk(store.data().clone(), 2);
+        let mut scratch = DecimationScratch::new();
+        let expected = rebuilt
+            .decimate(Range::new(0.0, 14.0), 1, &mut scratch)
+            .to_vec();
+        let decimated = store.decimate(Range::new(0.0, 14.0), 1, &mut scratch);
+        assert_eq!(decimated, expected.as_slice());
+
+        let generation = store.generation();
+        store.update_point(0, 42.0).unwrap();
+        assert!(store.generation() > generation);
+        let decimated = store.decimate(Range::new(0.0, 14.0), 1, &mut scratch);
+        assert!(decimated.contains(&Point::new(0.0, 42.0)));
+    }
+
+    #[test]
+    fn scrolling_decimation_matches_a_fresh_pass() {
+        let wave = |i: usize| ((i * 37) % 101) as f64 - 50.0;
+        let mut store = SeriesStore::indexed();
+        let _ = store.extend_y((0..1000).map(wave));
+        let mut scroll = ScrollDecimation::default();
+        let fresh = |store: &SeriesStore, range: Range| {
+            store
+                .decimate_scrolling(range, 10, &mut ScrollDecimation::default())
+                .map(<[Point]>::to_vec)
+        };
+
+        let range = Range::new(900.0, 999.0);
+        let first = store
+            .decimate_scrolling(range, 10, &mut scroll)
+            .map(<[Point]>::to_vec);
+        assert_eq!(first, fresh(&store, range));
+
+        for step in 1..6 {
+            let _ = store.extend_y((1000 + (step - 1) * 7..1000 + step * 7).map(wave));
+            store.truncate_before_x(800.0 + step as f64 * 7.0);
+            let range = Range::new(900.0 + step as f64 * 7.0, 999.0 + step as f64 * 7.0);
+            let scrolled = store
+                .decimate_scrolling(range, 10, &mut scroll)
+                .map(<[Point]>::to_vec);
+            assert_eq!(scrolled, fresh(&store, range));
+        }
+
+        store.update_point(store.data().len() - 50, 1000.0).unwrap();
+        let range = Range::new(935.0, 1034.0);
+        let scrolled = store
+            .decimate_scrolling(range, 10, &mut scroll)
+            .map(<[Point]>::to_vec);
+        assert_eq!(scrolled, fresh(&store, range));
+        assert!(scrolled.is_some_and(|points| points.iter().any(|point| point.y == 1000.0)));
+    }
 }
@@ -1,8 +1,12 @@
 //! Multi-level summaries and decimation helpers.
 
+use crate::datasource::AppendOnlyData;
 use crate::geom::Point;
 use crate::view::Range;
 
+/// Relative bucket width change still treated as the same zoom level.
+const SCROLL_WIDTH_TOLERANCE: f64 = 1e-9;
+
 /// Min/max envelope for a bucket of points.
 ///
 /// This preserves extrema within a bucket and supports ordered emission to
@@ -121,10 +125,6 @@ impl SummaryLevel {
             buckets: Vec::new(),
         }
     }
-
-    pub(crate) fn buckets(&self) -> &[MinMax] {
-        &self.buckets
-    }
 }
 
 /// Multi-level min/max summaries for append-only data.
@@ -175,6 +175,71 @@ impl SummaryLevels {
         }
     }
 
+    /// Drop summaries covering points at or after `len`.
+    ///
+    /// The cut is rounded down to a whole base chunk. Returns the number of
+    /// leading points still summarized; callers re-push the rest.
+    pub fn truncate(&mut self, len: usize) -> usize {
+        let kept = len / self.base_chunk * self.base_chunk;
+        for level in &mut self.levels {
+            level.buckets.truncate(kept / level.chunk_size);
+        }
+        self.levels.retain(|level| !level.buckets.is_empty());
+        self.partial = None;
+        kept
+    }
+
+    /// Emit min/max points covering all summarized data at roughly
+    /// `target_chunk` points per bucket.
+    ///
+    /// Coarse buckets are used where available and finer levels fill in the
+    /// tail not yet merged into the coarse level, so no raw points are read.
+    pub fn overview(&self, target_chunk: usize, out: &mut Vec<Point>) {
+        self.push_covering(target_chunk, |_| true, out);
+    }
+
+    /// Emit min/max points for buckets overlapping `x_range` at roughly
+    /// `target_chunk` points per bucket, filling the tail like
+    /// [`SummaryLevels::overview`].
+    pub(crate) fn overview_in(&self, target_chunk: usize, x_range: Range, out: &mut Vec<Point>) {
+        self.push_covering(
+            target_chunk,
+            |bucket| bucket.x_range.max >= x_range.min && bucket.x_range.min <= x_range.max,
+            out,
+        );
+    }
+
+    fn push_covering(
+        &self,
+        target_chunk: usize,
+        keep: impl Fn(&MinMax) -> bool,
+        out: &mut Vec<Point>,
+    ) {
+        let Some(level) = self.choose_level(target_chunk) else {
+            if let Some(partial) = self.partial_bucket().filter(&keep) {
+                partial.push_ordered(out);
+            }
+            return;
+        };
+        let top = self
+            .levels
+            .iter()
+            .position(|candidate| candidate.chunk_size == level.chunk_size)
+            .unwrap_or(0);
+        let mut covered = 0;
+        for level in self.levels[..=top].iter().rev() {
+            for bucket in level.buckets.iter().skip(covered / level.chunk_size) {
+                if keep(bucket) {
+                    bucket.push_ordered(out);
+                }
+            }
+            covered = covered.max(level.buckets.len() * level.chunk_size);
+        }
+        if let Some(partial) = self.partial_bucket().filter(&keep) {
+            partial.push_ordered(out);
+        }
+    }
+
     /// Return a partial bucket summary when the base chunk is not full.
     pub fn partial_bucket(&self) -> Option<MinMax> {
         self.partial.as_ref().map(MinMax::from_partial)
@@ -306,7 +371,11 @@ pub fn decimate_minmax<'a>(
     }
     let span = x_range.span();
     if span <= 0.0 {
-        scratch.points.extend_from_slice(points);
+        scratch.points.extend(
+            points
+                .iter()
+                .filter(|point| point.y.is_finite() && point.x == x_range.min),
+        );
         return scratch.output();
     }
 
@@ -340,6 +409,125 @@ pub fn decimate_minmax<'a>(
     scratch.output()
 }
 
+/// Decimated envelope reused while a view scrolls at a fixed zoom.
+///
+/// Buckets are aligned to multiples of the bucket width in absolute X, so a
+/// shifted view shares every bucket with the previous frame except those at
+/// its edges. Buckets that can no longer receive points are kept; buckets
+/// that scrolled out are dropped and only the rest is decimated again.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ScrollDecimation {
+    bucket_width: f64,
+    pixel_width: usize,
+    rewrites: u64,
+    trimmed: u64,
+    first_bucket: f64,
+    open_bucket: f64,
+    frozen: Vec<Point>,
+    output: Vec<Point>,
+}
+
+impl ScrollDecimation {
+    /// Approximate heap usage of the kept envelope in bytes.
+    pub(crate) fn memory_bytes(&self) -> usize {
+        (self.frozen.capacity() + self.output.capacity()) * size_of::<Point>()
+    }
+
+    /// Decimate monotonic `data` over `x_range`, reusing complete buckets
+    /// from the previous call.
+    ///
+    /// `rewrites` and `trimmed` identify the data layout: any change other
+    /// than an append, or a trim reaching into kept buckets, starts over.
+    pub(crate) fn update(
+        &mut self,
+        data: &AppendOnlyData,
+        rewrites: u64,
+        trimmed: u64,
+        x_range: Range,
+        pixel_width: usize,
+    ) -> &[Point] {
+        let bucket_width = x_range.span() / pixel_width as f64;
+        let first = (x_range.min / self.bucket_width).floor();
+        let first_x = data.points().first().map_or(f64::INFINITY, |point| point.x);
+        let reuse = self.pixel_width == pixel_width
+            && (self.bucket_width - bucket_width).abs() <= bucket_width * SCROLL_WIDTH_TOLERANCE
+            && self.rewrites == rewrites
+            && first >= self.first_bucket
+            && x_range.max >= self.open_bucket * self.bucket_width
+            && (self.trimmed == trimmed || first_x <= first * self.bucket_width);
+        if reuse {
+            let scrolled_out = self
+                .frozen
+                .partition_point(|point| (point.x / self.bucket_width).floor() < first);
+            self.frozen.drain(..scrolled_out);
+            self.first_bucket = first;
+            self.open_bucket = self.open_bucket.max(first);
+        } else {
+            let first = (x_range.min / bucket_width).floor();
+            *self = Self {
+                bucket_width,
+                pixel_width,
+                rewrites,
+                trimmed,
+                first_bucket: first,
+                open_bucket: first,
+                frozen: std::mem::take(&mut self.frozen),
+                output: std::mem::take(&mut self.output),
+            };
+            self.frozen.clear();
+        }
+        self.trimmed = trimmed;
+
+        let bucket_width = self.bucket_width;
+        let bucket_of = |x: f64| (x / bucket_width).floor();
+        let last = bucket_of(x_range.max);
+        // Later appends land in the bucket of the last point or after it.
+        let complete_before = data
+            .points()
+            .last()
+            .map_or(last, |point| bucket_of(point.x).min(last));
+        let scan = Range::new((self.open_bucket - 1.0) * bucket_width, x_range.max);
+        let index_range = data.range_by_x(scan);
+
+        self.output.clear();
+        self.output.extend_from_slice(&self.frozen);
+        let mut current: Option<(f64, Bucket)> = None;
+        for point in &data.points()[index_range] {
+            if !point.x.is_finite() || !point.y.is_finite() {
+                continue;
+            }
+            let index = bucket_of(point.x);
+            if index < self.open_bucket || index > last {
+                continue;
+            }
+            match current.as_mut() {
+                Some((open, bucket)) if *open == index => bucket.push(*point),
+                _ => {
+                    if let Some((open, bucket)) = current.take() {
+                        self.close_bucket(open, &bucket, complete_before);
+                    }
+                    let mut bucket = Bucket::default();
+                    bucket.push(*point);
+                    current = Some((index, bucket));
+                }
+            }
+        }
+        if let Some((open, bucket)) = current {
+            self.close_bucket(open, &bucket, complete_before);
+        }
+        self.open_bucket = self.open_bucket.max(complete_before);
+        &self.output
+    }
+
+    fn close_bucket(&mut self, index: f64, bucket: &Bucket, complete_before: f64) {
+        let start = self.output.len();
+        bucket.push_ordered(&mut self.output);
+        if index < complete_before {
+            self.frozen.extend_from_slice(&self.output[start..]);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -373,3 +561,100 @@ mod tests {
         assert_eq!(level.buckets.len(), 2);
     }
 }
+
+/// Property tests for decimation invariants; run with `--features fuzz`.
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::datasource::{AppendOnlyData, SeriesStore};
+
+    fn points(max_len: usize) -> impl Strategy<Value = Vec<Point>> {
+        prop::collection::vec((-1e3..1e3f64, -1e6..1e6f64), 0..max_len)
+            .prop_map(|pairs| pairs.into_iter().map(|(x, y)| Point::new(x, y)).collect())
+    }
+
+    fn sorted_points(max_len: usize) -> impl Strategy<Value = Vec<Point>> {
+        points(max_len).prop_map(|mut points| {
+            points.sort_by(|a, b| a.x.total_cmp(&b.x));
+            points
+        })
+    }
+
+    fn x_range() -> impl Strategy<Value = Range> {
+        (-1.2e3..1.2e3f64, -1.2e3..1.2e3f64).prop_map(|(a, b)| Range::new(a.min(b), a.max(b)))
+    }
+
+    fn is_sorted_by_x(points: &[Point]) -> bool {
+        points.windows(2).all(|pair| pair[0].x <= pair[1].x)
+    }
+
+    proptest! {
+        #[test]
+        fn minmax_output_stays_in_range_and_keeps_bucket_extrema(
+            points in points(400),
+            range in x_range(),
+            width in 1usize..64,
+        ) {
+            let mut scratch = DecimationScratch::new();
+            let out = decimate_minmax(&points, range, width, &mut scratch).to_vec();
+            prop_assert!(out.len() <= width * 2 || range.span() <= 0.0);
+            for point in &out {
+                prop_assert!(point.x >= range.min && point.x <= range.max);
+                prop_assert!(points.contains(point));
+            }
+            if range.span() > 0.0 {
+                let mut extrema = vec![None::<(f64, f64)>; width];
+                for point in &points {
+                    let t = (point.x - range.min) / range.span();
+                    if !(0.0..=1.0).contains(&t) {
+                        continue;
+                    }
+                    let index = ((t * width as f64) as usize).min(width - 1);
+                    let entry = extrema[index].get_or_insert((point.y, point.y));
+                    *entry = (entry.0.min(point.y), entry.1.max(point.y));
+                }
+                for (min, max) in extrema.into_iter().flatten() {
+                    prop_assert!(out.iter().any(|point| point.y == min));
+                    prop_assert!(out.iter().any(|point| point.y == max));
+                }
+            }
+        }
+
+        #[test]
+        fn minmax_keeps_sorted_input_sorted(
+            points in sorted_points(400),
+            range in x_range(),
+            width in 1usize..64,
+        ) {
+            let mut scratch = DecimationScratch::new();
+            let out = decimate_minmax(&points, range, width, &mut scratch);
+            prop_assert!(is_sorted_by_x(out));
+        }
+
+        #[test]
+        fn store_decimation_returns_sorted_data_points_with_global_extrema(
+            points in sorted_points(600),
+            width in 1usize..64,
+        ) {
+            let store = SeriesStore::with_base_chunk(
+                AppendOnlyData::from_iter_points(points.iter().copied()),
+                4,
+            );
+            let mut scratch = DecimationScratch::new();
+            let range = Range::new(-1e3, 1e3);
+            let out = store.decimate(range, width, &mut scratch);
+            prop_assert!(is_sorted_by_x(out));
+            for point in out {
+                prop_assert!(points.contains(point));
+            }
+            let min = points.iter().map(|point| point.y).reduce(f64::min);
+            let max = points.iter().map(|point| point.y).reduce(f64::max);
+            if let (Some(min), Some(max)) = (min, max) {
+                prop_assert!(out.iter().any(|point| point.y == min));
+                prop_assert!(out.iter().any(|point| point.y == max));
+            }
+        }
+    }
+}
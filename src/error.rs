@@ -0,0 +1,118 @@
+//! Crate-wide error type and lock poisoning recovery.
+//!
+//! Operations report their own focused errors, such as [`AppendError`] and
+//! [`SpecError`]; [`Error`] wraps all of them for callers that want a single
+//! type to propagate with `?`.
+//!
+//! Plot, series and link-group state lives behind `RwLock`s shared with the
+//! UI thread. A panic while one of them is held poisons the lock; the crate
+//! keeps using the data instead of panicking again on every later access,
+//! since each update leaves the state consistent before it can panic.
+
+use std::fmt;
+use std::io;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::datasource::AppendError;
+use crate::spec::SpecError;
+
+/// Any error reported by this crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Appending to a series failed.
+    Append(AppendError),
+    /// Reading or writing an export, import or recording failed.
+    Io(io::Error),
+    /// Parsing or building a plot spec failed.
+    Spec(SpecError),
+    /// A lock was poisoned by a panic on another thread.
+    ///
+    /// Only returned by the `try_` accessors; other accessors recover the
+    /// data and continue.
+    Poisoned,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Append(err) => write!(f, "append failed: {err}"),
+            Error::Io(err) => write!(f, "I/O error: {err}"),
+            Error::Spec(err) => write!(f, "plot spec error: {err}"),
+            Error::Poisoned => write!(f, "lock poisoned by a panic on another thread"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Append(err) => Some(err),
+            Error::Io(err) => Some(err),
+            Error::Spec(err) => Some(err),
+            Error::Poisoned => None,
+        }
+    }
+}
+
+impl From<AppendError> for Error {
+    fn from(err: AppendError) -> Self {
+        Error::Append(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<SpecError> for Error {
+    fn from(err: SpecError) -> Self {
+        Error::Spec(err)
+    }
+}
+
+impl<T> From<PoisonError<T>> for Error {
+    fn from(_: PoisonError<T>) -> Self {
+        Error::Poisoned
+    }
+}
+
+/// Lock access that recovers the data from a poisoned lock.
+pub(crate) trait RecoverLock<T> {
+    fn read_unpoisoned(&self) -> RwLockReadGuard<'_, T>;
+    fn write_unpoisoned(&self) -> RwLockWriteGuard<'_, T>;
+}
+
+impl<T> RecoverLock<T> for RwLock<T> {
+    fn read_unpoisoned(&self) -> RwLockReadGuard<'_, T> {
+        self.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_unpoisoned(&self) -> RwLockWriteGuard<'_, T> {
+        self.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn poisoned_lock_still_yields_data() {
+        let lock = Arc::new(RwLock::new(1));
+        let poisoner = Arc::clone(&lock);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.write().expect("fresh lock");
+            panic!("poison the lock");
+        })
+        .join();
+        assert!(lock.is_poisoned());
+        *lock.write_unpoisoned() += 1;
+        assert_eq!(*lock.read_unpoisoned(), 2);
+        let err: Error = lock.read().map(|_| ()).unwrap_err().into();
+        assert!(matches!(err, Error::Poisoned));
+    }
+}
@@ -0,0 +1,119 @@
+//! Tabular export of plot data.
+//!
+//! [`CursorTable`] holds the value of every visible series at one or more
+//! cursor X positions, as produced by
+//! [`Plot::cursor_table`](crate::plot::Plot::cursor_table). Tables can be
+//! written as CSV or as tab-separated text ready to paste into a
+//! spreadsheet.
+//!
+//! [`Plot::export_decimated`](crate::plot::Plot::export_decimated) writes a
+//! min/max reduced copy of a series as `x,y` CSV for lightweight previews.
+//!
+//! [`Plot::export_csv`](crate::plot::Plot::export_csv) writes the raw points
+//! of every visible series, limited to an [`ExportScope`], for offline
+//! analysis of the data just zoomed into.
+
+use std::io::{self, Write};
+
+use crate::geom::Point;
+use crate::view::Range;
+
+/// Which points [`Plot::export_csv`](crate::plot::Plot::export_csv) writes.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ExportScope {
+    /// Every point of every visible series.
+    #[default]
+    All,
+    /// Points inside the X range of the plot's current viewport.
+    Viewport,
+    /// Points inside an X range, such as a brush selection.
+    XRange(Range),
+}
+
+/// Values of each series at a set of cursor X positions.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CursorTable {
+    /// Cursor X positions, one column per value.
+    pub xs: Vec<f64>,
+    /// One row per series.
+    pub rows: Vec<CursorRow>,
+}
+
+/// Series values in a [`CursorTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CursorRow {
+    /// Series name.
+    pub series: String,
+    /// Value at each cursor X, or `None` outside the series data.
+    pub values: Vec<Option<f64>>,
+}
+
+impl CursorTable {
+    /// Write the table as CSV.
+    ///
+    /// The header row is `series` followed by the cursor X positions; values
+    /// missing at a cursor are left empty.
+    pub fn write_csv(&self, writer: impl Write) -> io::Result<()> {
+        self.write_delimited(writer, ',')
+    }
+
+    /// Format the table as tab-separated text for the clipboard.
+    pub fn to_tsv(&self) -> String {
+        let mut bytes = Vec::new();
+        self.write_delimited(&mut bytes, '\t')
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(bytes).expect("table text is UTF-8")
+    }
+
+    fn write_delimited(&self, mut writer: impl Write, delimiter: char) -> io::Result<()> {
+        write!(writer, "series")?;
+        for x in &self.xs {
+            write!(writer, "{delimiter}{x}")?;
+        }
+        writeln!(writer)?;
+        for row in &self.rows {
+            write!(writer, "{}", quote_field(&row.series, delimiter))?;
+            for value in &row.values {
+                match value {
+                    Some(value) => write!(writer, "{delimiter}{value}")?,
+                    None => write!(writer, "{delimiter}")?,
+                }
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write points as CSV with an `x,y` header.
+pub(crate) fn write_points_csv(mut writer: impl Write, points: &[Point]) -> io::Result<()> {
+    writeln!(writer, "x,y")?;
+    for point in points {
+        writeln!(writer, "{},{}", point.x, point.y)?;
+    }
+    Ok(())
+}
+
+/// Write `series,x,y` rows for the points of a named series.
+pub(crate) fn write_series_rows<'a>(
+    writer: &mut impl Write,
+    series: &str,
+    points: impl IntoIterator<Item = &'a Point>,
+) -> io::Result<usize> {
+    let series = quote_field(series, ',');
+    let mut rows = 0;
+    for point in points {
+        writeln!(writer, "{series},{},{}", point.x, point.y)?;
+        rows += 1;
+    }
+    Ok(rows)
+}
+
+/// Quote a field that contains the delimiter, quotes or line breaks.
+pub(crate) fn quote_field(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,512 @@
+use crate::axis::{
+    AxisConfig, AxisLayout, AxisUnits, DateBoundaries, TextMeasurer, UnitPlacement, measure_lines,
+};
+use crate::geom::{Point as DataPoint, ScreenPoint, ScreenRect};
+use crate::plot::Plot;
+use crate::render::{
+    Color, LineSegment, LineStyle, RectStyle, RenderCommand, RenderList, TextStyle,
+};
+use crate::style::Theme;
+use crate::transform::Transform;
+
+use super::constants::{
+    AXIS_PADDING, DATE_BOUNDARY_ALPHA, DATE_BOUNDARY_MIN_SPACING, DATE_BOUNDARY_WIDTH,
+    DATE_LABEL_FONT_SIZE, DATE_SHADE_ALPHA, TICK_LENGTH_MAJOR, TICK_LENGTH_MINOR,
+};
+use super::geometry::rect_intersects;
+use super::overlays::clamp_label_position;
+use super::series::with_alpha;
+
+pub(crate) fn build_grid(
+    render: &mut RenderList,
+    plot: &Plot,
+    x_layout: &AxisLayout,
+    y_layout: &AxisLayout,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let theme = plot.theme();
+    let mut major = Vec::new();
+    let mut minor = Vec::new();
+
+    if plot.x_axis().show_grid() {
+        for tick in &x_layout.ticks {
+            let x = transform
+                .data_to_screen(DataPoint::new(tick.value, transform.viewport().y.min))
+                .map(|p| p.x);
+            let Some(x) = x else { continue };
+            let segment = LineSegment::new(
+                ScreenPoint::new(x, plot_rect.min.y),
+                ScreenPoint::new(x, plot_rect.max.y),
+            );
+            if tick.is_major {
+                major.push(segment);
+            } else if plot.x_axis().show_minor_grid() {
+                minor.push(segment);
+            }
+        }
+    }
+
+    if plot.y_axis().show_grid() {
+        for tick in &y_layout.ticks {
+            let y = transform
+                .data_to_screen(DataPoint::new(transform.viewport().x.min, tick.value))
+                .map(|p| p.y);
+            let Some(y) = y else { continue };
+            let segment = LineSegment::new(
+                ScreenPoint::new(plot_rect.min.x, y),
+                ScreenPoint::new(plot_rect.max.x, y),
+            );
+            if tick.is_major {
+                major.push(segment);
+            } else if plot.y_axis().show_minor_grid() {
+                minor.push(segment);
+            }
+        }
+    }
+
+    render.push(RenderCommand::ClipRect(plot_rect));
+    let date_boundaries = plot.x_axis().date_boundaries().map(|boundaries| {
+        let max_count = (plot_rect.width() / DATE_BOUNDARY_MIN_SPACING).max(1.0) as usize;
+        (
+            boundaries,
+            boundaries.boundaries(transform.viewport().x, max_count),
+        )
+    });
+    if let Some((boundaries, values)) = &date_boundaries {
+        build_date_shading(render, theme, boundaries, values, transform, plot_rect);
+    }
+    if !minor.is_empty() {
+        render.push(RenderCommand::LineSegments {
+            segments: minor,
+            style: LineStyle {
+                color: theme.grid_minor,
+                width: 1.0,
+                dash: None,
+            },
+        });
+    }
+    if !major.is_empty() {
+        render.push(RenderCommand::LineSegments {
+            segments: major,
+            style: LineStyle {
+                color: theme.grid_major,
+                width: 1.0,
+                dash: None,
+            },
+        });
+    }
+
+    if let Some((boundaries, values)) = &date_boundaries {
+        build_date_boundaries(render, theme, boundaries, values, transform, plot_rect);
+    }
+
+    if plot.x_axis().show_zero_line() {
+        if transform.viewport().y.min <= 0.0 && transform.viewport().y.max >= 0.0 {
+            if let Some(y) = transform
+                .data_to_screen(DataPoint::new(transform.viewport().x.min, 0.0))
+                .map(|p| p.y)
+            {
+                render.push(RenderCommand::LineSegments {
+                    segments: vec![LineSegment::new(
+                        ScreenPoint::new(plot_rect.min.x, y),
+                        ScreenPoint::new(plot_rect.max.x, y),
+                    )],
+                    style: LineStyle {
+                        color: theme.axis,
+                        width: 1.0,
+                        dash: None,
+                    },
+                });
+            }
+        }
+    }
+
+    if plot.y_axis().show_zero_line() {
+        if transform.viewport().x.min <= 0.0 && transform.viewport().x.max >= 0.0 {
+            if let Some(x) = transform
+                .data_to_screen(DataPoint::new(0.0, transform.viewport().y.min))
+                .map(|p| p.x)
+            {
+                render.push(RenderCommand::LineSegments {
+                    segments: vec![LineSegment::new(
+                        ScreenPoint::new(x, plot_rect.min.y),
+                        ScreenPoint::new(x, plot_rect.max.y),
+                    )],
+                    style: LineStyle {
+                        color: theme.axis,
+                        width: 1.0,
+                        dash: None,
+                    },
+                });
+            }
+        }
+    }
+
+    render.push(RenderCommand::ClipEnd);
+}
+
+/// Shade every other calendar period behind the grid.
+fn build_date_shading(
+    render: &mut RenderList,
+    theme: &Theme,
+    boundaries: &DateBoundaries,
+    values: &[f64],
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let color = with_alpha(theme.grid_major, DATE_SHADE_ALPHA);
+    let y = transform.viewport().y.min;
+    for start in values.iter().filter(|start| boundaries.is_shaded(**start)) {
+        let end = start + boundaries.period_seconds();
+        let (Some(left), Some(right)) = (
+            transform.data_to_screen(DataPoint::new(*start, y)),
+            transform.data_to_screen(DataPoint::new(end, y)),
+        ) else {
+            continue;
+        };
+        let left = left.x.max(plot_rect.min.x);
+        let right = right.x.min(plot_rect.max.x);
+        if right <= left {
+            continue;
+        }
+        render.push(RenderCommand::Rect {
+            rect: ScreenRect::new(
+                ScreenPoint::new(left, plot_rect.min.y),
+                ScreenPoint::new(right, plot_rect.max.y),
+            ),
+            style: RectStyle {
+                fill: color,
+                stroke: color,
+                stroke_width: 0.0,
+            },
+        });
+    }
+}
+
+/// Draw calendar boundary lines with their date labels above the grid.
+fn build_date_boundaries(
+    render: &mut RenderList,
+    theme: &Theme,
+    boundaries: &DateBoundaries,
+    values: &[f64],
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let x_range = transform.viewport().x;
+    let mut segments = Vec::new();
+    let mut labels = Vec::new();
+    for value in values
+        .iter()
+        .filter(|value| **value >= x_range.min && **value <= x_range.max)
+    {
+        let Some(x) = transform
+            .data_to_screen(DataPoint::new(*value, transform.viewport().y.min))
+            .map(|p| p.x)
+        else {
+            continue;
+        };
+        segments.push(LineSegment::new(
+            ScreenPoint::new(x, plot_rect.min.y),
+            ScreenPoint::new(x, plot_rect.max.y),
+        ));
+        if boundaries.labels {
+            labels.push(RenderCommand::Text {
+                position: ScreenPoint::new(x + AXIS_PADDING * 0.5, plot_rect.min.y + 2.0),
+                text: boundaries.label(*value),
+                style: TextStyle {
+                    color: theme.axis,
+                    size: DATE_LABEL_FONT_SIZE,
+                },
+            });
+        }
+    }
+    if !segments.is_empty() {
+        render.push(RenderCommand::LineSegments {
+            segments,
+            style: LineStyle {
+                color: with_alpha(theme.axis, DATE_BOUNDARY_ALPHA),
+                width: DATE_BOUNDARY_WIDTH,
+                dash: None,
+            },
+        });
+    }
+    for label in labels {
+        render.push(label);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_axes(
+    render: &mut RenderList,
+    plot: &Plot,
+    x_layout: &AxisLayout,
+    y_layout: &AxisLayout,
+    plot_rect: ScreenRect,
+    transform: &Transform,
+    x_axis_rect: ScreenRect,
+    y_axis_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+) {
+    let theme = plot.theme();
+    let mut ticks_major = Vec::new();
+    let mut ticks_minor = Vec::new();
+    let label_gap = 2.0_f32;
+    let mut last_x_label_right = f32::NEG_INFINITY;
+    let mut last_y_label_top = f32::INFINITY;
+    let x_title_rect = axis_title_text(plot.x_axis(), &plot.inferred_x_units()).map(|title| {
+        let size = measurer.measure(&title, plot.x_axis().label_size());
+        let pos = clamp_label_position(
+            ScreenPoint::new(
+                plot_rect.min.x + (plot_rect.width() - size.0) * 0.5,
+                x_axis_rect.max.y - size.1 - AXIS_PADDING,
+            ),
+            size,
+            x_axis_rect,
+        );
+        ScreenRect::new(pos, ScreenPoint::new(pos.x + size.0, pos.y + size.1))
+    });
+    let y_title_rect = axis_title_text(plot.y_axis(), &plot.inferred_y_units()).map(|title| {
+        let size = measurer.measure(&title, plot.y_axis().label_size());
+        let pos = clamp_label_position(
+            ScreenPoint::new(
+                y_axis_rect.min.x + AXIS_PADDING,
+                y_axis_rect.min.y + AXIS_PADDING,
+            ),
+            size,
+            y_axis_rect,
+        );
+        ScreenRect::new(pos, ScreenPoint::new(pos.x + size.0, pos.y + size.1))
+    });
+
+    if plot.x_axis().show_border() {
+        render.push(RenderCommand::Rect {
+            rect: plot_rect,
+            style: RectStyle {
+                fill: Color::new(0.0, 0.0, 0.0, 0.0),
+                stroke: theme.axis,
+                stroke_width: 1.0,
+            },
+        });
+    }
+
+    for tick in &x_layout.ticks {
+        if let Some(x) = transform
+            .data_to_screen(DataPoint::new(tick.value, transform.viewport().y.min))
+            .map(|p| p.x)
+        {
+            let length = if tick.is_major {
+                TICK_LENGTH_MAJOR
+            } else {
+                TICK_LENGTH_MINOR
+            };
+            let segment = LineSegment::new(
+                ScreenPoint::new(x, plot_rect.max.y),
+                ScreenPoint::new(x, plot_rect.max.y + length),
+            );
+            if tick.is_major {
+                ticks_major.push(segment);
+            } else if plot.x_axis().show_minor_grid() {
+                ticks_minor.push(segment);
+            }
+
+            if tick.is_major && !tick.label.is_empty() {
+                let size = measure_lines(measurer, &tick.label, plot.x_axis().label_size());
+                let pos = clamp_label_position(
+                    ScreenPoint::new(
+                        x - size.0 * 0.5,
+                        plot_rect.max.y + TICK_LENGTH_MAJOR + AXIS_PADDING,
+                    ),
+                    size,
+                    x_axis_rect,
+                );
+                let label_left = pos.x;
+                let label_right = pos.x + size.0;
+                let label_rect =
+                    ScreenRect::new(pos, ScreenPoint::new(label_right, pos.y + size.1));
+                let overlaps_title = x_title_rect
+                    .map(|rect| rect_intersects(label_rect, rect))
+                    .unwrap_or(false);
+                if !overlaps_title && label_left >= last_x_label_right + label_gap {
+                    push_tick_label(
+                        render,
+                        measurer,
+                        pos,
+                        size.0,
+                        &tick.label,
+                        TextStyle {
+                            color: theme.axis,
+                            size: plot.x_axis().label_size(),
+                        },
+                    );
+                    last_x_label_right = label_right;
+                }
+            }
+        }
+    }
+
+    for tick in &y_layout.ticks {
+        if let Some(y) = transform
+            .data_to_screen(DataPoint::new(transform.viewport().x.min, tick.value))
+            .map(|p| p.y)
+        {
+            let length = if tick.is_major {
+                TICK_LENGTH_MAJOR
+            } else {
+                TICK_LENGTH_MINOR
+            };
+            let segment = LineSegment::new(
+                ScreenPoint::new(plot_rect.min.x - length, y),
+                ScreenPoint::new(plot_rect.min.x, y),
+            );
+            if tick.is_major {
+                ticks_major.push(segment);
+            } else if plot.y_axis().show_minor_grid() {
+                ticks_minor.push(segment);
+            }
+
+            if tick.is_major && !tick.label.is_empty() {
+                let size = measure_lines(measurer, &tick.label, plot.y_axis().label_size());
+                let pos = clamp_label_position(
+                    ScreenPoint::new(
+                        plot_rect.min.x - TICK_LENGTH_MAJOR - AXIS_PADDING - size.0,
+                        y - size.1 * 0.5,
+                    ),
+                    size,
+                    y_axis_rect,
+                );
+                let label_top = pos.y;
+                let label_bottom = pos.y + size.1;
+                let label_rect =
+                    ScreenRect::new(pos, ScreenPoint::new(pos.x + size.0, label_bottom));
+                let overlaps_title = y_title_rect
+                    .map(|rect| rect_intersects(label_rect, rect))
+                    .unwrap_or(false);
+                if !overlaps_title && label_bottom <= last_y_label_top - label_gap {
+                    push_tick_label(
+                        render,
+                        measurer,
+                        pos,
+                        size.0,
+                        &tick.label,
+                        TextStyle {
+                            color: theme.axis,
+                            size: plot.y_axis().label_size(),
+                        },
+                    );
+                    last_y_label_top = label_top;
+                }
+            }
+        }
+    }
+
+    if !ticks_minor.is_empty() {
+        render.push(RenderCommand::LineSegments {
+            segments: ticks_minor,
+            style: LineStyle {
+                color: theme.axis,
+                width: 1.0,
+                dash: None,
+            },
+        });
+    }
+    if !ticks_major.is_empty() {
+        render.push(RenderCommand::LineSegments {
+            segments: ticks_major,
+            style: LineStyle {
+                color: theme.axis,
+                width: 1.0,
+                dash: None,
+            },
+        });
+    }
+}
+
+pub(crate) fn build_axis_titles(
+    render: &mut RenderList,
+    plot: &Plot,
+    plot_rect: ScreenRect,
+    x_axis_rect: ScreenRect,
+    y_axis_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+) {
+    let theme = plot.theme();
+    if let Some(title) = axis_title_text(plot.x_axis(), &plot.inferred_x_units()) {
+        let size = measurer.measure(&title, plot.x_axis().label_size());
+        let pos = clamp_label_position(
+            ScreenPoint::new(
+                plot_rect.min.x + (plot_rect.width() - size.0) * 0.5,
+                x_axis_rect.max.y - size.1 - AXIS_PADDING,
+            ),
+            size,
+            x_axis_rect,
+        );
+        render.push(RenderCommand::Text {
+            position: pos,
+            text: title,
+            style: TextStyle {
+                color: theme.axis,
+                size: plot.x_axis().label_size(),
+            },
+        });
+    }
+
+    if let Some(title) = axis_title_text(plot.y_axis(), &plot.inferred_y_units()) {
+        let pos = clamp_label_position(
+            ScreenPoint::new(
+                y_axis_rect.min.x + AXIS_PADDING,
+                y_axis_rect.min.y + AXIS_PADDING,
+            ),
+            measurer.measure(&title, plot.y_axis().label_size()),
+            y_axis_rect,
+        );
+        render.push(RenderCommand::Text {
+            position: pos,
+            text: title,
+            style: TextStyle {
+                color: theme.axis,
+                size: plot.y_axis().label_size(),
+            },
+        });
+    }
+}
+
+/// Push a tick label at `position`, centering each line within `width`.
+fn push_tick_label(
+    render: &mut RenderList,
+    measurer: &impl TextMeasurer,
+    position: ScreenPoint,
+    width: f32,
+    label: &str,
+    style: TextStyle,
+) {
+    let mut y = position.y;
+    for line in label.lines() {
+        let (line_width, line_height) = measurer.measure(line, style.size);
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(position.x + (width - line_width) * 0.5, y),
+            text: line.to_string(),
+            style: style.clone(),
+        });
+        y += line_height;
+    }
+}
+
+pub(crate) fn axis_title_text(axis: &AxisConfig, inferred: &AxisUnits) -> Option<String> {
+    let inferred = match inferred {
+        AxisUnits::Unknown => None,
+        AxisUnits::Uniform(units) => Some(units.clone()),
+        AxisUnits::Mixed(units) => Some(format!("mixed units: {}", units.join(", "))),
+    };
+    let units = match axis.units() {
+        Some(_) if axis.unit_placement() != UnitPlacement::Title => None,
+        Some(units) => Some(units.to_string()),
+        None => inferred,
+    };
+    match (axis.title(), units) {
+        (Some(title), Some(units)) => Some(format!("{title} ({units})")),
+        (Some(title), None) => Some(title.to_string()),
+        (None, Some(units)) => Some(units.to_string()),
+        (None, None) => None,
+    }
+}
@@ -0,0 +1,165 @@
+use crate::annotation::{ReferenceKind, push_references};
+use crate::axis::TextMeasurer;
+use crate::geom::{Point as DataPoint, ScreenPoint, ScreenRect};
+use crate::plot::Plot;
+use crate::render::{RectStyle, RenderCommand, RenderList, TextStyle, fit_image};
+use crate::style::{BackdropFill, GradientDirection};
+use crate::transform::Transform;
+
+use super::constants::{
+    BACKDROP_GRADIENT_STEPS, REFERENCE_LABEL_FONT_SIZE, REFERENCE_LABEL_PADDING,
+};
+use super::geometry::rect_intersects;
+use super::series::{data_rect_to_screen, lerp_color};
+use super::state::PlotUiState;
+
+pub(crate) fn build_backdrop(
+    render: &mut RenderList,
+    plot: &Plot,
+    state: &mut PlotUiState,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let Some(backdrop) = plot.backdrop() else {
+        state.backdrop_image = None;
+        return;
+    };
+    let Some(rect) = data_rect_to_screen(transform, backdrop.extent) else {
+        return;
+    };
+    if !rect_intersects(rect, plot_rect) {
+        return;
+    }
+
+    render.push(RenderCommand::ClipRect(plot_rect));
+    match &backdrop.fill {
+        BackdropFill::Image(image) => {
+            if let Some((rect, image)) =
+                fit_image(image, rect, plot_rect, &mut state.backdrop_image)
+            {
+                render.push(RenderCommand::Image { rect, image });
+            }
+        }
+        BackdropFill::Gradient {
+            start,
+            end,
+            direction,
+        } => {
+            for step in 0..BACKDROP_GRADIENT_STEPS {
+                let t0 = step as f32 / BACKDROP_GRADIENT_STEPS as f32;
+                let t1 = (step + 1) as f32 / BACKDROP_GRADIENT_STEPS as f32;
+                let color = lerp_color(*start, *end, (t0 + t1) * 0.5);
+                let strip = match direction {
+                    GradientDirection::Horizontal => ScreenRect::new(
+                        ScreenPoint::new(rect.min.x + rect.width() * t0, rect.min.y),
+                        ScreenPoint::new(rect.min.x + rect.width() * t1, rect.max.y),
+                    ),
+                    GradientDirection::Vertical => ScreenRect::new(
+                        ScreenPoint::new(rect.min.x, rect.max.y - rect.height() * t1),
+                        ScreenPoint::new(rect.max.x, rect.max.y - rect.height() * t0),
+                    ),
+                };
+                if !rect_intersects(strip, plot_rect) {
+                    continue;
+                }
+                render.push(RenderCommand::Rect {
+                    rect: strip,
+                    style: RectStyle {
+                        fill: color,
+                        stroke: color,
+                        stroke_width: 0.0,
+                    },
+                });
+            }
+        }
+    }
+    render.push(RenderCommand::ClipEnd);
+}
+
+/// Fill the X spans returned by the plot's shading providers.
+pub(crate) fn build_shading(
+    render: &mut RenderList,
+    plot: &Plot,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    if plot.shading().is_empty() {
+        return;
+    }
+    let viewport = transform.viewport();
+    render.push(RenderCommand::ClipRect(plot_rect));
+    for provider in plot.shading() {
+        for region in provider.regions(viewport.x) {
+            let (Some(left), Some(right)) = (
+                transform.data_to_screen(DataPoint::new(region.x.min, viewport.y.min)),
+                transform.data_to_screen(DataPoint::new(region.x.max, viewport.y.min)),
+            ) else {
+                continue;
+            };
+            let left = left.x.max(plot_rect.min.x);
+            let right = right.x.min(plot_rect.max.x);
+            if right <= left {
+                continue;
+            }
+            render.push(RenderCommand::Rect {
+                rect: ScreenRect::new(
+                    ScreenPoint::new(left, plot_rect.min.y),
+                    ScreenPoint::new(right, plot_rect.max.y),
+                ),
+                style: RectStyle {
+                    fill: region.color,
+                    stroke: region.color,
+                    stroke_width: 0.0,
+                },
+            });
+        }
+    }
+    render.push(RenderCommand::ClipEnd);
+}
+
+/// Draw the plot's reference lines and bands with their labels.
+pub(crate) fn build_references(
+    render: &mut RenderList,
+    plot: &Plot,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+) {
+    if plot.references().is_empty() {
+        return;
+    }
+    render.push(RenderCommand::ClipRect(plot_rect));
+    push_references(render, plot.references(), transform, plot_rect);
+    for reference in plot.references() {
+        let Some(label) = &reference.label else {
+            continue;
+        };
+        let Some(rect) = reference.screen_rect(transform, plot_rect) else {
+            continue;
+        };
+        let size = measurer.measure(label, REFERENCE_LABEL_FONT_SIZE);
+        let position = match reference.kind {
+            ReferenceKind::HLine { .. } => ScreenPoint::new(
+                plot_rect.max.x - size.0 - REFERENCE_LABEL_PADDING,
+                rect.min.y - size.1 - 2.0,
+            ),
+            ReferenceKind::VLine { .. } => ScreenPoint::new(
+                rect.min.x + REFERENCE_LABEL_PADDING,
+                plot_rect.min.y + REFERENCE_LABEL_PADDING,
+            ),
+            ReferenceKind::HBand { .. } | ReferenceKind::VBand { .. } => ScreenPoint::new(
+                rect.min.x + REFERENCE_LABEL_PADDING,
+                rect.min.y + REFERENCE_LABEL_PADDING,
+            ),
+        };
+        render.push(RenderCommand::Text {
+            position,
+            text: label.clone(),
+            style: TextStyle {
+                color: reference.label_color(),
+                size: REFERENCE_LABEL_FONT_SIZE,
+            },
+        });
+    }
+    render.push(RenderCommand::ClipEnd);
+}
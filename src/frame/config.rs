@@ -0,0 +1,484 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::geom::{ScreenPoint, ScreenRect};
+use crate::render::LineStyle;
+use crate::series::Series;
+#[cfg(any(feature = "gpui", test))]
+use crate::view::Viewport;
+
+use super::constants::{TOOLTIP_CURSOR_OFFSET, TOOLTIP_DOCK_PADDING};
+use super::geometry::clamp_point;
+
+/// Formatter for series labels in the legend, hover and pin readouts.
+///
+/// Use [`SeriesLabelFormatter::Custom`] to include series metadata, such as
+/// device ids or channels, in the label.
+#[derive(Clone, Default)]
+pub enum SeriesLabelFormatter {
+    /// Use the series name.
+    #[default]
+    Name,
+    /// Custom formatter callback.
+    Custom(Arc<dyn Fn(&Series) -> String + Send + Sync>),
+}
+
+impl SeriesLabelFormatter {
+    /// Format the label for a series.
+    pub fn format(&self, series: &Series) -> String {
+        match self {
+            Self::Name => series.name().to_string(),
+            Self::Custom(formatter) => formatter(series),
+        }
+    }
+}
+
+impl std::fmt::Debug for SeriesLabelFormatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name => write!(f, "SeriesLabelFormatter::Name"),
+            Self::Custom(_) => write!(f, "SeriesLabelFormatter::Custom(..)"),
+        }
+    }
+}
+
+/// Content of a single legend row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendRow {
+    /// Row text.
+    pub text: String,
+    /// Swatch style override; `None` uses the series color.
+    pub swatch: Option<LineStyle>,
+}
+
+impl LegendRow {
+    /// Create a row with the given text and the default swatch.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            swatch: None,
+        }
+    }
+
+    /// Override the swatch style.
+    pub fn with_swatch(mut self, swatch: LineStyle) -> Self {
+        self.swatch = Some(swatch);
+        self
+    }
+}
+
+/// Callback that builds a legend row from a series and its label.
+pub type LegendRowFn = Arc<dyn Fn(&Series, &str) -> LegendRow + Send + Sync>;
+
+/// Renderer for legend rows.
+///
+/// Custom renderers receive the series and the label produced by
+/// [`PlotViewConfig::legend_label`], and can append values or units to it.
+#[derive(Clone, Default)]
+pub enum LegendEntryRenderer {
+    /// Use the legend label and the series color.
+    #[default]
+    Default,
+    /// Custom row callback.
+    Custom(LegendRowFn),
+}
+
+impl LegendEntryRenderer {
+    /// Build the legend row for a series.
+    pub fn render(&self, series: &Series, label: &str) -> LegendRow {
+        match self {
+            Self::Default => LegendRow::new(label),
+            Self::Custom(renderer) => renderer(series, label),
+        }
+    }
+}
+
+impl std::fmt::Debug for LegendEntryRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "LegendEntryRenderer::Default"),
+            Self::Custom(_) => write!(f, "LegendEntryRenderer::Custom(..)"),
+        }
+    }
+}
+
+/// Rubber-band limits for panning past the data bounds.
+///
+/// The limits are the data bounds grown by `margin_frac`. While dragging,
+/// the view can move beyond them with increasing resistance; on release it
+/// springs back so the view is within them again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftLimits {
+    /// Maximum overshoot as a fraction of the viewport span. Zero makes the
+    /// limits hard.
+    pub overscroll_frac: f64,
+    /// Empty space allowed around the data before the limits apply, as a
+    /// fraction of the data span on each axis.
+    pub margin_frac: f64,
+    /// Duration of the spring-back animation after release.
+    pub spring_back: Duration,
+}
+
+impl SoftLimits {
+    /// Hard limits: panning stops at `margin_frac` of the data span past the
+    /// data bounds.
+    pub fn hard(margin_frac: f64) -> Self {
+        Self {
+            overscroll_frac: 0.0,
+            margin_frac,
+            ..Self::default()
+        }
+    }
+
+    /// Set the margin allowed around the data.
+    pub fn with_margin(mut self, margin_frac: f64) -> Self {
+        self.margin_frac = margin_frac;
+        self
+    }
+
+    /// Pan limits for the given data bounds.
+    #[cfg(any(feature = "gpui", test))]
+    pub(crate) fn limits(&self, data: Viewport) -> Viewport {
+        let margin = if self.margin_frac.is_finite() {
+            self.margin_frac.max(0.0)
+        } else {
+            0.0
+        };
+        Viewport::new(data.x.padded(margin, 0.0), data.y.padded(margin, 0.0))
+    }
+}
+
+impl Default for SoftLimits {
+    fn default() -> Self {
+        Self {
+            overscroll_frac: 0.15,
+            margin_frac: 0.0,
+            spring_back: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Mouse-wheel zoom behavior for one region of the plot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelZoom {
+    /// Zoom per scrolled pixel; each event scales the view by
+    /// `1 - pixels * sensitivity`.
+    pub sensitivity: f64,
+    /// Reverse the zoom direction.
+    pub invert: bool,
+    /// Zoom by this fixed ratio per wheel notch (one line of scrolling)
+    /// instead of by scroll distance. Ratios at or below 1 are ignored.
+    pub step_ratio: Option<f64>,
+}
+
+impl WheelZoom {
+    /// Zoom factor for a wheel delta, where positive `delta_pixels` zooms in.
+    #[cfg(any(feature = "gpui", test))]
+    pub(crate) fn factor(&self, delta_pixels: f32, line_height: f32) -> f64 {
+        let delta = if self.invert {
+            -delta_pixels
+        } else {
+            delta_pixels
+        } as f64;
+        let factor = match self
+            .step_ratio
+            .filter(|ratio| ratio.is_finite() && *ratio > 1.0)
+        {
+            Some(ratio) => ratio.powf(-delta / line_height.max(1.0) as f64),
+            None => 1.0 - delta * self.sensitivity,
+        };
+        factor.clamp(0.1, 10.0)
+    }
+}
+
+impl Default for WheelZoom {
+    fn default() -> Self {
+        Self {
+            sensitivity: 0.002,
+            invert: false,
+            step_ratio: None,
+        }
+    }
+}
+
+/// How the Y axis width follows the width of its tick labels.
+///
+/// Tick labels change length while panning (for example `9.5` to `10.5`),
+/// which moves the plot area sideways when the axis fits them exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AxisWidthPolicy {
+    /// Fit the current labels exactly.
+    #[default]
+    Fit,
+    /// Round the width up to a multiple of this many pixels, so small label
+    /// changes do not move the plot area.
+    Quantize(f32),
+    /// Keep the widest width seen so far; the axis never shrinks.
+    KeepWidest,
+}
+
+impl AxisWidthPolicy {
+    /// Stabilize a measured width given the width used for the last frame.
+    pub(crate) fn apply(self, measured: f32, previous: Option<f32>) -> f32 {
+        match self {
+            Self::Fit => measured,
+            Self::Quantize(step) if step > 0.0 => (measured / step).ceil() * step,
+            Self::Quantize(_) => measured,
+            Self::KeepWidest => previous.map_or(measured, |previous| previous.max(measured)),
+        }
+    }
+}
+
+/// Where the hover readout is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TooltipMode {
+    /// Next to the cursor or hovered point, flipping sides near the edges.
+    #[default]
+    Follow,
+    /// Docked to a corner of the plot area, like an oscilloscope measurement
+    /// panel. The readout still updates as the cursor moves but stays put,
+    /// which reduces visual churn on dense plots. The legend sits in the
+    /// top-right corner.
+    Anchored(PlotCorner),
+}
+
+/// Corner of the plot area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlotCorner {
+    /// Top-left corner.
+    TopLeft,
+    /// Top-right corner.
+    TopRight,
+    /// Bottom-left corner.
+    BottomLeft,
+    /// Bottom-right corner.
+    BottomRight,
+}
+
+impl TooltipMode {
+    /// Top-left corner of a readout of `size` describing `anchor`.
+    pub(crate) fn origin(
+        self,
+        anchor: ScreenPoint,
+        size: (f32, f32),
+        plot_rect: ScreenRect,
+    ) -> ScreenPoint {
+        let origin = match self {
+            Self::Follow => {
+                let offset = TOOLTIP_CURSOR_OFFSET;
+                let mut origin = ScreenPoint::new(anchor.x + offset, anchor.y + offset);
+                if origin.x + size.0 > plot_rect.max.x {
+                    origin.x = anchor.x - size.0 - offset;
+                }
+                if origin.y + size.1 > plot_rect.max.y {
+                    origin.y = anchor.y - size.1 - offset;
+                }
+                origin
+            }
+            Self::Anchored(corner) => {
+                let left = plot_rect.min.x + TOOLTIP_DOCK_PADDING;
+                let right = plot_rect.max.x - size.0 - TOOLTIP_DOCK_PADDING;
+                let top = plot_rect.min.y + TOOLTIP_DOCK_PADDING;
+                let bottom = plot_rect.max.y - size.1 - TOOLTIP_DOCK_PADDING;
+                match corner {
+                    PlotCorner::TopLeft => ScreenPoint::new(left, top),
+                    PlotCorner::TopRight => ScreenPoint::new(right, top),
+                    PlotCorner::BottomLeft => ScreenPoint::new(left, bottom),
+                    PlotCorner::BottomRight => ScreenPoint::new(right, bottom),
+                }
+            }
+        };
+        clamp_point(origin, plot_rect, size)
+    }
+}
+
+/// Configuration for the GPUI plot view.
+///
+/// These values tune interaction thresholds and layout behavior for GPUI.
+#[derive(Debug, Clone)]
+pub struct PlotViewConfig {
+    /// Pixel threshold for starting a drag.
+    pub drag_threshold_px: f32,
+    /// Pixel threshold for pin hit testing.
+    pub pin_threshold_px: f32,
+    /// Pixel threshold for unpin hit testing.
+    pub unpin_threshold_px: f32,
+    /// Padding fraction applied when auto-fitting data.
+    pub padding_frac: f64,
+    /// Minimum padding applied when auto-fitting data.
+    pub min_padding: f64,
+    /// Show legend overlay.
+    pub show_legend: bool,
+    /// Show hover coordinate readout.
+    pub show_hover: bool,
+    /// List every series with a point within the pin threshold in the hover
+    /// readout, nearest first.
+    ///
+    /// The scroll wheel then cycles the hover target instead of zooming while
+    /// several series are in reach, and clicking a point pinned by the
+    /// previous click moves the pin to the next series.
+    pub hover_all_series: bool,
+    /// Show each visible series' value interpolated exactly at the cursor X,
+    /// with a marker on every line, instead of snapping to the nearest
+    /// sample. Suits sparse data; clicks still pin the nearest sample.
+    pub hover_interpolate: bool,
+    /// Placement of the hover readout.
+    pub tooltip_mode: TooltipMode,
+    /// Hide the legend and hover readout while a pan or zoom drag is active.
+    pub hide_overlays_while_dragging: bool,
+    /// Show a warning badge when series contain invalid data.
+    pub show_data_warnings: bool,
+    /// Show a diagnostics overlay with frame timings, cache counts and point
+    /// totals from the previous frame.
+    pub show_perf_overlay: bool,
+    /// Line, scatter and stem series with at most this many points inside
+    /// the visible X range are drawn directly, skipping decimation and the
+    /// render cache. Set to 0 to always decimate.
+    pub decimation_bypass_points: usize,
+    /// Memory budget in bytes for cached decimated points, images and
+    /// contours across all series. Least recently drawn series are evicted
+    /// first when the budget is exceeded.
+    pub series_cache_bytes: usize,
+    /// Mouse-wheel zoom over the plot area.
+    pub plot_wheel_zoom: WheelZoom,
+    /// Mouse-wheel zoom over the X axis.
+    pub x_axis_wheel_zoom: WheelZoom,
+    /// Mouse-wheel zoom over the Y axis.
+    pub y_axis_wheel_zoom: WheelZoom,
+    /// Smallest span zooming can leave on an axis, as a fraction of the
+    /// larger of the data's and the viewport's magnitude.
+    ///
+    /// Keeps repeated wheel or box zoom from collapsing an axis to zero
+    /// width, where nothing can be drawn.
+    pub min_zoom_span: f64,
+    /// Rubber-band limits for panning, or `None` to pan without limits.
+    pub soft_limits: Option<SoftLimits>,
+    /// Width stabilization for the Y axis.
+    pub y_axis_width: AxisWidthPolicy,
+    /// Draw arrows along the plot border pointing toward pins outside the
+    /// viewport, with a count badge where several lie in one direction.
+    pub offscreen_pin_indicators: bool,
+    /// Snap box-zoom edges and the linked cursor to axis ticks.
+    ///
+    /// Holding Ctrl inverts this setting while interacting.
+    pub snap_to_ticks: bool,
+    /// Series label formatter for legend entries.
+    pub legend_label: SeriesLabelFormatter,
+    /// Renderer for legend rows.
+    pub legend_entry: LegendEntryRenderer,
+    /// Series label formatter for hover, pin and linked cursor readouts.
+    pub hover_label: SeriesLabelFormatter,
+    /// Settle an overscrolled view instantly instead of animating the
+    /// spring-back, for users who prefer reduced motion.
+    pub reduce_motion: bool,
+    /// Handle keyboard shortcuts while the view has focus: arrow keys pan,
+    /// `+` and `-` zoom, `0` or Home resets the view, Escape cancels a drag,
+    /// and Tab moves focus to the next plot.
+    pub keyboard_shortcuts: bool,
+    /// Draw a ring around the view while it has keyboard focus.
+    pub focus_ring: bool,
+}
+
+impl Default for PlotViewConfig {
+    fn default() -> Self {
+        Self {
+            drag_threshold_px: 4.0,
+            pin_threshold_px: 12.0,
+            unpin_threshold_px: 18.0,
+            padding_frac: 0.05,
+            min_padding: 1e-6,
+            show_legend: true,
+            show_hover: true,
+            hover_all_series: false,
+            hover_interpolate: false,
+            tooltip_mode: TooltipMode::Follow,
+            hide_overlays_while_dragging: false,
+            show_data_warnings: true,
+            show_perf_overlay: false,
+            decimation_bypass_points: 256,
+            series_cache_bytes: 64 * 1024 * 1024,
+            plot_wheel_zoom: WheelZoom::default(),
+            x_axis_wheel_zoom: WheelZoom::default(),
+            y_axis_wheel_zoom: WheelZoom::default(),
+            min_zoom_span: 1e-9,
+            soft_limits: None,
+            offscreen_pin_indicators: false,
+            y_axis_width: AxisWidthPolicy::Fit,
+            snap_to_ticks: false,
+            legend_label: SeriesLabelFormatter::Name,
+            legend_entry: LegendEntryRenderer::Default,
+            hover_label: SeriesLabelFormatter::Name,
+            reduce_motion: false,
+            keyboard_shortcuts: true,
+            focus_ring: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wheel_zoom_factor_modes() {
+        let smooth = WheelZoom::default();
+        assert!((smooth.factor(50.0, 16.0) - 0.9).abs() < 1e-9);
+        let inverted = WheelZoom {
+            invert: true,
+            ..WheelZoom::default()
+        };
+        assert!((inverted.factor(50.0, 16.0) - 1.1).abs() < 1e-9);
+        let stepped = WheelZoom {
+            step_ratio: Some(2.0),
+            ..WheelZoom::default()
+        };
+        assert!((stepped.factor(16.0, 16.0) - 0.5).abs() < 1e-9);
+        assert!((stepped.factor(-32.0, 16.0) - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn axis_width_policies() {
+        assert_eq!(AxisWidthPolicy::Fit.apply(37.0, Some(50.0)), 37.0);
+        assert_eq!(AxisWidthPolicy::Quantize(8.0).apply(37.0, None), 40.0);
+        assert_eq!(AxisWidthPolicy::Quantize(8.0).apply(40.0, None), 40.0);
+        assert_eq!(AxisWidthPolicy::KeepWidest.apply(37.0, Some(50.0)), 50.0);
+        assert_eq!(AxisWidthPolicy::KeepWidest.apply(52.0, Some(50.0)), 52.0);
+    }
+
+    #[test]
+    fn hard_limits_stop_pan_at_margin() {
+        use crate::interaction::resist_pan;
+        use crate::view::Range;
+
+        let limits = SoftLimits::hard(0.25);
+        let data = Viewport::new(Range::new(0.0, 100.0), Range::new(-1.0, 1.0));
+        let bounds = limits.limits(data);
+        assert_eq!(bounds.x, Range::new(-25.0, 125.0));
+        assert_eq!(bounds.y, Range::new(-1.5, 1.5));
+
+        let current = Viewport::new(Range::new(0.0, 20.0), Range::new(-1.0, 1.0));
+        let next = Viewport::new(Range::new(-50.0, -30.0), Range::new(-1.0, 1.0));
+        let panned = resist_pan(current, next, bounds, limits.overscroll_frac);
+        assert_eq!(panned.x, Range::new(-25.0, -5.0));
+    }
+
+    #[test]
+    fn tooltip_modes_place_readout() {
+        let plot_rect = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(200.0, 100.0));
+        let size = (40.0, 20.0);
+        let cursor = ScreenPoint::new(190.0, 10.0);
+        assert_eq!(
+            TooltipMode::Follow.origin(cursor, size, plot_rect),
+            ScreenPoint::new(138.0, 22.0)
+        );
+        let docked = TooltipMode::Anchored(PlotCorner::BottomLeft);
+        assert_eq!(
+            docked.origin(cursor, size, plot_rect),
+            ScreenPoint::new(8.0, 72.0)
+        );
+        assert_eq!(
+            docked.origin(ScreenPoint::new(5.0, 95.0), size, plot_rect),
+            ScreenPoint::new(8.0, 72.0)
+        );
+    }
+}
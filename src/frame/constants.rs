@@ -0,0 +1,60 @@
+use crate::render::Color;
+
+pub(crate) const AXIS_PADDING: f32 = 6.0;
+pub(crate) const TICK_LENGTH_MAJOR: f32 = 6.0;
+pub(crate) const TICK_LENGTH_MINOR: f32 = 3.0;
+pub(crate) const PIN_RING_INNER_PAD: f32 = 4.0;
+pub(crate) const PIN_RING_OUTER_PAD: f32 = 8.0;
+pub(crate) const PIN_UNPIN_HIGHLIGHT: Color = Color::new(0.95, 0.25, 0.25, 1.0);
+pub(crate) const PIN_LABEL_OFFSET: f32 = 10.0;
+pub(crate) const MAX_PIN_LABELS: usize = 12;
+pub(crate) const MAX_PIN_LABEL_COVERAGE: f32 = 0.35;
+pub(crate) const PIN_CLUSTER_RADIUS: f32 = 40.0;
+pub(crate) const PIN_INDICATOR_INSET: f32 = 10.0;
+pub(crate) const PIN_INDICATOR_SIZE: f32 = 5.0;
+pub(crate) const PIN_INDICATOR_CLUSTER_RADIUS: f32 = 24.0;
+pub(crate) const PIN_INDICATOR_FONT_SIZE: f32 = 10.0;
+pub(crate) const LEGEND_FONT_SIZE: f32 = 12.0;
+pub(crate) const LEGEND_LINE_HEIGHT: f32 = 16.0;
+pub(crate) const LEGEND_PADDING: f32 = 6.0;
+pub(crate) const LEGEND_TOGGLE_DIAMETER: f32 = 12.0;
+pub(crate) const LEGEND_TOGGLE_INNER_DIAMETER: f32 = 8.0;
+pub(crate) const LEGEND_TOGGLE_GAP: f32 = 6.0;
+pub(crate) const LEGEND_SWATCH_WIDTH: f32 = 16.0;
+pub(crate) const LEGEND_SWATCH_GAP: f32 = 6.0;
+pub(crate) const LEGEND_HIDDEN_ALPHA: f32 = 0.35;
+pub(crate) const LEGEND_TEXT_HIDDEN_ALPHA: f32 = 0.45;
+pub(crate) const LINK_CURSOR_ALPHA: f32 = 0.65;
+pub(crate) const LINK_CURSOR_WIDTH: f32 = 1.0;
+pub(crate) const LINK_BRUSH_FILL_ALPHA: f32 = 0.35;
+pub(crate) const LINK_BRUSH_BORDER_ALPHA: f32 = 0.9;
+pub(crate) const BACKDROP_GRADIENT_STEPS: usize = 64;
+pub(crate) const CONTOUR_LABEL_FONT_SIZE: f32 = 10.0;
+pub(crate) const DATE_BOUNDARY_MIN_SPACING: f32 = 24.0;
+pub(crate) const DATE_BOUNDARY_WIDTH: f32 = 1.5;
+pub(crate) const DATE_BOUNDARY_ALPHA: f32 = 0.55;
+pub(crate) const DATE_SHADE_ALPHA: f32 = 0.3;
+pub(crate) const DATE_LABEL_FONT_SIZE: f32 = 10.0;
+pub(crate) const CONTOUR_LABEL_SPACING: f32 = 160.0;
+pub(crate) const QUIVER_MAX_ARROWS: usize = 4096;
+pub(crate) const QUIVER_COLOR_BINS: usize = 16;
+pub(crate) const QUIVER_HEAD_ANGLE: f32 = 0.45;
+pub(crate) const PERF_FPS_SMOOTHING: f32 = 0.1;
+pub(crate) const WARNING_FONT_SIZE: f32 = 11.0;
+pub(crate) const WARNING_PADDING: f32 = 6.0;
+pub(crate) const SPLINE_MIN_PIXELS_PER_POINT: f32 = 8.0;
+pub(crate) const SPLINE_PIXELS_PER_STEP: f32 = 4.0;
+pub(crate) const SPLINE_MAX_STEPS: usize = 16;
+pub(crate) const PERF_OVERLAY_FONT_SIZE: f32 = 10.0;
+pub(crate) const REFERENCE_LABEL_FONT_SIZE: f32 = 11.0;
+pub(crate) const REFERENCE_LABEL_PADDING: f32 = 4.0;
+pub(crate) const ANNOTATION_FONT_SIZE: f32 = 12.0;
+pub(crate) const ANNOTATION_LINE_HEIGHT: f32 = 14.0;
+pub(crate) const ANNOTATION_OFFSET: f32 = 6.0;
+pub(crate) const ANNOTATION_ARROW_HEAD_SIZE: f32 = 8.0;
+pub(crate) const ANNOTATION_ARROW_WIDTH: f32 = 1.5;
+pub(crate) const FOCUS_RING_WIDTH: f32 = 2.0;
+pub(crate) const PERF_OVERLAY_PADDING: f32 = 6.0;
+pub(crate) const TOOLTIP_CURSOR_OFFSET: f32 = 12.0;
+pub(crate) const TOOLTIP_DOCK_PADDING: f32 = 8.0;
+pub(crate) const CANDLE_MIN_SPACING: f32 = 4.0;
@@ -0,0 +1,422 @@
+use crate::geom::{ScreenPoint, ScreenRect};
+#[cfg(any(feature = "gpui", test))]
+use crate::interaction::{HitInfo, HitPoint};
+use crate::plot::Plot;
+use crate::transform::Transform;
+use crate::view::Range;
+
+use super::config::PlotViewConfig;
+use super::geometry::{distance_sq, rect_contains};
+use super::state::{HoverTarget, PlotUiState};
+
+#[cfg(feature = "gpui")]
+pub(crate) fn hover_target_within_threshold(
+    target: &HoverTarget,
+    cursor: ScreenPoint,
+    config: &PlotViewConfig,
+) -> bool {
+    let threshold = if target.is_pinned {
+        config.unpin_threshold_px
+    } else {
+        config.pin_threshold_px
+    };
+    distance_sq(target.screen, cursor) <= threshold * threshold
+}
+
+/// Hit test a screen position against the last frame built into `state`.
+#[cfg(any(feature = "gpui", test))]
+pub(crate) fn hit_test(
+    plot: &Plot,
+    state: &PlotUiState,
+    position: ScreenPoint,
+    config: &PlotViewConfig,
+) -> HitInfo {
+    if state.plot_rect.is_none() {
+        return HitInfo::outside();
+    }
+    let region = state.regions.hit_test(position);
+    if let Some(series_id) = state.legend_hit(position) {
+        return HitInfo {
+            region,
+            point: None,
+            legend_entry: Some(series_id),
+        };
+    }
+    let Some(transform) = state.transform.as_ref() else {
+        return HitInfo {
+            region,
+            point: None,
+            legend_entry: None,
+        };
+    };
+    let target = compute_hover_target(
+        plot,
+        transform,
+        position,
+        state.plot_rect,
+        config.pin_threshold_px,
+        config.unpin_threshold_px,
+    );
+    let point = target.and_then(|target| {
+        let series = plot
+            .series()
+            .iter()
+            .find(|series| series.id() == target.pin.series_id)?;
+        let data = series.point_by_id(target.pin.point_id)?;
+        Some(HitPoint {
+            pin: target.pin,
+            point: data,
+            screen: target.screen,
+            is_pinned: target.is_pinned,
+        })
+    });
+    HitInfo {
+        region,
+        point,
+        legend_entry: None,
+    }
+}
+
+pub(crate) fn update_hover_target(
+    plot: &Plot,
+    state: &mut PlotUiState,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    config: &PlotViewConfig,
+) {
+    let Some(cursor) = state.hover else {
+        state.hover_target = None;
+        state.hover_candidates.clear();
+        return;
+    };
+    if !config.hover_all_series {
+        state.hover_candidates.clear();
+        state.hover_target = compute_hover_target(
+            plot,
+            transform,
+            cursor,
+            Some(plot_rect),
+            config.pin_threshold_px,
+            config.unpin_threshold_px,
+        );
+        return;
+    }
+    state.hover_candidates = compute_hover_candidates(
+        plot,
+        transform,
+        cursor,
+        plot_rect,
+        config.pin_threshold_px,
+        config.unpin_threshold_px,
+    );
+    let focused = state.hover_focus.and_then(|series_id| {
+        state
+            .hover_candidates
+            .iter()
+            .find(|candidate| candidate.pin.series_id == series_id)
+    });
+    state.hover_target = focused.or(state.hover_candidates.first()).copied();
+    state.hover_focus = state.hover_target.map(|target| target.pin.series_id);
+}
+
+/// Nearest point of every visible series within the thresholds, nearest
+/// first.
+///
+/// Pinned points are matched with the unpin threshold and unpinned points with
+/// the pin threshold, as in [`compute_hover_target`].
+pub(crate) fn compute_hover_candidates(
+    plot: &Plot,
+    transform: &Transform,
+    cursor: ScreenPoint,
+    plot_rect: ScreenRect,
+    pin_threshold: f32,
+    unpin_threshold: f32,
+) -> Vec<HoverTarget> {
+    if !rect_contains(plot_rect, cursor) {
+        return Vec::new();
+    }
+    let Some(center) = transform.screen_to_data(cursor) else {
+        return Vec::new();
+    };
+    let reach = pin_threshold.max(unpin_threshold);
+    let Some(edge) = transform.screen_to_data(ScreenPoint::new(cursor.x + reach, cursor.y)) else {
+        return Vec::new();
+    };
+    let dx = (edge.x - center.x).abs();
+    let search_range = Range::new(center.x - dx, center.x + dx);
+    let pins = plot.pins();
+    let mut candidates: Vec<(HoverTarget, f32)> = Vec::new();
+
+    for series in plot.series() {
+        if !series.is_visible() || !series.is_hover_enabled() {
+            continue;
+        }
+        let best = series.with_store(|store| {
+            let data = store.data();
+            let mut best: Option<(HoverTarget, f32)> = None;
+            for index in data.range_by_x(search_range) {
+                let (Some(point), Some(point_id)) = (data.point(index), store.point_id(index))
+                else {
+                    continue;
+                };
+                let pin = crate::interaction::Pin {
+                    series_id: series.id(),
+                    point_id,
+                };
+                let Some(screen) = transform.data_to_screen(point) else {
+                    continue;
+                };
+                if !rect_contains(plot_rect, screen) {
+                    continue;
+                }
+                let is_pinned = pins.contains(&pin);
+                let threshold = if is_pinned {
+                    unpin_threshold
+                } else {
+                    pin_threshold
+                };
+                let dist = distance_sq(screen, cursor);
+                if dist > threshold * threshold || best.is_some_and(|best| dist >= best.1) {
+                    continue;
+                }
+                best = Some((
+                    HoverTarget {
+                        pin,
+                        screen,
+                        is_pinned,
+                    },
+                    dist,
+                ));
+            }
+            best
+        });
+        candidates.extend(best);
+    }
+
+    candidates.sort_by(|a, b| a.1.total_cmp(&b.1));
+    candidates.into_iter().map(|(target, _)| target).collect()
+}
+
+pub(crate) fn compute_hover_target(
+    plot: &Plot,
+    transform: &Transform,
+    cursor: ScreenPoint,
+    plot_rect: Option<ScreenRect>,
+    pin_threshold: f32,
+    unpin_threshold: f32,
+) -> Option<HoverTarget> {
+    let plot_rect = plot_rect?;
+    if cursor.x < plot_rect.min.x
+        || cursor.x > plot_rect.max.x
+        || cursor.y < plot_rect.min.y
+        || cursor.y > plot_rect.max.y
+    {
+        return None;
+    }
+
+    if let Some(target) = nearest_pinned_within(plot, transform, cursor, plot_rect, unpin_threshold)
+    {
+        return Some(target);
+    }
+
+    find_nearest_unpinned_point(plot, transform, cursor, plot_rect, pin_threshold)
+}
+
+fn nearest_pinned_within(
+    plot: &Plot,
+    transform: &Transform,
+    cursor: ScreenPoint,
+    plot_rect: ScreenRect,
+    threshold: f32,
+) -> Option<HoverTarget> {
+    let threshold_sq = threshold * threshold;
+    let mut best: Option<(crate::interaction::Pin, ScreenPoint, f32)> = None;
+    for pin in plot.pins() {
+        let Some(screen) = pin_screen_point(plot, *pin, transform) else {
+            continue;
+        };
+        if screen.x < plot_rect.min.x
+            || screen.x > plot_rect.max.x
+            || screen.y < plot_rect.min.y
+            || screen.y > plot_rect.max.y
+        {
+            continue;
+        }
+        let dist = distance_sq(screen, cursor);
+        if dist > threshold_sq {
+            continue;
+        }
+        if best.is_none_or(|best| dist < best.2) {
+            best = Some((*pin, screen, dist));
+        }
+    }
+    best.map(|(pin, screen, _)| HoverTarget {
+        pin,
+        screen,
+        is_pinned: true,
+    })
+}
+
+fn find_nearest_unpinned_point(
+    plot: &Plot,
+    transform: &Transform,
+    cursor: ScreenPoint,
+    plot_rect: ScreenRect,
+    threshold: f32,
+) -> Option<HoverTarget> {
+    let center = transform.screen_to_data(cursor)?;
+    let edge = transform.screen_to_data(ScreenPoint::new(cursor.x + threshold, cursor.y))?;
+    let dx = (edge.x - center.x).abs();
+    let search_range = Range::new(center.x - dx, center.x + dx);
+    let threshold_sq = threshold * threshold;
+    let pins = plot.pins();
+    let mut best: Option<(crate::interaction::Pin, ScreenPoint, f32)> = None;
+
+    for series in plot.series() {
+        if !series.is_visible() || !series.is_hover_enabled() {
+            continue;
+        }
+        series.with_store(|store| {
+            let data = store.data();
+            let index_range = data.range_by_x(search_range);
+            for index in index_range {
+                let (Some(point), Some(point_id)) = (data.point(index), store.point_id(index))
+                else {
+                    continue;
+                };
+                let pin = crate::interaction::Pin {
+                    series_id: series.id(),
+                    point_id,
+                };
+                if pins.contains(&pin) {
+                    continue;
+                }
+                let Some(screen) = transform.data_to_screen(point) else {
+                    continue;
+                };
+                if screen.x < plot_rect.min.x
+                    || screen.x > plot_rect.max.x
+                    || screen.y < plot_rect.min.y
+                    || screen.y > plot_rect.max.y
+                {
+                    continue;
+                }
+                let dist = distance_sq(screen, cursor);
+                if dist > threshold_sq {
+                    continue;
+                }
+                if best.is_none_or(|best| dist < best.2) {
+                    best = Some((pin, screen, dist));
+                }
+            }
+        });
+    }
+
+    best.map(|(pin, screen, _)| HoverTarget {
+        pin,
+        screen,
+        is_pinned: false,
+    })
+}
+
+fn pin_screen_point(
+    plot: &Plot,
+    pin: crate::interaction::Pin,
+    transform: &Transform,
+) -> Option<ScreenPoint> {
+    let series = plot
+        .series()
+        .iter()
+        .find(|series| series.id() == pin.series_id)?;
+    if !series.is_visible() {
+        return None;
+    }
+    let point = series.point_by_id(pin.point_id)?;
+    transform.data_to_screen(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::build_frame;
+    use crate::geom::Point;
+    use crate::interaction::HitRegion;
+    use crate::render::BitmapFont;
+    use crate::series::{Series, SeriesKind};
+    use crate::view::Viewport;
+
+    fn framed_plot(series: &[&Series]) -> (Plot, PlotUiState) {
+        let mut plot = Plot::new();
+        for series in series {
+            plot.add_series(series);
+        }
+        plot.set_manual_view(Viewport::new(Range::new(0.0, 10.0), Range::new(0.0, 10.0)));
+        let mut state = PlotUiState::default();
+        build_frame(
+            &mut plot,
+            &mut state,
+            &PlotViewConfig::default(),
+            ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(400.0, 300.0)),
+            &BitmapFont,
+            None,
+        );
+        (plot, state)
+    }
+
+    fn point_series(x: f64, y: f64) -> Series {
+        Series::from_iter_points(
+            "s",
+            [Point::new(x, y)],
+            SeriesKind::Line(Default::default()),
+        )
+    }
+
+    fn screen_of(state: &PlotUiState, x: f64, y: f64) -> ScreenPoint {
+        let transform = state.transform.as_ref().expect("transform");
+        transform.data_to_screen(Point::new(x, y)).expect("screen")
+    }
+
+    #[test]
+    fn hit_test_picks_nearest_point_across_series() {
+        let near = point_series(3.2, 3.0);
+        let far = point_series(3.0, 3.0);
+        let (plot, state) = framed_plot(&[&far, &near]);
+        let cursor = screen_of(&state, 3.2, 3.0);
+        let cursor = ScreenPoint::new(cursor.x + 1.0, cursor.y);
+
+        let hit = hit_test(&plot, &state, cursor, &PlotViewConfig::default());
+        assert_eq!(hit.region, HitRegion::Plot);
+        assert_eq!(hit.legend_entry, None);
+        let point = hit.point.expect("point");
+        assert_eq!(point.pin.series_id, plot.series()[1].id());
+        assert_eq!(point.point, Point::new(3.2, 3.0));
+    }
+
+    #[test]
+    fn hit_test_misses_outside_threshold() {
+        let series = point_series(3.0, 3.0);
+        let (plot, state) = framed_plot(&[&series]);
+        let config = PlotViewConfig::default();
+        let point = screen_of(&state, 3.0, 3.0);
+        let cursor = ScreenPoint::new(point.x + config.pin_threshold_px + 2.0, point.y);
+
+        let hit = hit_test(&plot, &state, cursor, &config);
+        assert_eq!(hit.region, HitRegion::Plot);
+        assert!(hit.point.is_none());
+    }
+
+    #[test]
+    fn hit_test_skips_hidden_series() {
+        let mut hidden = point_series(3.0, 3.0);
+        hidden.set_visible(false);
+        let shown = point_series(3.2, 3.0);
+        let (plot, state) = framed_plot(&[&hidden, &shown]);
+        let cursor = screen_of(&state, 3.0, 3.0);
+
+        let hit = hit_test(&plot, &state, cursor, &PlotViewConfig::default());
+        assert_eq!(
+            hit.point.map(|point| point.pin.series_id),
+            Some(plot.series()[1].id())
+        );
+    }
+}
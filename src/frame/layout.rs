@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use crate::axis::TextMeasurer;
+use crate::geom::{ScreenPoint, ScreenRect};
+use crate::plot::Plot;
+use crate::render::{Color, RectStyle, RenderCommand, RenderList, RenderRole, TextStyle};
+use crate::style::Theme;
+use crate::transform::Transform;
+use crate::view::{Range, Viewport};
+
+use super::PlotFrame;
+use super::axes::{axis_title_text, build_axes, build_axis_titles, build_grid};
+use super::background::{build_backdrop, build_references, build_shading};
+use super::config::PlotViewConfig;
+use super::constants::{AXIS_PADDING, FOCUS_RING_WIDTH, TICK_LENGTH_MAJOR};
+use super::hover::update_hover_target;
+use super::legend::build_legend;
+use super::overlays::{
+    build_data_warnings, build_hover, build_linked_brush, build_linked_cursor, build_perf_overlay,
+    build_selection,
+};
+use super::perf::{stage_elapsed, stage_start};
+use super::pins::{build_annotations, build_pins};
+use super::series::build_series;
+use super::state::PlotUiState;
+
+pub(crate) fn build_frame_commands(
+    plot: &mut Plot,
+    state: &mut PlotUiState,
+    config: &PlotViewConfig,
+    bounds: ScreenRect,
+    measurer: &impl TextMeasurer,
+    align_y_axis_width: Option<&dyn Fn(f32) -> f32>,
+) -> PlotFrame {
+    let mut render = RenderList::new();
+
+    let full_width = bounds.width();
+    let full_height = bounds.height();
+    if full_width <= 1.0 || full_height <= 1.0 {
+        return PlotFrame {
+            render: Arc::new(render),
+        };
+    }
+
+    plot.sync_pins();
+    let viewport = plot
+        .refresh_viewport(config.padding_frac, config.min_padding)
+        .unwrap_or_else(|| Viewport::new(Range::new(0.0, 1.0), Range::new(0.0, 1.0)));
+
+    state.viewport = Some(viewport);
+
+    let layout_started = stage_start();
+    let mut plot_width = full_width;
+    let mut plot_height = full_height;
+
+    let x_layout = state
+        .x_layout
+        .update(plot.x_axis(), viewport.x, plot_width as u32, measurer)
+        .clone();
+    let y_layout = state
+        .y_layout
+        .update(plot.y_axis(), viewport.y, plot_height as u32, measurer)
+        .clone();
+
+    let x_title = axis_title_text(plot.x_axis(), &plot.inferred_x_units());
+    let x_title_size = x_title
+        .as_ref()
+        .map(|title| measurer.measure(title, plot.x_axis().label_size()))
+        .unwrap_or((0.0, 0.0));
+
+    let x_axis_height =
+        x_layout.max_label_size.1 + TICK_LENGTH_MAJOR + AXIS_PADDING * 2.0 + x_title_size.1;
+    let y_axis_width = config.y_axis_width.apply(
+        y_layout.max_label_size.0 + TICK_LENGTH_MAJOR + AXIS_PADDING * 2.0,
+        state.y_axis_width,
+    );
+    state.y_axis_width = Some(y_axis_width);
+    let y_axis_width = align_y_axis_width.map_or(y_axis_width, |align| align(y_axis_width));
+
+    let x_axis_height = x_axis_height.clamp(0.0, full_height - 1.0);
+    let y_axis_width = y_axis_width.clamp(0.0, full_width - 1.0);
+
+    plot_width = (full_width - y_axis_width).max(1.0);
+    plot_height = (full_height - x_axis_height).max(1.0);
+
+    let x_layout = state
+        .x_layout
+        .update(plot.x_axis(), viewport.x, plot_width as u32, measurer)
+        .clone();
+    let y_layout = state
+        .y_layout
+        .update(plot.y_axis(), viewport.y, plot_height as u32, measurer)
+        .clone();
+
+    let origin_x = bounds.min.x;
+    let origin_y = bounds.min.y;
+    let full_max_x = origin_x + full_width;
+    let full_max_y = origin_y + full_height;
+
+    let plot_rect = ScreenRect::new(
+        ScreenPoint::new(origin_x + y_axis_width, origin_y),
+        ScreenPoint::new(full_max_x, full_max_y - x_axis_height),
+    );
+    let x_axis_rect = ScreenRect::new(
+        ScreenPoint::new(plot_rect.min.x, plot_rect.max.y),
+        ScreenPoint::new(plot_rect.max.x, full_max_y),
+    );
+    let y_axis_rect = ScreenRect::new(
+        ScreenPoint::new(origin_x, plot_rect.min.y),
+        ScreenPoint::new(plot_rect.min.x, plot_rect.max.y),
+    );
+
+    state.regions = crate::interaction::PlotRegions {
+        plot: plot_rect,
+        x_axis: x_axis_rect,
+        y_axis: y_axis_rect,
+    };
+    state.plot_rect = Some(plot_rect);
+    state.perf.layout += stage_elapsed(layout_started);
+
+    let transform = Transform::new(viewport, plot_rect);
+    state.transform = transform.clone();
+
+    if let Some(transform) = transform {
+        render.push(RenderCommand::Role(RenderRole::Series));
+        build_backdrop(&mut render, plot, state, &transform, plot_rect);
+        build_shading(&mut render, plot, &transform, plot_rect);
+        render.push(RenderCommand::Role(RenderRole::Grid));
+        build_grid(
+            &mut render,
+            plot,
+            &x_layout,
+            &y_layout,
+            &transform,
+            plot_rect,
+        );
+        render.push(RenderCommand::Role(RenderRole::Series));
+        build_references(&mut render, plot, &transform, plot_rect, measurer);
+        build_series(
+            &mut render,
+            plot,
+            state,
+            &transform,
+            plot_rect,
+            measurer,
+            config,
+        );
+        render.push(RenderCommand::Role(RenderRole::Chrome));
+        build_linked_brush(&mut render, plot, state, &transform, plot_rect);
+        build_selection(&mut render, plot, state);
+        update_hover_target(plot, state, &transform, plot_rect, config);
+        build_linked_cursor(
+            &mut render,
+            plot,
+            state,
+            &transform,
+            plot_rect,
+            measurer,
+            &config.hover_label,
+        );
+        build_pins(&mut render, plot, &transform, plot_rect, measurer, config);
+        render.push(RenderCommand::Role(RenderRole::Series));
+        build_annotations(&mut render, plot, &transform, plot_rect, measurer);
+        render.push(RenderCommand::Role(RenderRole::Axis));
+        build_axes(
+            &mut render,
+            plot,
+            &x_layout,
+            &y_layout,
+            plot_rect,
+            &transform,
+            x_axis_rect,
+            y_axis_rect,
+            measurer,
+        );
+        render.push(RenderCommand::Role(RenderRole::Chrome));
+        let dragging = config.hide_overlays_while_dragging && state.is_dragging();
+        if config.show_hover && !dragging {
+            build_hover(
+                &mut render,
+                plot,
+                state,
+                &transform,
+                plot_rect,
+                measurer,
+                config,
+            );
+        }
+        if config.show_legend && !dragging {
+            build_legend(
+                &mut render,
+                plot,
+                state,
+                plot_rect,
+                measurer,
+                &config.legend_label,
+                &config.legend_entry,
+            );
+        } else {
+            state.legend_layout = None;
+        }
+        if config.show_data_warnings {
+            build_data_warnings(
+                &mut render,
+                plot,
+                state,
+                plot_rect,
+                measurer,
+                &config.legend_label,
+            );
+        }
+        render.push(RenderCommand::Role(RenderRole::Axis));
+        build_axis_titles(
+            &mut render,
+            plot,
+            plot_rect,
+            x_axis_rect,
+            y_axis_rect,
+            measurer,
+        );
+        render.push(RenderCommand::Role(RenderRole::Chrome));
+        if config.show_perf_overlay {
+            build_perf_overlay(&mut render, plot, state, plot_rect, measurer);
+        }
+    } else {
+        state.legend_layout = None;
+        let message = "Invalid axis range";
+        let size = measurer.measure(message, 14.0);
+        let pos = ScreenPoint::new(
+            plot_rect.min.x + (plot_rect.width() - size.0) * 0.5,
+            plot_rect.min.y + (plot_rect.height() - size.1) * 0.5,
+        );
+        render.push(RenderCommand::Text {
+            position: pos,
+            text: message.to_string(),
+            style: TextStyle {
+                color: plot.theme().axis,
+                size: 14.0,
+            },
+        });
+    }
+
+    if config.focus_ring && state.focused {
+        build_focus_ring(&mut render, plot.theme(), bounds);
+    }
+
+    PlotFrame {
+        render: Arc::new(render),
+    }
+}
+
+/// Outline the whole view to show it has keyboard focus.
+fn build_focus_ring(render: &mut RenderList, theme: &Theme, bounds: ScreenRect) {
+    let inset = FOCUS_RING_WIDTH * 0.5;
+    let min = ScreenPoint::new(bounds.min.x + inset, bounds.min.y + inset);
+    let max = ScreenPoint::new(bounds.max.x - inset, bounds.max.y - inset);
+    render.push(RenderCommand::Rect {
+        rect: ScreenRect::new(min, max),
+        style: RectStyle {
+            fill: Color::new(0.0, 0.0, 0.0, 0.0),
+            stroke: theme.selection_border,
+            stroke_width: FOCUS_RING_WIDTH,
+        },
+    });
+}
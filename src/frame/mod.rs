@@ -0,0 +1,328 @@
+//! Backend-independent frame building.
+//!
+//! Lays out the axes, series, legend and overlays of a
+//! [`Plot`](crate::plot::Plot) into a [`RenderList`] for a screen rectangle.
+//! The GPUI view paints these frames interactively; the windowless export
+//! rasterizes them with a built-in bitmap font.
+//!
+//! [`layout`] sizes the axes and orders the drawing passes, each of which
+//! lives in its own module: [`background`] for backdrops, shading and
+//! reference lines, [`axes`] for grid, ticks and titles, [`series`] for
+//! data, [`pins`] for pins and annotations, [`overlays`] for hover,
+//! selection and linked cursors, and [`legend`].
+
+#![allow(clippy::collapsible_if)]
+
+mod axes;
+mod background;
+mod config;
+mod constants;
+pub(crate) mod geometry;
+pub(crate) mod hover;
+mod layout;
+mod legend;
+mod overlays;
+mod perf;
+mod pins;
+mod series;
+pub(crate) mod state;
+mod text;
+
+use std::sync::Arc;
+
+use crate::axis::TextMeasurer;
+use crate::geom::ScreenRect;
+use crate::plot::Plot;
+use crate::render::RenderList;
+
+use layout::build_frame_commands;
+use perf::{stage_elapsed, stage_start};
+
+pub use config::{
+    AxisWidthPolicy, LegendEntryRenderer, LegendRow, LegendRowFn, PlotCorner, PlotViewConfig,
+    SeriesLabelFormatter, SoftLimits, TooltipMode, WheelZoom,
+};
+pub use perf::PerfStats;
+#[cfg(feature = "gpui")]
+pub(crate) use series::series_color;
+pub(crate) use state::PlotUiState;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PlotFrame {
+    pub(crate) render: Arc<RenderList>,
+}
+
+/// Build the frame for `plot` inside `bounds`.
+///
+/// `measurer` sizes text for layout and `align_y_axis_width`, when set,
+/// negotiates the Y axis width with linked plots. Records frame timings in
+/// `state.perf` and keeps the result as `state.last_render`.
+pub(crate) fn build_frame(
+    plot: &mut Plot,
+    state: &mut PlotUiState,
+    config: &PlotViewConfig,
+    bounds: ScreenRect,
+    measurer: &impl TextMeasurer,
+    align_y_axis_width: Option<&dyn Fn(f32) -> f32>,
+) -> PlotFrame {
+    let started = stage_start();
+    state.perf.begin_frame();
+    let frame = build_frame_commands(plot, state, config, bounds, measurer, align_y_axis_width);
+    let interval = started
+        .zip(state.perf.last_frame)
+        .map(|(started, last)| started.saturating_duration_since(last));
+    state.perf.last_frame = started;
+    let decimation = state.perf.decimation;
+    state
+        .perf
+        .stats
+        .record(&frame.render, stage_elapsed(started), decimation, interval);
+    state.perf.finish_stages();
+    state.last_render = Some(Arc::clone(&frame.render));
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::{AxisConfig, AxisFormatter};
+    use crate::datasource::Grid;
+    use crate::geom::{Point as DataPoint, ScreenPoint};
+    use crate::interaction::Pin;
+    use crate::render::{BitmapFont, Color, ImageStyle, RasterImage, RenderCommand};
+    use crate::series::{Series, SeriesKind};
+    use crate::style::Backdrop;
+    use crate::view::{Range, Viewport};
+    use constants::PIN_INDICATOR_INSET;
+    use geometry::distance_sq;
+
+    fn frame(plot: &mut Plot, state: &mut PlotUiState, config: &PlotViewConfig) -> PlotFrame {
+        build_frame(
+            plot,
+            state,
+            config,
+            ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(400.0, 300.0)),
+            &BitmapFont,
+            None,
+        )
+    }
+
+    #[test]
+    fn decimation_bypass_counts_visible_points_only() {
+        let series = Series::from_iter_y(
+            "long",
+            (0..10_000).map(|i| (i as f64 * 0.01).sin()),
+            SeriesKind::Line(Default::default()),
+        );
+        let mut plot = Plot::new();
+        plot.add_series(&series);
+        let id = plot.series()[0].id();
+        let config = PlotViewConfig::default();
+        let mut state = PlotUiState::default();
+        let zoomed_in = Viewport::new(Range::new(100.0, 200.0), Range::new(-1.0, 1.0));
+        let zoomed_out = Viewport::new(Range::new(0.0, 10_000.0), Range::new(-1.0, 1.0));
+
+        plot.set_manual_view(zoomed_in);
+        frame(&mut plot, &mut state, &config);
+        assert!(!state.series_cache.contains_key(&id));
+        assert_eq!(state.perf.visible_points, 101);
+
+        plot.set_manual_view(zoomed_out);
+        frame(&mut plot, &mut state, &config);
+        assert!(state.series_cache[&id].key.is_some());
+
+        plot.set_manual_view(zoomed_in);
+        frame(&mut plot, &mut state, &config);
+        assert!(!state.series_cache.contains_key(&id));
+    }
+
+    #[test]
+    fn decimation_bypass_off_always_caches() {
+        let series = Series::from_iter_y(
+            "short",
+            [0.0, 1.0, 0.5],
+            SeriesKind::Line(Default::default()),
+        );
+        let mut plot = Plot::new();
+        plot.add_series(&series);
+        let id = plot.series()[0].id();
+        let config = PlotViewConfig {
+            decimation_bypass_points: 0,
+            ..PlotViewConfig::default()
+        };
+        let mut state = PlotUiState::default();
+
+        frame(&mut plot, &mut state, &config);
+        assert!(state.series_cache[&id].key.is_some());
+    }
+
+    #[test]
+    fn interpolated_hover_uses_series_axis() {
+        let volts = Series::from_iter_points(
+            "volts",
+            [DataPoint::new(0.0, 0.0), DataPoint::new(10.0, 10.0)],
+            SeriesKind::Line(Default::default()),
+        );
+        let amps = Series::from_iter_points(
+            "amps",
+            [DataPoint::new(0.0, 8.0), DataPoint::new(10.0, 4.0)],
+            SeriesKind::Line(Default::default()),
+        );
+        let y_axis = AxisConfig::builder()
+            .formatter(AxisFormatter::Custom(Arc::new(|value| {
+                format!("{value:.1} u")
+            })))
+            .build();
+        let mut plot = Plot::builder()
+            .y_axis(y_axis)
+            .series(&volts)
+            .series(&amps)
+            .build();
+        plot.set_manual_view(Viewport::new(Range::new(0.0, 10.0), Range::new(0.0, 10.0)));
+        let config = PlotViewConfig {
+            hover_interpolate: true,
+            ..PlotViewConfig::default()
+        };
+        let mut state = PlotUiState::default();
+        frame(&mut plot, &mut state, &config);
+        let transform = state.transform.clone().expect("transform");
+        state.hover = transform.data_to_screen(DataPoint::new(5.0, 1.0));
+
+        let built = frame(&mut plot, &mut state, &config);
+        let texts: Vec<&str> = built
+            .render
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"volts: 5.0 u"));
+        assert!(texts.contains(&"amps: 6.0 u"));
+        let markers: Vec<ScreenPoint> = built
+            .render
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::Points { points, .. } if points.len() == 1 => Some(points[0]),
+                _ => None,
+            })
+            .collect();
+        for y in [5.0, 6.0] {
+            let expected = transform
+                .data_to_screen(DataPoint::new(5.0, y))
+                .expect("screen");
+            assert!(
+                markers
+                    .iter()
+                    .any(|marker| distance_sq(*marker, expected) < 1.0)
+            );
+        }
+    }
+
+    /// Commands added by turning on off-screen pin indicators, with the
+    /// plot rectangle.
+    fn pin_indicator_commands(pinned_x: f64) -> (Vec<RenderCommand>, ScreenRect) {
+        let series = Series::from_iter_y(
+            "s",
+            (0..=20).map(|i| i as f64),
+            SeriesKind::Line(Default::default()),
+        );
+        let mut plot = Plot::new();
+        plot.add_series(&series);
+        let series = &plot.series()[0];
+        let pin = Pin {
+            series_id: series.id(),
+            point_id: series.point_id(pinned_x as usize).expect("point"),
+        };
+        plot.pins_mut().push(pin);
+        plot.set_manual_view(Viewport::new(Range::new(0.0, 10.0), Range::new(0.0, 20.0)));
+        let built = |indicators: bool| {
+            let config = PlotViewConfig {
+                offscreen_pin_indicators: indicators,
+                ..PlotViewConfig::default()
+            };
+            let mut state = PlotUiState::default();
+            let frame = frame(&mut plot.clone(), &mut state, &config);
+            (frame.render.commands().to_vec(), state.plot_rect)
+        };
+        let (without, _) = built(false);
+        let (with, plot_rect) = built(true);
+        // Render commands have no `PartialEq`; their debug output is exact.
+        let without: Vec<String> = without
+            .iter()
+            .map(|command| format!("{command:?}"))
+            .collect();
+        let added = with
+            .into_iter()
+            .filter(|command| !without.contains(&format!("{command:?}")))
+            .collect();
+        (added, plot_rect.expect("plot rect"))
+    }
+
+    #[test]
+    fn offscreen_pin_indicator_points_toward_the_pin() {
+        let (added, plot_rect) = pin_indicator_commands(15.0);
+        let [RenderCommand::LineSegments { segments, .. }] = added.as_slice() else {
+            panic!("expected one indicator, got {added:?}");
+        };
+        let center_y = (plot_rect.min.y + plot_rect.max.y) * 0.5;
+        for segment in segments {
+            assert!(segment.end.x > plot_rect.max.x - 2.0 * PIN_INDICATOR_INSET);
+            assert!(segment.end.x > segment.start.x);
+            assert!(segment.end.y < center_y);
+        }
+    }
+
+    #[test]
+    fn visible_pin_has_no_indicator() {
+        let (added, _) = pin_indicator_commands(5.0);
+        assert!(added.is_empty());
+    }
+
+    fn image_plot(resolution: usize) -> Plot {
+        let values = (0..resolution * resolution)
+            .map(|index| (index % 7) as f32)
+            .collect();
+        let grid = Grid::new(resolution, resolution, values).expect("grid");
+        let extent = Viewport::new(Range::new(0.0, 10.0), Range::new(0.0, 10.0));
+        let pixels = (0..resolution * resolution)
+            .map(|index| Color::new((index % 5) as f32 / 4.0, 0.0, 0.0, 1.0))
+            .collect();
+        let backdrop = RasterImage::new(resolution, resolution, pixels).expect("image");
+        let image = Series::image("camera", grid, extent, ImageStyle::default());
+        let mut plot = Plot::builder()
+            .backdrop(Backdrop::image(extent, backdrop))
+            .series(&image)
+            .build();
+        plot.set_manual_view(extent);
+        plot
+    }
+
+    #[test]
+    fn image_commands_are_bounded_by_screen_size() {
+        let config = PlotViewConfig::default();
+        let mut small_state = PlotUiState::default();
+        let small = frame(&mut image_plot(4), &mut small_state, &config);
+        let mut state = PlotUiState::default();
+        let large = frame(&mut image_plot(1024), &mut state, &config);
+        let plot_rect = state.plot_rect.expect("plot rect");
+        let screen_pixels = plot_rect.width().ceil() * plot_rect.height().ceil();
+
+        assert_eq!(large.render.commands().len(), small.render.commands().len());
+        let images: Vec<&RasterImage> = large
+            .render
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::Image { image, .. } => Some(image),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(images.len(), 2);
+        for image in images {
+            assert!((image.width() * image.height()) as f32 <= screen_pixels);
+        }
+    }
+}
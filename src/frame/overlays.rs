@@ -0,0 +1,649 @@
+use std::time::Duration;
+
+use crate::axis::TextMeasurer;
+use crate::geom::{Point as DataPoint, ScreenPoint, ScreenRect};
+use crate::plot::Plot;
+use crate::render::{
+    LineSegment, LineStyle, MarkerShape, MarkerStyle, RectStyle, RenderCommand, RenderList,
+    TextStyle,
+};
+use crate::series::ResampleMethod;
+use crate::transform::Transform;
+
+use super::config::{PlotViewConfig, SeriesLabelFormatter};
+use super::constants::{
+    LINK_BRUSH_BORDER_ALPHA, LINK_BRUSH_FILL_ALPHA, LINK_CURSOR_ALPHA, LINK_CURSOR_WIDTH,
+    PERF_OVERLAY_FONT_SIZE, PERF_OVERLAY_PADDING, PIN_RING_INNER_PAD, PIN_RING_OUTER_PAD,
+    PIN_UNPIN_HIGHLIGHT, WARNING_FONT_SIZE, WARNING_PADDING,
+};
+use super::geometry::{clamp_point, normalized_rect};
+use super::series::{marker_style_and_size, with_alpha};
+use super::state::{HoverTarget, PlotUiState};
+use super::text::measure_multiline;
+
+pub(crate) fn build_selection(render: &mut RenderList, plot: &Plot, state: &PlotUiState) {
+    if let Some(rect) = state.selection_rect {
+        let rect = normalized_rect(rect);
+        render.push(RenderCommand::Rect {
+            rect,
+            style: RectStyle {
+                fill: plot.theme().selection_fill,
+                stroke: plot.theme().selection_border,
+                stroke_width: 1.0,
+            },
+        });
+    }
+}
+
+pub(crate) fn clamp_label_position(
+    pos: ScreenPoint,
+    size: (f32, f32),
+    rect: ScreenRect,
+) -> ScreenPoint {
+    let max_x = (rect.max.x - size.0).max(rect.min.x);
+    let max_y = (rect.max.y - size.1).max(rect.min.y);
+    ScreenPoint::new(
+        pos.x.clamp(rect.min.x, max_x),
+        pos.y.clamp(rect.min.y, max_y),
+    )
+}
+
+/// Tooltip text listing every series near the cursor, marking the target.
+fn hover_candidates_label(
+    plot: &Plot,
+    state: &PlotUiState,
+    target: HoverTarget,
+    series_labels: &SeriesLabelFormatter,
+) -> String {
+    let mut lines = Vec::with_capacity(state.hover_candidates.len());
+    for candidate in &state.hover_candidates {
+        let Some(series) = plot
+            .series()
+            .iter()
+            .find(|series| series.id() == candidate.pin.series_id)
+        else {
+            continue;
+        };
+        let Some(point) = series.point_by_id(candidate.pin.point_id) else {
+            continue;
+        };
+        let marker = if candidate.pin == target.pin {
+            '>'
+        } else {
+            ' '
+        };
+        lines.push(format!(
+            "{marker} {}  x: {}  y: {}",
+            series_labels.format(series),
+            plot.x_axis().format_value(point.x),
+            plot.y_axis().format_value(point.y),
+        ));
+    }
+    lines.join("\n")
+}
+
+pub(crate) fn build_hover(
+    render: &mut RenderList,
+    plot: &Plot,
+    state: &PlotUiState,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+    config: &PlotViewConfig,
+) {
+    let series_labels = &config.hover_label;
+    let theme = plot.theme();
+    let Some(cursor) = state.hover else { return };
+    if cursor.x < plot_rect.min.x
+        || cursor.x > plot_rect.max.x
+        || cursor.y < plot_rect.min.y
+        || cursor.y > plot_rect.max.y
+    {
+        return;
+    }
+    if config.hover_interpolate {
+        build_interpolated_hover(render, plot, cursor, transform, plot_rect, measurer, config);
+        return;
+    }
+
+    if let Some(target) = state.hover_target {
+        let Some(series) = plot
+            .series()
+            .iter()
+            .find(|series| series.id() == target.pin.series_id)
+        else {
+            return;
+        };
+        let Some(point) = series.point_by_id(target.pin.point_id) else {
+            return;
+        };
+        let screen = target.screen;
+        if screen.x < plot_rect.min.x
+            || screen.x > plot_rect.max.x
+            || screen.y < plot_rect.min.y
+            || screen.y > plot_rect.max.y
+        {
+            return;
+        }
+
+        let listed = state.hover_candidates.len() >= 2;
+        if target.is_pinned {
+            let (_, base_size) = marker_style_and_size(series);
+            let ring_outer = base_size + PIN_RING_OUTER_PAD;
+            let ring_inner = base_size + PIN_RING_INNER_PAD;
+            render.push(RenderCommand::Points {
+                points: vec![screen],
+                style: MarkerStyle {
+                    color: PIN_UNPIN_HIGHLIGHT,
+                    size: ring_outer,
+                    shape: MarkerShape::Circle,
+                },
+            });
+            render.push(RenderCommand::Points {
+                points: vec![screen],
+                style: MarkerStyle {
+                    color: theme.background,
+                    size: ring_inner,
+                    shape: MarkerShape::Circle,
+                },
+            });
+            if !listed {
+                return;
+            }
+        } else {
+            let (marker_style, base_size) = marker_style_and_size(series);
+            let ring_outer = base_size + PIN_RING_OUTER_PAD;
+            let ring_inner = base_size + PIN_RING_INNER_PAD;
+            render.push(RenderCommand::Points {
+                points: vec![screen],
+                style: MarkerStyle {
+                    color: theme.axis,
+                    size: ring_outer,
+                    shape: MarkerShape::Circle,
+                },
+            });
+            render.push(RenderCommand::Points {
+                points: vec![screen],
+                style: MarkerStyle {
+                    color: theme.background,
+                    size: ring_inner,
+                    shape: MarkerShape::Circle,
+                },
+            });
+            render.push(RenderCommand::Points {
+                points: vec![screen],
+                style: marker_style,
+            });
+        }
+
+        let label = if listed {
+            hover_candidates_label(plot, state, target, series_labels)
+        } else {
+            let x_text = plot.x_axis().format_value(point.x);
+            let y_text = plot.y_axis().format_value(point.y);
+            format!("{}\nx: {x_text}\ny: {y_text}", series_labels.format(series))
+        };
+        let size = measure_multiline(measurer, &label, 12.0);
+        let origin = config.tooltip_mode.origin(screen, size, plot_rect);
+
+        render.push(RenderCommand::Rect {
+            rect: ScreenRect::new(
+                origin,
+                ScreenPoint::new(origin.x + size.0, origin.y + size.1),
+            ),
+            style: RectStyle {
+                fill: theme.pin_bg,
+                stroke: theme.pin_border,
+                stroke_width: 1.0,
+            },
+        });
+
+        for (index, line) in label.lines().enumerate() {
+            let line_y = origin.y + index as f32 * 14.0 + 2.0;
+            render.push(RenderCommand::Text {
+                position: ScreenPoint::new(origin.x + 4.0, line_y),
+                text: line.to_string(),
+                style: TextStyle {
+                    color: theme.axis,
+                    size: 12.0,
+                },
+            });
+        }
+        return;
+    }
+
+    let Some(data) = transform.screen_to_data(cursor) else {
+        return;
+    };
+    let x_text = plot.x_axis().format_value(data.x);
+    let y_text = plot.y_axis().format_value(data.y);
+    let label = format!("x: {x_text}\ny: {y_text}");
+
+    let size = measure_multiline(measurer, &label, 12.0);
+    let origin = config.tooltip_mode.origin(cursor, size, plot_rect);
+
+    render.push(RenderCommand::Rect {
+        rect: ScreenRect::new(
+            origin,
+            ScreenPoint::new(origin.x + size.0, origin.y + size.1),
+        ),
+        style: RectStyle {
+            fill: theme.hover_bg,
+            stroke: theme.hover_border,
+            stroke_width: 1.0,
+        },
+    });
+
+    for (index, line) in label.lines().enumerate() {
+        let line_y = origin.y + index as f32 * 14.0 + 2.0;
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(origin.x + 4.0, line_y),
+            text: line.to_string(),
+            style: TextStyle {
+                color: theme.axis,
+                size: 12.0,
+            },
+        });
+    }
+}
+
+/// Mark every visible series at the cursor X and list the interpolated
+/// values.
+fn build_interpolated_hover(
+    render: &mut RenderList,
+    plot: &Plot,
+    cursor: ScreenPoint,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+    config: &PlotViewConfig,
+) {
+    let series_labels = &config.hover_label;
+    let theme = plot.theme();
+    let Some(x) = transform.screen_to_data(cursor).map(|point| point.x) else {
+        return;
+    };
+    let mut lines = vec![format!("x: {}", plot.x_axis().format_value(x))];
+    let mut hidden = 0usize;
+    for series in plot.series() {
+        if !series.is_visible() || !series.is_hover_enabled() || series.grid_extent().is_some() {
+            continue;
+        }
+        let Some(y_axis) = plot.series_y_axis(series.id()) else {
+            continue;
+        };
+        let Some(y) = series.value_at(x, ResampleMethod::Linear) else {
+            continue;
+        };
+        if let Some(screen) = transform.data_to_screen(DataPoint::new(x, y))
+            && screen.y >= plot_rect.min.y
+            && screen.y <= plot_rect.max.y
+        {
+            let (marker_style, base_size) = marker_style_and_size(series);
+            render.push(RenderCommand::Points {
+                points: vec![screen],
+                style: MarkerStyle {
+                    color: theme.background,
+                    size: base_size + PIN_RING_INNER_PAD,
+                    shape: MarkerShape::Circle,
+                },
+            });
+            render.push(RenderCommand::Points {
+                points: vec![screen],
+                style: marker_style,
+            });
+        }
+        if lines.len() <= 6 {
+            lines.push(format!(
+                "{}: {}",
+                series_labels.format(series),
+                y_axis.format_value(y)
+            ));
+        } else {
+            hidden += 1;
+        }
+    }
+    if hidden > 0 {
+        lines.push(format!("+{hidden} more"));
+    }
+
+    let label = lines.join("\n");
+    let size = measure_multiline(measurer, &label, 12.0);
+    let origin = config.tooltip_mode.origin(cursor, size, plot_rect);
+
+    render.push(RenderCommand::Rect {
+        rect: ScreenRect::new(
+            origin,
+            ScreenPoint::new(origin.x + size.0, origin.y + size.1),
+        ),
+        style: RectStyle {
+            fill: theme.hover_bg,
+            stroke: theme.hover_border,
+            stroke_width: 1.0,
+        },
+    });
+    for (index, line) in label.lines().enumerate() {
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(origin.x + 4.0, origin.y + index as f32 * 14.0 + 2.0),
+            text: line.to_string(),
+            style: TextStyle {
+                color: theme.axis,
+                size: 12.0,
+            },
+        });
+    }
+}
+
+pub(crate) fn build_linked_cursor(
+    render: &mut RenderList,
+    plot: &Plot,
+    state: &PlotUiState,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+    series_labels: &SeriesLabelFormatter,
+) {
+    let Some(x) = state.linked_cursor_x else {
+        return;
+    };
+    if state.hover.is_some() {
+        return;
+    }
+
+    let Some(screen_x) = transform
+        .data_to_screen(DataPoint::new(x, transform.viewport().y.min))
+        .map(|point| point.x)
+    else {
+        return;
+    };
+
+    if screen_x < plot_rect.min.x || screen_x > plot_rect.max.x {
+        return;
+    }
+
+    let theme = plot.theme();
+
+    render.push(RenderCommand::ClipRect(plot_rect));
+    render.push(RenderCommand::LineSegments {
+        segments: vec![LineSegment::new(
+            ScreenPoint::new(screen_x, plot_rect.min.y),
+            ScreenPoint::new(screen_x, plot_rect.max.y),
+        )],
+        style: LineStyle {
+            color: with_alpha(theme.axis, LINK_CURSOR_ALPHA),
+            width: LINK_CURSOR_WIDTH,
+            dash: None,
+        },
+    });
+    render.push(RenderCommand::ClipEnd);
+
+    let mut lines = Vec::new();
+    lines.push(format!("x: {}", plot.x_axis().format_value(x)));
+
+    let mut hidden = 0usize;
+    for series in plot.series() {
+        if !series.is_visible() {
+            continue;
+        }
+        let point = series.with_store(|store| {
+            let data = store.data();
+            data.nearest_index_by_x(x)
+                .and_then(|index| data.point(index))
+        });
+        if let Some(point) = point {
+            if lines.len() <= 6 {
+                lines.push(format!(
+                    "{}: {}",
+                    series_labels.format(series),
+                    plot.y_axis().format_value(point.y)
+                ));
+            } else {
+                hidden += 1;
+            }
+        }
+    }
+    if hidden > 0 {
+        lines.push(format!("+{hidden} more"));
+    }
+    if lines.is_empty() {
+        return;
+    }
+
+    let label = lines.join("\n");
+    let font_size = 12.0;
+    let size = measure_multiline(measurer, &label, font_size);
+    let mut origin = ScreenPoint::new(screen_x + 10.0, plot_rect.min.y + 10.0);
+    if origin.x + size.0 > plot_rect.max.x {
+        origin.x = screen_x - size.0 - 10.0;
+    }
+    origin = clamp_point(origin, plot_rect, size);
+
+    render.push(RenderCommand::Rect {
+        rect: ScreenRect::new(
+            origin,
+            ScreenPoint::new(origin.x + size.0, origin.y + size.1),
+        ),
+        style: RectStyle {
+            fill: with_alpha(theme.hover_bg, 0.9),
+            stroke: with_alpha(theme.hover_border, 0.9),
+            stroke_width: 1.0,
+        },
+    });
+
+    for (index, line) in label.lines().enumerate() {
+        let line_y = origin.y + index as f32 * 14.0 + 2.0;
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(origin.x + 4.0, line_y),
+            text: line.to_string(),
+            style: TextStyle {
+                color: theme.axis,
+                size: font_size,
+            },
+        });
+    }
+}
+
+pub(crate) fn build_data_warnings(
+    render: &mut RenderList,
+    plot: &Plot,
+    state: &PlotUiState,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+    series_labels: &SeriesLabelFormatter,
+) {
+    let warnings = plot.data_warnings();
+    if warnings.is_empty() {
+        return;
+    }
+    let theme = plot.theme();
+    let badge_text = if warnings.len() == 1 {
+        "! 1 data warning".to_string()
+    } else {
+        format!("! {} data warnings", warnings.len())
+    };
+    let badge_size = measurer.measure(&badge_text, WARNING_FONT_SIZE);
+    let origin = ScreenPoint::new(
+        plot_rect.min.x + WARNING_PADDING,
+        plot_rect.min.y + WARNING_PADDING,
+    );
+    let badge = ScreenRect::new(
+        origin,
+        ScreenPoint::new(
+            origin.x + badge_size.0 + WARNING_PADDING * 2.0,
+            origin.y + badge_size.1 + WARNING_PADDING,
+        ),
+    );
+    render.push(RenderCommand::Rect {
+        rect: badge,
+        style: RectStyle {
+            fill: with_alpha(theme.hover_bg, 0.9),
+            stroke: theme.warning,
+            stroke_width: 1.0,
+        },
+    });
+    render.push(RenderCommand::Text {
+        position: ScreenPoint::new(origin.x + WARNING_PADDING, origin.y + WARNING_PADDING * 0.5),
+        text: badge_text,
+        style: TextStyle {
+            color: theme.warning,
+            size: WARNING_FONT_SIZE,
+        },
+    });
+
+    let hovered = state.hover.is_some_and(|cursor| {
+        cursor.x >= badge.min.x
+            && cursor.x <= badge.max.x
+            && cursor.y >= badge.min.y
+            && cursor.y <= badge.max.y
+    });
+    if !hovered {
+        return;
+    }
+    let lines: Vec<String> = warnings
+        .iter()
+        .filter_map(|(series_id, warning)| {
+            let series = plot.series().iter().find(|s| s.id() == *series_id)?;
+            Some(format!("{}: {warning}", series_labels.format(series)))
+        })
+        .collect();
+    let label = lines.join("\n");
+    let size = measure_multiline(measurer, &label, WARNING_FONT_SIZE);
+    let details = ScreenPoint::new(badge.min.x, badge.max.y + WARNING_PADDING);
+    let details = clamp_point(details, plot_rect, size);
+    render.push(RenderCommand::Rect {
+        rect: ScreenRect::new(
+            details,
+            ScreenPoint::new(details.x + size.0, details.y + size.1),
+        ),
+        style: RectStyle {
+            fill: with_alpha(theme.hover_bg, 0.95),
+            stroke: with_alpha(theme.hover_border, 0.9),
+            stroke_width: 1.0,
+        },
+    });
+    for (index, line) in lines.iter().enumerate() {
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(
+                details.x + 4.0,
+                details.y + index as f32 * (WARNING_FONT_SIZE + 2.0) + 2.0,
+            ),
+            text: line.clone(),
+            style: TextStyle {
+                color: theme.axis,
+                size: WARNING_FONT_SIZE,
+            },
+        });
+    }
+}
+
+pub(crate) fn build_perf_overlay(
+    render: &mut RenderList,
+    plot: &Plot,
+    state: &PlotUiState,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+) {
+    let stats = state.perf.stats;
+    let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    let lines = [
+        format!(
+            "frame {:.2} ms  {:.0} fps",
+            ms(stats.frame_build),
+            stats.fps
+        ),
+        format!(
+            "decimate {:.2} ms  layout {:.2} ms  paint {:.2} ms",
+            ms(stats.decimation),
+            ms(stats.layout),
+            ms(stats.paint)
+        ),
+        format!(
+            "cache {} hit / {} miss",
+            stats.cache_hits, stats.cache_misses
+        ),
+        format!(
+            "points {}  segments {}  markers {}",
+            stats.visible_points, stats.segment_count, stats.point_count
+        ),
+    ];
+    let theme = plot.theme();
+    let size = measure_multiline(measurer, &lines.join("\n"), PERF_OVERLAY_FONT_SIZE);
+    let origin = ScreenPoint::new(
+        plot_rect.min.x + PERF_OVERLAY_PADDING,
+        plot_rect.max.y - PERF_OVERLAY_PADDING - size.1,
+    );
+    let origin = clamp_point(origin, plot_rect, size);
+    render.push(RenderCommand::Rect {
+        rect: ScreenRect::new(
+            origin,
+            ScreenPoint::new(origin.x + size.0, origin.y + size.1),
+        ),
+        style: RectStyle {
+            fill: with_alpha(theme.hover_bg, 0.85),
+            stroke: with_alpha(theme.hover_border, 0.9),
+            stroke_width: 1.0,
+        },
+    });
+    for (index, line) in lines.into_iter().enumerate() {
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(
+                origin.x + 4.0,
+                origin.y + index as f32 * (PERF_OVERLAY_FONT_SIZE + 2.0) + 2.0,
+            ),
+            text: line,
+            style: TextStyle {
+                color: theme.axis,
+                size: PERF_OVERLAY_FONT_SIZE,
+            },
+        });
+    }
+}
+
+pub(crate) fn build_linked_brush(
+    render: &mut RenderList,
+    plot: &Plot,
+    state: &PlotUiState,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let Some(range) = state.linked_brush_x else {
+        return;
+    };
+    if state.selection_rect.is_some() {
+        return;
+    }
+
+    let Some(start_x) = transform
+        .data_to_screen(DataPoint::new(range.min, transform.viewport().y.min))
+        .map(|point| point.x)
+    else {
+        return;
+    };
+    let Some(end_x) = transform
+        .data_to_screen(DataPoint::new(range.max, transform.viewport().y.min))
+        .map(|point| point.x)
+    else {
+        return;
+    };
+
+    let min_x = start_x.min(end_x).clamp(plot_rect.min.x, plot_rect.max.x);
+    let max_x = start_x.max(end_x).clamp(plot_rect.min.x, plot_rect.max.x);
+    if (max_x - min_x).abs() < 1.0 {
+        return;
+    }
+
+    let theme = plot.theme();
+    render.push(RenderCommand::ClipRect(plot_rect));
+    render.push(RenderCommand::Rect {
+        rect: ScreenRect::new(
+            ScreenPoint::new(min_x, plot_rect.min.y),
+            ScreenPoint::new(max_x, plot_rect.max.y),
+        ),
+        style: RectStyle {
+            fill: with_alpha(theme.selection_fill, LINK_BRUSH_FILL_ALPHA),
+            stroke: with_alpha(theme.selection_border, LINK_BRUSH_BORDER_ALPHA),
+            stroke_width: 1.0,
+        },
+    });
+    render.push(RenderCommand::ClipEnd);
+}
@@ -0,0 +1,170 @@
+use std::time::{Duration, Instant};
+
+use crate::render::{RenderCommand, RenderList};
+
+use super::constants::PERF_FPS_SMOOTHING;
+
+/// Rendering statistics for a plot view.
+///
+/// Updated every time a plot view builds a frame; read them with
+/// `GpuiPlotView::perf_stats` to drive a performance HUD or regression
+/// benchmarks.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PerfStats {
+    /// Smoothed frames per second.
+    pub fps: f32,
+    /// Time spent building the most recent frame.
+    pub frame_build: Duration,
+    /// Portion of `frame_build` spent decimating series data.
+    pub decimation: Duration,
+    /// Portion of `frame_build` spent on axis layout.
+    pub layout: Duration,
+    /// Time spent painting the previous frame.
+    pub paint: Duration,
+    /// Series whose decimated points were reused from the cache.
+    pub cache_hits: usize,
+    /// Series that had to be decimated again.
+    pub cache_misses: usize,
+    /// Decimated points across visible line, scatter and stem series.
+    pub visible_points: usize,
+    /// Render commands in the most recent frame.
+    pub command_count: usize,
+    /// Line segments in the most recent frame.
+    pub segment_count: usize,
+    /// Markers in the most recent frame.
+    pub point_count: usize,
+    /// Total frames built.
+    pub frames: u64,
+}
+
+impl PerfStats {
+    pub(crate) fn record(
+        &mut self,
+        render: &RenderList,
+        frame_build: Duration,
+        decimation: Duration,
+        interval: Option<Duration>,
+    ) {
+        self.frame_build = frame_build;
+        self.decimation = decimation;
+        self.command_count = render.commands().len();
+        self.segment_count = 0;
+        self.point_count = 0;
+        for command in render.commands() {
+            match command {
+                RenderCommand::LineSegments { segments, .. } => {
+                    self.segment_count += segments.len();
+                }
+                RenderCommand::Points { points, .. } => self.point_count += points.len(),
+                _ => {}
+            }
+        }
+        self.frames = self.frames.wrapping_add(1);
+        if let Some(interval) = interval.filter(|interval| !interval.is_zero()) {
+            let fps = 1.0 / interval.as_secs_f32();
+            self.fps = if self.fps > 0.0 {
+                self.fps + (fps - self.fps) * PERF_FPS_SMOOTHING
+            } else {
+                fps
+            };
+        }
+    }
+}
+
+/// Start timing a frame stage.
+///
+/// Returns `None` on `wasm32-unknown-unknown`, which has no wall clock; the
+/// stage durations then stay zero.
+pub(crate) fn stage_start() -> Option<Instant> {
+    if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+        None
+    } else {
+        Some(Instant::now())
+    }
+}
+
+/// Time elapsed since a [`stage_start`].
+pub(crate) fn stage_elapsed(started: Option<Instant>) -> Duration {
+    started.map_or(Duration::ZERO, |started| started.elapsed())
+}
+
+/// Frame timing bookkeeping kept in the view state.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PerfTracker {
+    pub(crate) stats: PerfStats,
+    pub(crate) decimation: Duration,
+    pub(crate) layout: Duration,
+    pub(crate) cache_hits: usize,
+    pub(crate) cache_misses: usize,
+    pub(crate) visible_points: usize,
+    pub(crate) last_frame: Option<Instant>,
+}
+
+impl PerfTracker {
+    /// Reset per-frame counters before building a frame.
+    pub(crate) fn begin_frame(&mut self) {
+        self.decimation = Duration::ZERO;
+        self.layout = Duration::ZERO;
+        self.cache_hits = 0;
+        self.cache_misses = 0;
+        self.visible_points = 0;
+    }
+
+    /// Publish per-stage counters gathered while building a frame.
+    pub(crate) fn finish_stages(&mut self) {
+        self.stats.layout = self.layout;
+        self.stats.cache_hits = self.cache_hits;
+        self.stats.cache_misses = self.cache_misses;
+        self.stats.visible_points = self.visible_points;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::{ScreenPoint, ScreenRect};
+    use crate::render::{LineSegment, LineStyle};
+
+    #[test]
+    fn record_counts_commands_and_smooths_fps() {
+        let mut render = RenderList::new();
+        let origin = ScreenPoint::new(0.0, 0.0);
+        render.push(RenderCommand::ClipRect(ScreenRect::new(origin, origin)));
+        render.push(RenderCommand::LineSegments {
+            segments: vec![LineSegment::new(origin, origin); 3],
+            style: LineStyle::default(),
+        });
+        let mut stats = PerfStats::default();
+        stats.record(&render, Duration::ZERO, Duration::ZERO, None);
+        assert_eq!((stats.command_count, stats.segment_count), (2, 3));
+        assert_eq!(stats.fps, 0.0);
+
+        stats.record(
+            &render,
+            Duration::ZERO,
+            Duration::ZERO,
+            Some(Duration::from_millis(10)),
+        );
+        assert!((stats.fps - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn tracker_publishes_stage_counters() {
+        let mut tracker = PerfTracker {
+            cache_hits: 5,
+            ..PerfTracker::default()
+        };
+        tracker.begin_frame();
+        tracker.cache_misses += 2;
+        tracker.visible_points += 40;
+        tracker.finish_stages();
+        assert_eq!(
+            (
+                tracker.stats.cache_hits,
+                tracker.stats.cache_misses,
+                tracker.stats.visible_points
+            ),
+            (0, 2, 40)
+        );
+    }
+}
@@ -0,0 +1,564 @@
+use std::cmp::Ordering;
+
+use crate::annotation::AnnotationKind;
+use crate::axis::TextMeasurer;
+use crate::geom::{ScreenPoint, ScreenRect};
+use crate::plot::Plot;
+use crate::render::{
+    Color, LineSegment, LineStyle, MarkerShape, MarkerStyle, RectStyle, RenderCommand, RenderList,
+    TextStyle,
+};
+use crate::style::Theme;
+use crate::transform::Transform;
+
+use super::config::PlotViewConfig;
+use super::constants::{
+    ANNOTATION_ARROW_HEAD_SIZE, ANNOTATION_ARROW_WIDTH, ANNOTATION_FONT_SIZE,
+    ANNOTATION_LINE_HEIGHT, ANNOTATION_OFFSET, MAX_PIN_LABEL_COVERAGE, MAX_PIN_LABELS,
+    PIN_CLUSTER_RADIUS, PIN_INDICATOR_CLUSTER_RADIUS, PIN_INDICATOR_FONT_SIZE, PIN_INDICATOR_INSET,
+    PIN_INDICATOR_SIZE, PIN_LABEL_OFFSET, PIN_RING_INNER_PAD, PIN_RING_OUTER_PAD,
+};
+use super::geometry::{clamp_point, distance_sq, rect_intersects_any};
+use super::series::{marker_style_and_size, push_arrow};
+use super::text::measure_multiline;
+
+pub(crate) fn build_pins(
+    render: &mut RenderList,
+    plot: &Plot,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+    config: &PlotViewConfig,
+) {
+    if plot.pins().is_empty() {
+        return;
+    }
+
+    let theme = plot.theme();
+    let series_labels = &config.hover_label;
+    let font_size = 12.0;
+    let line_height = 14.0;
+    let mut labels: Vec<PinLabel> = Vec::new();
+    let mut offscreen: Vec<ScreenPoint> = Vec::new();
+    render.push(RenderCommand::ClipRect(plot_rect));
+
+    for pin in plot.pins() {
+        let Some(series) = plot
+            .series()
+            .iter()
+            .find(|series| series.id() == pin.series_id)
+        else {
+            continue;
+        };
+        if !series.is_visible() {
+            continue;
+        }
+        let Some(point) = series.point_by_id(pin.point_id) else {
+            continue;
+        };
+        let Some(screen) = transform.data_to_screen(point) else {
+            continue;
+        };
+
+        if screen.x < plot_rect.min.x
+            || screen.x > plot_rect.max.x
+            || screen.y < plot_rect.min.y
+            || screen.y > plot_rect.max.y
+        {
+            offscreen.push(screen);
+            continue;
+        }
+
+        let (marker_style, base_size) = marker_style_and_size(series);
+
+        let ring_outer = base_size + PIN_RING_OUTER_PAD;
+        let ring_inner = base_size + PIN_RING_INNER_PAD;
+        render.push(RenderCommand::Points {
+            points: vec![screen],
+            style: MarkerStyle {
+                color: theme.axis,
+                size: ring_outer,
+                shape: MarkerShape::Circle,
+            },
+        });
+        render.push(RenderCommand::Points {
+            points: vec![screen],
+            style: MarkerStyle {
+                color: theme.background,
+                size: ring_inner,
+                shape: MarkerShape::Circle,
+            },
+        });
+
+        render.push(RenderCommand::Points {
+            points: vec![screen],
+            style: marker_style,
+        });
+
+        let x_text = plot.x_axis().format_value(point.x);
+        let y_text = plot.y_axis().format_value(point.y);
+        let label = format!("{}\nx: {x_text}\ny: {y_text}", series_labels.format(series));
+        let size = measure_multiline(measurer, &label, font_size);
+        labels.push(PinLabel {
+            screen,
+            label,
+            size,
+        });
+    }
+
+    if config.offscreen_pin_indicators {
+        build_offscreen_pin_indicators(render, theme, &offscreen, plot_rect, measurer);
+    }
+
+    if labels.is_empty() {
+        render.push(RenderCommand::ClipEnd);
+        return;
+    }
+
+    let plot_area = plot_rect.width().max(1.0) * plot_rect.height().max(1.0);
+    let total_label_area: f32 = labels.iter().map(|label| label.size.0 * label.size.1).sum();
+    let dense =
+        labels.len() > MAX_PIN_LABELS || total_label_area > plot_area * MAX_PIN_LABEL_COVERAGE;
+
+    let mut clusters = cluster_pin_labels(&labels, PIN_CLUSTER_RADIUS);
+    clusters.sort_by(|a, b| {
+        let size_cmp = b.len().cmp(&a.len());
+        if size_cmp != Ordering::Equal {
+            return size_cmp;
+        }
+        let min_a = a.iter().copied().min().unwrap_or(0);
+        let min_b = b.iter().copied().min().unwrap_or(0);
+        min_a.cmp(&min_b)
+    });
+
+    let mut placed: Vec<ScreenRect> = Vec::new();
+    let mut single_budget = if dense { MAX_PIN_LABELS } else { usize::MAX };
+    for cluster in clusters {
+        if cluster.len() >= 2 {
+            if !dense {
+                let mut local_placed = placed.clone();
+                let mut placements: Vec<(ScreenPoint, ScreenRect, usize)> = Vec::new();
+                let mut success = true;
+                for index in &cluster {
+                    let entry = &labels[*index];
+                    if let Some((origin, rect)) = place_label(
+                        entry.screen,
+                        entry.size,
+                        plot_rect,
+                        PIN_LABEL_OFFSET,
+                        &local_placed,
+                    ) {
+                        local_placed.push(rect);
+                        placements.push((origin, rect, *index));
+                    } else {
+                        success = false;
+                        break;
+                    }
+                }
+
+                if success {
+                    placed = local_placed;
+                    for (origin, rect, index) in placements {
+                        let entry = &labels[index];
+                        push_label_with_leader(
+                            render,
+                            rect,
+                            origin,
+                            entry.screen,
+                            &entry.label,
+                            font_size,
+                            line_height,
+                            theme,
+                        );
+                    }
+                    continue;
+                }
+            }
+
+            let center = cluster_center(&labels, &cluster);
+            let label = format!("{} pins", cluster.len());
+            let size = measure_multiline(measurer, &label, font_size);
+            if let Some((origin, rect)) =
+                place_label(center, size, plot_rect, PIN_LABEL_OFFSET, &placed)
+            {
+                placed.push(rect);
+                push_label_with_leader(
+                    render,
+                    rect,
+                    origin,
+                    center,
+                    &label,
+                    font_size,
+                    line_height,
+                    theme,
+                );
+            }
+            continue;
+        }
+
+        if single_budget == 0 {
+            continue;
+        }
+        let index = cluster[0];
+        let entry = &labels[index];
+        if let Some((origin, rect)) = place_label(
+            entry.screen,
+            entry.size,
+            plot_rect,
+            PIN_LABEL_OFFSET,
+            &placed,
+        ) {
+            placed.push(rect);
+            push_label_with_leader(
+                render,
+                rect,
+                origin,
+                entry.screen,
+                &entry.label,
+                font_size,
+                line_height,
+                theme,
+            );
+            single_budget = single_budget.saturating_sub(1);
+        }
+    }
+
+    render.push(RenderCommand::ClipEnd);
+}
+
+#[derive(Debug, Clone)]
+struct PinLabel {
+    screen: ScreenPoint,
+    label: String,
+    size: (f32, f32),
+}
+
+/// Draw an arrow at the plot border toward each group of off-screen pins,
+/// with the group size next to arrows that stand for several pins.
+fn build_offscreen_pin_indicators(
+    render: &mut RenderList,
+    theme: &Theme,
+    pins: &[ScreenPoint],
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+) {
+    let inset = ScreenRect::new(
+        ScreenPoint::new(
+            plot_rect.min.x + PIN_INDICATOR_INSET,
+            plot_rect.min.y + PIN_INDICATOR_INSET,
+        ),
+        ScreenPoint::new(
+            plot_rect.max.x - PIN_INDICATOR_INSET,
+            plot_rect.max.y - PIN_INDICATOR_INSET,
+        ),
+    );
+    if !inset.is_valid() {
+        return;
+    }
+    let mut segments = Vec::new();
+    for (anchor, (dx, dy), count) in offscreen_pin_groups(pins, inset, PIN_INDICATOR_CLUSTER_RADIUS)
+    {
+        let size = PIN_INDICATOR_SIZE;
+        let tip = ScreenPoint::new(anchor.x + dx * size, anchor.y + dy * size);
+        for side in [-1.0, 1.0] {
+            let wing = ScreenPoint::new(
+                anchor.x - dx * size - dy * size * side,
+                anchor.y - dy * size + dx * size * side,
+            );
+            segments.push(LineSegment::new(wing, tip));
+        }
+        if count < 2 {
+            continue;
+        }
+        let text = count.to_string();
+        let (width, height) = measurer.measure(&text, PIN_INDICATOR_FONT_SIZE);
+        let offset = size * 2.0 + width.max(height) * 0.5;
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(
+                anchor.x - dx * offset - width * 0.5,
+                anchor.y - dy * offset - height * 0.5,
+            ),
+            text,
+            style: TextStyle {
+                color: theme.axis,
+                size: PIN_INDICATOR_FONT_SIZE,
+            },
+        });
+    }
+    if !segments.is_empty() {
+        render.push(RenderCommand::LineSegments {
+            segments,
+            style: LineStyle {
+                color: theme.axis,
+                width: 2.0,
+                dash: None,
+            },
+        });
+    }
+}
+
+/// Group off-screen pins by where they leave `bounds`.
+///
+/// Each pin is clamped onto `bounds`; pins landing within `radius` of an
+/// earlier group's anchor join it. Returns each group's anchor, the unit
+/// direction from it toward its pins and the pin count.
+fn offscreen_pin_groups(
+    pins: &[ScreenPoint],
+    bounds: ScreenRect,
+    radius: f32,
+) -> Vec<(ScreenPoint, (f32, f32), usize)> {
+    let mut groups: Vec<(ScreenPoint, (f32, f32), usize)> = Vec::new();
+    for pin in pins {
+        let anchor = ScreenPoint::new(
+            pin.x.clamp(bounds.min.x, bounds.max.x),
+            pin.y.clamp(bounds.min.y, bounds.max.y),
+        );
+        let direction = (pin.x - anchor.x, pin.y - anchor.y);
+        match groups
+            .iter_mut()
+            .find(|(existing, _, _)| distance_sq(*existing, anchor) <= radius * radius)
+        {
+            Some((_, sum, count)) => {
+                sum.0 += direction.0;
+                sum.1 += direction.1;
+                *count += 1;
+            }
+            None => groups.push((anchor, direction, 1)),
+        }
+    }
+    groups.retain_mut(|(_, (dx, dy), _)| {
+        let length = (*dx * *dx + *dy * *dy).sqrt();
+        if !length.is_finite() || length <= 0.0 {
+            return false;
+        }
+        *dx /= length;
+        *dy /= length;
+        true
+    });
+    groups
+}
+
+fn cluster_pin_labels(labels: &[PinLabel], radius: f32) -> Vec<Vec<usize>> {
+    let radius_sq = radius * radius;
+    let mut visited = vec![false; labels.len()];
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+
+    for start in 0..labels.len() {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut cluster = Vec::new();
+        let mut stack = vec![start];
+        while let Some(index) = stack.pop() {
+            cluster.push(index);
+            for next in 0..labels.len() {
+                if visited[next] {
+                    continue;
+                }
+                if distance_sq(labels[index].screen, labels[next].screen) <= radius_sq {
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        clusters.push(cluster);
+    }
+
+    clusters
+}
+
+fn cluster_center(labels: &[PinLabel], cluster: &[usize]) -> ScreenPoint {
+    let mut sum_x = 0.0;
+    let mut sum_y = 0.0;
+    for index in cluster {
+        let screen = labels[*index].screen;
+        sum_x += screen.x;
+        sum_y += screen.y;
+    }
+    let count = cluster.len().max(1) as f32;
+    ScreenPoint::new(sum_x / count, sum_y / count)
+}
+
+/// Draw the plot's text, arrow and callout annotations.
+///
+/// Labels try the pin label positions around their anchor and skip those
+/// overlapping labels placed earlier; a label with no free position is
+/// dropped, though its arrow is still drawn.
+pub(crate) fn build_annotations(
+    render: &mut RenderList,
+    plot: &Plot,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+) {
+    let viewport = transform.viewport();
+    let theme = plot.theme();
+    let visible: Vec<_> = plot
+        .annotations()
+        .filter(|(_, annotation)| annotation.intersects(viewport))
+        .collect();
+    if visible.is_empty() {
+        return;
+    }
+    render.push(RenderCommand::ClipRect(plot_rect));
+    let mut placed: Vec<ScreenRect> = Vec::new();
+    for (_, annotation) in visible {
+        let Some(anchor) = transform.data_to_screen(annotation.anchor) else {
+            continue;
+        };
+        let color = annotation.color.unwrap_or(theme.axis);
+        let size = measure_multiline(measurer, &annotation.text, ANNOTATION_FONT_SIZE);
+        match annotation.kind {
+            AnnotationKind::Text => {
+                if annotation.text.is_empty() {
+                    continue;
+                }
+                if let Some((origin, rect)) =
+                    place_label(anchor, size, plot_rect, ANNOTATION_OFFSET, &placed)
+                {
+                    placed.push(rect);
+                    push_annotation_text(render, origin, &annotation.text, color);
+                }
+            }
+            AnnotationKind::Arrow { tail } => {
+                let Some(tail) = transform.data_to_screen(tail) else {
+                    continue;
+                };
+                let mut segments = Vec::new();
+                push_arrow(
+                    &mut segments,
+                    tail,
+                    anchor,
+                    ANNOTATION_ARROW_HEAD_SIZE,
+                    plot_rect,
+                );
+                render.push(RenderCommand::LineSegments {
+                    segments,
+                    style: LineStyle {
+                        color,
+                        width: ANNOTATION_ARROW_WIDTH,
+                        dash: None,
+                    },
+                });
+                if annotation.text.is_empty() {
+                    continue;
+                }
+                if let Some((origin, rect)) = place_label(tail, size, plot_rect, 2.0, &placed) {
+                    placed.push(rect);
+                    push_annotation_text(render, origin, &annotation.text, color);
+                }
+            }
+            AnnotationKind::Callout => {
+                if let Some((origin, rect)) =
+                    place_label(anchor, size, plot_rect, PIN_LABEL_OFFSET, &placed)
+                {
+                    placed.push(rect);
+                    push_label_with_leader(
+                        render,
+                        rect,
+                        origin,
+                        anchor,
+                        &annotation.text,
+                        ANNOTATION_FONT_SIZE,
+                        ANNOTATION_LINE_HEIGHT,
+                        theme,
+                    );
+                }
+            }
+        }
+    }
+    render.push(RenderCommand::ClipEnd);
+}
+
+/// Draw unboxed annotation text with the padding of a pin label.
+fn push_annotation_text(render: &mut RenderList, origin: ScreenPoint, text: &str, color: Color) {
+    for (index, line) in text.lines().enumerate() {
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(
+                origin.x + 4.0,
+                origin.y + index as f32 * ANNOTATION_LINE_HEIGHT + 2.0,
+            ),
+            text: line.to_string(),
+            style: TextStyle {
+                color,
+                size: ANNOTATION_FONT_SIZE,
+            },
+        });
+    }
+}
+
+fn pin_label_candidates(screen: ScreenPoint, size: (f32, f32), offset: f32) -> [ScreenPoint; 6] {
+    [
+        ScreenPoint::new(screen.x + offset, screen.y + offset),
+        ScreenPoint::new(screen.x + offset, screen.y - size.1 - offset),
+        ScreenPoint::new(screen.x - size.0 - offset, screen.y + offset),
+        ScreenPoint::new(screen.x - size.0 - offset, screen.y - size.1 - offset),
+        ScreenPoint::new(screen.x - size.0 * 0.5, screen.y - size.1 - offset),
+        ScreenPoint::new(screen.x - size.0 * 0.5, screen.y + offset),
+    ]
+}
+
+fn place_label(
+    screen: ScreenPoint,
+    size: (f32, f32),
+    plot_rect: ScreenRect,
+    offset: f32,
+    placed: &[ScreenRect],
+) -> Option<(ScreenPoint, ScreenRect)> {
+    for origin in pin_label_candidates(screen, size, offset) {
+        let origin = clamp_point(origin, plot_rect, size);
+        let rect = ScreenRect::new(
+            origin,
+            ScreenPoint::new(origin.x + size.0, origin.y + size.1),
+        );
+        if !rect_intersects_any(rect, placed) {
+            return Some((origin, rect));
+        }
+    }
+    None
+}
+
+#[allow(clippy::too_many_arguments)]
+fn push_label_with_leader(
+    render: &mut RenderList,
+    rect: ScreenRect,
+    origin: ScreenPoint,
+    screen: ScreenPoint,
+    label: &str,
+    font_size: f32,
+    line_height: f32,
+    theme: &Theme,
+) {
+    let anchor = ScreenPoint::new(
+        screen.x.clamp(rect.min.x, rect.max.x),
+        screen.y.clamp(rect.min.y, rect.max.y),
+    );
+    render.push(RenderCommand::LineSegments {
+        segments: vec![LineSegment::new(screen, anchor)],
+        style: LineStyle {
+            color: theme.pin_border,
+            width: 1.0,
+            dash: None,
+        },
+    });
+    render.push(RenderCommand::Rect {
+        rect,
+        style: RectStyle {
+            fill: theme.pin_bg,
+            stroke: theme.pin_border,
+            stroke_width: 1.0,
+        },
+    });
+    for (index, line) in label.lines().enumerate() {
+        let line_y = origin.y + index as f32 * line_height + 2.0;
+        render.push(RenderCommand::Text {
+            position: ScreenPoint::new(origin.x + 4.0, line_y),
+            text: line.to_string(),
+            style: TextStyle {
+                color: theme.axis,
+                size: font_size,
+            },
+        });
+    }
+}
@@ -0,0 +1,995 @@
+use std::collections::HashSet;
+
+use crate::axis::TextMeasurer;
+use crate::datasource::{ErrorBar, ScrollDecimation};
+use crate::geom::{Point as DataPoint, ScreenPoint, ScreenRect};
+use crate::plot::Plot;
+use crate::render::{
+    ArrowScale, BandStyle, BlendMode, BoxStyle, CandleStyle, Color, ContourStyle, ImageStyle,
+    Interpolation, LineSegment, LineStyle, MarkerShape, MarkerStyle, QuiverStyle, RectStyle,
+    RenderCacheKey, RenderCommand, RenderList, TextStyle, build_error_whiskers, build_fill_between,
+    build_line_segments, build_scatter_points, build_stem_segments, contour_levels, dash_segments,
+    fit_image, interpolate_points, rasterize_grid, rasterize_heatmap,
+};
+use crate::series::{Series, SeriesId, SeriesKind};
+use crate::style::Theme;
+use crate::transform::Transform;
+use crate::view::{Range, View, Viewport};
+
+use super::config::PlotViewConfig;
+use super::constants::{
+    CANDLE_MIN_SPACING, CONTOUR_LABEL_FONT_SIZE, CONTOUR_LABEL_SPACING, QUIVER_COLOR_BINS,
+    QUIVER_HEAD_ANGLE, QUIVER_MAX_ARROWS, SPLINE_MAX_STEPS, SPLINE_MIN_PIXELS_PER_POINT,
+    SPLINE_PIXELS_PER_STEP,
+};
+use super::geometry::{distance_sq, normalized_rect};
+use super::perf::{stage_elapsed, stage_start};
+use super::state::{PlotUiState, SeriesCache};
+
+pub(crate) fn build_series(
+    render: &mut RenderList,
+    plot: &Plot,
+    state: &mut PlotUiState,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+    config: &PlotViewConfig,
+) {
+    let plot_width = plot_rect.width().max(1.0) as usize;
+    let size = (
+        plot_rect.width().round() as u32,
+        plot_rect.height().round() as u32,
+    );
+
+    render.push(RenderCommand::ClipRect(plot_rect));
+    state.cache_frame = state.cache_frame.wrapping_add(1);
+    let frame = state.cache_frame;
+    let mut blend = BlendMode::Normal;
+    // Follow views mostly shift by a few pixels per frame.
+    let scrolling = matches!(
+        plot.view(),
+        View::FollowLastN { .. } | View::FollowLastNXY { .. }
+    );
+
+    for series in plot.series_in_draw_order() {
+        if !series.is_visible() {
+            continue;
+        }
+        if series.blend_mode() != blend {
+            blend = series.blend_mode();
+            render.push(RenderCommand::Blend(blend));
+        }
+        let bypass_points = config.decimation_bypass_points;
+        if bypass_points > 0
+            && !series.is_live_sampled()
+            && matches!(
+                series.kind(),
+                SeriesKind::Line(_)
+                    | SeriesKind::Scatter(_)
+                    | SeriesKind::Stem(_)
+                    | SeriesKind::Area(_)
+            )
+        {
+            let drawn = series.with_store(|store| {
+                let data = store.data();
+                let visible = data.range_by_x(transform.viewport().x);
+                if visible.len() > bypass_points || store.has_breaks() {
+                    return false;
+                }
+                // Keep the neighbors just outside the view so lines reach
+                // the plot edges.
+                let start = visible.start.saturating_sub(1);
+                let end = (visible.end + 1).min(data.points().len());
+                state.perf.visible_points += visible.len();
+                push_point_series(
+                    render,
+                    series,
+                    &data.points()[start..end],
+                    transform,
+                    plot_rect,
+                );
+                true
+            });
+            if drawn {
+                build_error_bars(render, series, transform, plot_rect);
+                state.series_cache.remove(&series.id());
+                continue;
+            }
+        }
+        let cache = state.series_cache.entry(series.id()).or_default();
+        let kind = std::mem::discriminant(series.kind());
+        if cache.kind != Some(kind) {
+            *cache = SeriesCache {
+                kind: Some(kind),
+                ..SeriesCache::default()
+            };
+        }
+        cache.last_used = frame;
+        match series.kind() {
+            SeriesKind::Image(style) => {
+                build_image_series(render, series, style, cache, transform, plot_rect);
+                continue;
+            }
+            SeriesKind::Heatmap(style) => {
+                build_heatmap_series(render, series, style, cache, transform);
+                continue;
+            }
+            SeriesKind::Contour(style) => {
+                build_contour_series(
+                    render,
+                    plot.theme(),
+                    series,
+                    style,
+                    cache,
+                    transform,
+                    plot_rect,
+                    measurer,
+                );
+                continue;
+            }
+            SeriesKind::Quiver(style) => {
+                build_quiver_series(render, series, style, transform, plot_rect);
+                continue;
+            }
+            SeriesKind::Box(style) => {
+                build_box_series(render, series, style, transform);
+                continue;
+            }
+            SeriesKind::Band(style) => {
+                build_band_series(render, series, style, transform, plot_rect);
+                continue;
+            }
+            SeriesKind::Candlestick(style) => {
+                build_candle_series(render, series, style, transform, plot_rect);
+                continue;
+            }
+            SeriesKind::Line(_)
+            | SeriesKind::Scatter(_)
+            | SeriesKind::Stem(_)
+            | SeriesKind::Area(_) => {}
+        }
+        let key = RenderCacheKey {
+            viewport: transform.viewport(),
+            size,
+            generation: series.generation(),
+        };
+        if cache.key.as_ref() == Some(&key) {
+            state.perf.cache_hits += 1;
+        } else {
+            state.perf.cache_misses += 1;
+            let decimation_started = stage_start();
+            let x_range = transform.viewport().x;
+            if series.sample_live(x_range, plot_width, &mut cache.points) {
+                cache.scroll = ScrollDecimation::default();
+            } else {
+                series.with_store(|store| {
+                    let scrolled = if scrolling {
+                        store.decimate_scrolling(x_range, plot_width, &mut cache.scroll)
+                    } else {
+                        cache.scroll = ScrollDecimation::default();
+                        None
+                    };
+                    let decimated = match scrolled {
+                        Some(points) => points,
+                        None => store.decimate(x_range, plot_width, &mut state.decimation_scratch),
+                    };
+                    cache.points.clear();
+                    cache.points.extend_from_slice(decimated);
+                });
+            }
+            cache.key = Some(key.clone());
+            state.perf.decimation += stage_elapsed(decimation_started);
+        }
+        state.perf.visible_points += cache.points.len();
+
+        push_point_series(render, series, &cache.points, transform, plot_rect);
+        build_error_bars(render, series, transform, plot_rect);
+    }
+
+    if blend != BlendMode::Normal {
+        render.push(RenderCommand::Blend(BlendMode::Normal));
+    }
+    let live: HashSet<SeriesId> = plot.series().iter().map(Series::id).collect();
+    state.trim_series_cache(&live, config.series_cache_bytes);
+    render.push(RenderCommand::ClipEnd);
+}
+
+/// Push draw commands for a line, scatter, stem or area series from data
+/// points.
+fn push_point_series(
+    render: &mut RenderList,
+    series: &Series,
+    points: &[DataPoint],
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    match series.kind() {
+        SeriesKind::Line(style) => {
+            let mut smooth = Vec::new();
+            let outline = smoothed_points(series, points, plot_rect, &mut smooth);
+            push_line(render, outline, *style, transform, plot_rect);
+        }
+        SeriesKind::Area(style) => {
+            let mut smooth = Vec::new();
+            let outline = smoothed_points(series, points, plot_rect, &mut smooth);
+            let columns: Vec<(f64, f64, f64)> = outline
+                .iter()
+                .map(|point| (point.x, point.y, style.baseline))
+                .collect();
+            push_fill(render, &columns, style.fill, transform, plot_rect);
+            push_line(render, outline, style.line, transform, plot_rect);
+        }
+        SeriesKind::Scatter(style) => {
+            let mut markers = Vec::new();
+            build_scatter_points(points, transform, plot_rect, &mut markers);
+            if !markers.is_empty() {
+                render.push(RenderCommand::Points {
+                    points: markers,
+                    style: *style,
+                });
+            }
+        }
+        SeriesKind::Stem(style) => {
+            let mut segments = Vec::new();
+            build_stem_segments(points, style.baseline, transform, plot_rect, &mut segments);
+            if !segments.is_empty() {
+                render.push(RenderCommand::LineSegments {
+                    segments,
+                    style: style.line,
+                });
+            }
+            let mut markers = Vec::new();
+            build_scatter_points(points, transform, plot_rect, &mut markers);
+            if !markers.is_empty() {
+                render.push(RenderCommand::Points {
+                    points: markers,
+                    style: style.marker,
+                });
+            }
+        }
+        SeriesKind::Image(_)
+        | SeriesKind::Heatmap(_)
+        | SeriesKind::Contour(_)
+        | SeriesKind::Quiver(_)
+        | SeriesKind::Box(_)
+        | SeriesKind::Band(_)
+        | SeriesKind::Candlestick(_) => {}
+    }
+}
+
+/// Push error whiskers for the visible points of a line or scatter series.
+///
+/// Skipped while more points are visible than half the plot width, where
+/// the whiskers would merge into a solid band.
+fn build_error_bars(
+    render: &mut RenderList,
+    series: &Series,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    if !matches!(series.kind(), SeriesKind::Line(_) | SeriesKind::Scatter(_)) {
+        return;
+    }
+    let max_points = (plot_rect.width() * 0.5) as usize;
+    let samples: Vec<(DataPoint, ErrorBar)> = series.with_store(|store| {
+        if !store.has_errors() {
+            return Vec::new();
+        }
+        let data = store.data();
+        let range = data.range_by_x(transform.viewport().x);
+        if range.len() > max_points {
+            return Vec::new();
+        }
+        range
+            .filter_map(|index| Some((data.point(index)?, store.error(index)?)))
+            .collect()
+    });
+    let mut whiskers = Vec::new();
+    build_error_whiskers(&samples, transform, &mut whiskers);
+    if whiskers.is_empty() {
+        return;
+    }
+    let style = series.error_style();
+    render.push(RenderCommand::ErrorBars {
+        whiskers,
+        style: LineStyle {
+            color: style.color.unwrap_or_else(|| series_color(series)),
+            width: style.width,
+            dash: None,
+        },
+        cap_width: style.cap_width,
+    });
+}
+
+/// Points along the series' interpolated curve or staircase, or `points`
+/// when drawn with straight segments.
+fn smoothed_points<'a>(
+    series: &Series,
+    points: &'a [DataPoint],
+    plot_rect: ScreenRect,
+    smooth: &'a mut Vec<DataPoint>,
+) -> &'a [DataPoint] {
+    let interpolation = series.interpolation();
+    let steps = match interpolation {
+        Interpolation::Linear => return points,
+        Interpolation::Step(_) => 0,
+        _ => match spline_steps(points.len(), plot_rect.width()) {
+            steps if steps >= 2 => steps,
+            _ => return points,
+        },
+    };
+    interpolate_points(points, interpolation, steps, smooth);
+    smooth
+}
+
+fn push_line(
+    render: &mut RenderList,
+    points: &[DataPoint],
+    style: LineStyle,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let mut segments = Vec::new();
+    build_line_segments(points, transform, plot_rect, &mut segments);
+    if let Some(dash) = style.dash {
+        segments = dash_segments(&segments, dash);
+    }
+    if !segments.is_empty() {
+        render.push(RenderCommand::LineSegments { segments, style });
+    }
+}
+
+/// Push fills between the two Y values of `(x, y_upper, y_lower)` columns.
+fn push_fill(
+    render: &mut RenderList,
+    columns: &[(f64, f64, f64)],
+    color: Color,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let mut fills = Vec::new();
+    build_fill_between(columns, transform, plot_rect, &mut fills);
+    for (upper, lower) in fills {
+        render.push(RenderCommand::FillBetween {
+            upper,
+            lower,
+            color,
+        });
+    }
+}
+
+/// Samples per span for smoothed lines, or 0 when points are too dense.
+fn spline_steps(points: usize, width: f32) -> usize {
+    if points < 3 || points as f32 * SPLINE_MIN_PIXELS_PER_POINT > width {
+        return 0;
+    }
+    ((width / points as f32 / SPLINE_PIXELS_PER_STEP) as usize).clamp(2, SPLINE_MAX_STEPS)
+}
+
+fn build_image_series(
+    render: &mut RenderList,
+    series: &Series,
+    style: &ImageStyle,
+    cache: &mut SeriesCache,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let generation = series.generation();
+    let stale = cache
+        .raster_key
+        .as_ref()
+        .is_none_or(|(cached_generation, cached_style, _)| {
+            *cached_generation != generation || cached_style != style
+        });
+    if stale {
+        cache.raster = series
+            .with_grid(|store| rasterize_grid(store.grid(), style))
+            .flatten();
+        cache.raster_key = Some((generation, style.clone(), [0; 4]));
+    }
+    let Some(image) = cache.raster.as_ref() else {
+        return;
+    };
+    let Some((rect, image)) = series
+        .grid_extent()
+        .and_then(|extent| data_rect_to_screen(transform, extent))
+        .and_then(|rect| fit_image(image, rect, plot_rect, &mut cache.screen_image))
+    else {
+        return;
+    };
+    render.push(RenderCommand::Image { rect, image });
+}
+
+/// Draw the heatmap rows inside the viewport, reduced to at most one cell
+/// per pixel.
+fn build_heatmap_series(
+    render: &mut RenderList,
+    series: &Series,
+    style: &ImageStyle,
+    cache: &mut SeriesCache,
+    transform: &Transform,
+) {
+    let generation = series.generation();
+    let drawn = series.with_grid(|store| {
+        let rows = store.grid().height();
+        let extent = store.bounds()?;
+        let step = extent.x.span() / rows as f64;
+        let viewport = transform.viewport();
+        let row_at = |x: f64| ((x - extent.x.min) / step).clamp(0.0, rows as f64);
+        let first = row_at(viewport.x.min).floor() as usize;
+        let last = row_at(viewport.x.max).ceil() as usize;
+        if first >= last {
+            return None;
+        }
+        let visible = Viewport::new(
+            Range::new(
+                extent.x.min + first as f64 * step,
+                extent.x.min + last as f64 * step,
+            ),
+            extent.y,
+        );
+        let rect = data_rect_to_screen(transform, visible)?;
+        let window = [
+            first,
+            last,
+            rect.width().ceil() as usize,
+            rect.height().ceil() as usize,
+        ];
+        let stale =
+            cache
+                .raster_key
+                .as_ref()
+                .is_none_or(|(cached, cached_style, cached_window)| {
+                    *cached != generation || cached_style != style || *cached_window != window
+                });
+        if stale {
+            cache.raster =
+                rasterize_heatmap(store.grid(), first..last, window[2], window[3], style);
+            cache.raster_key = Some((generation, style.clone(), window));
+        }
+        Some(rect)
+    });
+    if let (Some(Some(rect)), Some(image)) = (drawn, cache.raster.clone()) {
+        render.push(RenderCommand::Image { rect, image });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_contour_series(
+    render: &mut RenderList,
+    theme: &Theme,
+    series: &Series,
+    style: &ContourStyle,
+    cache: &mut SeriesCache,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+    measurer: &impl TextMeasurer,
+) {
+    let generation = series.generation();
+    let stale = cache
+        .contour_key
+        .as_ref()
+        .is_none_or(|(cached_generation, cached_levels)| {
+            *cached_generation != generation || cached_levels != &style.levels
+        });
+    if stale {
+        cache.contours = series
+            .with_grid(|store| contour_levels(store.grid(), store.extent(), &style.levels))
+            .unwrap_or_default();
+        cache.contour_key = Some((generation, style.levels.clone()));
+    }
+
+    let mut segments = Vec::new();
+    let mut labels: Vec<(ScreenPoint, String)> = Vec::new();
+    for level in &cache.contours {
+        let mut level_segments = Vec::new();
+        for (start, end) in &level.segments {
+            build_line_segments(&[*start, *end], transform, plot_rect, &mut level_segments);
+            segments.extend_from_slice(&level_segments);
+            if !style.show_labels {
+                continue;
+            }
+            let Some(segment) = level_segments.first() else {
+                continue;
+            };
+            let mid = ScreenPoint::new(
+                (segment.start.x + segment.end.x) * 0.5,
+                (segment.start.y + segment.end.y) * 0.5,
+            );
+            let spacing_sq = CONTOUR_LABEL_SPACING * CONTOUR_LABEL_SPACING;
+            if labels
+                .iter()
+                .all(|(placed, _)| distance_sq(*placed, mid) >= spacing_sq)
+            {
+                labels.push((mid, format!("{}", level.level)));
+            }
+        }
+    }
+    if !segments.is_empty() {
+        render.push(RenderCommand::LineSegments {
+            segments,
+            style: style.line,
+        });
+    }
+    for (center, text) in labels {
+        let size = measurer.measure(&text, CONTOUR_LABEL_FONT_SIZE);
+        let origin = ScreenPoint::new(center.x - size.0 * 0.5, center.y - size.1 * 0.5);
+        render.push(RenderCommand::Rect {
+            rect: ScreenRect::new(
+                origin,
+                ScreenPoint::new(origin.x + size.0, origin.y + size.1),
+            ),
+            style: RectStyle {
+                fill: with_alpha(theme.background, 0.85),
+                stroke: with_alpha(theme.background, 0.0),
+                stroke_width: 0.0,
+            },
+        });
+        render.push(RenderCommand::Text {
+            position: origin,
+            text,
+            style: TextStyle {
+                color: style.line.color,
+                size: CONTOUR_LABEL_FONT_SIZE,
+            },
+        });
+    }
+}
+
+fn build_quiver_series(
+    render: &mut RenderList,
+    series: &Series,
+    style: &QuiverStyle,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let viewport = transform.viewport();
+    let mut arrows: Vec<(ScreenPoint, ScreenPoint, f64)> = Vec::new();
+    series.with_store(|store| {
+        let data = store.data();
+        let range = data.range_by_x(viewport.x);
+        let stride = range.len().div_ceil(QUIVER_MAX_ARROWS).max(1);
+        for index in range.step_by(stride) {
+            let (Some(point), Some(vector)) = (data.point(index), store.vector(index)) else {
+                continue;
+            };
+            if point.x < viewport.x.min
+                || point.x > viewport.x.max
+                || point.y < viewport.y.min
+                || point.y > viewport.y.max
+            {
+                continue;
+            }
+            let Some(start) = transform.data_to_screen(point) else {
+                continue;
+            };
+            let end = match style.scale {
+                ArrowScale::Data(factor) => transform.data_to_screen(DataPoint::new(
+                    point.x + vector.dx * factor,
+                    point.y + vector.dy * factor,
+                )),
+                ArrowScale::Pixels(factor) => Some(ScreenPoint::new(
+                    start.x + vector.dx as f32 * factor,
+                    start.y - vector.dy as f32 * factor,
+                )),
+            };
+            if let Some(end) = end {
+                arrows.push((start, end, vector.magnitude()));
+            }
+        }
+    });
+    if arrows.is_empty() {
+        return;
+    }
+
+    let magnitude_range = style.magnitude_range.unwrap_or_else(|| {
+        arrows.iter().fold(
+            Range::new(f64::INFINITY, f64::NEG_INFINITY),
+            |range, arrow| Range::new(range.min.min(arrow.2), range.max.max(arrow.2)),
+        )
+    });
+    let bins = if style.magnitude_colormap.is_some() {
+        QUIVER_COLOR_BINS
+    } else {
+        1
+    };
+    let mut binned: Vec<Vec<LineSegment>> = vec![Vec::new(); bins];
+    for (start, end, magnitude) in arrows {
+        let bin = if bins > 1 && magnitude_range.span() > 0.0 {
+            let t = (magnitude - magnitude_range.min) / magnitude_range.span();
+            ((t.clamp(0.0, 1.0) * bins as f64) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        push_arrow(&mut binned[bin], start, end, style.head_size, plot_rect);
+    }
+    for (bin, segments) in binned.into_iter().enumerate() {
+        if segments.is_empty() {
+            continue;
+        }
+        let color = match &style.magnitude_colormap {
+            Some(colormap) => colormap.sample((bin as f32 + 0.5) / bins as f32),
+            None => style.color,
+        };
+        render.push(RenderCommand::LineSegments {
+            segments,
+            style: LineStyle {
+                color,
+                width: style.width,
+                dash: None,
+            },
+        });
+    }
+}
+
+pub(crate) fn push_arrow(
+    out: &mut Vec<LineSegment>,
+    start: ScreenPoint,
+    end: ScreenPoint,
+    head_size: f32,
+    plot_rect: ScreenRect,
+) {
+    let dx = end.x - start.x;
+    let dy = end.y - start.y;
+    let length = (dx * dx + dy * dy).sqrt();
+    if !length.is_finite() || length <= f32::EPSILON {
+        return;
+    }
+    let margin = length + head_size;
+    if end.x < plot_rect.min.x - margin
+        || end.x > plot_rect.max.x + margin
+        || end.y < plot_rect.min.y - margin
+        || end.y > plot_rect.max.y + margin
+    {
+        return;
+    }
+    out.push(LineSegment::new(start, end));
+    let head = head_size.min(length * 0.5);
+    let (ux, uy) = (dx / length, dy / length);
+    let (sin, cos) = QUIVER_HEAD_ANGLE.sin_cos();
+    for side in [-1.0_f32, 1.0] {
+        let rx = ux * cos - side * uy * sin;
+        let ry = side * ux * sin + uy * cos;
+        out.push(LineSegment::new(
+            end,
+            ScreenPoint::new(end.x - rx * head, end.y - ry * head),
+        ));
+    }
+}
+
+fn build_band_series(
+    render: &mut RenderList,
+    series: &Series,
+    style: &BandStyle,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let viewport = transform.viewport();
+    let buckets = plot_rect.width().max(1.0) as usize;
+    let bucket_span = viewport.x.span() / buckets as f64;
+    let mut columns: Vec<(f64, f64, f64)> = Vec::new();
+    series.with_store(|store| {
+        let data = store.data();
+        let visible = data.range_by_x(viewport.x);
+        // One band past each side keeps the fill running to the plot edges.
+        let range = visible.start.saturating_sub(1)..(visible.end + 1).min(data.len());
+        // Dense bands keep the lowest and highest value per pixel column.
+        let merge = range.len() > buckets * 2;
+        let mut last_bucket = None;
+        for index in range {
+            let (Some(point), Some(band)) = (data.point(index), store.band(index)) else {
+                continue;
+            };
+            let bucket = ((point.x - viewport.x.min) / bucket_span).floor();
+            if merge
+                && last_bucket == Some(bucket)
+                && let Some(column) = columns.last_mut()
+            {
+                column.1 = column.1.max(band.max);
+                column.2 = column.2.min(band.min);
+                continue;
+            }
+            last_bucket = Some(bucket);
+            columns.push((point.x, band.max, band.min));
+        }
+    });
+    push_fill(render, &columns, style.fill, transform, plot_rect);
+    if let Some(edge) = style.edges {
+        for upper in [true, false] {
+            let points: Vec<DataPoint> = columns
+                .iter()
+                .map(|&(x, high, low)| DataPoint::new(x, if upper { high } else { low }))
+                .collect();
+            push_line(render, &points, edge, transform, plot_rect);
+        }
+    }
+}
+
+fn build_box_series(
+    render: &mut RenderList,
+    series: &Series,
+    style: &BoxStyle,
+    transform: &Transform,
+) {
+    let viewport = transform.viewport();
+    let half = style.box_width.abs() * 0.5;
+    let to_screen = |x: f64, y: f64| transform.data_to_screen(DataPoint::new(x, y));
+    let mut segments = Vec::new();
+    let mut rects = Vec::new();
+    series.with_store(|store| {
+        let data = store.data();
+        let range = data.range_by_x(Range::new(viewport.x.min - half, viewport.x.max + half));
+        for index in range {
+            let (Some(point), Some(stats)) = (data.point(index), store.box_stats(index)) else {
+                continue;
+            };
+            let x = point.x;
+            let violin = style.violin && !stats.density.is_empty();
+            let box_half = if violin { half * 0.25 } else { half };
+            let (Some(q1_left), Some(q3_right), Some(median_left), Some(median_right)) = (
+                to_screen(x - box_half, stats.q1),
+                to_screen(x + box_half, stats.q3),
+                to_screen(x - box_half, stats.median),
+                to_screen(x + box_half, stats.median),
+            ) else {
+                continue;
+            };
+            rects.push(normalized_rect(ScreenRect::new(q1_left, q3_right)));
+            segments.push(LineSegment::new(median_left, median_right));
+
+            let cap_half = half * 0.5;
+            for (from, to) in [(stats.q1, stats.min), (stats.q3, stats.max)] {
+                if let (Some(start), Some(end)) = (to_screen(x, from), to_screen(x, to)) {
+                    segments.push(LineSegment::new(start, end));
+                }
+                if !violin
+                    && let (Some(left), Some(right)) =
+                        (to_screen(x - cap_half, to), to_screen(x + cap_half, to))
+                {
+                    segments.push(LineSegment::new(left, right));
+                }
+            }
+
+            if violin {
+                let steps = (stats.density.len() - 1).max(1) as f64;
+                for side in [-1.0, 1.0] {
+                    let outline: Vec<ScreenPoint> = stats
+                        .density
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, density)| {
+                            let y = stats.min + (stats.max - stats.min) * i as f64 / steps;
+                            to_screen(x + side * density * half, y)
+                        })
+                        .collect();
+                    segments.extend(
+                        outline
+                            .windows(2)
+                            .map(|pair| LineSegment::new(pair[0], pair[1])),
+                    );
+                }
+            }
+        }
+    });
+
+    for rect in rects {
+        render.push(RenderCommand::Rect {
+            rect,
+            style: RectStyle {
+                fill: style.fill,
+                stroke: style.line.color,
+                stroke_width: style.line.width,
+            },
+        });
+    }
+    if !segments.is_empty() {
+        render.push(RenderCommand::LineSegments {
+            segments,
+            style: style.line,
+        });
+    }
+}
+
+fn build_candle_series(
+    render: &mut RenderList,
+    series: &Series,
+    style: &CandleStyle,
+    transform: &Transform,
+    plot_rect: ScreenRect,
+) {
+    let max_candles = (plot_rect.width() / CANDLE_MIN_SPACING).max(1.0) as usize;
+    let candles =
+        series.with_store(|store| store.decimate_candles(transform.viewport().x, max_candles));
+    let to_screen = |x: f64, y: f64| transform.data_to_screen(DataPoint::new(x, y));
+    let mut wicks = [Vec::new(), Vec::new()];
+    for (extent, candle) in candles {
+        // Merged candles widen to cover every bucket they replace.
+        let half = (extent.span() + style.candle_width.abs()) * 0.5;
+        let x = (extent.min + extent.max) * 0.5;
+        let (color, side) = if candle.is_rising() {
+            (style.up, 0)
+        } else {
+            (style.down, 1)
+        };
+        if let (Some(high), Some(low)) = (to_screen(x, candle.high), to_screen(x, candle.low)) {
+            wicks[side].push(LineSegment::new(high, low));
+        }
+        let (Some(open), Some(close)) = (
+            to_screen(x - half, candle.open),
+            to_screen(x + half, candle.close),
+        ) else {
+            continue;
+        };
+        let rect = normalized_rect(ScreenRect::new(open, close));
+        if rect.height() < 1.0 {
+            // Flat candles draw as a tick so they stay visible.
+            let y = (rect.min.y + rect.max.y) * 0.5;
+            wicks[side].push(LineSegment::new(
+                ScreenPoint::new(rect.min.x, y),
+                ScreenPoint::new(rect.max.x, y),
+            ));
+            continue;
+        }
+        render.push(RenderCommand::Rect {
+            rect,
+            style: RectStyle {
+                fill: color,
+                stroke: color,
+                stroke_width: 0.0,
+            },
+        });
+    }
+    for (segments, color) in wicks.into_iter().zip([style.up, style.down]) {
+        if !segments.is_empty() {
+            render.push(RenderCommand::LineSegments {
+                segments,
+                style: LineStyle {
+                    color,
+                    width: style.wick_width,
+                    dash: None,
+                },
+            });
+        }
+    }
+}
+
+pub(crate) fn data_rect_to_screen(transform: &Transform, extent: Viewport) -> Option<ScreenRect> {
+    let corner_a = transform.data_to_screen(DataPoint::new(extent.x.min, extent.y.max))?;
+    let corner_b = transform.data_to_screen(DataPoint::new(extent.x.max, extent.y.min))?;
+    let rect = normalized_rect(ScreenRect::new(corner_a, corner_b));
+    rect.is_valid().then_some(rect)
+}
+
+pub(crate) fn marker_style_and_size(series: &Series) -> (MarkerStyle, f32) {
+    match series.kind() {
+        SeriesKind::Line(line) => (
+            MarkerStyle {
+                color: line.color,
+                size: 6.0,
+                shape: MarkerShape::Circle,
+            },
+            6.0,
+        ),
+        SeriesKind::Scatter(marker) => (
+            MarkerStyle {
+                color: marker.color,
+                size: marker.size.max(6.0),
+                shape: marker.shape,
+            },
+            marker.size.max(6.0),
+        ),
+        SeriesKind::Image(style) | SeriesKind::Heatmap(style) => (
+            MarkerStyle {
+                color: style.colormap.sample(0.5),
+                size: 6.0,
+                shape: MarkerShape::Square,
+            },
+            6.0,
+        ),
+        SeriesKind::Contour(style) => (
+            MarkerStyle {
+                color: style.line.color,
+                size: 6.0,
+                shape: MarkerShape::Circle,
+            },
+            6.0,
+        ),
+        SeriesKind::Quiver(style) => (
+            MarkerStyle {
+                color: quiver_color(style),
+                size: 6.0,
+                shape: MarkerShape::Circle,
+            },
+            6.0,
+        ),
+        SeriesKind::Stem(style) => (
+            MarkerStyle {
+                color: style.marker.color,
+                size: style.marker.size.max(6.0),
+                shape: style.marker.shape,
+            },
+            style.marker.size.max(6.0),
+        ),
+        SeriesKind::Box(style) => (
+            MarkerStyle {
+                color: style.line.color,
+                size: 6.0,
+                shape: MarkerShape::Square,
+            },
+            6.0,
+        ),
+        SeriesKind::Area(style) => (
+            MarkerStyle {
+                color: style.line.color,
+                size: 6.0,
+                shape: MarkerShape::Circle,
+            },
+            6.0,
+        ),
+        SeriesKind::Band(style) => (
+            MarkerStyle {
+                color: band_color(style),
+                size: 6.0,
+                shape: MarkerShape::Square,
+            },
+            6.0,
+        ),
+        SeriesKind::Candlestick(style) => (
+            MarkerStyle {
+                color: style.up,
+                size: 6.0,
+                shape: MarkerShape::Square,
+            },
+            6.0,
+        ),
+    }
+}
+
+pub(crate) fn series_color(series: &Series) -> Color {
+    match series.kind() {
+        SeriesKind::Line(style) => style.color,
+        SeriesKind::Scatter(style) => style.color,
+        SeriesKind::Image(style) | SeriesKind::Heatmap(style) => style.colormap.sample(0.5),
+        SeriesKind::Contour(style) => style.line.color,
+        SeriesKind::Quiver(style) => quiver_color(style),
+        SeriesKind::Box(style) => style.line.color,
+        SeriesKind::Stem(style) => style.marker.color,
+        SeriesKind::Area(style) => style.line.color,
+        SeriesKind::Band(style) => band_color(style),
+        SeriesKind::Candlestick(style) => style.up,
+    }
+}
+
+/// Opaque band color for swatches and labels, preferring the edge color.
+fn band_color(style: &BandStyle) -> Color {
+    match style.edges {
+        Some(edge) => edge.color,
+        None => Color {
+            a: 1.0,
+            ..style.fill
+        },
+    }
+}
+
+pub(crate) fn lerp_color(start: Color, end: Color, t: f32) -> Color {
+    Color::new(
+        start.r + (end.r - start.r) * t,
+        start.g + (end.g - start.g) * t,
+        start.b + (end.b - start.b) * t,
+        start.a + (end.a - start.a) * t,
+    )
+}
+
+pub(crate) fn with_alpha(color: Color, alpha: f32) -> Color {
+    Color {
+        a: (color.a * alpha).clamp(0.0, 1.0),
+        ..color
+    }
+}
+
+fn quiver_color(style: &QuiverStyle) -> Color {
+    style
+        .magnitude_colormap
+        .as_ref()
+        .map_or(style.color, |colormap| colormap.sample(0.5))
+}
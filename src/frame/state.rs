@@ -0,0 +1,468 @@
+use std::collections::{HashMap, HashSet};
+use std::mem::Discriminant;
+use std::sync::Arc;
+#[cfg(feature = "gpui")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "gpui")]
+use gpui::MouseButton;
+
+use crate::axis::AxisLayoutCache;
+#[cfg(feature = "gpui")]
+use crate::axis::nearest_tick;
+use crate::datasource::{DecimationScratch, ScrollDecimation};
+use crate::geom::Point;
+use crate::geom::{ScreenPoint, ScreenRect};
+#[cfg(feature = "gpui")]
+use crate::interaction::HitRegion;
+use crate::interaction::{Pin, PlotRegions};
+use crate::render::{
+    ContourLevel, ImageStyle, RasterImage, RenderCacheKey, RenderList, ScreenImage,
+};
+use crate::series::{SeriesId, SeriesKind};
+use crate::transform::Transform;
+use crate::view::{Range, Viewport};
+
+#[cfg(any(feature = "gpui", test))]
+use super::geometry::rect_contains;
+use super::perf::PerfTracker;
+
+#[cfg(feature = "gpui")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DragMode {
+    Pan,
+    ZoomRect,
+    ZoomX,
+    ZoomY,
+}
+
+#[cfg(feature = "gpui")]
+#[derive(Debug, Clone)]
+pub(crate) struct DragState {
+    pub(crate) mode: DragMode,
+    pub(crate) start: ScreenPoint,
+    pub(crate) last: ScreenPoint,
+    pub(crate) active: bool,
+    /// Plot viewport when the drag started, for the interaction log.
+    pub(crate) start_viewport: Option<Viewport>,
+}
+
+#[cfg(feature = "gpui")]
+impl DragState {
+    pub(crate) fn new(mode: DragMode, start: ScreenPoint, active: bool) -> Self {
+        Self {
+            mode,
+            start,
+            last: start,
+            active,
+            start_viewport: None,
+        }
+    }
+}
+
+/// Animated return from an overscrolled viewport.
+#[cfg(feature = "gpui")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SpringBack {
+    pub(crate) from: Viewport,
+    pub(crate) to: Viewport,
+    pub(crate) started: Instant,
+    pub(crate) duration: Duration,
+}
+
+#[cfg(feature = "gpui")]
+impl SpringBack {
+    /// Viewport at `now` with ease-out timing, and whether the animation is
+    /// finished.
+    pub(crate) fn sample(&self, now: Instant) -> (Viewport, bool) {
+        let elapsed = now.saturating_duration_since(self.started);
+        if elapsed >= self.duration || self.duration.is_zero() {
+            return (self.to, true);
+        }
+        let t = elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        let eased = 1.0 - (1.0 - t).powi(3);
+        let lerp = |from: Range, to: Range| {
+            Range::new(
+                from.min + (to.min - from.min) * eased,
+                from.max + (to.max - from.max) * eased,
+            )
+        };
+        (
+            Viewport::new(lerp(self.from.x, self.to.x), lerp(self.from.y, self.to.y)),
+            false,
+        )
+    }
+}
+
+#[cfg(feature = "gpui")]
+#[derive(Debug, Clone)]
+pub(crate) struct ClickState {
+    pub(crate) region: HitRegion,
+    pub(crate) button: MouseButton,
+}
+
+#[cfg(feature = "gpui")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PinToggle {
+    pub(crate) pin: Pin,
+    pub(crate) added: bool,
+    pub(crate) at: Instant,
+    pub(crate) screen_pos: ScreenPoint,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct HoverTarget {
+    pub(crate) pin: Pin,
+    pub(crate) screen: ScreenPoint,
+    pub(crate) is_pinned: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SeriesCache {
+    pub(crate) kind: Option<Discriminant<SeriesKind>>,
+    pub(crate) key: Option<RenderCacheKey>,
+    pub(crate) points: Vec<crate::geom::Point>,
+    pub(crate) scroll: ScrollDecimation,
+    /// Generation, style and drawn window (first row, last row, width,
+    /// height) of the cached raster; the window is unused for images.
+    pub(crate) raster_key: Option<(u64, ImageStyle, [usize; 4])>,
+    pub(crate) raster: Option<RasterImage>,
+    /// Visible part of an image raster, reduced to the screen resolution.
+    pub(crate) screen_image: Option<ScreenImage>,
+    pub(crate) contour_key: Option<(u64, Vec<f64>)>,
+    pub(crate) contours: Vec<ContourLevel>,
+    pub(crate) last_used: u64,
+}
+
+impl SeriesCache {
+    /// Approximate heap usage of the cached geometry in bytes.
+    pub(crate) fn memory_bytes(&self) -> usize {
+        let points = self.points.capacity() * size_of::<Point>();
+        let raster = self.raster.as_ref().map_or(0, |raster| {
+            raster.width() * raster.height() * size_of::<crate::render::Color>()
+        });
+        let contours: usize = self
+            .contours
+            .iter()
+            .map(|level| level.segments.capacity() * size_of::<(Point, Point)>())
+            .sum();
+        let screen_image = self
+            .screen_image
+            .as_ref()
+            .map_or(0, ScreenImage::memory_bytes);
+        points + self.scroll.memory_bytes() + raster + screen_image + contours
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    not(feature = "gpui"),
+    allow(dead_code, reason = "read by the GPUI hit test")
+)]
+pub(crate) struct LegendEntry {
+    pub(crate) series_id: SeriesId,
+    pub(crate) row_rect: ScreenRect,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    not(feature = "gpui"),
+    allow(dead_code, reason = "read by the GPUI hit test")
+)]
+pub(crate) struct LegendLayout {
+    pub(crate) rect: ScreenRect,
+    pub(crate) entries: Vec<LegendEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PlotUiState {
+    pub(crate) x_layout: AxisLayoutCache,
+    pub(crate) y_layout: AxisLayoutCache,
+    pub(crate) y_axis_width: Option<f32>,
+    pub(crate) regions: PlotRegions,
+    pub(crate) plot_rect: Option<ScreenRect>,
+    pub(crate) transform: Option<Transform>,
+    pub(crate) viewport: Option<Viewport>,
+    #[cfg(feature = "gpui")]
+    pub(crate) drag: Option<DragState>,
+    #[cfg(feature = "gpui")]
+    pub(crate) spring_back: Option<SpringBack>,
+    #[cfg(feature = "gpui")]
+    pub(crate) pending_click: Option<ClickState>,
+    #[cfg(feature = "gpui")]
+    pub(crate) last_pin_toggle: Option<PinToggle>,
+    pub(crate) hover_target: Option<HoverTarget>,
+    pub(crate) hover_candidates: Vec<HoverTarget>,
+    pub(crate) hover_focus: Option<SeriesId>,
+    pub(crate) selection_rect: Option<ScreenRect>,
+    pub(crate) hover: Option<ScreenPoint>,
+    #[cfg(feature = "gpui")]
+    pub(crate) last_cursor: Option<ScreenPoint>,
+    pub(crate) linked_cursor_x: Option<f64>,
+    pub(crate) linked_brush_x: Option<Range>,
+    #[cfg(feature = "gpui")]
+    pub(crate) link_view_seq: u64,
+    #[cfg(feature = "gpui")]
+    pub(crate) link_cursor_seq: u64,
+    #[cfg(feature = "gpui")]
+    pub(crate) link_brush_seq: u64,
+    pub(crate) decimation_scratch: DecimationScratch,
+    pub(crate) series_cache: HashMap<SeriesId, SeriesCache>,
+    pub(crate) cache_frame: u64,
+    pub(crate) legend_layout: Option<LegendLayout>,
+    pub(crate) backdrop_image: Option<ScreenImage>,
+    pub(crate) perf: PerfTracker,
+    pub(crate) last_render: Option<Arc<RenderList>>,
+    pub(crate) focused: bool,
+}
+
+impl Default for PlotUiState {
+    fn default() -> Self {
+        Self {
+            x_layout: AxisLayoutCache::default(),
+            y_layout: AxisLayoutCache::default(),
+            y_axis_width: None,
+            regions: PlotRegions {
+                plot: ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(0.0, 0.0)),
+                x_axis: ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(0.0, 0.0)),
+                y_axis: ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(0.0, 0.0)),
+            },
+            plot_rect: None,
+            transform: None,
+            viewport: None,
+            #[cfg(feature = "gpui")]
+            drag: None,
+            #[cfg(feature = "gpui")]
+            spring_back: None,
+            #[cfg(feature = "gpui")]
+            pending_click: None,
+            #[cfg(feature = "gpui")]
+            last_pin_toggle: None,
+            hover_target: None,
+            hover_candidates: Vec::new(),
+            hover_focus: None,
+            selection_rect: None,
+            hover: None,
+            #[cfg(feature = "gpui")]
+            last_cursor: None,
+            linked_cursor_x: None,
+            linked_brush_x: None,
+            #[cfg(feature = "gpui")]
+            link_view_seq: 0,
+            #[cfg(feature = "gpui")]
+            link_cursor_seq: 0,
+            #[cfg(feature = "gpui")]
+            link_brush_seq: 0,
+            decimation_scratch: DecimationScratch::new(),
+            series_cache: HashMap::new(),
+            cache_frame: 0,
+            legend_layout: None,
+            backdrop_image: None,
+            perf: PerfTracker::default(),
+            last_render: None,
+            focused: false,
+        }
+    }
+}
+
+impl PlotUiState {
+    /// Forget linked overlays and treat updates up to `seq` as applied.
+    #[cfg(feature = "gpui")]
+    pub(crate) fn reset_link_state(&mut self, seq: u64) {
+        self.linked_cursor_x = None;
+        self.linked_brush_x = None;
+        self.link_view_seq = seq;
+        self.link_cursor_seq = seq;
+        self.link_brush_seq = seq;
+    }
+
+    #[cfg(feature = "gpui")]
+    pub(crate) fn clear_interaction(&mut self) {
+        self.drag = None;
+        self.pending_click = None;
+        self.selection_rect = None;
+    }
+
+    /// Whether a mouse drag is in progress.
+    #[cfg(feature = "gpui")]
+    pub(crate) fn is_dragging(&self) -> bool {
+        self.drag.as_ref().is_some_and(|drag| drag.active)
+    }
+
+    #[cfg(not(feature = "gpui"))]
+    pub(crate) fn is_dragging(&self) -> bool {
+        false
+    }
+
+    #[cfg(any(feature = "gpui", test))]
+    pub(crate) fn legend_hit(&self, point: ScreenPoint) -> Option<SeriesId> {
+        let layout = self.legend_layout.as_ref()?;
+        if !rect_contains(layout.rect, point) {
+            return None;
+        }
+        for entry in &layout.entries {
+            if rect_contains(entry.row_rect, point) {
+                return Some(entry.series_id);
+            }
+        }
+        None
+    }
+
+    /// Move the hover target to the next (or previous) series near the
+    /// cursor.
+    ///
+    /// Returns the new target and whether the cycle wrapped around, or `None`
+    /// when fewer than two series are within reach.
+    #[cfg(any(feature = "gpui", test))]
+    pub(crate) fn cycle_hover_target(&mut self, forward: bool) -> Option<(HoverTarget, bool)> {
+        let count = self.hover_candidates.len();
+        if count < 2 {
+            return None;
+        }
+        let current = self
+            .hover_target
+            .and_then(|target| {
+                self.hover_candidates
+                    .iter()
+                    .position(|candidate| candidate.pin.series_id == target.pin.series_id)
+            })
+            .unwrap_or(0);
+        let (next, wrapped) = if forward {
+            ((current + 1) % count, current + 1 == count)
+        } else {
+            ((current + count - 1) % count, current == 0)
+        };
+        let target = self.hover_candidates[next];
+        self.hover_target = Some(target);
+        self.hover_focus = Some(target.pin.series_id);
+        Some((target, wrapped))
+    }
+
+    /// Drop caches of series no longer in the plot, then evict the least
+    /// recently drawn entries until the total fits in `max_bytes`.
+    ///
+    /// Entries drawn in the current frame are never evicted, so a budget
+    /// smaller than one frame's caches does not rebuild them every frame.
+    pub(crate) fn trim_series_cache(&mut self, live: &HashSet<SeriesId>, max_bytes: usize) {
+        self.series_cache.retain(|id, _| live.contains(id));
+        let mut total: usize = self
+            .series_cache
+            .values()
+            .map(SeriesCache::memory_bytes)
+            .sum();
+        if total <= max_bytes {
+            return;
+        }
+        let mut entries: Vec<(u64, SeriesId)> = self
+            .series_cache
+            .iter()
+            .filter(|(_, cache)| cache.last_used != self.cache_frame)
+            .map(|(id, cache)| (cache.last_used, *id))
+            .collect();
+        entries.sort_unstable_by_key(|(last_used, _)| *last_used);
+        for (_, id) in entries {
+            if total <= max_bytes {
+                break;
+            }
+            if let Some(cache) = self.series_cache.remove(&id) {
+                total -= cache.memory_bytes();
+            }
+        }
+    }
+
+    /// Snap a screen point onto the nearest X and Y tick values.
+    #[cfg(feature = "gpui")]
+    pub(crate) fn snap_to_ticks(&self, point: ScreenPoint) -> ScreenPoint {
+        let Some(transform) = self.transform.as_ref() else {
+            return point;
+        };
+        let Some(data) = transform.screen_to_data(point) else {
+            return point;
+        };
+        let x = nearest_tick(self.x_layout.ticks(), data.x).unwrap_or(data.x);
+        let y = nearest_tick(self.y_layout.ticks(), data.y).unwrap_or(data.y);
+        transform.data_to_screen(Point::new(x, y)).unwrap_or(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::series::{Series, SeriesKind};
+
+    #[test]
+    fn trim_series_cache_drops_removed_and_least_recent_entries() {
+        let ids: Vec<SeriesId> = (0..3).map(|_| Series::line("s").id()).collect();
+        let mut state = PlotUiState::default();
+        for (frame, id) in ids.iter().enumerate() {
+            let cache = state.series_cache.entry(*id).or_default();
+            cache.points = vec![Point::new(0.0, 0.0); 4];
+            cache.last_used = frame as u64;
+        }
+        state.cache_frame = 2;
+        let entry_bytes = state.series_cache[&ids[0]].memory_bytes();
+
+        let live: HashSet<SeriesId> = ids[..2].iter().copied().collect();
+        state.trim_series_cache(&live, usize::MAX);
+        assert_eq!(state.series_cache.len(), 2);
+
+        let live: HashSet<SeriesId> = ids.iter().copied().collect();
+        state.trim_series_cache(&live, entry_bytes);
+        assert_eq!(state.series_cache.len(), 1);
+        assert!(state.series_cache.contains_key(&ids[1]));
+    }
+
+    #[test]
+    fn trim_series_cache_keeps_current_frame_entries() {
+        let ids: Vec<SeriesId> = (0..3).map(|_| Series::line("s").id()).collect();
+        let mut state = PlotUiState {
+            cache_frame: 5,
+            ..PlotUiState::default()
+        };
+        for (index, id) in ids.iter().enumerate() {
+            let cache = state.series_cache.entry(*id).or_default();
+            cache.points = vec![Point::new(0.0, 0.0); 4];
+            cache.last_used = if index == 0 { 4 } else { 5 };
+        }
+
+        let live: HashSet<SeriesId> = ids.iter().copied().collect();
+        state.trim_series_cache(&live, 0);
+        assert_eq!(state.series_cache.len(), 2);
+        assert!(!state.series_cache.contains_key(&ids[0]));
+    }
+
+    #[test]
+    fn cycle_hover_target_walks_candidates_and_reports_wrap() {
+        let series: Vec<Series> = (0..3)
+            .map(|_| Series::from_iter_y("s", [0.0], SeriesKind::Line(Default::default())))
+            .collect();
+        let mut state = PlotUiState::default();
+        state.hover_candidates = series
+            .iter()
+            .map(|series| HoverTarget {
+                pin: Pin {
+                    series_id: series.id(),
+                    point_id: series.point_id(0).expect("point"),
+                },
+                screen: ScreenPoint::new(0.0, 0.0),
+                is_pinned: false,
+            })
+            .collect();
+        state.hover_target = state.hover_candidates.first().copied();
+        let index_of = |id: SeriesId| series.iter().position(|series| series.id() == id);
+
+        let steps: Vec<(Option<usize>, bool)> = (0..3)
+            .filter_map(|_| state.cycle_hover_target(true))
+            .map(|(target, wrapped)| (index_of(target.pin.series_id), wrapped))
+            .collect();
+        assert_eq!(steps, [(Some(1), false), (Some(2), false), (Some(0), true)]);
+        let back = state
+            .cycle_hover_target(false)
+            .map(|(target, _)| target.pin);
+        assert_eq!(back.and_then(|pin| index_of(pin.series_id)), Some(2));
+        assert_eq!(state.hover_focus, back.map(|pin| pin.series_id));
+
+        state.hover_candidates.truncate(1);
+        assert!(state.cycle_hover_target(true).is_none());
+    }
+}
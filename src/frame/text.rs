@@ -0,0 +1,14 @@
+use crate::axis::TextMeasurer;
+
+/// Measure a label box that may span several lines, padded for a tooltip
+/// or legend background.
+pub(crate) fn measure_multiline(measurer: &impl TextMeasurer, text: &str, size: f32) -> (f32, f32) {
+    let mut width: f32 = 0.0;
+    let mut height: f32 = 0.0;
+    for line in text.lines() {
+        let (w, h) = measurer.measure(line, size);
+        width = width.max(w);
+        height += h.max(size * 1.2);
+    }
+    (width + 8.0, height + 8.0)
+}
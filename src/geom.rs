@@ -1,7 +1,8 @@
 //! Geometric primitives used by the plotting pipeline.
 //!
-//! Public types in this module represent data-space coordinates. Screen-space
-//! types are internal to render backends.
+//! [`Point`] and [`Vector`] are data-space coordinates. [`ScreenPoint`] and
+//! [`ScreenRect`] are logical pixels with the origin at the top-left, as used by
+//! [`RenderList`](crate::render::RenderList).
 
 /// A point in data space.
 ///
@@ -21,49 +22,114 @@ impl Point {
     }
 }
 
+/// A direction and magnitude in data space.
+///
+/// Used by vector-field (quiver) series.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vector {
+    /// X component in data units.
+    pub dx: f64,
+    /// Y component in data units.
+    pub dy: f64,
+}
+
+impl Vector {
+    /// Create a new vector.
+    pub fn new(dx: f64, dy: f64) -> Self {
+        Self { dx, dy }
+    }
+
+    /// Euclidean length of the vector.
+    pub fn magnitude(&self) -> f64 {
+        self.dx.hypot(self.dy)
+    }
+}
+
 /// A point in screen space (pixel coordinates).
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) struct ScreenPoint {
+pub struct ScreenPoint {
     /// X value in screen pixels.
-    pub(crate) x: f32,
+    pub x: f32,
     /// Y value in screen pixels.
-    pub(crate) y: f32,
+    pub y: f32,
 }
 
 impl ScreenPoint {
     /// Create a new screen point.
-    pub(crate) fn new(x: f32, y: f32) -> Self {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Convert to normalized device coordinates for a surface of the given size.
+    ///
+    /// The result spans `-1.0..=1.0` on both axes with Y pointing up, matching
+    /// wgpu clip space.
+    pub fn to_ndc(self, width: f32, height: f32) -> [f32; 2] {
+        [self.x / width * 2.0 - 1.0, 1.0 - self.y / height * 2.0]
+    }
+}
+
+impl From<ScreenPoint> for [f32; 2] {
+    fn from(point: ScreenPoint) -> Self {
+        [point.x, point.y]
+    }
+}
+
+impl From<[f32; 2]> for ScreenPoint {
+    fn from([x, y]: [f32; 2]) -> Self {
         Self { x, y }
     }
 }
 
 /// A rectangle in screen space (pixel coordinates).
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) struct ScreenRect {
+pub struct ScreenRect {
     /// Top-left corner.
-    pub(crate) min: ScreenPoint,
+    pub min: ScreenPoint,
     /// Bottom-right corner.
-    pub(crate) max: ScreenPoint,
+    pub max: ScreenPoint,
 }
 
 impl ScreenRect {
     /// Create a new screen rectangle from corners.
-    pub(crate) fn new(min: ScreenPoint, max: ScreenPoint) -> Self {
+    pub fn new(min: ScreenPoint, max: ScreenPoint) -> Self {
         Self { min, max }
     }
 
     /// Rectangle width in pixels.
-    pub(crate) fn width(&self) -> f32 {
+    pub fn width(&self) -> f32 {
         self.max.x - self.min.x
     }
 
     /// Rectangle height in pixels.
-    pub(crate) fn height(&self) -> f32 {
+    pub fn height(&self) -> f32 {
         self.max.y - self.min.y
     }
 
     /// Check whether the rectangle has positive area.
-    pub(crate) fn is_valid(&self) -> bool {
+    pub fn is_valid(&self) -> bool {
         self.width() > 0.0 && self.height() > 0.0
     }
+
+    /// Intersection with another rectangle.
+    ///
+    /// Disjoint rectangles yield an empty rectangle with zero width or height.
+    pub fn intersect(&self, other: ScreenRect) -> ScreenRect {
+        let min = ScreenPoint::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = ScreenPoint::new(
+            self.max.x.min(other.max.x).max(min.x),
+            self.max.y.min(other.max.y).max(min.y),
+        );
+        ScreenRect::new(min, max)
+    }
+
+    /// Corners in clockwise order starting at the top-left.
+    pub fn corners(&self) -> [ScreenPoint; 4] {
+        [
+            self.min,
+            ScreenPoint::new(self.max.x, self.min.y),
+            self.max,
+            ScreenPoint::new(self.min.x, self.max.y),
+        ]
+    }
 }
@@ -0,0 +1,244 @@
+//! Persistence helpers for multi-plot dashboards.
+
+use crate::plot::PlotState;
+
+use super::link::{PlotLinkGroup, PlotLinkOptions};
+use super::view::GpuiPlotView;
+
+/// Grid placement of a plot inside a dashboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutCell {
+    /// Zero-based row index.
+    pub row: u32,
+    /// Zero-based column index.
+    pub column: u32,
+    /// Number of rows spanned.
+    pub row_span: u32,
+    /// Number of columns spanned.
+    pub column_span: u32,
+}
+
+impl LayoutCell {
+    /// Create a single-cell placement.
+    pub fn new(row: u32, column: u32) -> Self {
+        Self {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    /// Set the number of rows and columns spanned.
+    pub fn with_span(mut self, row_span: u32, column_span: u32) -> Self {
+        self.row_span = row_span.max(1);
+        self.column_span = column_span.max(1);
+        self
+    }
+}
+
+/// Link-group membership of a dashboard plot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DashboardLink {
+    /// Index of the link group within the dashboard.
+    pub group: usize,
+    /// Link behavior of the member.
+    pub options: PlotLinkOptions,
+}
+
+/// A recorded plot entry in a [`DashboardLayout`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DashboardPlot {
+    /// Host-chosen key identifying the plot.
+    pub key: String,
+    /// Placement in the dashboard grid.
+    pub cell: LayoutCell,
+    /// Link-group membership, if the view is linked.
+    pub link: Option<DashboardLink>,
+    /// Captured plot state.
+    pub state: PlotState,
+}
+
+/// Arrangement, link-group membership and state of several plot views.
+///
+/// Record each [`GpuiPlotView`] under a stable key, persist the layout (with
+/// the `serde` feature), and later use [`DashboardLayout::link_groups`] and
+/// [`DashboardLayout::restore`] to rebuild an equivalent dashboard.
+///
+/// Link groups are persisted as the group indices of each plot; loading a
+/// layout creates one fresh group per index.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SavedLayout"))]
+pub struct DashboardLayout {
+    /// Recorded plots in insertion order.
+    pub plots: Vec<DashboardPlot>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    groups: Vec<PlotLinkGroup>,
+}
+
+/// Serialized form of a [`DashboardLayout`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SavedLayout {
+    plots: Vec<DashboardPlot>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SavedLayout> for DashboardLayout {
+    fn from(saved: SavedLayout) -> Self {
+        let mut layout = Self {
+            plots: saved.plots,
+            groups: Vec::new(),
+        };
+        let count = layout.group_count();
+        layout.groups.resize_with(count, PlotLinkGroup::new);
+        layout
+    }
+}
+
+impl DashboardLayout {
+    /// Create an empty layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a view under `key`, replacing any previous entry with that key.
+    pub fn record(&mut self, key: impl Into<String>, cell: LayoutCell, view: &GpuiPlotView) {
+        let key = key.into();
+        let link = view.link_binding().map(|binding| DashboardLink {
+            group: self.group_index(&binding.group),
+            options: binding.options,
+        });
+        let entry = DashboardPlot {
+            key,
+            cell,
+            link,
+            state: view.plot_handle().read(|plot| plot.capture_state()),
+        };
+        match self.plots.iter_mut().find(|plot| plot.key == entry.key) {
+            Some(existing) => *existing = entry,
+            None => self.plots.push(entry),
+        }
+    }
+
+    /// Access a recorded plot by key.
+    pub fn plot(&self, key: &str) -> Option<&DashboardPlot> {
+        self.plots.iter().find(|plot| plot.key == key)
+    }
+
+    /// Link groups for every group index referenced by the layout.
+    ///
+    /// Attach views with the group at [`DashboardLink::group`] and the
+    /// recorded options to restore link membership. Views attached this way
+    /// keep their group index when recorded again.
+    pub fn link_groups(&self) -> Vec<PlotLinkGroup> {
+        let count = self.group_count();
+        let mut groups: Vec<_> = self.groups.iter().take(count).cloned().collect();
+        groups.resize_with(count, PlotLinkGroup::new);
+        groups
+    }
+
+    /// Restore the recorded state for `key` onto a view.
+    ///
+    /// Returns `false` when no plot was recorded under `key`.
+    pub fn restore(&self, key: &str, view: &GpuiPlotView) -> bool {
+        let Some(entry) = self.plot(key) else {
+            return false;
+        };
+        view.plot_handle()
+            .write(|plot| plot.restore_state(&entry.state));
+        true
+    }
+
+    fn group_index(&mut self, group: &PlotLinkGroup) -> usize {
+        if let Some(index) = self.groups.iter().position(|known| known.same_group(group)) {
+            return index;
+        }
+        let index = self.groups.len().max(self.group_count());
+        self.groups.resize_with(index, PlotLinkGroup::new);
+        self.groups.push(group.clone());
+        index
+    }
+
+    fn group_count(&self) -> usize {
+        self.plots
+            .iter()
+            .filter_map(|plot| plot.link.map(|link| link.group + 1))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plot::Plot;
+
+    #[test]
+    fn record_shares_group_index_for_linked_views() {
+        let group = PlotLinkGroup::new();
+        let options = PlotLinkOptions::default();
+        let top = GpuiPlotView::new(Plot::new()).with_link_group(group.clone(), options);
+        let bottom = GpuiPlotView::new(Plot::new()).with_link_group(group, options);
+        let other = GpuiPlotView::new(Plot::new()).with_link_group(PlotLinkGroup::new(), options);
+
+        let mut layout = DashboardLayout::new();
+        layout.record("top", LayoutCell::new(0, 0), &top);
+        layout.record("bottom", LayoutCell::new(1, 0), &bottom);
+        layout.record("other", LayoutCell::new(0, 1).with_span(2, 1), &other);
+        layout.record("top", LayoutCell::new(0, 0), &top);
+
+        assert_eq!(layout.plots.len(), 3);
+        assert_eq!(
+            layout.plot("bottom").and_then(|p| p.link).map(|l| l.group),
+            Some(0)
+        );
+        assert_eq!(
+            layout.plot("other").and_then(|p| p.link).map(|l| l.group),
+            Some(1)
+        );
+        assert_eq!(layout.link_groups().len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn saved_layout_keeps_link_groups() {
+        let group = PlotLinkGroup::new();
+        let options = PlotLinkOptions::default();
+        let top = GpuiPlotView::new(Plot::new()).with_link_group(group.clone(), options);
+        let bottom = GpuiPlotView::new(Plot::new()).with_link_group(group, options);
+        let other = GpuiPlotView::new(Plot::new()).with_link_group(PlotLinkGroup::new(), options);
+        let mut layout = DashboardLayout::new();
+        layout.record("top", LayoutCell::new(0, 0), &top);
+        layout.record("bottom", LayoutCell::new(1, 0), &bottom);
+        layout.record("other", LayoutCell::new(0, 1), &other);
+
+        let text = serde_json::to_string(&layout).expect("serialize layout");
+        let mut loaded: DashboardLayout = serde_json::from_str(&text).expect("load layout");
+        assert_eq!(loaded.plots, layout.plots);
+        let groups = loaded.link_groups();
+        assert_eq!(groups.len(), 2);
+
+        // Rebuild the views from the loaded groups and record them again.
+        for key in ["top", "bottom", "other"] {
+            let entry = loaded.plot(key).expect("recorded plot").clone();
+            let link = entry.link.expect("linked plot");
+            let view = GpuiPlotView::new(Plot::new())
+                .with_link_group(groups[link.group].clone(), link.options);
+            loaded.record(key, entry.cell, &view);
+        }
+        assert_eq!(
+            loaded.plot("bottom").and_then(|p| p.link).map(|l| l.group),
+            Some(0)
+        );
+        assert_eq!(
+            loaded.plot("other").and_then(|p| p.link).map(|l| l.group),
+            Some(1)
+        );
+        assert_eq!(loaded.link_groups().len(), 2);
+    }
+}
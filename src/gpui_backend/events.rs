@@ -0,0 +1,106 @@
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::SystemTime;
+
+use crate::interaction::Pin;
+use crate::series::SeriesId;
+use crate::view::Viewport;
+
+/// A user interaction handled by a `GpuiPlotView`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InteractionKind {
+    /// A pan drag ended.
+    Pan {
+        /// Viewport when the drag started.
+        from: Viewport,
+        /// Viewport the drag settled on.
+        to: Viewport,
+    },
+    /// The view was zoomed by wheel, axis drag or box zoom.
+    Zoom {
+        /// Viewport before the zoom.
+        from: Viewport,
+        /// Viewport after the zoom.
+        to: Viewport,
+    },
+    /// The view was reset by double click.
+    Reset,
+    /// A point was pinned or unpinned.
+    PinToggled {
+        /// The toggled pin.
+        pin: Pin,
+        /// Whether the pin was added.
+        pinned: bool,
+    },
+    /// A pin slice was toggled with Ctrl+click.
+    PinSliceToggled {
+        /// X position of the slice.
+        x: f64,
+        /// Whether the slice was added.
+        added: bool,
+    },
+    /// A series was shown or hidden from the legend.
+    VisibilityToggled {
+        /// The toggled series.
+        series_id: SeriesId,
+        /// Whether the series is now visible.
+        visible: bool,
+    },
+}
+
+/// A timestamped [`InteractionKind`].
+///
+/// Delivered to receivers from
+/// [`GpuiPlotView::subscribe_interactions`](super::GpuiPlotView::subscribe_interactions)
+/// in the order interactions happened, for analytics, macro replay or undo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InteractionEvent {
+    /// Wall-clock time of the interaction.
+    pub time: SystemTime,
+    /// What happened.
+    pub kind: InteractionKind,
+}
+
+/// Fan-out of interaction events to subscribers.
+#[derive(Debug, Default)]
+pub(crate) struct InteractionLog {
+    subscribers: Vec<Sender<InteractionEvent>>,
+}
+
+impl InteractionLog {
+    pub(crate) fn subscribe(&mut self) -> Receiver<InteractionEvent> {
+        let (sender, receiver) = channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    /// Send an event to every subscriber, dropping those that hung up.
+    pub(crate) fn record(&mut self, kind: InteractionKind) {
+        if self.subscribers.is_empty() {
+            return;
+        }
+        let event = InteractionEvent {
+            time: SystemTime::now(),
+            kind,
+        };
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropped_subscribers_are_pruned() {
+        let mut log = InteractionLog::default();
+        let kept = log.subscribe();
+        drop(log.subscribe());
+        log.record(InteractionKind::Reset);
+        assert_eq!(log.subscribers.len(), 1);
+        assert_eq!(
+            kept.try_recv().map(|event| event.kind),
+            Ok(InteractionKind::Reset)
+        );
+    }
+}
@@ -1,15 +1,28 @@
-use std::sync::{Arc, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, Weak};
 
+use crate::error::RecoverLock;
+use crate::plot::Plot;
 use crate::view::{Range, Viewport};
 
+use super::redraw::RedrawSignal;
+
 const LINK_EPSILON: f64 = 1e-9;
 
 /// Member identifier inside a plot link group.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LinkMemberId(u64);
 
+impl LinkMemberId {
+    /// Source of updates broadcast through the public `PlotLinkGroup` API.
+    ///
+    /// Registered members start at 1, so every member applies these.
+    const EXTERNAL: Self = Self(0);
+}
+
 /// Link behavior switches for multi-plot synchronization.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlotLinkOptions {
     /// Synchronize X-axis range updates.
     pub link_x: bool,
@@ -21,6 +34,10 @@ pub struct PlotLinkOptions {
     pub link_brush: bool,
     /// Synchronize reset-view actions (double click reset).
     pub link_reset: bool,
+    /// Give every member the widest Y-axis width in the group, so stacked
+    /// plot areas and their grids line up pixel-exactly.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub align_y_axis_width: bool,
 }
 
 impl Default for PlotLinkOptions {
@@ -31,6 +48,7 @@ impl Default for PlotLinkOptions {
             link_cursor: false,
             link_brush: false,
             link_reset: true,
+            align_y_axis_width: false,
         }
     }
 }
@@ -47,12 +65,114 @@ impl PlotLinkGroup {
         Self::default()
     }
 
+    /// Check whether two handles refer to the same link group.
+    pub fn same_group(&self, other: &PlotLinkGroup) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Drive the X range of every member that links X.
+    ///
+    /// Members apply the range on their next repaint, as if another plot in
+    /// the group had been panned or zoomed.
+    pub fn set_x_range(&self, x_range: Range) {
+        let viewport = Viewport::new(x_range, Range::new(0.0, 1.0));
+        self.publish_manual_view(LinkMemberId::EXTERNAL, viewport, true, false);
+    }
+
+    /// Move the linked cursor of every member that links the cursor.
+    ///
+    /// `None` hides the cursor.
+    pub fn set_cursor_x(&self, x: Option<f64>) {
+        self.publish_cursor_x(LinkMemberId::EXTERNAL, x);
+    }
+
+    /// Reset the view of every member that links resets.
+    pub fn reset(&self) {
+        // Always issue a fresh update, so repeated resets are not deduplicated.
+        let mut state = self.inner.write_unpoisoned();
+        let seq = state.next_seq();
+        state.view_update = Some(ViewLinkUpdate {
+            seq,
+            source: LinkMemberId::EXTERNAL,
+            kind: ViewSyncKind::Reset,
+        });
+    }
+
     pub(crate) fn register_member(&self) -> LinkMemberId {
-        let mut state = self.inner.write().expect("link group lock");
+        let mut state = self.inner.write_unpoisoned();
         state.next_member_id = state.next_member_id.wrapping_add(1);
         LinkMemberId(state.next_member_id)
     }
 
+    /// Drop everything the group holds for a member that left or muted.
+    ///
+    /// Remaining members redraw when the member held the widest Y axis.
+    pub(crate) fn unregister_member(&self, member: LinkMemberId) {
+        let mut state = self.inner.write_unpoisoned();
+        let shared = state.shared_y_axis_width();
+        state.y_axis_widths.remove(&member);
+        state.members.retain(|entry| entry.id != member);
+        if state.focused == Some(member) {
+            state.focused = None;
+        }
+        if state.shared_y_axis_width() != shared {
+            state.request_redraws(None);
+        }
+    }
+
+    /// Member whose view has keyboard focus, if any.
+    ///
+    /// The focused member is the source of keyboard interactions; compare
+    /// it with [`GpuiPlotView::link_member_id`](super::GpuiPlotView::link_member_id).
+    pub fn focused_member(&self) -> Option<LinkMemberId> {
+        self.inner.read_unpoisoned().focused
+    }
+
+    /// Record a member gaining or losing focus.
+    ///
+    /// Losing focus only clears the record when that member held it, so the
+    /// order in which views observe a focus change does not matter.
+    pub(crate) fn set_member_focused(&self, member: LinkMemberId, focused: bool) {
+        let mut state = self.inner.write_unpoisoned();
+        if focused {
+            state.focused = Some(member);
+        } else if state.focused == Some(member) {
+            state.focused = None;
+        }
+    }
+
+    /// Make a member's plot visible to group-wide readouts, and let the
+    /// group ask its view for a redraw.
+    pub(crate) fn attach_view(
+        &self,
+        member: LinkMemberId,
+        plot: &Arc<RwLock<Plot>>,
+        redraw: &Arc<RedrawSignal>,
+    ) {
+        let mut state = self.inner.write_unpoisoned();
+        state.members.push(LinkMember {
+            id: member,
+            plot: Arc::downgrade(plot),
+            redraw: Arc::clone(redraw),
+        });
+    }
+
+    /// Plots of the current members, in joining order.
+    pub(crate) fn member_plots(&self) -> Vec<Arc<RwLock<Plot>>> {
+        let mut state = self.inner.write_unpoisoned();
+        state.members.retain(|entry| entry.plot.strong_count() > 0);
+        state
+            .members
+            .iter()
+            .filter_map(|entry| entry.plot.upgrade())
+            .collect()
+    }
+
+    /// Sequence number of the most recent update of any kind.
+    pub(crate) fn current_seq(&self) -> u64 {
+        self.inner.read_unpoisoned().next_seq
+    }
+
     pub(crate) fn publish_manual_view(
         &self,
         source: LinkMemberId,
@@ -63,7 +183,7 @@ impl PlotLinkGroup {
         if !sync_x && !sync_y {
             return;
         }
-        let mut state = self.inner.write().expect("link group lock");
+        let mut state = self.inner.write_unpoisoned();
         if let Some(current) = state.view_update
             && let ViewSyncKind::Manual {
                 viewport: current_viewport,
@@ -90,7 +210,7 @@ impl PlotLinkGroup {
     }
 
     pub(crate) fn publish_reset(&self, source: LinkMemberId) {
-        let mut state = self.inner.write().expect("link group lock");
+        let mut state = self.inner.write_unpoisoned();
         if let Some(current) = state.view_update
             && matches!(current.kind, ViewSyncKind::Reset)
             && current.source == source
@@ -106,7 +226,7 @@ impl PlotLinkGroup {
     }
 
     pub(crate) fn publish_cursor_x(&self, source: LinkMemberId, x: Option<f64>) {
-        let mut state = self.inner.write().expect("link group lock");
+        let mut state = self.inner.write_unpoisoned();
         if let Some(current) = state.cursor_update
             && current.source == source
             && option_f64_approx_eq(current.x, x)
@@ -118,7 +238,7 @@ impl PlotLinkGroup {
     }
 
     pub(crate) fn publish_brush_x(&self, source: LinkMemberId, x_range: Option<Range>) {
-        let mut state = self.inner.write().expect("link group lock");
+        let mut state = self.inner.write_unpoisoned();
         if let Some(current) = state.brush_update
             && current.source == source
             && option_range_approx_eq(current.x_range, x_range)
@@ -133,16 +253,32 @@ impl PlotLinkGroup {
         });
     }
 
+    /// Record the Y-axis width a member needs and return the width shared
+    /// by the group: the widest among all members.
+    ///
+    /// When the shared width changes, the other members redraw so every
+    /// plot area moves together.
+    pub(crate) fn negotiate_y_axis_width(&self, source: LinkMemberId, width: f32) -> f32 {
+        let mut state = self.inner.write_unpoisoned();
+        let previous = state.shared_y_axis_width();
+        state.y_axis_widths.insert(source, width);
+        let shared = state.shared_y_axis_width().unwrap_or(width);
+        if previous.is_some_and(|previous| previous != shared) {
+            state.request_redraws(Some(source));
+        }
+        shared
+    }
+
     pub(crate) fn latest_view_update(&self) -> Option<ViewLinkUpdate> {
-        self.inner.read().expect("link group lock").view_update
+        self.inner.read_unpoisoned().view_update
     }
 
     pub(crate) fn latest_cursor_update(&self) -> Option<CursorLinkUpdate> {
-        self.inner.read().expect("link group lock").cursor_update
+        self.inner.read_unpoisoned().cursor_update
     }
 
     pub(crate) fn latest_brush_update(&self) -> Option<BrushLinkUpdate> {
-        self.inner.read().expect("link group lock").brush_update
+        self.inner.read_unpoisoned().brush_update
     }
 }
 
@@ -153,6 +289,9 @@ struct LinkGroupState {
     view_update: Option<ViewLinkUpdate>,
     cursor_update: Option<CursorLinkUpdate>,
     brush_update: Option<BrushLinkUpdate>,
+    y_axis_widths: HashMap<LinkMemberId, f32>,
+    members: Vec<LinkMember>,
+    focused: Option<LinkMemberId>,
 }
 
 impl LinkGroupState {
@@ -160,6 +299,51 @@ impl LinkGroupState {
         self.next_seq = self.next_seq.wrapping_add(1);
         self.next_seq
     }
+
+    /// Widest Y-axis width reported by a member, if any reported one.
+    fn shared_y_axis_width(&self) -> Option<f32> {
+        self.y_axis_widths.values().copied().reduce(f32::max)
+    }
+
+    /// Ask every member view except `skip` to redraw.
+    fn request_redraws(&self, skip: Option<LinkMemberId>) {
+        for entry in &self.members {
+            if Some(entry.id) != skip {
+                entry.redraw.request();
+            }
+        }
+    }
+}
+
+/// A view attached to a link group.
+#[derive(Debug)]
+struct LinkMember {
+    id: LinkMemberId,
+    plot: Weak<RwLock<Plot>>,
+    redraw: Arc<RedrawSignal>,
+}
+
+/// Unregisters a member from its group once the last clone of its view is
+/// dropped.
+#[derive(Debug)]
+pub(crate) struct LinkMembership {
+    group: PlotLinkGroup,
+    member_id: LinkMemberId,
+}
+
+impl LinkMembership {
+    pub(crate) fn new(group: &PlotLinkGroup, member_id: LinkMemberId) -> Self {
+        Self {
+            group: group.clone(),
+            member_id,
+        }
+    }
+}
+
+impl Drop for LinkMembership {
+    fn drop(&mut self) {
+        self.group.unregister_member(self.member_id);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -167,6 +351,9 @@ pub(crate) struct LinkBinding {
     pub(crate) group: PlotLinkGroup,
     pub(crate) member_id: LinkMemberId,
     pub(crate) options: PlotLinkOptions,
+    pub(crate) muted: bool,
+    /// Keeps the member registered while any clone of the binding lives.
+    pub(crate) _membership: Arc<LinkMembership>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -259,4 +446,78 @@ mod tests {
         assert!(update.seq > first);
         assert!(matches!(update.kind, ViewSyncKind::Reset));
     }
+
+    #[test]
+    fn broadcasts_come_from_outside_the_group() {
+        let group = PlotLinkGroup::new();
+        let member = group.register_member();
+        group.set_x_range(Range::new(5.0, 15.0));
+        let update = group.latest_view_update().expect("view update");
+        assert_ne!(update.source, member);
+        let ViewSyncKind::Manual {
+            viewport,
+            sync_x,
+            sync_y,
+        } = update.kind
+        else {
+            panic!("expected a manual view update");
+        };
+        assert_eq!(viewport.x, Range::new(5.0, 15.0));
+        assert!(sync_x && !sync_y);
+
+        group.reset();
+        group.reset();
+        let reset = group.latest_view_update().expect("reset update");
+        assert!(matches!(reset.kind, ViewSyncKind::Reset));
+        assert_eq!(reset.seq, update.seq + 2);
+
+        group.set_cursor_x(Some(7.5));
+        assert_eq!(group.latest_cursor_update().map(|u| u.x), Some(Some(7.5)));
+    }
+
+    #[test]
+    fn broadcasts_survive_a_poisoned_group_lock() {
+        let group = PlotLinkGroup::new();
+        let poisoner = group.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.inner.write().expect("fresh lock");
+            panic!("poison the group lock");
+        })
+        .join();
+        assert!(group.inner.is_poisoned());
+
+        group.reset();
+        let reset = group.latest_view_update().expect("reset update");
+        assert!(matches!(reset.kind, ViewSyncKind::Reset));
+        group.set_x_range(Range::new(0.0, 1.0));
+        assert!(group.latest_view_update().expect("view update").seq > reset.seq);
+    }
+
+    #[test]
+    fn focus_moves_between_members() {
+        let group = PlotLinkGroup::new();
+        let top = group.register_member();
+        let bottom = group.register_member();
+        group.set_member_focused(top, true);
+        assert_eq!(group.focused_member(), Some(top));
+        // Focus arriving before the previous member reports the loss.
+        group.set_member_focused(bottom, true);
+        group.set_member_focused(top, false);
+        assert_eq!(group.focused_member(), Some(bottom));
+        group.unregister_member(bottom);
+        assert_eq!(group.focused_member(), None);
+    }
+
+    #[test]
+    fn y_axis_width_follows_the_widest_member() {
+        let group = PlotLinkGroup::new();
+        let narrow = group.register_member();
+        let wide = group.register_member();
+        assert_eq!(group.negotiate_y_axis_width(narrow, 30.0), 30.0);
+        assert_eq!(group.negotiate_y_axis_width(wide, 52.0), 52.0);
+        assert_eq!(group.negotiate_y_axis_width(narrow, 30.0), 52.0);
+        assert_eq!(group.negotiate_y_axis_width(wide, 28.0), 30.0);
+        group.unregister_member(narrow);
+        assert_eq!(group.negotiate_y_axis_width(wide, 28.0), 28.0);
+    }
 }
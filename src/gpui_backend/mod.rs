@@ -6,17 +6,22 @@
 
 #![allow(clippy::collapsible_if)]
 
-mod config;
 mod constants;
-mod frame;
-mod geometry;
-mod hover;
+mod dashboard;
+mod events;
 mod link;
 mod paint;
-mod state;
+mod readout;
+mod redraw;
 mod text;
 mod view;
 
-pub use config::PlotViewConfig;
+pub use crate::frame::{
+    AxisWidthPolicy, LegendEntryRenderer, LegendRow, LegendRowFn, PerfStats, PlotCorner,
+    PlotViewConfig, SeriesLabelFormatter, SoftLimits, TooltipMode, WheelZoom,
+};
+pub use dashboard::{DashboardLayout, DashboardLink, DashboardPlot, LayoutCell};
+pub use events::{InteractionEvent, InteractionKind};
 pub use link::{LinkMemberId, PlotLinkGroup, PlotLinkOptions};
+pub use readout::{LinkedReadout, LinkedReadoutRow};
 pub use view::{GpuiPlotView, PlotHandle};
@@ -3,46 +3,69 @@ use gpui::{
     font, point, px, quad,
 };
 
+use crate::frame::PlotFrame;
 use crate::geom::{ScreenPoint, ScreenRect};
 use crate::render::{
-    Color, LineSegment, LineStyle, MarkerShape, MarkerStyle, RectStyle, RenderCommand, TextStyle,
+    ClipStack, Color, LineSegment, LineStyle, MarkerShape, MarkerStyle, RasterImage, RectStyle,
+    RenderCommand, TextStyle,
 };
 
-use super::frame::PlotFrame;
-
 pub(crate) fn paint_frame(frame: &PlotFrame, window: &mut Window, cx: &mut App) {
-    let mut clip_stack: Vec<ContentMask<Pixels>> = Vec::new();
+    let mut clips = ClipStack::default();
     for command in frame.render.commands() {
+        let clip = clips.current();
         match command {
-            RenderCommand::ClipRect(rect) => {
-                clip_stack.push(ContentMask {
-                    bounds: to_bounds(*rect),
-                });
-            }
-            RenderCommand::ClipEnd => {
-                clip_stack.pop();
-            }
+            RenderCommand::ClipRect(rect) => clips.push(*rect),
+            RenderCommand::ClipEnd => clips.pop(),
+            RenderCommand::Blend(_) | RenderCommand::Role(_) => {}
             RenderCommand::LineSegments { segments, style } => {
-                with_clip(window, &clip_stack, |window| {
+                with_clip(window, clip, |window| {
                     paint_lines(window, segments, *style);
                 });
             }
             RenderCommand::Points { points, style } => {
-                with_clip(window, &clip_stack, |window| {
+                with_clip(window, clip, |window| {
                     paint_points(window, points, *style);
                 });
             }
             RenderCommand::Rect { rect, style } => {
-                with_clip(window, &clip_stack, |window| {
+                with_clip(window, clip, |window| {
                     paint_rect(window, *rect, *style);
                 });
             }
+            RenderCommand::ErrorBars {
+                whiskers,
+                style,
+                cap_width,
+            } => {
+                let segments: Vec<LineSegment> = whiskers
+                    .iter()
+                    .flat_map(|whisker| whisker.with_caps(*cap_width))
+                    .collect();
+                with_clip(window, clip, |window| {
+                    paint_lines(window, &segments, *style);
+                });
+            }
+            RenderCommand::FillBetween {
+                upper,
+                lower,
+                color,
+            } => {
+                with_clip(window, clip, |window| {
+                    paint_fill(window, upper, lower, *color);
+                });
+            }
+            RenderCommand::Image { rect, image } => {
+                with_clip(window, clip, |window| {
+                    paint_image(window, *rect, image, clip);
+                });
+            }
             RenderCommand::Text {
                 position,
                 text,
                 style,
             } => {
-                with_clip(window, &clip_stack, |window| {
+                with_clip(window, clip, |window| {
                     paint_text(window, cx, *position, text, style);
                 });
             }
@@ -126,6 +149,26 @@ fn paint_points(window: &mut Window, points: &[ScreenPoint], style: MarkerStyle)
     }
 }
 
+fn paint_fill(window: &mut Window, upper: &[ScreenPoint], lower: &[ScreenPoint], color: Color) {
+    if upper.len() < 2 || color.a <= 0.0 {
+        return;
+    }
+    // Edges may cross at a column; the even-odd fill keeps both lobes.
+    let mut builder = PathBuilder::fill();
+    let mut outline = upper.iter().chain(lower.iter().rev());
+    let Some(first) = outline.next() else {
+        return;
+    };
+    builder.move_to(point(px(first.x), px(first.y)));
+    for vertex in outline {
+        builder.line_to(point(px(vertex.x), px(vertex.y)));
+    }
+    builder.close();
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, to_rgba(color));
+    }
+}
+
 fn paint_rect(window: &mut Window, rect: ScreenRect, style: RectStyle) {
     let bounds = to_bounds(rect);
     let quad = quad(
@@ -139,6 +182,58 @@ fn paint_rect(window: &mut Window, rect: ScreenRect, style: RectStyle) {
     window.paint_quad(quad);
 }
 
+fn paint_image(
+    window: &mut Window,
+    rect: ScreenRect,
+    image: &RasterImage,
+    clip: Option<ScreenRect>,
+) {
+    let cell_w = rect.width() / image.width() as f32;
+    let cell_h = rect.height() / image.height() as f32;
+    if cell_w <= 0.0 || cell_h <= 0.0 {
+        return;
+    }
+    let visible = clip.unwrap_or(rect);
+    let col_start = ((visible.min.x - rect.min.x) / cell_w).floor().max(0.0) as usize;
+    let col_end =
+        (((visible.max.x - rect.min.x) / cell_w).ceil().max(0.0) as usize).min(image.width());
+    let row_start = ((visible.min.y - rect.min.y) / cell_h).floor().max(0.0) as usize;
+    let row_end =
+        (((visible.max.y - rect.min.y) / cell_h).ceil().max(0.0) as usize).min(image.height());
+
+    // Frames fit images to the screen, so each quad covers at least a
+    // pixel; runs of one color share a quad.
+    for row in row_start..row_end {
+        let y0 = rect.min.y + row as f32 * cell_h;
+        let mut col = col_start;
+        while col < col_end {
+            let Some(color) = image.pixel(col, row) else {
+                break;
+            };
+            let run_start = col;
+            col += 1;
+            while col < col_end && image.pixel(col, row) == Some(color) {
+                col += 1;
+            }
+            if color.a <= 0.0 {
+                continue;
+            }
+            let bounds = Bounds::from_corners(
+                point(px(rect.min.x + run_start as f32 * cell_w), px(y0)),
+                point(px(rect.min.x + col as f32 * cell_w), px(y0 + cell_h)),
+            );
+            window.paint_quad(quad(
+                bounds,
+                Corners::all(px(0.0)),
+                to_rgba(color),
+                Edges::all(px(0.0)),
+                to_rgba(color),
+                BorderStyle::default(),
+            ));
+        }
+    }
+}
+
 fn paint_text(
     window: &mut Window,
     cx: &mut App,
@@ -186,9 +281,12 @@ fn to_bounds(rect: ScreenRect) -> Bounds<Pixels> {
     )
 }
 
-fn with_clip(window: &mut Window, stack: &[ContentMask<Pixels>], f: impl FnOnce(&mut Window)) {
-    if let Some(mask) = stack.last() {
-        window.with_content_mask(Some(mask.clone()), f);
+fn with_clip(window: &mut Window, clip: Option<ScreenRect>, f: impl FnOnce(&mut Window)) {
+    if let Some(rect) = clip {
+        let mask = ContentMask {
+            bounds: to_bounds(rect),
+        };
+        window.with_content_mask(Some(mask), f);
     } else {
         f(window);
     }
@@ -0,0 +1,164 @@
+use gpui::prelude::*;
+use gpui::{Window, div, px};
+
+use crate::error::RecoverLock;
+use crate::frame::series_color;
+use crate::render::Color;
+use crate::series::ResampleMethod;
+
+use super::constants::READOUT_FONT_SIZE;
+use super::link::PlotLinkGroup;
+use super::paint::to_hsla;
+
+/// One series value in a [`LinkedReadout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkedReadoutRow {
+    /// Series name.
+    pub series: String,
+    /// Series color.
+    pub color: Color,
+    /// Value at the shared cursor X, formatted by the plot's Y axis, or
+    /// `None` outside the series data.
+    pub value: Option<String>,
+}
+
+/// A single readout table for every plot in a link group.
+///
+/// Shows the value of each visible series of every member plot at the shared
+/// cursor X, replacing one tooltip per plot in a dashboard. The panel is
+/// empty while no member publishes a cursor; enable
+/// [`PlotLinkOptions::link_cursor`](super::PlotLinkOptions::link_cursor) on
+/// the members. Place it in the same window as the members, which redraw
+/// the window whenever the cursor moves.
+#[derive(Debug, Clone)]
+pub struct LinkedReadout {
+    group: PlotLinkGroup,
+    method: ResampleMethod,
+}
+
+impl LinkedReadout {
+    /// Create a readout for a link group.
+    ///
+    /// Values are linearly interpolated by default.
+    pub fn new(group: PlotLinkGroup) -> Self {
+        Self {
+            group,
+            method: ResampleMethod::Linear,
+        }
+    }
+
+    /// Set how values between samples are read.
+    pub fn with_method(mut self, method: ResampleMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Shared cursor X, if any member currently shows a cursor.
+    pub fn cursor_x(&self) -> Option<f64> {
+        self.group
+            .latest_cursor_update()
+            .and_then(|update| update.x)
+    }
+
+    /// Sample every visible series of every member at the shared cursor X.
+    ///
+    /// Rows follow the order in which plots joined the group; gridded series
+    /// are skipped. Returns an empty list when there is no cursor.
+    pub fn rows(&self) -> Vec<LinkedReadoutRow> {
+        let Some(x) = self.cursor_x() else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+        for plot in self.group.member_plots() {
+            let plot = plot.read_unpoisoned();
+            rows.extend(
+                plot.series()
+                    .iter()
+                    .filter(|series| series.is_visible() && series.grid_extent().is_none())
+                    .map(|series| LinkedReadoutRow {
+                        series: series.name().to_string(),
+                        color: series_color(series),
+                        value: series
+                            .value_at(x, self.method)
+                            .map(|value| plot.y_axis().format_value(value)),
+                    }),
+            );
+        }
+        rows
+    }
+}
+
+impl Render for LinkedReadout {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(x) = self.cursor_x() else {
+            return div();
+        };
+        let Some(first) = self.group.member_plots().into_iter().next() else {
+            return div();
+        };
+        let (theme, x_text) = {
+            let plot = first.read_unpoisoned();
+            (plot.theme().clone(), plot.x_axis().format_value(x))
+        };
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .p_2()
+            .text_size(px(READOUT_FONT_SIZE))
+            .bg(to_hsla(theme.hover_bg))
+            .border_1()
+            .border_color(to_hsla(theme.hover_border))
+            .rounded_md()
+            .text_color(to_hsla(theme.axis))
+            .child(format!("x = {x_text}"))
+            .children(self.rows().into_iter().map(|row| {
+                div()
+                    .flex()
+                    .flex_row()
+                    .justify_between()
+                    .gap_4()
+                    .child(div().text_color(to_hsla(row.color)).child(row.series))
+                    .child(row.value.unwrap_or_else(|| "-".to_string()))
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plot::Plot;
+    use crate::series::{Series, SeriesKind};
+    use crate::{GpuiPlotView, PlotLinkOptions};
+
+    fn view(group: &PlotLinkGroup, name: &str, ys: [f64; 2]) -> GpuiPlotView {
+        let mut plot = Plot::new();
+        plot.add_series(&Series::from_iter_y(
+            name,
+            ys,
+            SeriesKind::Line(Default::default()),
+        ));
+        GpuiPlotView::new(plot).with_link_group(group.clone(), PlotLinkOptions::default())
+    }
+
+    #[test]
+    fn rows_cover_every_member_at_the_shared_cursor() {
+        let group = PlotLinkGroup::new();
+        let readout = LinkedReadout::new(group.clone());
+        let _top = view(&group, "rpm", [0.0, 100.0]);
+        let mut bottom = view(&group, "temp", [20.0, 30.0]);
+        assert!(readout.rows().is_empty());
+
+        group.set_cursor_x(Some(0.5));
+        let rows = readout.rows();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].series, "rpm");
+        assert!(rows[0].value.is_some());
+        assert_eq!(rows[1].series, "temp");
+
+        bottom.set_link_muted(true);
+        assert_eq!(readout.rows().len(), 1);
+        group.set_cursor_x(Some(5.0));
+        assert_eq!(readout.rows()[0].value, None);
+    }
+}
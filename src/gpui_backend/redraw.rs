@@ -0,0 +1,83 @@
+use std::future::poll_fn;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Poll, Waker};
+
+use crate::error::RecoverLock;
+
+/// Redraw request shared by a view, its plot handles and its link group.
+///
+/// Requests set a flag and wake the task waiting on the view's behalf, so
+/// an idle view does no work until something asks it to redraw.
+#[derive(Debug, Default)]
+pub(crate) struct RedrawSignal {
+    requested: AtomicBool,
+    waker: RwLock<Option<Waker>>,
+}
+
+impl RedrawSignal {
+    /// Ask for a redraw, waking the waiting task if there is one.
+    pub(crate) fn request(&self) {
+        self.requested.store(true, Ordering::Release);
+        if let Some(waker) = self.waker.write_unpoisoned().take() {
+            waker.wake();
+        }
+    }
+
+    /// Clear the pending request and report whether there was one.
+    pub(crate) fn take(&self) -> bool {
+        self.requested.swap(false, Ordering::AcqRel)
+    }
+
+    /// Wait for the next request and consume it.
+    pub(crate) async fn requested(&self) {
+        poll_fn(|cx| {
+            if self.take() {
+                return Poll::Ready(());
+            }
+            *self.waker.write_unpoisoned() = Some(cx.waker().clone());
+            // A request may have landed before the waker was stored.
+            if self.take() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
+    use std::task::{Context, Wake};
+
+    use super::*;
+
+    struct CountWakes(AtomicUsize);
+
+    impl Wake for CountWakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn request_wakes_the_waiting_task() {
+        let signal = RedrawSignal::default();
+        let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&wakes));
+        let mut cx = Context::from_waker(&waker);
+        let mut wait = pin!(signal.requested());
+        assert!(wait.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 0);
+
+        signal.request();
+        signal.request();
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+        assert!(wait.as_mut().poll(&mut cx).is_ready());
+        assert!(!signal.take());
+    }
+}
@@ -10,17 +10,6 @@ impl<'a> GpuiTextMeasurer<'a> {
     pub(crate) fn new(window: &'a Window) -> Self {
         Self { window }
     }
-
-    pub(crate) fn measure_multiline(&self, text: &str, size: f32) -> (f32, f32) {
-        let mut width: f32 = 0.0;
-        let mut height: f32 = 0.0;
-        for line in text.lines() {
-            let (w, h) = self.measure(line, size);
-            width = width.max(w);
-            height += h.max(size * 1.2);
-        }
-        (width + 8.0, height + 8.0)
-    }
 }
 
 impl TextMeasurer for GpuiTextMeasurer<'_> {
@@ -1,39 +1,58 @@
-use std::sync::{Arc, RwLock};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, OnceLock, RwLock};
 use std::time::{Duration, Instant};
 
 use gpui::prelude::*;
 use gpui::{
-    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, ScrollWheelEvent,
-    Window, canvas, div, px,
+    App, EntityId, FocusHandle, Focusable, KeyDownEvent, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, Pixels, Point, ScrollWheelEvent, Window, canvas, div, px,
 };
 
-use crate::geom::{Point as DataPoint, ScreenPoint, ScreenRect};
+use crate::error::{Error, RecoverLock};
+use crate::export::CursorTable;
+use crate::frame::geometry::{distance_sq, normalized_rect};
+use crate::frame::hover::{compute_hover_target, hit_test, hover_target_within_threshold};
+use crate::frame::state::{ClickState, DragMode, DragState, PinToggle, SpringBack};
+use crate::frame::{PerfStats, PlotUiState, PlotViewConfig, build_frame};
+use crate::geom::{ScreenPoint, ScreenRect};
 use crate::interaction::{
-    HitRegion, pan_viewport, toggle_pin, zoom_factor_from_drag, zoom_to_rect, zoom_viewport,
+    HitInfo, HitRegion, MinSpan, PlotRegions, pan_viewport, resist_pan, settle_viewport,
+    toggle_pin, zoom_factor_from_drag, zoom_to_rect, zoom_viewport, zoom_viewport_at,
 };
 use crate::plot::Plot;
+use crate::render::RenderList;
+use crate::series::ResampleMethod;
 use crate::transform::Transform;
 use crate::view::{Range, Viewport};
 
-use super::config::PlotViewConfig;
-use super::constants::DOUBLE_CLICK_PIN_GRACE_MS;
-use super::frame::build_frame;
-use super::geometry::{distance_sq, normalized_rect};
-use super::hover::{compute_hover_target, hover_target_within_threshold};
-use super::link::{LinkBinding, PlotLinkGroup, PlotLinkOptions, ViewSyncKind};
+use super::constants::{DOUBLE_CLICK_PIN_GRACE_MS, KEYBOARD_PAN_FRACTION, KEYBOARD_ZOOM_FACTOR};
+use super::events::{InteractionEvent, InteractionKind, InteractionLog};
+use super::link::{
+    LinkBinding, LinkMemberId, LinkMembership, PlotLinkGroup, PlotLinkOptions, ViewSyncKind,
+};
 use super::paint::{paint_frame, to_hsla};
-use super::state::{ClickState, DragMode, DragState, PinToggle, PlotUiState};
+use super::redraw::RedrawSignal;
+use super::text::GpuiTextMeasurer;
 
 /// A GPUI view that renders a [`Plot`] with interactive controls.
 ///
 /// This view handles pan/zoom/box-zoom, hover readouts, and pin interactions
 /// while delegating data management to the underlying [`Plot`].
+///
+/// Clicking a view gives it keyboard focus, so shortcuts reach the plot the
+/// user last touched when several share a window; Tab moves focus between
+/// plots in [`GpuiPlotView::with_tab_index`] order.
 #[derive(Clone)]
 pub struct GpuiPlotView {
     plot: Arc<RwLock<Plot>>,
     state: Arc<RwLock<PlotUiState>>,
     config: PlotViewConfig,
     link: Option<LinkBinding>,
+    redraw: Arc<RedrawSignal>,
+    redraw_watch: Option<EntityId>,
+    interactions: Arc<RwLock<InteractionLog>>,
+    focus: Arc<OnceLock<FocusHandle>>,
+    tab_index: isize,
 }
 
 impl GpuiPlotView {
@@ -46,6 +65,11 @@ impl GpuiPlotView {
             state: Arc::new(RwLock::new(PlotUiState::default())),
             config: PlotViewConfig::default(),
             link: None,
+            redraw: Arc::new(RedrawSignal::default()),
+            redraw_watch: None,
+            interactions: Arc::new(RwLock::new(InteractionLog::default())),
+            focus: Arc::new(OnceLock::new()),
+            tab_index: 0,
         }
     }
 
@@ -56,19 +80,210 @@ impl GpuiPlotView {
             state: Arc::new(RwLock::new(PlotUiState::default())),
             config,
             link: None,
+            redraw: Arc::new(RedrawSignal::default()),
+            redraw_watch: None,
+            interactions: Arc::new(RwLock::new(InteractionLog::default())),
+            focus: Arc::new(OnceLock::new()),
+            tab_index: 0,
         }
     }
 
+    /// Set the position of this view in the window's Tab order.
+    ///
+    /// Views with lower indices receive focus first; equal indices follow
+    /// paint order. Call before the view is first rendered.
+    pub fn with_tab_index(mut self, index: isize) -> Self {
+        self.tab_index = index;
+        self
+    }
+
+    /// Check whether the view had keyboard focus when last painted.
+    pub fn is_focused(&self) -> bool {
+        self.state.read_unpoisoned().focused
+    }
+
+    /// Identifier of this view inside its link group, if it joined one.
+    ///
+    /// Compare with [`PlotLinkGroup::focused_member`] to find the plot the
+    /// user is interacting with.
+    pub fn link_member_id(&self) -> Option<LinkMemberId> {
+        self.link.as_ref().map(|link| link.member_id)
+    }
+
     /// Attach this view to a multi-plot link group.
     ///
     /// Link groups synchronize viewport/cursor/brush state between views.
     pub fn with_link_group(mut self, group: PlotLinkGroup, options: PlotLinkOptions) -> Self {
+        self.join_link_group(group, options);
+        self
+    }
+
+    /// Move this view into a link group at runtime, leaving any current one.
+    ///
+    /// The view adopts the group's latest shared view, cursor and brush.
+    pub fn join_link_group(&mut self, group: PlotLinkGroup, options: PlotLinkOptions) {
+        self.leave_link_group();
+        let member_id = group.register_member();
+        group.attach_view(member_id, &self.plot, &self.redraw);
+        if self.is_focused() {
+            group.set_member_focused(member_id, true);
+        }
         self.link = Some(LinkBinding {
-            member_id: group.register_member(),
+            member_id,
+            _membership: Arc::new(LinkMembership::new(&group, member_id)),
             group,
             options,
+            muted: false,
         });
-        self
+    }
+
+    /// Remove this view from its link group.
+    ///
+    /// Linked cursor and brush overlays are cleared; the current view is kept.
+    pub fn leave_link_group(&mut self) {
+        if let Some(link) = self.link.take() {
+            link.group.unregister_member(link.member_id);
+        }
+        self.state.write_unpoisoned().reset_link_state(0);
+    }
+
+    /// Temporarily stop publishing to and applying updates from the link group.
+    ///
+    /// While muted the view behaves as if detached. Updates published in the
+    /// meantime are skipped on unmute rather than replayed.
+    pub fn set_link_muted(&mut self, muted: bool) {
+        let Some(link) = self.link.as_mut() else {
+            return;
+        };
+        if link.muted == muted {
+            return;
+        }
+        link.muted = muted;
+        if muted {
+            link.group.unregister_member(link.member_id);
+        } else {
+            link.group
+                .attach_view(link.member_id, &self.plot, &self.redraw);
+            if self.state.read_unpoisoned().focused {
+                link.group.set_member_focused(link.member_id, true);
+            }
+        }
+        let seq = link.group.current_seq();
+        self.state.write_unpoisoned().reset_link_state(seq);
+    }
+
+    /// Check whether link participation is muted.
+    pub fn is_link_muted(&self) -> bool {
+        self.link.as_ref().is_some_and(|link| link.muted)
+    }
+
+    /// Access rendering statistics for the most recent frame.
+    pub fn perf_stats(&self) -> PerfStats {
+        self.state.read_unpoisoned().perf.stats
+    }
+
+    /// Render commands from the most recent frame.
+    ///
+    /// Hosts with their own GPU pipeline can replay these commands instead of,
+    /// or in addition to, GPUI painting. Returns `None` before the first frame.
+    pub fn last_render(&self) -> Option<Arc<RenderList>> {
+        self.state.read_unpoisoned().last_render.clone()
+    }
+
+    /// Screen regions from the most recent layout pass.
+    ///
+    /// Returns `None` before the view has been painted.
+    pub fn regions(&self) -> Option<PlotRegions> {
+        let state = self.state.read_unpoisoned();
+        state.plot_rect.map(|_| state.regions)
+    }
+
+    /// Hit test a screen position against the most recent frame.
+    ///
+    /// Reports the region under the position, the nearest point within the
+    /// pin threshold, and the legend entry under the position, using the same
+    /// rules as the built-in hover and click handling.
+    pub fn hit_test(&self, position: ScreenPoint) -> HitInfo {
+        let state = self.state.read_unpoisoned();
+        let plot = self.plot.read_unpoisoned();
+        hit_test(&plot, &state, position, &self.config)
+    }
+
+    /// Sample every visible series at the current cursor positions.
+    ///
+    /// Uses the two edges of a linked brush selection as A/B cursors when
+    /// one is active, otherwise the linked cursor X or the local hover
+    /// position. Returns `None` when there is no cursor. See
+    /// [`Plot::cursor_table`] for the sampling rules.
+    pub fn cursor_table(&self, method: ResampleMethod) -> Option<CursorTable> {
+        let xs = {
+            let state = self.state.read_unpoisoned();
+            if let Some(brush) = state.linked_brush_x {
+                vec![brush.min, brush.max]
+            } else {
+                let hover_x = state.hover.and_then(|hover| {
+                    let transform = state.transform.as_ref()?;
+                    Some(transform.screen_to_data(hover)?.x)
+                });
+                vec![hover_x.or(state.linked_cursor_x)?]
+            }
+        };
+        let plot = self.plot.read_unpoisoned();
+        Some(plot.cursor_table(&xs, method))
+    }
+
+    /// X range of the linked brush selection shown in this view, if any.
+    ///
+    /// Pass it as [`ExportScope::XRange`](crate::export::ExportScope::XRange)
+    /// to [`Plot::export_csv`] to export the selected data.
+    pub fn brush_x_range(&self) -> Option<Range> {
+        self.state.read_unpoisoned().linked_brush_x
+    }
+
+    /// Receive an event for every zoom, pan, reset, pin toggle and legend
+    /// visibility toggle made in this view.
+    ///
+    /// Pans and axis-drag zooms are reported once when the drag ends. Each
+    /// call returns an independent receiver; dropping it unsubscribes.
+    pub fn subscribe_interactions(&self) -> Receiver<InteractionEvent> {
+        self.interactions.write_unpoisoned().subscribe()
+    }
+
+    fn record_interaction(&self, kind: InteractionKind) {
+        self.interactions.write_unpoisoned().record(kind);
+    }
+
+    /// Access the link-group binding, if any.
+    pub(crate) fn link_binding(&self) -> Option<&LinkBinding> {
+        self.link.as_ref()
+    }
+
+    fn active_link(&self) -> Option<&LinkBinding> {
+        self.link.as_ref().filter(|link| !link.muted)
+    }
+
+    /// Notify this entity whenever a [`PlotHandle`] or the link group
+    /// requests a redraw.
+    ///
+    /// Started on first render. The task sleeps until a request wakes it,
+    /// and releasing the entity wakes it one last time so it can end.
+    fn watch_redraw_requests(&mut self, cx: &mut Context<Self>) {
+        let entity_id = cx.entity_id();
+        if self.redraw_watch == Some(entity_id) {
+            return;
+        }
+        self.redraw_watch = Some(entity_id);
+        cx.on_release(|this, _| this.redraw.request()).detach();
+        let redraw = Arc::clone(&self.redraw);
+        cx.spawn(async move |this, cx| {
+            loop {
+                redraw.requested().await;
+                if this.update(cx, |_, cx| cx.notify()).is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
     }
 
     /// Get a handle for mutating the underlying plot.
@@ -77,11 +292,12 @@ impl GpuiPlotView {
     pub fn plot_handle(&self) -> PlotHandle {
         PlotHandle {
             plot: Arc::clone(&self.plot),
+            redraw: Arc::clone(&self.redraw),
         }
     }
 
     fn publish_manual_view_link(&self, viewport: Viewport) {
-        let Some(link) = self.link.as_ref() else {
+        let Some(link) = self.active_link() else {
             return;
         };
         link.group.publish_manual_view(
@@ -93,7 +309,7 @@ impl GpuiPlotView {
     }
 
     fn publish_reset_link(&self) {
-        let Some(link) = self.link.as_ref() else {
+        let Some(link) = self.active_link() else {
             return;
         };
         if link.options.link_reset {
@@ -102,7 +318,7 @@ impl GpuiPlotView {
     }
 
     fn publish_cursor_link(&self, x: Option<f64>) {
-        let Some(link) = self.link.as_ref() else {
+        let Some(link) = self.active_link() else {
             return;
         };
         if link.options.link_cursor {
@@ -111,7 +327,7 @@ impl GpuiPlotView {
     }
 
     fn publish_brush_link(&self, x_range: Option<Range>) {
-        let Some(link) = self.link.as_ref() else {
+        let Some(link) = self.active_link() else {
             return;
         };
         if link.options.link_brush {
@@ -119,6 +335,10 @@ impl GpuiPlotView {
         }
     }
 
+    fn min_span(&self, plot: &Plot, viewport: Viewport) -> MinSpan {
+        MinSpan::new(viewport, plot.data_bounds(), self.config.min_zoom_span)
+    }
+
     fn apply_manual_view_with_link(
         &self,
         plot: &mut Plot,
@@ -134,19 +354,23 @@ impl GpuiPlotView {
 
     fn on_mouse_down(&mut self, ev: &MouseDownEvent, cx: &mut Context<Self>) {
         let pos = screen_point(ev.position);
-        let mut state = self.state.write().expect("plot state lock");
+        let mut state = self.state.write_unpoisoned();
         state.last_cursor = Some(pos);
+        state.spring_back = None;
 
         if let Some(series_id) = state.legend_hit(pos) {
             if ev.button == MouseButton::Left && ev.click_count == 1 {
-                if let Ok(mut plot) = self.plot.write() {
-                    if let Some(series) = plot
-                        .series_mut()
-                        .iter_mut()
-                        .find(|series| series.id() == series_id)
-                    {
-                        series.set_visible(!series.is_visible());
-                    }
+                let mut plot = self.plot.write_unpoisoned();
+                if let Some(series) = plot
+                    .series_mut()
+                    .iter_mut()
+                    .find(|series| series.id() == series_id)
+                {
+                    series.set_visible(!series.is_visible());
+                    self.record_interaction(InteractionKind::VisibilityToggled {
+                        series_id,
+                        visible: series.is_visible(),
+                    });
                 }
             }
             state.clear_interaction();
@@ -159,20 +383,20 @@ impl GpuiPlotView {
         let region = state.regions.hit_test(pos);
         if ev.button == MouseButton::Left && ev.click_count >= 2 && region == HitRegion::Plot {
             let last_toggle = state.last_pin_toggle.take();
-            if let Ok(mut plot) = self.plot.write() {
-                if let Some(last_toggle) = last_toggle {
-                    if last_toggle.at.elapsed() <= Duration::from_millis(DOUBLE_CLICK_PIN_GRACE_MS)
-                        && distance_sq(last_toggle.screen_pos, pos)
-                            <= self.config.pin_threshold_px.powi(2)
-                    {
-                        revert_pin_toggle(&mut plot, last_toggle);
-                    }
+            let mut plot = self.plot.write_unpoisoned();
+            if let Some(last_toggle) = last_toggle {
+                if last_toggle.at.elapsed() <= Duration::from_millis(DOUBLE_CLICK_PIN_GRACE_MS)
+                    && distance_sq(last_toggle.screen_pos, pos)
+                        <= self.config.pin_threshold_px.powi(2)
+                {
+                    revert_pin_toggle(&mut plot, last_toggle);
                 }
-                plot.reset_view();
-                state.linked_brush_x = None;
-                self.publish_reset_link();
-                self.publish_brush_link(None);
             }
+            plot.reset_view();
+            state.linked_brush_x = None;
+            self.publish_reset_link();
+            self.publish_brush_link(None);
+            self.record_interaction(InteractionKind::Reset);
             state.clear_interaction();
             cx.notify();
             return;
@@ -199,13 +423,16 @@ impl GpuiPlotView {
             }
             _ => {}
         }
+        if let Some(drag) = state.drag.as_mut() {
+            drag.start_viewport = self.plot.read_unpoisoned().viewport();
+        }
 
         cx.notify();
     }
 
     fn on_mouse_move(&mut self, ev: &MouseMoveEvent, cx: &mut Context<Self>) {
         let pos = screen_point(ev.position);
-        let mut state = self.state.write().expect("plot state lock");
+        let mut state = self.state.write_unpoisoned();
         state.last_cursor = Some(pos);
 
         if state.legend_hit(pos).is_some() {
@@ -215,11 +442,13 @@ impl GpuiPlotView {
         } else {
             state.hover = None;
         }
+        let snap = self.config.snap_to_ticks != ev.modifiers.control;
+        let cursor = if snap { state.snap_to_ticks(pos) } else { pos };
         let linked_cursor_x = state.hover.and_then(|_| {
             state
                 .transform
                 .as_ref()
-                .and_then(|transform| transform.screen_to_data(pos))
+                .and_then(|transform| transform.screen_to_data(cursor))
                 .map(|point| point.x)
         });
         self.publish_cursor_link(linked_cursor_x);
@@ -254,45 +483,56 @@ impl GpuiPlotView {
         match drag.mode {
             DragMode::Pan => {
                 if let (Some(rect), Some(transform)) = (plot_rect, transform) {
-                    if let Ok(mut plot) = self.plot.write() {
-                        if let Some(viewport) = plot.viewport() {
-                            if let Some(next) = pan_viewport(viewport, delta, &transform) {
-                                self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
-                            }
+                    let mut plot = self.plot.write_unpoisoned();
+                    if let Some(viewport) = plot.viewport() {
+                        if let Some(next) = pan_viewport(viewport, delta, &transform) {
+                            let next = match (self.config.soft_limits, plot.data_bounds()) {
+                                (Some(limits), Some(bounds)) => resist_pan(
+                                    viewport,
+                                    next,
+                                    limits.limits(bounds),
+                                    limits.overscroll_frac,
+                                ),
+                                _ => next,
+                            };
+                            self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
                         }
                     }
                 }
             }
             DragMode::ZoomRect => {
-                state.selection_rect = Some(ScreenRect::new(drag.start, pos));
+                let (start, end) = if snap {
+                    (state.snap_to_ticks(drag.start), state.snap_to_ticks(pos))
+                } else {
+                    (drag.start, pos)
+                };
+                state.selection_rect = Some(ScreenRect::new(start, end));
             }
             DragMode::ZoomX => {
-                if let (Some(rect), Some(transform)) = (plot_rect, transform) {
+                if let Some(rect) = plot_rect {
                     let axis_pixels = rect.width().max(1.0);
                     let factor = zoom_factor_from_drag(delta.x, axis_pixels);
-                    if let Ok(mut plot) = self.plot.write() {
-                        if let Some(viewport) = plot.viewport() {
-                            let center = transform
-                                .screen_to_data(pos)
-                                .unwrap_or_else(|| viewport.x_center());
-                            let next = zoom_viewport(viewport, center, factor, 1.0);
-                            self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
-                        }
+                    let mut plot = self.plot.write_unpoisoned();
+                    if let Some(viewport) = plot.viewport() {
+                        let (factor, _) = self
+                            .min_span(&plot, viewport)
+                            .clamp_factors(viewport, factor, 1.0);
+                        let next = zoom_viewport_at(viewport, rect, pos, factor, 1.0);
+                        self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
                     }
                 }
             }
             DragMode::ZoomY => {
-                if let (Some(rect), Some(transform)) = (plot_rect, transform) {
+                if let Some(rect) = plot_rect {
                     let axis_pixels = rect.height().max(1.0);
                     let factor = zoom_factor_from_drag(-delta.y, axis_pixels);
-                    if let Ok(mut plot) = self.plot.write() {
-                        if let Some(viewport) = plot.viewport() {
-                            let center = transform
-                                .screen_to_data(pos)
-                                .unwrap_or_else(|| viewport.y_center());
-                            let next = zoom_viewport(viewport, center, 1.0, factor);
-                            self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
-                        }
+                    let mut plot = self.plot.write_unpoisoned();
+                    if let Some(viewport) = plot.viewport() {
+                        let (_, factor) = self
+                            .min_span(&plot, viewport)
+                            .clamp_factors(viewport, 1.0, factor);
+                        let next = zoom_viewport_at(viewport, rect, pos, 1.0, factor);
+                        self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
                     }
                 }
             }
@@ -306,7 +546,7 @@ impl GpuiPlotView {
 
     fn on_mouse_up(&mut self, ev: &MouseUpEvent, cx: &mut Context<Self>) {
         let pos = screen_point(ev.position);
-        let mut state = self.state.write().expect("plot state lock");
+        let mut state = self.state.write_unpoisoned();
         let drag = state.drag.clone();
 
         if let Some(drag_state) = drag.as_ref() {
@@ -315,49 +555,144 @@ impl GpuiPlotView {
                     (state.selection_rect.take(), state.transform.clone())
                 {
                     let rect = normalized_rect(rect);
-                    if let Ok(mut plot) = self.plot.write() {
-                        if let Some(viewport) = plot.viewport() {
-                            if let Some(next) = zoom_to_rect(viewport, rect, &transform) {
-                                self.apply_manual_view_with_link(
-                                    &mut plot,
-                                    &mut state,
-                                    transform.screen(),
-                                    next,
-                                );
-                                self.publish_brush_link(Some(next.x));
-                            }
+                    let mut plot = self.plot.write_unpoisoned();
+                    if let Some(viewport) = plot.viewport() {
+                        let min_span = self.min_span(&plot, viewport);
+                        if let Some(next) = zoom_to_rect(viewport, rect, &transform, min_span) {
+                            self.apply_manual_view_with_link(
+                                &mut plot,
+                                &mut state,
+                                transform.screen(),
+                                next,
+                            );
+                            self.publish_brush_link(Some(next.x));
+                            self.record_interaction(InteractionKind::Zoom {
+                                from: viewport,
+                                to: next,
+                            });
                         }
                     }
                 }
             }
         }
 
+        if let Some(limits) = self.config.soft_limits
+            && drag
+                .as_ref()
+                .is_some_and(|drag| drag.active && drag.mode == DragMode::Pan)
+            && let (Some(viewport), Some(bounds)) = {
+                let plot = self.plot.read_unpoisoned();
+                (plot.viewport(), plot.data_bounds())
+            }
+        {
+            let to = settle_viewport(viewport, limits.limits(bounds));
+            if to != viewport {
+                state.spring_back = Some(SpringBack {
+                    from: viewport,
+                    to,
+                    started: Instant::now(),
+                    duration: if self.config.reduce_motion {
+                        Duration::ZERO
+                    } else {
+                        limits.spring_back
+                    },
+                });
+            }
+        }
+
+        if let Some(drag) = drag.as_ref().filter(|drag| drag.active)
+            && let Some(from) = drag.start_viewport
+            && let Some(to) = state
+                .spring_back
+                .map(|spring| spring.to)
+                .or_else(|| self.plot.read_unpoisoned().viewport())
+        {
+            match drag.mode {
+                DragMode::Pan => self.record_interaction(InteractionKind::Pan { from, to }),
+                DragMode::ZoomX | DragMode::ZoomY => {
+                    self.record_interaction(InteractionKind::Zoom { from, to })
+                }
+                DragMode::ZoomRect => {}
+            }
+        }
+
         let click = state.pending_click.take();
         let should_toggle = click.as_ref().is_some_and(|click| {
             click.button == MouseButton::Left && click.region == HitRegion::Plot
         }) && drag.as_ref().is_none_or(|drag| !drag.active)
             && ev.click_count == 1;
 
-        if should_toggle {
+        if should_toggle && ev.modifiers.control {
+            // Ctrl+click pins every visible series at the cursor X.
+            if let Some(point) = state
+                .transform
+                .as_ref()
+                .and_then(|transform| transform.screen_to_data(pos))
+            {
+                let added = self.plot.write_unpoisoned().toggle_pin_slice(point.x);
+                self.record_interaction(InteractionKind::PinSliceToggled { x: point.x, added });
+            }
+            state.last_pin_toggle = None;
+        } else if should_toggle {
             if let Some(transform) = state.transform.clone() {
-                if let Ok(mut plot) = self.plot.write() {
-                    let target = state
-                        .hover_target
-                        .filter(|target| hover_target_within_threshold(target, pos, &self.config))
-                        .or_else(|| {
-                            compute_hover_target(
-                                &plot,
-                                &transform,
-                                pos,
-                                state.plot_rect,
-                                self.config.pin_threshold_px,
-                                self.config.unpin_threshold_px,
-                            )
-                        });
+                let mut plot = self.plot.write_unpoisoned();
+                let target = state
+                    .hover_target
+                    .filter(|target| hover_target_within_threshold(target, pos, &self.config))
+                    .or_else(|| {
+                        compute_hover_target(
+                            &plot,
+                            &transform,
+                            pos,
+                            state.plot_rect,
+                            self.config.pin_threshold_px,
+                            self.config.unpin_threshold_px,
+                        )
+                    });
 
-                    if let Some(target) = target {
+                if let Some(target) = target {
+                    let now = Instant::now();
+                    let cycling = self.config.hover_all_series
+                        && state
+                            .last_pin_toggle
+                            .is_some_and(|last| last.added && last.pin == target.pin);
+                    let next = if cycling {
+                        state.cycle_hover_target(true)
+                    } else {
+                        None
+                    };
+                    if let Some((next, wrapped)) = next {
+                        let pinned = toggle_pin(plot.pins_mut(), target.pin);
+                        self.record_interaction(InteractionKind::PinToggled {
+                            pin: target.pin,
+                            pinned,
+                        });
+                        state.last_pin_toggle = Some(if wrapped {
+                            PinToggle {
+                                pin: target.pin,
+                                added: false,
+                                at: now,
+                                screen_pos: target.screen,
+                            }
+                        } else {
+                            let added = toggle_pin(plot.pins_mut(), next.pin);
+                            self.record_interaction(InteractionKind::PinToggled {
+                                pin: next.pin,
+                                pinned: added,
+                            });
+                            PinToggle {
+                                pin: next.pin,
+                                added,
+                                at: now,
+                                screen_pos: next.screen,
+                            }
+                        });
+                    } else {
                         let added = toggle_pin(plot.pins_mut(), target.pin);
-                        let now = Instant::now();
+                        self.record_interaction(InteractionKind::PinToggled {
+                            pin: target.pin,
+                            pinned: added,
+                        });
                         state.last_pin_toggle = Some(PinToggle {
                             pin: target.pin,
                             added,
@@ -378,7 +713,7 @@ impl GpuiPlotView {
     }
 
     fn on_mouse_up_out(&mut self, _ev: &MouseUpEvent, cx: &mut Context<Self>) {
-        let mut state = self.state.write().expect("plot state lock");
+        let mut state = self.state.write_unpoisoned();
         state.clear_interaction();
         self.publish_cursor_link(None);
         cx.notify();
@@ -386,12 +721,12 @@ impl GpuiPlotView {
 
     fn on_scroll(&mut self, ev: &ScrollWheelEvent, _window: &Window, cx: &mut Context<Self>) {
         let pos = screen_point(ev.position);
-        let mut state = self.state.write().expect("plot state lock");
+        let mut state = self.state.write_unpoisoned();
         if state.legend_hit(pos).is_some() {
             return;
         }
         let region = state.regions.hit_test(pos);
-        let Some(transform) = state.transform.clone() else {
+        let Some(rect) = state.plot_rect else {
             return;
         };
 
@@ -401,71 +736,260 @@ impl GpuiPlotView {
         if zoom_delta.abs() < 0.01 {
             return;
         }
-        let factor = (1.0 - (zoom_delta as f64 * 0.002)).clamp(0.1, 10.0);
-
-        if let Ok(mut plot) = self.plot.write() {
-            if let Some(viewport) = plot.viewport() {
-                let center = transform
-                    .screen_to_data(pos)
-                    .unwrap_or_else(|| viewport.center());
-                let (factor_x, factor_y) = match region {
-                    HitRegion::XAxis => (factor, 1.0),
-                    HitRegion::YAxis => (1.0, factor),
-                    HitRegion::Plot => (factor, factor),
-                    HitRegion::Outside => (1.0, 1.0),
+        if self.config.hover_all_series
+            && region == HitRegion::Plot
+            && state.cycle_hover_target(zoom_delta < 0.0).is_some()
+        {
+            cx.notify();
+            return;
+        }
+        let wheel = match region {
+            HitRegion::XAxis => Some(self.config.x_axis_wheel_zoom),
+            HitRegion::YAxis => Some(self.config.y_axis_wheel_zoom),
+            HitRegion::Plot => Some(self.config.plot_wheel_zoom),
+            HitRegion::Outside => None,
+        };
+        let Some(wheel) = wheel else {
+            return;
+        };
+        let factor = wheel.factor(zoom_delta, f32::from(line_height));
+
+        let mut plot = self.plot.write_unpoisoned();
+        if let Some(viewport) = plot.viewport() {
+            let (factor_x, factor_y) = match region {
+                HitRegion::XAxis => (factor, 1.0),
+                HitRegion::YAxis => (1.0, factor),
+                HitRegion::Plot => (factor, factor),
+                HitRegion::Outside => (1.0, 1.0),
+            };
+            let (factor_x, factor_y) = self
+                .min_span(&plot, viewport)
+                .clamp_factors(viewport, factor_x, factor_y);
+            if factor_x != 1.0 || factor_y != 1.0 {
+                let next = zoom_viewport_at(viewport, rect, pos, factor_x, factor_y);
+                self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
+                self.record_interaction(InteractionKind::Zoom {
+                    from: viewport,
+                    to: next,
+                });
+            }
+        }
+
+        cx.notify();
+    }
+}
+
+impl GpuiPlotView {
+    fn on_key_down(&mut self, ev: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.config.keyboard_shortcuts {
+            return;
+        }
+        let keystroke = &ev.keystroke;
+        let Some(action) = KeyAction::from_key(&keystroke.key, keystroke.modifiers.shift) else {
+            return;
+        };
+        match action {
+            KeyAction::FocusNext => window.focus_next(),
+            KeyAction::FocusPrev => window.focus_prev(),
+            KeyAction::Cancel => {
+                self.state.write_unpoisoned().clear_interaction();
+            }
+            KeyAction::Reset => {
+                let mut state = self.state.write_unpoisoned();
+                let mut plot = self.plot.write_unpoisoned();
+                plot.reset_view();
+                state.linked_brush_x = None;
+                self.publish_reset_link();
+                self.publish_brush_link(None);
+                self.record_interaction(InteractionKind::Reset);
+            }
+            KeyAction::Pan { .. } | KeyAction::Zoom { .. } => {
+                let mut state = self.state.write_unpoisoned();
+                let Some(rect) = state.plot_rect else {
+                    return;
                 };
-                if factor_x != 1.0 || factor_y != 1.0 {
-                    let next = zoom_viewport(viewport, center, factor_x, factor_y);
-                    if let Some(rect) = state.plot_rect {
-                        self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
+                let mut plot = self.plot.write_unpoisoned();
+                let Some(viewport) = plot.viewport() else {
+                    return;
+                };
+                let next = match action {
+                    KeyAction::Zoom { zoom_in } => {
+                        let factor = if zoom_in {
+                            KEYBOARD_ZOOM_FACTOR
+                        } else {
+                            KEYBOARD_ZOOM_FACTOR.recip()
+                        };
+                        let (factor_x, factor_y) = self
+                            .min_span(&plot, viewport)
+                            .clamp_factors(viewport, factor, factor);
+                        let center = crate::geom::Point::new(
+                            (viewport.x.min + viewport.x.max) * 0.5,
+                            (viewport.y.min + viewport.y.max) * 0.5,
+                        );
+                        zoom_viewport(viewport, center, factor_x, factor_y)
                     }
-                }
+                    _ => action.pan(viewport),
+                };
+                self.apply_manual_view_with_link(&mut plot, &mut state, rect, next);
+                let kind = match action {
+                    KeyAction::Zoom { .. } => InteractionKind::Zoom {
+                        from: viewport,
+                        to: next,
+                    },
+                    _ => InteractionKind::Pan {
+                        from: viewport,
+                        to: next,
+                    },
+                };
+                self.record_interaction(kind);
             }
         }
-
+        cx.stop_propagation();
         cx.notify();
     }
 }
 
+/// Keyboard shortcut handled by a focused [`GpuiPlotView`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeyAction {
+    /// Shift the view by a fraction of its span; `dx` and `dy` are -1, 0 or 1.
+    Pan {
+        dx: f64,
+        dy: f64,
+    },
+    Zoom {
+        zoom_in: bool,
+    },
+    Reset,
+    Cancel,
+    FocusNext,
+    FocusPrev,
+}
+
+impl KeyAction {
+    fn from_key(key: &str, shift: bool) -> Option<Self> {
+        Some(match key {
+            "left" => Self::Pan { dx: -1.0, dy: 0.0 },
+            "right" => Self::Pan { dx: 1.0, dy: 0.0 },
+            "up" => Self::Pan { dx: 0.0, dy: 1.0 },
+            "down" => Self::Pan { dx: 0.0, dy: -1.0 },
+            "+" | "=" => Self::Zoom { zoom_in: true },
+            "-" => Self::Zoom { zoom_in: false },
+            "0" | "home" => Self::Reset,
+            "escape" => Self::Cancel,
+            "tab" if shift => Self::FocusPrev,
+            "tab" => Self::FocusNext,
+            _ => return None,
+        })
+    }
+
+    /// Viewport after a pan action; other actions leave it unchanged.
+    fn pan(self, viewport: Viewport) -> Viewport {
+        let Self::Pan { dx, dy } = self else {
+            return viewport;
+        };
+        let shift = |range: Range, direction: f64| {
+            let offset = range.span() * KEYBOARD_PAN_FRACTION * direction;
+            Range::new(range.min + offset, range.max + offset)
+        };
+        Viewport::new(shift(viewport.x, dx), shift(viewport.y, dy))
+    }
+}
+
+impl Focusable for GpuiPlotView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.focus
+            .get_or_init(|| cx.focus_handle().tab_index(self.tab_index).tab_stop(true))
+            .clone()
+    }
+}
+
 impl Render for GpuiPlotView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.watch_redraw_requests(cx);
+        // This frame shows every edit made so far.
+        self.redraw.take();
         let plot = Arc::clone(&self.plot);
         let state = Arc::clone(&self.state);
         let config = self.config.clone();
-        let link = self.link.clone();
-        let theme = plot.read().expect("plot lock").theme().clone();
+        let link = self.active_link().cloned();
+        let focus = self.focus_handle(cx);
+        let frame_focus = focus.clone();
+        let paint_state = Arc::clone(&self.state);
+        let theme = plot.read_unpoisoned().theme().clone();
 
         div()
             .size_full()
+            .track_focus(&focus)
             .bg(to_hsla(theme.background))
             .child(
                 canvas(
                     move |bounds, window, _| {
-                        let mut plot = plot.write().expect("plot lock");
-                        let mut state = state.write().expect("plot state lock");
+                        let mut plot = plot.write_unpoisoned();
+                        let mut state = state.write_unpoisoned();
+                        let focused = frame_focus.is_focused(window);
                         if let Some(link) = &link {
+                            if state.focused != focused {
+                                link.group.set_member_focused(link.member_id, focused);
+                            }
                             apply_link_updates(link, &mut plot, &mut state);
                         }
-                        build_frame(&mut plot, &mut state, &config, bounds, window)
+                        state.focused = focused;
+                        advance_spring_back(link.as_ref(), &mut plot, &mut state, window);
+                        let bounds = ScreenRect::new(
+                            ScreenPoint::new(
+                                f32::from(bounds.origin.x),
+                                f32::from(bounds.origin.y),
+                            ),
+                            ScreenPoint::new(
+                                f32::from(bounds.origin.x + bounds.size.width),
+                                f32::from(bounds.origin.y + bounds.size.height),
+                            ),
+                        );
+                        let align_y_axis_width = link
+                            .as_ref()
+                            .filter(|link| link.options.align_y_axis_width)
+                            .map(|link| {
+                                move |width| {
+                                    link.group.negotiate_y_axis_width(link.member_id, width)
+                                }
+                            });
+                        build_frame(
+                            &mut plot,
+                            &mut state,
+                            &config,
+                            bounds,
+                            &GpuiTextMeasurer::new(window),
+                            align_y_axis_width
+                                .as_ref()
+                                .map(|align| align as &dyn Fn(f32) -> f32),
+                        )
                     },
                     move |_, frame, window, cx| {
+                        let started = Instant::now();
                         paint_frame(&frame, window, cx);
+                        paint_state.write_unpoisoned().perf.stats.paint = started.elapsed();
                     },
                 )
                 .size_full(),
             )
             .on_mouse_down(
                 MouseButton::Left,
-                cx.listener(|this, ev, _, cx| {
+                cx.listener(|this, ev, window, cx| {
+                    window.focus(&this.focus_handle(cx));
                     this.on_mouse_down(ev, cx);
                 }),
             )
             .on_mouse_down(
                 MouseButton::Right,
-                cx.listener(|this, ev, _, cx| {
+                cx.listener(|this, ev, window, cx| {
+                    window.focus(&this.focus_handle(cx));
                     this.on_mouse_down(ev, cx);
                 }),
             )
+            .on_key_down(cx.listener(|this, ev, window, cx| {
+                this.on_key_down(ev, window, cx);
+            }))
             .on_mouse_move(cx.listener(|this, ev, _, cx| {
                 this.on_mouse_move(ev, cx);
             }))
@@ -501,27 +1025,65 @@ impl Render for GpuiPlotView {
 
 /// A handle for mutating a [`Plot`] held inside a `GpuiPlotView`.
 ///
-/// The handle clones cheaply and can be moved into async tasks.
+/// The handle clones cheaply and can be moved into async tasks. Writes
+/// record a redraw request shared by every handle of the view; the request
+/// wakes the view, and writes made before it redraws share one frame, so
+/// update tasks need not notify the view themselves.
 #[derive(Clone)]
 pub struct PlotHandle {
     plot: Arc<RwLock<Plot>>,
+    redraw: Arc<RedrawSignal>,
 }
 
 impl PlotHandle {
     /// Read the plot state.
     ///
-    /// The plot is locked for the duration of the callback.
+    /// The plot is locked for the duration of the callback. If a panic on
+    /// another thread poisoned the lock, the plot is read anyway.
     pub fn read<R>(&self, f: impl FnOnce(&Plot) -> R) -> R {
-        let plot = self.plot.read().expect("plot lock");
+        let plot = self.plot.read_unpoisoned();
         f(&plot)
     }
 
     /// Mutate the plot state.
     ///
-    /// The plot is locked for the duration of the callback.
+    /// The plot is locked for the duration of the callback. If a panic on
+    /// another thread poisoned the lock, the plot is written anyway.
     pub fn write<R>(&self, f: impl FnOnce(&mut Plot) -> R) -> R {
-        let mut plot = self.plot.write().expect("plot lock");
-        f(&mut plot)
+        let mut plot = self.plot.write_unpoisoned();
+        let result = f(&mut plot);
+        self.request_redraw();
+        result
+    }
+
+    /// Ask for the view to be redrawn, e.g. after appending to a shared
+    /// [`Series`](crate::series::Series) handle directly.
+    pub fn request_redraw(&self) {
+        self.redraw.request();
+    }
+
+    /// Clear the pending redraw request and report whether there was one.
+    ///
+    /// A rendered view consumes requests on its own, so this is only needed
+    /// when driving redraws manually.
+    pub fn take_redraw_request(&self) -> bool {
+        self.redraw.take()
+    }
+
+    /// Read the plot state, failing with [`Error::Poisoned`] instead of
+    /// recovering from a poisoned lock.
+    pub fn try_read<R>(&self, f: impl FnOnce(&Plot) -> R) -> Result<R, Error> {
+        let plot = self.plot.read()?;
+        Ok(f(&plot))
+    }
+
+    /// Mutate the plot state, failing with [`Error::Poisoned`] instead of
+    /// recovering from a poisoned lock.
+    pub fn try_write<R>(&self, f: impl FnOnce(&mut Plot) -> R) -> Result<R, Error> {
+        let mut plot = self.plot.write()?;
+        let result = f(&mut plot);
+        self.request_redraw();
+        Ok(result)
     }
 }
 
@@ -606,6 +1168,36 @@ fn screen_point(point: Point<Pixels>) -> ScreenPoint {
     ScreenPoint::new(f32::from(point.x), f32::from(point.y))
 }
 
+/// Step an active spring-back animation and schedule the next frame.
+fn advance_spring_back(
+    link: Option<&LinkBinding>,
+    plot: &mut Plot,
+    state: &mut PlotUiState,
+    window: &Window,
+) {
+    let Some(spring) = state.spring_back else {
+        return;
+    };
+    let (viewport, done) = spring.sample(Instant::now());
+    match state.plot_rect {
+        Some(rect) => apply_manual_view(plot, state, rect, viewport),
+        None => plot.set_manual_view(viewport),
+    }
+    if let Some(link) = link {
+        link.group.publish_manual_view(
+            link.member_id,
+            viewport,
+            link.options.link_x,
+            link.options.link_y,
+        );
+    }
+    if done {
+        state.spring_back = None;
+    } else {
+        window.request_animation_frame();
+    }
+}
+
 fn apply_manual_view(
     plot: &mut Plot,
     state: &mut PlotUiState,
@@ -636,38 +1228,12 @@ fn is_drag_button_held(mode: DragMode, pressed_button: Option<MouseButton>) -> b
     pressed_button == Some(expected)
 }
 
-trait ViewportCenter {
-    fn center(&self) -> DataPoint;
-    fn x_center(&self) -> DataPoint;
-    fn y_center(&self) -> DataPoint;
-}
-
-impl ViewportCenter for Viewport {
-    fn center(&self) -> DataPoint {
-        DataPoint::new(
-            (self.x.min + self.x.max) * 0.5,
-            (self.y.min + self.y.max) * 0.5,
-        )
-    }
-
-    fn x_center(&self) -> DataPoint {
-        DataPoint::new(
-            (self.x.min + self.x.max) * 0.5,
-            (self.y.min + self.y.max) * 0.5,
-        )
-    }
-
-    fn y_center(&self) -> DataPoint {
-        DataPoint::new(
-            (self.x.min + self.x.max) * 0.5,
-            (self.y.min + self.y.max) * 0.5,
-        )
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{DragMode, MouseButton, is_drag_button_held};
+    use super::{
+        DragMode, GpuiPlotView, KeyAction, MouseButton, Plot, PlotLinkGroup, PlotLinkOptions,
+        Range, Viewport, is_drag_button_held,
+    };
 
     #[test]
     fn drag_requires_matching_button() {
@@ -690,4 +1256,74 @@ mod tests {
         ));
         assert!(!is_drag_button_held(DragMode::ZoomRect, None));
     }
+
+    #[test]
+    fn arrow_keys_pan_by_a_fraction_of_the_span() {
+        let viewport = Viewport::new(Range::new(0.0, 10.0), Range::new(0.0, 10.0));
+        let right = KeyAction::from_key("right", false).expect("action");
+        assert_eq!(right.pan(viewport).x, Range::new(1.0, 11.0));
+        let down = KeyAction::from_key("down", false).expect("action");
+        assert_eq!(down.pan(viewport).y, Range::new(-1.0, 9.0));
+        assert_eq!(KeyAction::from_key("tab", true), Some(KeyAction::FocusPrev));
+        assert_eq!(KeyAction::from_key("q", false), None);
+    }
+
+    #[test]
+    fn unmuting_skips_updates_published_while_muted() {
+        let group = PlotLinkGroup::new();
+        let mut view = GpuiPlotView::new(Plot::new())
+            .with_link_group(group.clone(), PlotLinkOptions::default());
+        view.set_link_muted(true);
+        assert!(view.is_link_muted());
+        group.set_cursor_x(Some(3.0));
+        view.set_link_muted(false);
+        let state = view.state.read().expect("plot state lock");
+        assert_eq!(state.link_cursor_seq, group.current_seq());
+        assert_eq!(state.linked_cursor_x, None);
+        drop(state);
+
+        view.leave_link_group();
+        assert!(view.link_binding().is_none());
+        assert!(!view.is_link_muted());
+    }
+
+    #[test]
+    fn dropping_a_linked_view_releases_its_axis_width() {
+        let group = PlotLinkGroup::new();
+        let options = PlotLinkOptions {
+            align_y_axis_width: true,
+            ..PlotLinkOptions::default()
+        };
+        let narrow = GpuiPlotView::new(Plot::new()).with_link_group(group.clone(), options);
+        let wide = GpuiPlotView::new(Plot::new()).with_link_group(group.clone(), options);
+        let narrow_id = narrow.link_member_id().expect("member");
+        let wide_id = wide.link_member_id().expect("member");
+        let handle = narrow.plot_handle();
+
+        assert_eq!(group.negotiate_y_axis_width(narrow_id, 30.0), 30.0);
+        assert!(!handle.take_redraw_request());
+        assert_eq!(group.negotiate_y_axis_width(wide_id, 52.0), 52.0);
+        assert!(handle.take_redraw_request());
+
+        // A surviving clone keeps the member registered.
+        let clone = wide.clone();
+        drop(wide);
+        assert!(!handle.take_redraw_request());
+        drop(clone);
+        assert!(handle.take_redraw_request());
+        assert_eq!(group.negotiate_y_axis_width(narrow_id, 30.0), 30.0);
+    }
+
+    #[test]
+    fn writes_before_a_redraw_share_one_request() {
+        let view = GpuiPlotView::new(Plot::new());
+        let handle = view.plot_handle();
+        assert!(!handle.take_redraw_request());
+        for _ in 0..3 {
+            handle.write(|plot| plot.add_series(&crate::series::Series::line("s")));
+        }
+        assert!(view.plot_handle().take_redraw_request());
+        assert!(!handle.take_redraw_request());
+        assert_eq!(handle.read(|plot| plot.series().len()), 3);
+    }
 }
@@ -3,14 +3,17 @@
 //! These helpers are used by render backends to implement consistent
 //! interaction semantics across platforms.
 
+use crate::datasource::PointId;
 use crate::geom::{Point, ScreenPoint, ScreenRect};
 use crate::series::SeriesId;
+#[cfg(any(feature = "gpui", test))]
 use crate::transform::Transform;
+#[cfg(any(feature = "gpui", test))]
 use crate::view::{Range, Viewport};
 
 /// Interaction hit regions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum HitRegion {
+pub enum HitRegion {
     /// Plot data area.
     Plot,
     /// X axis area.
@@ -22,19 +25,21 @@ pub(crate) enum HitRegion {
 }
 
 /// Screen regions for hit testing.
+///
+/// Regions are recomputed by the backend on every layout pass.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) struct PlotRegions {
+pub struct PlotRegions {
     /// Plot data area.
-    pub(crate) plot: ScreenRect,
+    pub plot: ScreenRect,
     /// X axis area.
-    pub(crate) x_axis: ScreenRect,
+    pub x_axis: ScreenRect,
     /// Y axis area.
-    pub(crate) y_axis: ScreenRect,
+    pub y_axis: ScreenRect,
 }
 
 impl PlotRegions {
     /// Determine which region contains the point.
-    pub(crate) fn hit_test(&self, point: ScreenPoint) -> HitRegion {
+    pub fn hit_test(&self, point: ScreenPoint) -> HitRegion {
         if contains(self.plot, point) {
             HitRegion::Plot
         } else if contains(self.x_axis, point) {
@@ -49,17 +54,68 @@ impl PlotRegions {
 
 /// Pin binding to a stable point identity.
 ///
-/// Pins are stable references to a specific series and point index, allowing
-/// annotations to remain consistent even when the view is decimated.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Pins are stable references to a specific series and point, allowing
+/// annotations to remain consistent even when the view is decimated, older
+/// points are evicted or late points are merged in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Pin {
     /// Series identifier.
     pub series_id: SeriesId,
-    /// Point index within the series.
-    pub point_index: usize,
+    /// Point identifier within the series.
+    pub point_id: PointId,
+}
+
+/// Pins placed together at one X position across series.
+///
+/// Created by [`Plot::toggle_pin_slice`](crate::plot::Plot::toggle_pin_slice)
+/// and removed as a group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinSlice {
+    /// X position the slice was placed at.
+    pub x: f64,
+    /// Label shared by the slice, also stored as the note of each pin.
+    pub label: String,
+    /// Pinned point of each series.
+    pub pins: Vec<Pin>,
+}
+
+/// Data point found by a hit test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitPoint {
+    /// Stable identity of the point.
+    pub pin: Pin,
+    /// Point position in data space.
+    pub point: Point,
+    /// Point position in screen space.
+    pub screen: ScreenPoint,
+    /// Whether the point is currently pinned.
+    pub is_pinned: bool,
+}
+
+/// Result of hit testing a screen position against a rendered plot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitInfo {
+    /// Region containing the position.
+    pub region: HitRegion,
+    /// Nearest point within the pin threshold, if any.
+    pub point: Option<HitPoint>,
+    /// Legend entry under the position, if any.
+    pub legend_entry: Option<SeriesId>,
+}
+
+impl HitInfo {
+    /// Hit info for a position that touches nothing.
+    pub fn outside() -> Self {
+        Self {
+            region: HitRegion::Outside,
+            point: None,
+            legend_entry: None,
+        }
+    }
 }
 
 /// Toggle a pin in the list. Returns true if added, false if removed.
+#[cfg(feature = "gpui")]
 pub(crate) fn toggle_pin(pins: &mut Vec<Pin>, pin: Pin) -> bool {
     if let Some(index) = pins.iter().position(|existing| *existing == pin) {
         pins.swap_remove(index);
@@ -71,6 +127,7 @@ pub(crate) fn toggle_pin(pins: &mut Vec<Pin>, pin: Pin) -> bool {
 }
 
 /// Pan a viewport by a pixel delta.
+#[cfg(feature = "gpui")]
 pub(crate) fn pan_viewport(
     viewport: Viewport,
     delta_pixels: ScreenPoint,
@@ -87,6 +144,7 @@ pub(crate) fn pan_viewport(
 }
 
 /// Zoom a viewport around a center point.
+#[cfg(any(feature = "gpui", test))]
 pub(crate) fn zoom_viewport(
     viewport: Viewport,
     center: Point,
@@ -100,11 +158,43 @@ pub(crate) fn zoom_viewport(
     Viewport::new(Range::new(x_min, x_max), Range::new(y_min, y_max))
 }
 
+/// Zoom a viewport so the data under a screen position stays under it.
+///
+/// The anchor is mapped with `viewport` itself rather than a cached
+/// transform from the last frame, and in `f64`, so repeated zoom steps
+/// between frames keep the same data point under the cursor.
+#[cfg(any(feature = "gpui", test))]
+pub(crate) fn zoom_viewport_at(
+    viewport: Viewport,
+    screen: ScreenRect,
+    anchor: ScreenPoint,
+    factor_x: f64,
+    factor_y: f64,
+) -> Viewport {
+    let mut center = Point::new(
+        (viewport.x.min + viewport.x.max) * 0.5,
+        (viewport.y.min + viewport.y.max) * 0.5,
+    );
+    if screen.width() > 0.0 {
+        let t = (anchor.x as f64 - screen.min.x as f64) / screen.width() as f64;
+        center.x = viewport.x.min + t * viewport.x.span();
+    }
+    if screen.height() > 0.0 {
+        let t = (screen.max.y as f64 - anchor.y as f64) / screen.height() as f64;
+        center.y = viewport.y.min + t * viewport.y.span();
+    }
+    zoom_viewport(viewport, center, factor_x, factor_y)
+}
+
 /// Convert a zoom rectangle into a new viewport.
+///
+/// Spans narrower than `min_span` are widened around the rectangle center.
+#[cfg(any(feature = "gpui", test))]
 pub(crate) fn zoom_to_rect(
     viewport: Viewport,
     rect: ScreenRect,
     transform: &Transform,
+    min_span: MinSpan,
 ) -> Option<Viewport> {
     if rect.width().abs() < 2.0 || rect.height().abs() < 2.0 {
         return Some(viewport);
@@ -112,12 +202,68 @@ pub(crate) fn zoom_to_rect(
     let data_min = transform.screen_to_data(rect.min)?;
     let data_max = transform.screen_to_data(rect.max)?;
     Some(Viewport::new(
-        Range::new(data_min.x, data_max.x),
-        Range::new(data_min.y, data_max.y),
+        Range::new(data_min.x, data_max.x).with_min_span(min_span.x),
+        Range::new(data_min.y, data_max.y).with_min_span(min_span.y),
     ))
 }
 
+/// Smallest spans a zoom may leave on each axis.
+///
+/// Without a floor, repeated zooming shrinks an axis until its ends round to
+/// the same value and the transform can no longer map it.
+#[cfg(any(feature = "gpui", test))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MinSpan {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+}
+
+#[cfg(any(feature = "gpui", test))]
+impl MinSpan {
+    /// Floors at `relative` times the magnitude of the viewport or the data,
+    /// whichever is larger, so the limit follows the resolution the values
+    /// can be represented and read at.
+    pub(crate) fn new(viewport: Viewport, data: Option<Viewport>, relative: f64) -> Self {
+        let axis = |range: Range, data: Option<Range>| {
+            let magnitude = |range: Range| range.min.abs().max(range.max.abs()).max(range.span());
+            let scale = data.map_or(0.0, magnitude).max(magnitude(range));
+            let relative = relative.max(0.0);
+            if scale.is_finite() && scale > 0.0 {
+                scale * relative
+            } else {
+                relative
+            }
+        };
+        Self {
+            x: axis(viewport.x, data.map(|data| data.x)),
+            y: axis(viewport.y, data.map(|data| data.y)),
+        }
+    }
+
+    /// Limit zoom-in factors so neither span drops below the floor.
+    ///
+    /// Zooming out is never limited.
+    pub(crate) fn clamp_factors(
+        &self,
+        viewport: Viewport,
+        factor_x: f64,
+        factor_y: f64,
+    ) -> (f64, f64) {
+        let clamp = |span: f64, factor: f64, min_span: f64| {
+            if factor >= 1.0 || span * factor >= min_span || span.is_nan() || span <= 0.0 {
+                return factor;
+            }
+            (min_span / span).clamp(factor, 1.0)
+        };
+        (
+            clamp(viewport.x.span(), factor_x, self.x),
+            clamp(viewport.y.span(), factor_y, self.y),
+        )
+    }
+}
+
 /// Compute a zoom factor from a drag delta and axis length.
+#[cfg(feature = "gpui")]
 pub(crate) fn zoom_factor_from_drag(delta_pixels: f32, axis_pixels: f32) -> f64 {
     if axis_pixels <= 0.0 {
         return 1.0;
@@ -126,6 +272,69 @@ pub(crate) fn zoom_factor_from_drag(delta_pixels: f32, axis_pixels: f32) -> f64
     (1.0 - normalized).clamp(0.1, 10.0)
 }
 
+/// Nearest viewport that keeps the data in view.
+///
+/// On each axis a viewport narrower than the data is shifted inside the data
+/// range, and a wider one is shifted to contain it. Spans are unchanged.
+#[cfg(any(feature = "gpui", test))]
+pub(crate) fn settle_viewport(viewport: Viewport, bounds: Viewport) -> Viewport {
+    Viewport::new(
+        settle_range(viewport.x, bounds.x),
+        settle_range(viewport.y, bounds.y),
+    )
+}
+
+#[cfg(any(feature = "gpui", test))]
+fn settle_range(range: Range, bounds: Range) -> Range {
+    let inside = range.span() <= bounds.span();
+    let shift = if (range.min < bounds.min) == inside {
+        bounds.min - range.min
+    } else if (range.max > bounds.max) == inside {
+        bounds.max - range.max
+    } else {
+        0.0
+    };
+    if shift == 0.0 || !shift.is_finite() {
+        return range;
+    }
+    Range::new(range.min + shift, range.max + shift)
+}
+
+/// Apply a pan from `current` to `next` with rubber-band resistance past the
+/// data bounds.
+///
+/// Movement away from the data slows down as the overshoot approaches
+/// `overscroll_frac` of the viewport span, which it never exceeds. A fraction
+/// of zero makes the data bounds a hard limit.
+#[cfg(any(feature = "gpui", test))]
+pub(crate) fn resist_pan(
+    current: Viewport,
+    next: Viewport,
+    bounds: Viewport,
+    overscroll_frac: f64,
+) -> Viewport {
+    Viewport::new(
+        resist_range(current.x, next.x, bounds.x, overscroll_frac),
+        resist_range(current.y, next.y, bounds.y, overscroll_frac),
+    )
+}
+
+#[cfg(any(feature = "gpui", test))]
+fn resist_range(current: Range, next: Range, bounds: Range, overscroll_frac: f64) -> Range {
+    let delta = next.min - current.min;
+    let limit = current.span() * overscroll_frac.max(0.0);
+    let overshoot = current.min - settle_range(current, bounds).min;
+    let scale = if overshoot * delta > 0.0 && limit > 0.0 {
+        (1.0 - overshoot.abs() / limit).max(0.0)
+    } else {
+        1.0
+    };
+    let moved = Range::new(current.min + delta * scale, current.max + delta * scale);
+    let rest = settle_range(moved, bounds);
+    let offset = (moved.min - rest.min).clamp(-limit, limit);
+    Range::new(rest.min + offset, rest.max + offset)
+}
+
 fn contains(rect: ScreenRect, point: ScreenPoint) -> bool {
     point.x >= rect.min.x && point.x <= rect.max.x && point.y >= rect.min.y && point.y <= rect.max.y
 }
@@ -154,4 +363,73 @@ mod tests {
             HitRegion::YAxis
         );
     }
+
+    #[test]
+    fn repeated_zoom_keeps_anchor_fixed() {
+        let screen = ScreenRect::new(ScreenPoint::new(10.0, 20.0), ScreenPoint::new(410.0, 320.0));
+        let anchor = ScreenPoint::new(137.3, 201.9);
+        let start = Viewport::new(Range::new(-3.0, 7.0), Range::new(100.0, 101.0));
+        let data_at = |viewport: Viewport| {
+            Transform::new(viewport, screen)
+                .and_then(|transform| transform.screen_to_data(anchor))
+                .expect("anchor")
+        };
+        let expected = data_at(start);
+        let mut viewport = start;
+        for step in 0..200 {
+            let factor = if step % 3 == 0 { 1.25 } else { 0.8 };
+            viewport = zoom_viewport_at(viewport, screen, anchor, factor, factor);
+        }
+        let actual = data_at(viewport);
+        assert!((actual.x - expected.x).abs() <= viewport.x.span() * 1e-6);
+        assert!((actual.y - expected.y).abs() <= viewport.y.span() * 1e-6);
+    }
+
+    #[test]
+    fn min_span_stops_degenerate_zoom() {
+        let screen = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(400.0, 300.0));
+        let anchor = ScreenPoint::new(123.0, 45.0);
+        let data = Viewport::new(Range::new(1000.0, 2000.0), Range::new(-1.0, 1.0));
+        let mut viewport = data;
+        for _ in 0..2000 {
+            let min_span = MinSpan::new(viewport, Some(data), 1e-9);
+            let (factor_x, factor_y) = min_span.clamp_factors(viewport, 0.5, 0.5);
+            viewport = zoom_viewport_at(viewport, screen, anchor, factor_x, factor_y);
+        }
+        assert!(viewport.x.span() >= 2000.0 * 1e-9 * 0.999);
+        assert!(viewport.y.span() >= 2.0 * 1e-9 * 0.999);
+        assert!(Transform::new(viewport, screen).is_some());
+
+        let transform = Transform::new(data, screen).expect("transform");
+        let rect = ScreenRect::new(ScreenPoint::new(10.0, 10.0), ScreenPoint::new(12.0, 12.0));
+        let min_span = MinSpan { x: 50.0, y: 0.5 };
+        let zoomed = zoom_to_rect(data, rect, &transform, min_span).expect("zoom");
+        assert!((zoomed.x.span() - 50.0).abs() < 1e-9);
+        assert!((zoomed.y.span() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn soft_limits_resist_and_settle() {
+        let bounds = Viewport::new(Range::new(0.0, 100.0), Range::new(0.0, 100.0));
+        let current = Viewport::new(Range::new(0.0, 10.0), Range::new(40.0, 50.0));
+        let pan = |viewport: Viewport, dx: f64| {
+            Viewport::new(
+                Range::new(viewport.x.min + dx, viewport.x.max + dx),
+                viewport.y,
+            )
+        };
+
+        let next = resist_pan(current, pan(current, -50.0), bounds, 0.2);
+        assert_eq!(next.x, Range::new(-2.0, 8.0));
+        assert_eq!(next.y, current.y);
+        let further = resist_pan(next, pan(next, -1.0), bounds, 0.2);
+        assert_eq!(further.x, next.x);
+
+        let hard = resist_pan(current, pan(current, -5.0), bounds, 0.0);
+        assert_eq!(hard.x, current.x);
+
+        assert_eq!(settle_viewport(next, bounds).x, Range::new(0.0, 10.0));
+        let wide = Viewport::new(Range::new(50.0, 250.0), Range::new(0.0, 100.0));
+        assert_eq!(settle_viewport(wide, bounds).x, Range::new(0.0, 200.0));
+    }
 }
@@ -7,7 +7,24 @@
 //! - Interactive pan, zoom, box zoom, hover readout, and pin annotations via GPUI.
 //!
 //! # Feature flags
-//! - None at the moment.
+//! - `gpui` (default): `gpui_backend::GpuiPlotView` and the rest of the GPUI
+//!   integration. Without it the crate has no GPUI dependency, and the core
+//!   (data sources, decimation, render command generation) builds for
+//!   `wasm32-unknown-unknown`.
+//! - `serde`: `Serialize`/`Deserialize` for persisted state such as
+//!   [`PlotState`] and `gpui_backend::DashboardLayout`, plus JSON pin
+//!   export with [`PinFormat::Json`], and [`PlotSpec::from_json`].
+//! - `toml`: [`PlotSpec::from_toml`] for declarative plot specs in TOML
+//!   (implies `serde`).
+//! - `gif`: [`FrameRecorder::write_gif`] for animated GIF clips of a plot.
+//! - `png`: [`Plot::write_png`] for windowless snapshots and
+//!   [`FrameRecorder::write_png_sequence`] for numbered PNG frames.
+//! - `tui`: `tui_backend::TuiPlot`, a ratatui widget that draws plots as
+//!   braille dots in a terminal.
+//! - `wgpu`: standalone `wgpu_backend::WgpuRenderer` that draws [`RenderList`]s
+//!   without GPUI.
+//! - `fuzz`: property-based decimation tests using `proptest`; run them with
+//!   `cargo test --features fuzz`.
 //!
 //! # Quick start
 //! ```rust
@@ -23,35 +40,109 @@
 //! plot.refresh_viewport(0.05, 1e-6);
 //! ```
 //!
+//! For a quick look, [`Series::auto`] picks a line or scatter from the data
+//! and [`Plot::quick`] assigns palette colors:
+//! ```rust
+//! use gpui_liveplot::{Plot, Series};
+//!
+//! let mean: Vec<f64> = (0..100).map(|i| (i as f64 * 0.1).sin()).collect();
+//! let limit = [(0.0, 0.8), (99.0, 0.8)];
+//! let plot = Plot::quick([Series::auto("mean", mean), Series::auto("limit", limit).dashed()]);
+//! assert_eq!(plot.series().len(), 2);
+//! ```
+//!
 //! # GPUI integration
-//! Use [`gpui_backend::GpuiPlotView`] to render and interact with a plot inside a GPUI
+//! Use `gpui_backend::GpuiPlotView` to render and interact with a plot inside a GPUI
 //! window. See the `examples/` directory for complete runnable examples.
+//!
+//! # WebAssembly
+//! `std::time::Instant` and `SystemTime` panic on `wasm32-unknown-unknown`,
+//! so the wall-clock driven [`batch`] and [`record`] modules are not built
+//! for that target.
 
 #![forbid(unsafe_code)]
 
+pub mod analysis;
+pub mod annotation;
 pub mod axis;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub mod batch;
+pub mod capture;
 pub mod datasource;
+pub mod error;
+pub mod export;
+mod frame;
 pub mod geom;
 pub mod interaction;
+pub mod pins;
 pub mod plot;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub mod record;
 pub mod render;
 pub mod series;
+pub mod spec;
 pub mod style;
+pub mod testdata;
 pub mod transform;
 pub mod view;
 
+#[cfg(feature = "gpui")]
 pub mod gpui_backend;
+#[cfg(feature = "tui")]
+pub mod tui_backend;
+#[cfg(feature = "wgpu")]
+pub mod wgpu_backend;
 
-pub use axis::{AxisConfig, AxisConfigBuilder, AxisFormatter, TickConfig};
-pub use datasource::AppendError;
-pub use geom::Point;
-pub use interaction::Pin;
-pub use plot::{Plot, PlotBuilder};
-pub use render::{Color, LineStyle, MarkerShape, MarkerStyle};
-pub use series::{Series, SeriesId, SeriesKind};
-pub use style::Theme;
-pub use view::{Range, View, Viewport};
+pub use analysis::{
+    Crossing, CrossingDetector, CrossingDirection, Edge, RollingBand, RollingStat, RollingStats,
+    RollingWindow, RunAlignment, TriggerCapture, overlay_runs,
+};
+pub use annotation::{Annotation, AnnotationId, AnnotationKind, Reference, ReferenceKind};
+pub use axis::{
+    AxisConfig, AxisConfigBuilder, AxisFormatter, AxisUnits, DateBoundaries, DatePeriod,
+    TickConfig, TickDensity, TimeTicks, UnitPlacement,
+};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use batch::{BatchAppender, BatchStats, OverflowPolicy};
+pub use capture::FrameRecorder;
+pub use datasource::{
+    AppendError, AppendPolicy, BoxStats, DataWarning, ErrorBar, Grid, Ohlc, PointId, Retention,
+    SegmentStats,
+};
+pub use error::Error;
+pub use export::{CursorRow, CursorTable, ExportScope};
+pub use frame::{
+    AxisWidthPolicy, LegendEntryRenderer, LegendRow, LegendRowFn, PerfStats, PlotCorner,
+    PlotViewConfig, SeriesLabelFormatter, SoftLimits, TooltipMode, WheelZoom,
+};
+pub use geom::{Point, ScreenPoint, ScreenRect, Vector};
+pub use interaction::{HitInfo, HitPoint, HitRegion, Pin, PinSlice, PlotRegions};
+pub use pins::{PinFormat, PinRecord};
+pub use plot::{Bookmark, PinState, Plot, PlotBuilder, PlotState};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use record::{RecordedSample, RecordedValue, Recorder, Replayer};
+pub use render::{
+    AreaStyle, ArrowScale, BandStyle, BlendMode, BoxStyle, BrailleCanvas, CandleStyle, Color,
+    Colormap, ContourStyle, ErrorBarStyle, ImageStyle, Interpolation, LineDash, LineSegment,
+    LineStyle, MarkerShape, MarkerStyle, Mesh, MeshBatch, MeshVertex, QuiverStyle, RasterImage,
+    RectStyle, RenderCommand, RenderList, RenderRole, Sampling, StemStyle, StepMode, TextStyle,
+};
+pub use series::{AutoSample, ResampleMethod, Series, SeriesId, SeriesKind};
+pub use spec::{
+    AnnotationSpec, AxisSpec, PlotSpec, SeriesKindSpec, SeriesSpec, SpecError, ThemeSpec,
+};
+pub use style::{
+    Backdrop, BackdropFill, ExportStyle, GradientDirection, ShadedRegion, ShadingProvider, Theme,
+};
+pub use view::{Headroom, Range, View, Viewport};
 
+#[cfg(feature = "gpui")]
 pub use gpui_backend::{
-    GpuiPlotView, LinkMemberId, PlotHandle, PlotLinkGroup, PlotLinkOptions, PlotViewConfig,
+    DashboardLayout, DashboardLink, DashboardPlot, GpuiPlotView, InteractionEvent, InteractionKind,
+    LayoutCell, LinkMemberId, LinkedReadout, LinkedReadoutRow, PlotHandle, PlotLinkGroup,
+    PlotLinkOptions,
 };
+#[cfg(feature = "tui")]
+pub use tui_backend::TuiPlot;
+#[cfg(feature = "wgpu")]
+pub use wgpu_backend::{WgpuRenderer, WgpuTarget};
@@ -0,0 +1,170 @@
+//! Pin export and import.
+//!
+//! [`Plot::export_pins`](crate::plot::Plot::export_pins) writes every pin with
+//! its series name, resolved data coordinates and note, so marked events can
+//! be shared between analysts or loaded into spreadsheets and notebooks.
+//! [`Plot::import_pins`](crate::plot::Plot::import_pins) reads them back.
+//!
+//! CSV files have a `series,point_index,x,y,note` header and quote fields
+//! containing commas, quotes or line breaks. JSON (behind the `serde`
+//! feature) is an array of [`PinRecord`] objects.
+
+use std::io::{self, Read, Write};
+
+use crate::export::quote_field;
+
+const CSV_HEADER: &str = "series,point_index,x,y,note";
+
+/// File format used for pin export and import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PinFormat {
+    /// Comma-separated values with a header row.
+    #[default]
+    Csv,
+    /// JSON array of [`PinRecord`] objects.
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+/// Exported pin with resolved data coordinates.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PinRecord {
+    /// Name of the pinned series.
+    pub series: String,
+    /// Point index within the series at export time.
+    pub point_index: usize,
+    /// X value of the pinned point.
+    pub x: f64,
+    /// Y value of the pinned point.
+    pub y: f64,
+    /// Free-form note attached to the pin.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub note: Option<String>,
+}
+
+pub(crate) fn write_records(
+    mut writer: impl Write,
+    records: &[PinRecord],
+    format: PinFormat,
+) -> io::Result<()> {
+    match format {
+        PinFormat::Csv => {
+            writeln!(writer, "{CSV_HEADER}")?;
+            for record in records {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{}",
+                    quote_field(&record.series, ','),
+                    record.point_index,
+                    record.x,
+                    record.y,
+                    quote_field(record.note.as_deref().unwrap_or_default(), ','),
+                )?;
+            }
+            Ok(())
+        }
+        #[cfg(feature = "serde")]
+        PinFormat::Json => serde_json::to_writer_pretty(writer, records).map_err(io::Error::from),
+    }
+}
+
+pub(crate) fn read_records(mut reader: impl Read, format: PinFormat) -> io::Result<Vec<PinRecord>> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    match format {
+        PinFormat::Csv => {
+            let mut rows = parse_csv(&text).into_iter().enumerate();
+            rows.next();
+            rows.map(|(row_index, row)| {
+                parse_row(&row).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid pin row {}", row_index + 1),
+                    )
+                })
+            })
+            .collect()
+        }
+        #[cfg(feature = "serde")]
+        PinFormat::Json => serde_json::from_str(&text).map_err(io::Error::from),
+    }
+}
+
+fn parse_row(row: &[String]) -> Option<PinRecord> {
+    let [series, point_index, x, y, rest @ ..] = row else {
+        return None;
+    };
+    let note = rest.first().filter(|note| !note.is_empty()).cloned();
+    Some(PinRecord {
+        series: series.clone(),
+        point_index: point_index.trim().parse().ok()?,
+        x: x.trim().parse().ok()?,
+        y: y.trim().parse().ok()?,
+        note,
+    })
+}
+
+/// Split CSV text into rows of fields, skipping blank lines.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                if row.len() > 1 || !row[0].is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    if !row.is_empty() || !field.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_round_trips_quoted_fields() {
+        let records = vec![
+            PinRecord {
+                series: "temp, \"inner\"".to_string(),
+                point_index: 3,
+                x: 1.5,
+                y: -2.0,
+                note: Some("spike\nafter reset".to_string()),
+            },
+            PinRecord {
+                series: "rpm".to_string(),
+                point_index: 0,
+                x: 0.0,
+                y: 10.0,
+                note: None,
+            },
+        ];
+        let mut bytes = Vec::new();
+        write_records(&mut bytes, &records, PinFormat::Csv).expect("write");
+        assert!(bytes.starts_with(CSV_HEADER.as_bytes()));
+        let parsed = read_records(bytes.as_slice(), PinFormat::Csv).expect("read");
+        assert_eq!(parsed, records);
+        assert!(read_records("series\nrpm,x,0,0\n".as_bytes(), PinFormat::Csv).is_err());
+    }
+}
@@ -3,26 +3,97 @@
 //! A [`Plot`] owns axis configuration, view mode, and a set of series. All
 //! series in a plot share the same axes and transforms.
 
-use crate::axis::AxisConfig;
-use crate::interaction::Pin;
-use crate::series::Series;
-use crate::style::Theme;
-use crate::view::{Range, View, Viewport};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+
+use crate::annotation::{Annotation, AnnotationId, Reference, ReferenceKind, push_references};
+use crate::axis::{AxisConfig, AxisUnits};
+use crate::capture::rasterize_plot;
+use crate::datasource::{DataWarning, DecimationScratch};
+use crate::export::{CursorRow, CursorTable, ExportScope, write_points_csv, write_series_rows};
+use crate::geom::{Point, ScreenPoint, ScreenRect};
+use crate::interaction::{Pin, PinSlice};
+use crate::pins::{PinFormat, PinRecord, read_records, write_records};
+use crate::render::{
+    BlendMode, Color, LineStyle, MarkerStyle, RasterImage, RectStyle, RenderCommand, RenderList,
+    RenderRole, build_line_segments, build_scatter_points,
+};
+use crate::series::{ResampleMethod, Series, SeriesId, SeriesKind};
+use crate::style::{Backdrop, ShadingProvider, Theme};
+use crate::transform::Transform;
+use crate::view::{Headroom, Range, View, Viewport};
+
+/// Serializable snapshot of a plot's view and interaction state.
+///
+/// Series are referenced by name so a state captured in one session can be
+/// restored onto freshly created series. Data is not included.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlotState {
+    /// Active view mode.
+    pub view: View,
+    /// Current viewport, if one has been computed.
+    pub viewport: Option<Viewport>,
+    /// Names of hidden series.
+    pub hidden_series: Vec<String>,
+    /// Pinned points.
+    pub pins: Vec<PinState>,
+    /// Saved viewport bookmarks.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bookmarks: Vec<Bookmark>,
+}
+
+/// Named viewport saved with [`Plot::save_bookmark`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bookmark {
+    /// Bookmark name.
+    pub name: String,
+    /// Saved viewport.
+    pub viewport: Viewport,
+}
+
+/// Pin reference stored in a [`PlotState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PinState {
+    /// Name of the pinned series.
+    pub series: String,
+    /// Point index within the series.
+    pub point_index: usize,
+}
 
 /// Main plot widget container.
 ///
 /// A plot is backend-agnostic and focuses on data, view state, and styling.
 /// Render backends (such as the GPUI backend) drive viewport refreshes and
 /// interaction state.
+///
+/// Series added with [`Plot::add_series`] share their data with the handle
+/// they were added from, so appends through that handle show up in the plot.
+/// Cloning a plot copies the data as it is now: the clone no longer sees
+/// those appends, but keeps the same [`SeriesId`]s. Use
+/// [`Plot::deep_clone`] for a copy with fresh IDs and [`Plot::snapshot`] for
+/// a frozen copy that also keeps the current viewport.
 #[derive(Debug, Clone)]
 pub struct Plot {
     theme: Theme,
+    backdrop: Option<Backdrop>,
+    shading: Vec<ShadingProvider>,
+    references: Vec<Reference>,
+    annotations: Vec<(AnnotationId, Annotation)>,
     x_axis: AxisConfig,
     y_axis: AxisConfig,
     view: View,
+    follow_headroom: Headroom,
     viewport: Option<Viewport>,
+    last_finite_viewport: Option<Viewport>,
     series: Vec<Series>,
     pins: Vec<Pin>,
+    pin_notes: HashMap<Pin, String>,
+    pin_slices: Vec<PinSlice>,
+    trim_marks: HashMap<SeriesId, u64>,
+    bookmarks: Vec<Bookmark>,
 }
 
 impl Plot {
@@ -32,12 +103,22 @@ impl Plot {
     pub fn new() -> Self {
         Self {
             theme: Theme::default(),
+            backdrop: None,
+            shading: Vec::new(),
+            references: Vec::new(),
+            annotations: Vec::new(),
             x_axis: AxisConfig::default(),
             y_axis: AxisConfig::default(),
             view: View::default(),
+            follow_headroom: Headroom::default(),
             viewport: None,
+            last_finite_viewport: None,
             series: Vec::new(),
             pins: Vec::new(),
+            pin_notes: HashMap::new(),
+            pin_slices: Vec::new(),
+            trim_marks: HashMap::new(),
+            bookmarks: Vec::new(),
         }
     }
 
@@ -46,6 +127,23 @@ impl Plot {
         PlotBuilder::default()
     }
 
+    /// Create an auto-fitting plot showing `series` with default settings.
+    ///
+    /// Series still drawn in the default black get [`Color::palette`]
+    /// colors by position, so
+    /// `Plot::quick([Series::auto("a", a), Series::auto("b", b)])` needs no
+    /// further styling.
+    pub fn quick(series: impl IntoIterator<Item = Series>) -> Self {
+        let mut plot = Self::new();
+        for (index, mut series) in series.into_iter().enumerate() {
+            if series.primary_color() == Some(Color::BLACK) {
+                series.set_color(Color::palette(index));
+            }
+            plot.add_series(&series);
+        }
+        plot
+    }
+
     /// Access the current theme.
     pub fn theme(&self) -> &Theme {
         &self.theme
@@ -56,6 +154,122 @@ impl Plot {
         self.theme = theme;
     }
 
+    /// Access the backdrop painted behind the data region.
+    pub fn backdrop(&self) -> Option<&Backdrop> {
+        self.backdrop.as_ref()
+    }
+
+    /// Set or clear the backdrop painted behind the data region.
+    pub fn set_backdrop(&mut self, backdrop: Option<Backdrop>) {
+        self.backdrop = backdrop;
+    }
+
+    /// Access the shading providers, drawn behind the data in order.
+    pub fn shading(&self) -> &[ShadingProvider] {
+        &self.shading
+    }
+
+    /// Add a shading provider drawn behind the data.
+    pub fn add_shading(&mut self, provider: ShadingProvider) {
+        self.shading.push(provider);
+    }
+
+    /// Remove all shading providers.
+    pub fn clear_shading(&mut self) {
+        self.shading.clear();
+    }
+
+    /// Access the reference lines and bands, drawn behind the series in
+    /// order.
+    pub fn references(&self) -> &[Reference] {
+        &self.references
+    }
+
+    /// Add a reference line or band drawn behind the series.
+    pub fn add_reference(&mut self, reference: Reference) {
+        self.references.push(reference);
+    }
+
+    /// Add a horizontal line at `y`, such as an alarm threshold.
+    pub fn add_hline(&mut self, y: f64, style: LineStyle, label: Option<&str>) {
+        self.add_reference(Reference::new(ReferenceKind::HLine { y, style }, label));
+    }
+
+    /// Add a vertical line at `x`, such as an event marker.
+    pub fn add_vline(&mut self, x: f64, style: LineStyle, label: Option<&str>) {
+        self.add_reference(Reference::new(ReferenceKind::VLine { x, style }, label));
+    }
+
+    /// Add a horizontal band covering the Y range `y`, such as a normal
+    /// operating range.
+    pub fn add_hband(&mut self, y: Range, fill: Color, label: Option<&str>) {
+        self.add_reference(Reference::new(ReferenceKind::HBand { y, fill }, label));
+    }
+
+    /// Add a vertical band covering the X range `x`.
+    pub fn add_vband(&mut self, x: Range, fill: Color, label: Option<&str>) {
+        self.add_reference(Reference::new(ReferenceKind::VBand { x, fill }, label));
+    }
+
+    /// Remove all reference lines and bands.
+    pub fn clear_references(&mut self) {
+        self.references.clear();
+    }
+
+    /// Iterate over the annotations in the order they are drawn.
+    pub fn annotations(&self) -> impl Iterator<Item = (AnnotationId, &Annotation)> {
+        self.annotations
+            .iter()
+            .map(|(id, annotation)| (*id, annotation))
+    }
+
+    /// Look up an annotation by ID.
+    pub fn annotation(&self, id: AnnotationId) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .find(|(existing, _)| *existing == id)
+            .map(|(_, annotation)| annotation)
+    }
+
+    /// Look up an annotation by ID for editing.
+    pub fn annotation_mut(&mut self, id: AnnotationId) -> Option<&mut Annotation> {
+        self.annotations
+            .iter_mut()
+            .find(|(existing, _)| *existing == id)
+            .map(|(_, annotation)| annotation)
+    }
+
+    /// Collect the IDs of annotations whose anchor or arrow tail lies
+    /// inside `viewport`.
+    pub fn annotations_in(&self, viewport: Viewport) -> Vec<AnnotationId> {
+        self.annotations
+            .iter()
+            .filter(|(_, annotation)| annotation.intersects(viewport))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Add an annotation drawn above the series and return its ID.
+    pub fn add_annotation(&mut self, annotation: Annotation) -> AnnotationId {
+        let id = AnnotationId::next();
+        self.annotations.push((id, annotation));
+        id
+    }
+
+    /// Remove an annotation, returning it if it existed.
+    pub fn remove_annotation(&mut self, id: AnnotationId) -> Option<Annotation> {
+        let index = self
+            .annotations
+            .iter()
+            .position(|(existing, _)| *existing == id)?;
+        Some(self.annotations.remove(index).1)
+    }
+
+    /// Remove all annotations.
+    pub fn clear_annotations(&mut self) {
+        self.annotations.clear();
+    }
+
     /// Access the X axis configuration.
     pub fn x_axis(&self) -> &AxisConfig {
         &self.x_axis
@@ -71,6 +285,18 @@ impl Plot {
         self.view
     }
 
+    /// Access the Y headroom used by [`View::FollowLastNXY`].
+    pub fn follow_headroom(&self) -> Headroom {
+        self.follow_headroom
+    }
+
+    /// Set the Y headroom used by [`View::FollowLastNXY`].
+    ///
+    /// Takes effect on the next [`Plot::refresh_viewport`].
+    pub fn set_follow_headroom(&mut self, headroom: Headroom) {
+        self.follow_headroom = headroom;
+    }
+
     /// Access the current viewport.
     ///
     /// The viewport is computed by [`Plot::refresh_viewport`].
@@ -83,6 +309,14 @@ impl Plot {
         &self.series
     }
 
+    /// Series in draw order: ascending [`Series::z_order`], then insertion
+    /// order.
+    pub(crate) fn series_in_draw_order(&self) -> Vec<&Series> {
+        let mut series: Vec<&Series> = self.series.iter().collect();
+        series.sort_by_key(|series| series.z_order());
+        series
+    }
+
     /// Access all series mutably.
     ///
     /// Returning the backing vector allows callers to add, remove, and reorder
@@ -96,7 +330,100 @@ impl Plot {
     /// The plot stores a shared handle instead of taking unique ownership.
     /// Appends made through other shared handles are visible immediately.
     pub fn add_series(&mut self, series: &Series) {
-        self.series.push(series.share());
+        let series = series.share();
+        self.trim_marks.insert(series.id(), series.trimmed_count());
+        self.series.push(series);
+    }
+
+    /// Copy the plot with independent series data and new series IDs.
+    ///
+    /// Appends to the source series are not visible in the copy, and the
+    /// copy can be shown alongside the source without ID clashes. Pins and
+    /// their notes follow their series; the view mode is kept.
+    pub fn deep_clone(&self) -> Plot {
+        let mut plot = self.clone();
+        plot.series = self.series.iter().map(Series::deep_clone).collect();
+        let ids: HashMap<SeriesId, SeriesId> = self
+            .series
+            .iter()
+            .zip(&plot.series)
+            .map(|(old, new)| (old.id(), new.id()))
+            .collect();
+        let remap = |pin: &Pin| Pin {
+            series_id: ids.get(&pin.series_id).copied().unwrap_or(pin.series_id),
+            point_id: pin.point_id,
+        };
+        plot.pins = self.pins.iter().map(remap).collect();
+        plot.pin_notes = self
+            .pin_notes
+            .iter()
+            .map(|(pin, note)| (remap(pin), note.clone()))
+            .collect();
+        for slice in &mut plot.pin_slices {
+            slice.pins = slice.pins.iter().map(remap).collect();
+        }
+        plot.trim_marks = self
+            .trim_marks
+            .iter()
+            .filter_map(|(id, trimmed)| Some((*ids.get(id)?, *trimmed)))
+            .collect();
+        plot
+    }
+
+    /// Copy the plot as a frozen snapshot of its current data and view.
+    ///
+    /// Like [`Plot::deep_clone`], but the copy is switched to a manual view
+    /// at the current viewport, so it keeps showing exactly what the source
+    /// showed. Useful for before/after comparisons and exports.
+    pub fn snapshot(&self) -> Plot {
+        let mut plot = self.deep_clone();
+        if let Some(viewport) = self.viewport {
+            plot.set_manual_view(viewport);
+        }
+        plot
+    }
+
+    /// Copy the data inside an X window into a new standalone plot.
+    ///
+    /// Use it with a brush or box-zoom selection to open the selection in a
+    /// new view. The new plot keeps the theme, backdrop, references,
+    /// annotations and axis settings,
+    /// holds independent copies of every series (see [`Series::slice_x`]),
+    /// and keeps the pins that fall inside the window. Its view starts in
+    /// auto-fit mode.
+    pub fn slice_x(&self, x_range: Range) -> Plot {
+        let mut plot = Plot {
+            theme: self.theme.clone(),
+            backdrop: self.backdrop.clone(),
+            shading: self.shading.clone(),
+            references: self.references.clone(),
+            annotations: self.annotations.clone(),
+            x_axis: self.x_axis.clone(),
+            y_axis: self.y_axis.clone(),
+            ..Plot::new()
+        };
+        for source in &self.series {
+            let (series, indices) = source.slice_x_with_indices(x_range);
+            for pin in self.pins.iter().filter(|pin| pin.series_id == source.id()) {
+                let sliced = source
+                    .point_index(pin.point_id)
+                    .and_then(|index| indices.binary_search(&index).ok())
+                    .and_then(|index| series.point_id(index));
+                if let Some(point_id) = sliced {
+                    let sliced = Pin {
+                        series_id: series.id(),
+                        point_id,
+                    };
+                    if let Some(note) = self.pin_notes.get(pin) {
+                        plot.pin_notes.insert(sliced, note.clone());
+                    }
+                    plot.pins.push(sliced);
+                }
+            }
+            plot.trim_marks.insert(series.id(), series.trimmed_count());
+            plot.series.push(series);
+        }
+        plot
     }
 
     /// Access the pinned points.
@@ -109,6 +436,526 @@ impl Plot {
         &mut self.pins
     }
 
+    /// Attach a note to a pin, or remove it with `None`.
+    ///
+    /// Notes are kept while the pin exists and are included in
+    /// [`Plot::export_pins`].
+    pub fn set_pin_note(&mut self, pin: Pin, note: Option<String>) {
+        match note {
+            Some(note) => {
+                self.pin_notes.insert(pin, note);
+            }
+            None => {
+                self.pin_notes.remove(&pin);
+            }
+        }
+    }
+
+    /// Access the note attached to a pin.
+    pub fn pin_note(&self, pin: Pin) -> Option<&str> {
+        self.pin_notes.get(&pin).map(String::as_str)
+    }
+
+    /// Access the pin slices, oldest first.
+    pub fn pin_slices(&self) -> &[PinSlice] {
+        &self.pin_slices
+    }
+
+    /// Pin the point nearest `x` in every visible, hoverable series as one
+    /// slice.
+    ///
+    /// When those points already form a slice, the slice and its pins are
+    /// removed instead. New pins are labelled with the formatted X value
+    /// unless they already have a note. Returns `true` when a slice was
+    /// added.
+    pub fn toggle_pin_slice(&mut self, x: f64) -> bool {
+        let pins: Vec<Pin> = self
+            .series
+            .iter()
+            .filter(|series| {
+                series.is_visible() && series.is_hover_enabled() && series.grid_extent().is_none()
+            })
+            .filter_map(|series| {
+                let point_id = series
+                    .with_store(|store| store.point_id(store.data().nearest_index_by_x(x)?))?;
+                Some(Pin {
+                    series_id: series.id(),
+                    point_id,
+                })
+            })
+            .collect();
+        if pins.is_empty() {
+            return false;
+        }
+        if let Some(index) = self.pin_slices.iter().position(|slice| slice.pins == pins) {
+            self.remove_pin_slice(index);
+            return false;
+        }
+        let label = format!("x = {}", self.x_axis.format_value(x));
+        for pin in &pins {
+            if !self.pins.contains(pin) {
+                self.pins.push(*pin);
+            }
+            self.pin_notes.entry(*pin).or_insert_with(|| label.clone());
+        }
+        self.pin_slices.push(PinSlice { x, label, pins });
+        true
+    }
+
+    /// Remove a pin slice and its pins.
+    pub fn remove_pin_slice(&mut self, index: usize) -> Option<PinSlice> {
+        if index >= self.pin_slices.len() {
+            return None;
+        }
+        let slice = self.pin_slices.remove(index);
+        self.pins.retain(|pin| !slice.pins.contains(pin));
+        for pin in &slice.pins {
+            self.pin_notes.remove(pin);
+        }
+        Some(slice)
+    }
+
+    /// Resolve every pin to its series name, data coordinates and note.
+    ///
+    /// Pins whose series or point no longer exists are skipped.
+    pub fn pin_records(&self) -> Vec<PinRecord> {
+        self.pins
+            .iter()
+            .filter_map(|pin| {
+                let series = self.series.iter().find(|s| s.id() == pin.series_id)?;
+                let point_index = series.point_index(pin.point_id)?;
+                let point = series.with_store(|store| store.data().point(point_index))?;
+                Some(PinRecord {
+                    series: series.name().to_string(),
+                    point_index,
+                    x: point.x,
+                    y: point.y,
+                    note: self.pin_notes.get(pin).cloned(),
+                })
+            })
+            .collect()
+    }
+
+    /// Write all pins to `writer` in the given format.
+    ///
+    /// See [`crate::pins`] for the file layout.
+    pub fn export_pins(&self, writer: impl Write, format: PinFormat) -> io::Result<()> {
+        write_records(writer, &self.pin_records(), format)
+    }
+
+    /// Read pins written by [`Plot::export_pins`] and add them to the plot.
+    ///
+    /// Series are matched by name. The stored point index is used when the
+    /// point there still has the exported X value; otherwise the pin moves
+    /// to the point nearest that X value. Records for missing series are
+    /// skipped. Returns the number of pins added or updated.
+    pub fn import_pins(&mut self, reader: impl Read, format: PinFormat) -> io::Result<usize> {
+        let records = read_records(reader, format)?;
+        let mut imported = 0;
+        for record in records {
+            let Some(series) = self.series.iter().find(|s| s.name() == record.series) else {
+                continue;
+            };
+            let point_id = series.with_store(|store| {
+                let data = store.data();
+                let index = match data.point(record.point_index) {
+                    Some(point) if point.x == record.x => Some(record.point_index),
+                    _ => data.nearest_index_by_x(record.x),
+                };
+                store.point_id(index?)
+            });
+            let Some(point_id) = point_id else {
+                continue;
+            };
+            let pin = Pin {
+                series_id: series.id(),
+                point_id,
+            };
+            if !self.pins.contains(&pin) {
+                self.pins.push(pin);
+            }
+            self.set_pin_note(pin, record.note);
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    /// Drop pins whose points were cleared, truncated or evicted.
+    ///
+    /// Pins refer to points by [`PointId`](crate::datasource::PointId), so
+    /// the remaining pins and their notes keep following their points.
+    /// Render backends call this before building a frame.
+    pub fn sync_pins(&mut self) {
+        for series in &self.series {
+            let trimmed = series.trimmed_count();
+            let seen = self.trim_marks.entry(series.id()).or_insert(trimmed);
+            if std::mem::replace(seen, trimmed) == trimmed {
+                continue;
+            }
+            let stored = |pin: &Pin| {
+                pin.series_id != series.id() || series.point_index(pin.point_id).is_some()
+            };
+            self.pins.retain(stored);
+            for slice in &mut self.pin_slices {
+                slice.pins.retain(stored);
+            }
+        }
+        let pins = &self.pins;
+        self.pin_notes.retain(|pin, _| pins.contains(pin));
+        self.pin_slices.retain_mut(|slice| {
+            slice.pins.retain(|pin| pins.contains(pin));
+            !slice.pins.is_empty()
+        });
+        let series = &self.series;
+        self.trim_marks
+            .retain(|id, _| series.iter().any(|series| series.id() == *id));
+    }
+
+    /// Sample every visible series at the given cursor X positions.
+    ///
+    /// Values are interpolated with [`Series::value_at`]. Pass one X for a
+    /// single cursor or two for an A/B measurement; gridded series are
+    /// skipped.
+    pub fn cursor_table(&self, xs: &[f64], method: ResampleMethod) -> CursorTable {
+        CursorTable {
+            xs: xs.to_vec(),
+            rows: self
+                .series
+                .iter()
+                .filter(|series| series.is_visible() && series.grid_extent().is_none())
+                .map(|series| CursorRow {
+                    series: series.name().to_string(),
+                    values: xs.iter().map(|x| series.value_at(*x, method)).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Write a reduced copy of a series that keeps its min/max envelope.
+    ///
+    /// The series X extent is split into `points / 2` equal buckets and the
+    /// lowest and highest point of each bucket is kept, so spikes survive any
+    /// reduction. Rows are written as `x,y` CSV. Returns the number of rows,
+    /// at most `points`; fails with [`io::ErrorKind::NotFound`] for an
+    /// unknown series.
+    pub fn export_decimated(
+        &self,
+        series_id: SeriesId,
+        points: usize,
+        writer: impl Write,
+    ) -> io::Result<usize> {
+        let series = self
+            .series
+            .iter()
+            .find(|series| series.id() == series_id)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "unknown series"))?;
+        let mut scratch = DecimationScratch::new();
+        series.with_store(|store| {
+            let envelope = store.envelope(points, &mut scratch);
+            write_points_csv(writer, envelope)?;
+            Ok(envelope.len())
+        })
+    }
+
+    /// Write the points of every visible series as CSV.
+    ///
+    /// Rows are `series,x,y` under a header, series in plot order and
+    /// points in storage order, so series with different X values share one
+    /// file. `scope` limits the rows to the current viewport or another X
+    /// range; [`ExportScope::Viewport`] exports everything before the first
+    /// refresh. Returns the number of data rows.
+    pub fn export_csv(&self, mut writer: impl Write, scope: ExportScope) -> io::Result<usize> {
+        let x_range = match scope {
+            ExportScope::All => None,
+            ExportScope::Viewport => self.viewport.map(|viewport| viewport.x),
+            ExportScope::XRange(range) => Some(range),
+        };
+        writeln!(writer, "series,x,y")?;
+        let mut rows = 0;
+        for series in self.series.iter().filter(|series| series.is_visible()) {
+            rows += series.with_store(|store| {
+                let data = store.data();
+                let range = match x_range {
+                    Some(x_range) => data.range_by_x(x_range),
+                    None => 0..data.len(),
+                };
+                let points = data.points()[range].iter().filter(|point| {
+                    x_range.is_none_or(|x_range| point.x >= x_range.min && point.x <= x_range.max)
+                });
+                write_series_rows(&mut writer, series.name(), points)
+            })?;
+        }
+        Ok(rows)
+    }
+
+    /// Y axis a series is drawn against, or `None` for an unknown series.
+    ///
+    /// Readouts use this to format and place each series' values. Plots
+    /// have a single Y axis, so every series is drawn against it.
+    pub fn series_y_axis(&self, series_id: SeriesId) -> Option<&AxisConfig> {
+        self.series
+            .iter()
+            .any(|series| series.id() == series_id)
+            .then_some(&self.y_axis)
+    }
+
+    /// Infer the X axis unit from the units declared by the series.
+    ///
+    /// Check [`AxisUnits::is_mixed`] to detect series with conflicting units
+    /// sharing the axis.
+    pub fn inferred_x_units(&self) -> AxisUnits {
+        AxisUnits::infer(self.series.iter().filter_map(Series::x_units))
+    }
+
+    /// Infer the Y axis unit from the units declared by the series.
+    ///
+    /// Check [`AxisUnits::is_mixed`] to detect series with conflicting units
+    /// sharing the axis.
+    pub fn inferred_y_units(&self) -> AxisUnits {
+        AxisUnits::infer(self.series.iter().filter_map(Series::y_units))
+    }
+
+    /// Collect data quality warnings across all series.
+    pub fn data_warnings(&self) -> Vec<(SeriesId, DataWarning)> {
+        self.series
+            .iter()
+            .flat_map(|series| {
+                series
+                    .data_warnings()
+                    .into_iter()
+                    .map(|warning| (series.id(), warning))
+            })
+            .collect()
+    }
+
+    /// Capture the view, visibility and pin state of the plot.
+    pub fn capture_state(&self) -> PlotState {
+        PlotState {
+            view: self.view,
+            viewport: self.viewport,
+            hidden_series: self
+                .series
+                .iter()
+                .filter(|series| !series.is_visible())
+                .map(|series| series.name().to_string())
+                .collect(),
+            pins: self
+                .pins
+                .iter()
+                .filter_map(|pin| {
+                    let series = self.series.iter().find(|s| s.id() == pin.series_id)?;
+                    Some(PinState {
+                        series: series.name().to_string(),
+                        point_index: series.point_index(pin.point_id)?,
+                    })
+                })
+                .collect(),
+            bookmarks: self.bookmarks.clone(),
+        }
+    }
+
+    /// Restore state captured with [`Plot::capture_state`].
+    ///
+    /// Series and pins are matched by name; pins whose series or point is
+    /// missing are dropped.
+    pub fn restore_state(&mut self, state: &PlotState) {
+        self.view = state.view;
+        self.viewport = state.viewport;
+        for series in &mut self.series {
+            let hidden = state.hidden_series.iter().any(|name| name == series.name());
+            series.set_visible(!hidden);
+        }
+        self.pins = state
+            .pins
+            .iter()
+            .filter_map(|pin| {
+                let series = self.series.iter().find(|s| s.name() == pin.series)?;
+                Some(Pin {
+                    series_id: series.id(),
+                    point_id: series.point_id(pin.point_index)?,
+                })
+            })
+            .collect();
+        self.bookmarks = state.bookmarks.clone();
+    }
+
+    /// Save the current viewport under `name`.
+    ///
+    /// An existing bookmark with the same name is replaced. Returns `false`
+    /// when no viewport has been computed yet.
+    pub fn save_bookmark(&mut self, name: impl Into<String>) -> bool {
+        let Some(viewport) = self.viewport else {
+            return false;
+        };
+        let name = name.into();
+        match self
+            .bookmarks
+            .iter_mut()
+            .find(|bookmark| bookmark.name == name)
+        {
+            Some(bookmark) => bookmark.viewport = viewport,
+            None => self.bookmarks.push(Bookmark { name, viewport }),
+        }
+        true
+    }
+
+    /// List saved bookmarks in the order they were created.
+    pub fn list_bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Jump to a saved bookmark, switching to manual view.
+    ///
+    /// Returns `false` when no bookmark has the given name.
+    pub fn goto_bookmark(&mut self, name: &str) -> bool {
+        let Some(viewport) = self
+            .bookmarks
+            .iter()
+            .find(|bookmark| bookmark.name == name)
+            .map(|bookmark| bookmark.viewport)
+        else {
+            return false;
+        };
+        self.set_manual_view(viewport);
+        true
+    }
+
+    /// Remove a saved bookmark. Returns `false` when it does not exist.
+    pub fn remove_bookmark(&mut self, name: &str) -> bool {
+        let before = self.bookmarks.len();
+        self.bookmarks.retain(|bookmark| bookmark.name != name);
+        self.bookmarks.len() != before
+    }
+
+    /// Render a small overview of all visible series.
+    ///
+    /// The thumbnail covers the full data bounds and is built from the
+    /// decimation summaries only, so it stays cheap for long recordings.
+    /// Line and stem series are drawn as lines and scatter series as small
+    /// markers; gridded, quiver and box series are skipped. Returns an empty
+    /// list when the size is zero or there is no data.
+    pub fn render_thumbnail(&self, width: u32, height: u32) -> RenderList {
+        let Some(bounds) = self.data_bounds() else {
+            return RenderList::new();
+        };
+        self.render_series_overview(bounds, width, height, |series, points| {
+            series.with_store(|store| store.overview(width as usize, points));
+        })
+    }
+
+    /// Render all visible series over `viewport` at the given size.
+    ///
+    /// Uses the same viewport-aware decimation as interactive rendering and
+    /// the same simplified styling as [`Plot::render_thumbnail`], without
+    /// axes, legend or overlays. Useful for coarse backends such as terminals.
+    /// Live-sampled series (see [`Series::from_live_callback`]) are sampled
+    /// again at `width` resolution.
+    pub fn render_viewport(&self, viewport: Viewport, width: u32, height: u32) -> RenderList {
+        let mut scratch = DecimationScratch::default();
+        self.render_series_overview(viewport, width, height, |series, points| {
+            if series.sample_live(viewport.x, width as usize, points) {
+                return;
+            }
+            series.with_store(|store| {
+                points.clear();
+                points.extend_from_slice(store.decimate(viewport.x, width as usize, &mut scratch));
+            });
+        })
+    }
+
+    /// Rasterize the plot without a window.
+    ///
+    /// Draws a full frame with axes, tick labels, legend and annotations,
+    /// laid out like the interactive view, using the software rasterizer and
+    /// a built-in bitmap font. Useful for CI reports and logged snapshots.
+    /// Returns `None` when either size is zero.
+    pub fn rasterize(&self, width: usize, height: usize) -> Option<RasterImage> {
+        rasterize_plot(self, width, height, None)
+    }
+
+    /// Write the plot as a `width` x `height` PNG without a window.
+    ///
+    /// See [`Plot::rasterize`] for what is drawn. Fails with
+    /// [`io::ErrorKind::InvalidInput`] when either size is zero.
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, writer: impl Write, width: usize, height: usize) -> io::Result<()> {
+        let image = self.rasterize(width, height).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "PNG size must be non-zero")
+        })?;
+        image.write_png(writer)
+    }
+
+    fn render_series_overview(
+        &self,
+        viewport: Viewport,
+        width: u32,
+        height: u32,
+        mut sample: impl FnMut(&Series, &mut Vec<Point>),
+    ) -> RenderList {
+        let mut render = RenderList::new();
+        let rect = ScreenRect::new(
+            ScreenPoint::new(0.0, 0.0),
+            ScreenPoint::new(width as f32, height as f32),
+        );
+        let Some(transform) = Transform::new(viewport, rect) else {
+            return render;
+        };
+        render.push(RenderCommand::Rect {
+            rect,
+            style: RectStyle {
+                fill: self.theme.background,
+                stroke: self.theme.background,
+                stroke_width: 0.0,
+            },
+        });
+        render.push(RenderCommand::ClipRect(rect));
+        render.push(RenderCommand::Role(RenderRole::Series));
+        push_references(&mut render, &self.references, &transform, rect);
+        let mut points: Vec<Point> = Vec::new();
+        let mut blend = BlendMode::Normal;
+        for series in self.series_in_draw_order() {
+            if !series.is_visible() {
+                continue;
+            }
+            if series.blend_mode() != blend {
+                blend = series.blend_mode();
+                render.push(RenderCommand::Blend(blend));
+            }
+            let line = match series.kind() {
+                SeriesKind::Line(style) => Some(*style),
+                SeriesKind::Stem(style) => Some(style.line),
+                SeriesKind::Area(style) => Some(style.line),
+                SeriesKind::Scatter(_) => None,
+                _ => continue,
+            };
+            sample(series, &mut points);
+            match (line, series.kind()) {
+                (Some(style), _) => {
+                    let mut segments = Vec::new();
+                    build_line_segments(&points, &transform, rect, &mut segments);
+                    render.push(RenderCommand::LineSegments { segments, style });
+                }
+                (None, SeriesKind::Scatter(style)) => {
+                    let mut markers = Vec::new();
+                    build_scatter_points(&points, &transform, rect, &mut markers);
+                    render.push(RenderCommand::Points {
+                        points: markers,
+                        style: MarkerStyle {
+                            size: style.size.min(2.0),
+                            ..*style
+                        },
+                    });
+                }
+                (None, _) => {}
+            }
+        }
+        if blend != BlendMode::Normal {
+            render.push(RenderCommand::Blend(BlendMode::Normal));
+        }
+        render.push(RenderCommand::ClipEnd);
+        render
+    }
+
     /// Compute bounds across all visible series.
     pub fn data_bounds(&self) -> Option<Viewport> {
         let mut x_range: Option<Range> = None;
@@ -117,7 +964,7 @@ impl Plot {
             if !series.is_visible() {
                 continue;
             }
-            if let Some(bounds) = series.bounds() {
+            if let Some(bounds) = series.bounds().filter(Viewport::is_finite) {
                 x_range = Some(match x_range {
                     None => bounds.x,
                     Some(existing) => Range::union(existing, bounds.x)?,
@@ -177,9 +1024,28 @@ impl Plot {
                 self.viewport = self.follow_last(points, true);
             }
         }
+        self.recover_viewport(bounds);
         self.viewport
     }
 
+    /// Replace a non-finite viewport with the last finite one.
+    ///
+    /// Overflowing zoom, padding near `f64::MAX` or a bad manual viewport
+    /// would otherwise leave the plot without a drawable range. Falls back to
+    /// the data bounds when no finite viewport has been seen yet.
+    fn recover_viewport(&mut self, bounds: Viewport) {
+        match self.viewport {
+            Some(viewport) if viewport.is_finite() => {
+                self.last_finite_viewport = Some(viewport);
+            }
+            _ => {
+                self.viewport = self
+                    .last_finite_viewport
+                    .or(Some(bounds).filter(Viewport::is_finite));
+            }
+        }
+    }
+
     fn follow_last(&self, points: usize, follow_y: bool) -> Option<Viewport> {
         let mut max_series: Option<&Series> = None;
         let mut max_point: Option<crate::geom::Point> = None;
@@ -187,7 +1053,15 @@ impl Plot {
             if !series.is_visible() {
                 continue;
             }
-            let last_point = series.with_store(|store| store.data().points().last().copied());
+            let last_point = series.with_store(|store| {
+                store
+                    .data()
+                    .points()
+                    .iter()
+                    .rev()
+                    .find(|point| point.x.is_finite() && point.y.is_finite())
+                    .copied()
+            });
             if let Some(point) = last_point
                 && max_point.is_none_or(|max| point.x > max.x)
             {
@@ -202,7 +1076,11 @@ impl Plot {
             let data = store.data();
             let len = data.len();
             let start_index = len.saturating_sub(points);
-            (len, data.point(start_index))
+            let start_point = data.points()[start_index..]
+                .iter()
+                .find(|point| point.x.is_finite())
+                .copied();
+            (len, start_point)
         });
         if len == 0 {
             return None;
@@ -232,7 +1110,7 @@ impl Plot {
                     }
                 });
             }
-            y_range?
+            self.follow_headroom.apply(y_range?)
         } else if let Some(current) = self.viewport {
             current.y
         } else {
@@ -255,9 +1133,14 @@ impl Default for Plot {
 #[derive(Debug, Default)]
 pub struct PlotBuilder {
     theme: Theme,
+    backdrop: Option<Backdrop>,
+    shading: Vec<ShadingProvider>,
+    references: Vec<Reference>,
+    annotations: Vec<Annotation>,
     x_axis: AxisConfig,
     y_axis: AxisConfig,
     view: View,
+    follow_headroom: Headroom,
     series: Vec<Series>,
 }
 
@@ -268,6 +1151,30 @@ impl PlotBuilder {
         self
     }
 
+    /// Set the backdrop painted behind the data region.
+    pub fn backdrop(mut self, backdrop: Backdrop) -> Self {
+        self.backdrop = Some(backdrop);
+        self
+    }
+
+    /// Add a shading provider drawn behind the data.
+    pub fn shading(mut self, provider: ShadingProvider) -> Self {
+        self.shading.push(provider);
+        self
+    }
+
+    /// Add a reference line or band drawn behind the series.
+    pub fn reference(mut self, reference: Reference) -> Self {
+        self.references.push(reference);
+        self
+    }
+
+    /// Add a text, arrow or callout annotation.
+    pub fn annotation(mut self, annotation: Annotation) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
     /// Set the X axis configuration.
     pub fn x_axis(mut self, axis: AxisConfig) -> Self {
         self.x_axis = axis;
@@ -286,6 +1193,15 @@ impl PlotBuilder {
         self
     }
 
+    /// Set the Y headroom used by [`View::FollowLastNXY`].
+    ///
+    /// Applied instead of the auto-fit padding while following, so the
+    /// trace does not touch the top and bottom edges.
+    pub fn follow_headroom(mut self, headroom: Headroom) -> Self {
+        self.follow_headroom = headroom;
+        self
+    }
+
     /// Add a series to the plot.
     ///
     /// The builder stores a shared handle to the given series.
@@ -296,14 +1212,33 @@ impl PlotBuilder {
 
     /// Build the plot.
     pub fn build(self) -> Plot {
+        let trim_marks = self
+            .series
+            .iter()
+            .map(|series| (series.id(), series.trimmed_count()))
+            .collect();
         Plot {
             theme: self.theme,
+            backdrop: self.backdrop,
+            shading: self.shading,
+            references: self.references,
+            annotations: self
+                .annotations
+                .into_iter()
+                .map(|annotation| (AnnotationId::next(), annotation))
+                .collect(),
             x_axis: self.x_axis,
             y_axis: self.y_axis,
             view: self.view,
+            follow_headroom: self.follow_headroom,
             viewport: None,
+            last_finite_viewport: None,
             series: self.series,
             pins: Vec::new(),
+            pin_notes: HashMap::new(),
+            pin_slices: Vec::new(),
+            trim_marks,
+            bookmarks: Vec::new(),
         }
     }
 }
@@ -311,6 +1246,8 @@ impl PlotBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::annotation::AnnotationKind;
+    use crate::datasource::AppendPolicy;
     use crate::series::Series;
 
     #[test]
@@ -330,6 +1267,189 @@ mod tests {
         assert_eq!(next_bounds.y.max, 3.0);
     }
 
+    #[test]
+    fn series_draw_in_z_order_with_blend_changes() {
+        let mut plot = Plot::new();
+        for (name, z_order) in [("a", 0), ("fill", -1), ("b", 0)] {
+            let mut series = Series::line(name)
+                .with_z_order(z_order)
+                .with_blend_mode(BlendMode::Additive);
+            let _ = series.extend_y([0.0, 1.0]);
+            plot.add_series(&series);
+        }
+        let names: Vec<&str> = plot
+            .series_in_draw_order()
+            .into_iter()
+            .map(Series::name)
+            .collect();
+        assert_eq!(names, ["fill", "a", "b"]);
+
+        let viewport = Viewport::new(Range::new(0.0, 1.0), Range::new(0.0, 1.0));
+        let render = plot.render_viewport(viewport, 10, 10);
+        let blends: Vec<BlendMode> = render
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::Blend(mode) => Some(*mode),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(blends, [BlendMode::Additive, BlendMode::Normal]);
+    }
+
+    #[test]
+    fn references_render_behind_series() {
+        let mut series = Series::line("trace");
+        let _ = series.extend_y([0.0, 10.0]);
+        let mut plot = Plot::builder().series(&series).build();
+        let fill = Color::new(1.0, 0.0, 0.0, 0.2);
+        plot.add_hband(Range::new(2.0, 4.0), fill, Some("normal"));
+        plot.add_hline(5.0, LineStyle::default(), Some("limit"));
+        plot.add_vline(20.0, LineStyle::default(), None);
+        assert_eq!(plot.references().len(), 3);
+
+        let viewport = Viewport::new(Range::new(0.0, 10.0), Range::new(0.0, 10.0));
+        let render = plot.render_viewport(viewport, 100, 100);
+        let commands = render.commands();
+        let band = commands
+            .iter()
+            .position(|command| {
+                matches!(command, RenderCommand::Rect { rect, style }
+                    if style.fill == fill && rect.min.y == 60.0 && rect.max.y == 80.0)
+            })
+            .expect("band");
+        let lines: Vec<usize> = commands
+            .iter()
+            .enumerate()
+            .filter(|(_, command)| matches!(command, RenderCommand::LineSegments { .. }))
+            .map(|(index, _)| index)
+            .collect();
+        // The off-screen vertical line is skipped; the threshold comes first.
+        assert_eq!(lines.len(), 2);
+        assert!(band < lines[0]);
+        let RenderCommand::LineSegments { segments, .. } = &commands[lines[0]] else {
+            unreachable!();
+        };
+        assert_eq!(segments[0].start.y, 50.0);
+    }
+
+    #[test]
+    fn annotations_can_be_added_queried_and_removed() {
+        let mut plot = Plot::new();
+        let peak = plot.add_annotation(Annotation::callout(Point::new(2.0, 5.0), "peak"));
+        let dip = plot.add_annotation(Annotation::arrow(
+            Point::new(20.0, 1.0),
+            Point::new(8.0, -1.0),
+            "dip",
+        ));
+        assert_ne!(peak, dip);
+        assert_eq!(plot.annotation(peak).map(|a| a.text.as_str()), Some("peak"));
+
+        let viewport = Viewport::new(Range::new(0.0, 10.0), Range::new(0.0, 10.0));
+        assert_eq!(plot.annotations_in(viewport), [peak]);
+        let wide = Viewport::new(Range::new(0.0, 30.0), Range::new(0.0, 10.0));
+        assert_eq!(plot.annotations_in(wide), [peak, dip]);
+
+        let removed = plot.remove_annotation(peak).expect("removed");
+        assert_eq!(removed.kind, AnnotationKind::Callout);
+        assert!(plot.remove_annotation(peak).is_none());
+        let ids: Vec<AnnotationId> = plot.annotations().map(|(id, _)| id).collect();
+        assert_eq!(ids, [dip]);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn write_png_encodes_plot_without_window() {
+        let plot = Plot::quick([Series::auto("trace", [0.0, 1.0, 0.5])]);
+        let (width, height) = (240, 160);
+        let mut bytes = Vec::new();
+        plot.write_png(&mut bytes, width, height).expect("png");
+        let decoder = png::Decoder::new(bytes.as_slice());
+        let mut reader = decoder.read_info().expect("decode");
+        assert_eq!(
+            (reader.info().width, reader.info().height),
+            (width as u32, height as u32)
+        );
+        let mut rgba = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut rgba).expect("frame");
+        let background = RasterImage::new(1, 1, vec![plot.theme().background])
+            .expect("pixel")
+            .to_rgba8();
+        let inked = |rows: std::ops::Range<usize>| {
+            rows.flat_map(|row| (0..width).map(move |column| (row * width + column) * 4))
+                .filter(|offset| rgba[*offset..*offset + 4] != background)
+                .count()
+        };
+        // The X axis line spans the plot width...
+        assert!((0..height).any(|row| inked(row..row + 1) > width / 2));
+        // ...and tick labels are drawn in the strip below it.
+        assert!(inked(height - 20..height) > 20);
+        assert!(plot.write_png(Vec::new(), 0, 16).is_err());
+    }
+
+    #[test]
+    fn export_csv_limits_rows_to_scope() {
+        let mut plot = Plot::new();
+        let mut a = Series::line("a");
+        let _ = a.extend_y([0.0, 1.0, 2.0, 3.0]);
+        let mut b = Series::line("b, raw");
+        let _ = b.extend_y([5.0, 6.0]);
+        plot.add_series(&a);
+        plot.add_series(&b);
+
+        let mut all = Vec::new();
+        assert_eq!(plot.export_csv(&mut all, ExportScope::All).expect("csv"), 6);
+        let all = String::from_utf8(all).expect("utf8");
+        assert!(all.starts_with("series,x,y\na,0,0\n"));
+        assert!(all.ends_with("\"b, raw\",1,6\n"));
+
+        plot.set_manual_view(Viewport::new(Range::new(0.5, 2.0), Range::new(0.0, 5.0)));
+        let mut zoomed = Vec::new();
+        assert_eq!(
+            plot.export_csv(&mut zoomed, ExportScope::Viewport)
+                .expect("csv"),
+            3
+        );
+        assert_eq!(
+            String::from_utf8(zoomed).expect("utf8"),
+            "series,x,y\na,1,1\na,2,2\n\"b, raw\",1,6\n"
+        );
+        let selected = ExportScope::XRange(Range::new(3.0, 3.0));
+        assert_eq!(plot.export_csv(std::io::sink(), selected).expect("csv"), 1);
+    }
+
+    #[test]
+    fn slice_x_copies_window_and_remaps_pins() {
+        let mut series = Series::line("signal").with_y_units("V");
+        let _ = series.extend_y([0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut plot = Plot::new();
+        plot.add_series(&series);
+        let source_id = plot.series()[0].id();
+        plot.pins_mut().extend([1, 3].map(|index| Pin {
+            series_id: source_id,
+            point_id: series.point_id(index).expect("point"),
+        }));
+
+        let slice = plot.slice_x(Range::new(2.0, 4.0));
+        let sliced = &slice.series()[0];
+        assert_ne!(sliced.id(), source_id);
+        assert_eq!(sliced.y_units(), Some("V"));
+        assert_eq!(
+            sliced.with_store(|store| store.data().points().to_vec()),
+            [2.0, 3.0, 4.0].map(|x| Point::new(x, x))
+        );
+        assert_eq!(
+            slice.pins(),
+            [Pin {
+                series_id: sliced.id(),
+                point_id: sliced.point_id(1).expect("point"),
+            }]
+        );
+
+        let _ = series.push_y(6.0);
+        assert_eq!(sliced.with_store(|store| store.data().len()), 3);
+    }
+
     #[test]
     fn series_mut_can_remove_series() {
         let mut first = Series::line("first");
@@ -346,4 +1466,360 @@ mod tests {
         assert_eq!(plot.series().len(), 1);
         assert_eq!(plot.series()[0].name(), "first");
     }
+
+    #[test]
+    fn pins_export_and_import_with_notes() {
+        let mut plot = Plot::new();
+        plot.add_series(&Series::from_iter_y(
+            "temp",
+            [1.0, 2.0, 3.0, 4.0],
+            SeriesKind::Line(Default::default()),
+        ));
+        let pin = Pin {
+            series_id: plot.series()[0].id(),
+            point_id: plot.series()[0].point_id(2).expect("point"),
+        };
+        plot.pins_mut().push(pin);
+        plot.set_pin_note(pin, Some("peak, check log".to_string()));
+        let mut bytes = Vec::new();
+        plot.export_pins(&mut bytes, PinFormat::Csv)
+            .expect("export");
+        assert_eq!(
+            String::from_utf8(bytes.clone()).expect("utf8"),
+            "series,point_index,x,y,note\ntemp,2,2,3,\"peak, check log\"\n"
+        );
+
+        let mut other = Plot::new();
+        other.add_series(&Series::from_iter_points(
+            "temp",
+            [0.0, 1.0, 2.0].map(|x| Point::new(x + 1.0, x)),
+            SeriesKind::Line(Default::default()),
+        ));
+        assert_eq!(
+            other
+                .import_pins(bytes.as_slice(), PinFormat::Csv)
+                .expect("import"),
+            1
+        );
+        let imported = other.pins()[0];
+        assert_eq!(other.series()[0].point_index(imported.point_id), Some(1));
+        assert_eq!(other.pin_note(imported), Some("peak, check log"));
+    }
+
+    #[test]
+    fn cursor_table_interpolates_visible_series() {
+        let mut plot = Plot::new();
+        plot.add_series(&Series::from_iter_y(
+            "a",
+            [0.0, 10.0, 20.0],
+            SeriesKind::Line(Default::default()),
+        ));
+        plot.add_series(&Series::from_iter_y(
+            "b, raw",
+            [1.0, 2.0],
+            SeriesKind::Line(Default::default()),
+        ));
+        plot.add_series(&Series::line("hidden"));
+        plot.series_mut()[2].set_visible(false);
+
+        let table = plot.cursor_table(&[0.5, 1.5], ResampleMethod::Linear);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0].values, [Some(5.0), Some(15.0)]);
+        assert_eq!(table.rows[1].values, [Some(1.5), None]);
+        let mut csv = Vec::new();
+        table.write_csv(&mut csv).expect("csv");
+        assert_eq!(
+            String::from_utf8(csv).expect("utf8"),
+            "series,0.5,1.5\na,5,15\n\"b, raw\",1.5,\n"
+        );
+        assert_eq!(
+            table.to_tsv(),
+            "series\t0.5\t1.5\na\t5\t15\nb, raw\t1.5\t\n"
+        );
+    }
+
+    #[test]
+    fn non_finite_viewports_recover() {
+        let series = Series::from_iter_points(
+            "spiky",
+            [
+                Point::new(0.0, 1.0),
+                Point::new(1.0, f64::INFINITY),
+                Point::new(2.0, 3.0),
+                Point::new(f64::NEG_INFINITY, 0.0),
+            ],
+            SeriesKind::Line(Default::default()),
+        );
+        let mut plot = Plot::builder().series(&series).build();
+        let fitted = plot.refresh_viewport(0.0, 0.0).expect("viewport");
+        assert_eq!(fitted.x, Range::new(0.0, 2.0));
+        assert_eq!(fitted.y, Range::new(1.0, 3.0));
+
+        plot.set_manual_view(Viewport::new(
+            Range::new(f64::NAN, 1.0),
+            Range::new(0.0, 1.0),
+        ));
+        assert_eq!(plot.refresh_viewport(0.0, 0.0), Some(fitted));
+
+        let mut follow = Plot::builder()
+            .view(View::FollowLastN { points: 2 })
+            .series(&series)
+            .build();
+        let viewport = follow.refresh_viewport(0.0, 0.0).expect("viewport");
+        assert_eq!(viewport.x, Range::new(2.0, 2.0));
+    }
+
+    #[test]
+    fn export_decimated_keeps_spikes_within_budget() {
+        let series = Series::from_iter_y(
+            "adc",
+            (0..10_000).map(|i| if i == 4321 { 99.0 } else { (i % 7) as f64 }),
+            SeriesKind::Line(Default::default()),
+        );
+        let plot = Plot::builder().series(&series).build();
+        let mut bytes = Vec::new();
+        let rows = plot
+            .export_decimated(plot.series()[0].id(), 100, &mut bytes)
+            .expect("export");
+        let text = String::from_utf8(bytes).expect("utf8");
+        assert!(rows <= 100);
+        assert_eq!(text.lines().count(), rows + 1);
+        assert!(text.starts_with("x,y\n"));
+        assert!(text.contains("4321,99\n"));
+
+        let missing = Series::line("missing");
+        let err = plot.export_decimated(missing.id(), 100, io::sink());
+        assert_eq!(err.map_err(|err| err.kind()), Err(io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn pin_slice_toggles_every_visible_series_as_a_group() {
+        let a = Series::from_iter_y(
+            "a",
+            [0.0, 1.0, 2.0, 3.0],
+            SeriesKind::Line(Default::default()),
+        );
+        let b = Series::from_iter_points(
+            "b",
+            [Point::new(0.0, 5.0), Point::new(2.5, 6.0)],
+            SeriesKind::Line(Default::default()),
+        );
+        let mut plot = Plot::builder().series(&a).series(&b).build();
+        assert!(plot.toggle_pin_slice(2.2));
+        let slice = plot.pin_slices()[0].clone();
+        assert_eq!(
+            slice
+                .pins
+                .iter()
+                .zip([&a, &b])
+                .map(|(pin, series)| series.point_index(pin.point_id))
+                .collect::<Vec<_>>(),
+            vec![Some(2), Some(1)]
+        );
+        assert_eq!(plot.pins().len(), 2);
+        assert_eq!(plot.pin_note(slice.pins[0]), Some(slice.label.as_str()));
+
+        assert!(!plot.toggle_pin_slice(2.1));
+        assert!(plot.pin_slices().is_empty());
+        assert!(plot.pins().is_empty());
+    }
+
+    #[test]
+    fn pin_slice_skips_series_with_hover_disabled() {
+        let trace = Series::from_iter_y(
+            "trace",
+            [0.0, 1.0, 2.0],
+            SeriesKind::Line(Default::default()),
+        );
+        let limit = Series::from_iter_y(
+            "limit",
+            [1.5, 1.5, 1.5],
+            SeriesKind::Line(Default::default()),
+        )
+        .with_hover_enabled(false);
+        let mut plot = Plot::builder().series(&trace).series(&limit).build();
+        assert!(plot.toggle_pin_slice(1.0));
+        let pins = &plot.pin_slices()[0].pins;
+        assert_eq!(pins.len(), 1);
+        assert_eq!(pins[0].series_id, plot.series()[0].id());
+    }
+
+    #[test]
+    fn follow_headroom_pads_only_follow_y() {
+        let series =
+            Series::from_iter_y("y", [0.0, 10.0, 20.0], SeriesKind::Line(Default::default()));
+        let mut plot = Plot::builder()
+            .view(View::FollowLastNXY { points: 2 })
+            .follow_headroom(Headroom::Fraction(0.1))
+            .series(&series)
+            .build();
+        let viewport = plot.refresh_viewport(0.0, 0.0).expect("viewport");
+        assert_eq!(viewport.x, Range::new(1.0, 2.0));
+        assert_eq!(viewport.y, Range::new(9.0, 21.0));
+
+        plot.set_follow_headroom(Headroom::Absolute(5.0));
+        let viewport = plot.refresh_viewport(0.0, 0.0).expect("viewport");
+        assert_eq!(viewport.y, Range::new(5.0, 25.0));
+
+        plot.reset_view();
+        let viewport = plot.refresh_viewport(0.0, 0.0).expect("viewport");
+        assert_eq!(viewport.y, Range::new(0.0, 20.0));
+    }
+
+    #[test]
+    fn snapshot_is_independent_of_source() {
+        let mut source = Series::line("live");
+        let _ = source.extend_y([1.0, 2.0]);
+        let mut plot = Plot::new();
+        plot.add_series(&source);
+        let pin = Pin {
+            series_id: plot.series()[0].id(),
+            point_id: source.point_id(1).expect("point"),
+        };
+        plot.pins_mut().push(pin);
+        plot.set_pin_note(pin, Some("peak".to_string()));
+        let viewport = plot.refresh_viewport(0.0, 0.0).expect("viewport");
+
+        let snapshot = plot.snapshot();
+        let _ = source.push_y(3.0);
+        assert_eq!(plot.series()[0].generation(), 3);
+        assert_eq!(snapshot.series()[0].generation(), 2);
+        assert_ne!(snapshot.series()[0].id(), plot.series()[0].id());
+        assert_eq!(snapshot.view(), View::Manual);
+        assert_eq!(snapshot.viewport(), Some(viewport));
+        let copied = snapshot.pins()[0];
+        assert_eq!(copied.series_id, snapshot.series()[0].id());
+        assert_eq!(snapshot.pin_note(copied), Some("peak"));
+    }
+
+    #[test]
+    fn restore_state_matches_series_by_name() {
+        let series_a = || Series::from_iter_y("a", [0.0; 5], SeriesKind::Line(Default::default()));
+        let mut plot = Plot::new();
+        plot.add_series(&series_a());
+        plot.add_series(&Series::line("b"));
+        plot.series_mut()[1].set_visible(false);
+        let pin = Pin {
+            series_id: plot.series()[0].id(),
+            point_id: plot.series()[0].point_id(3).expect("point"),
+        };
+        plot.pins_mut().push(pin);
+        plot.set_manual_view(Viewport::new(Range::new(0.0, 1.0), Range::new(0.0, 2.0)));
+        let state = plot.capture_state();
+
+        let mut restored = Plot::new();
+        restored.add_series(&series_a());
+        restored.add_series(&Series::line("b"));
+        restored.restore_state(&state);
+        assert_eq!(restored.pins().len(), 1);
+        assert_eq!(restored.capture_state(), state);
+        assert!(!restored.series()[1].is_visible());
+    }
+
+    #[test]
+    fn bookmarks_save_goto_and_persist() {
+        let mut plot = Plot::new();
+        assert!(!plot.save_bookmark("empty"));
+        let fault = Viewport::new(Range::new(10.0, 20.0), Range::new(-1.0, 1.0));
+        plot.set_manual_view(fault);
+        assert!(plot.save_bookmark("fault at 12:03"));
+        plot.reset_view();
+        assert!(plot.goto_bookmark("fault at 12:03"));
+        assert_eq!(plot.viewport(), Some(fault));
+        assert!(!plot.goto_bookmark("missing"));
+
+        let mut restored = Plot::new();
+        restored.restore_state(&plot.capture_state());
+        assert_eq!(restored.list_bookmarks(), plot.list_bookmarks());
+        assert!(restored.remove_bookmark("fault at 12:03"));
+        assert!(restored.list_bookmarks().is_empty());
+    }
+
+    #[test]
+    fn thumbnail_uses_summaries_for_long_series() {
+        let series = Series::from_iter_y(
+            "long",
+            (0..100_000).map(|i| (i as f64 * 0.01).sin()),
+            SeriesKind::Line(Default::default()),
+        );
+        let plot = Plot::builder().series(&series).build();
+        let thumbnail = plot.render_thumbnail(64, 32);
+        let segments = thumbnail
+            .commands()
+            .iter()
+            .find_map(|command| match command {
+                RenderCommand::LineSegments { segments, .. } => Some(segments.len()),
+                _ => None,
+            })
+            .expect("line segments");
+        assert!(segments > 0 && segments < 1_000);
+        assert!(plot.render_thumbnail(0, 32).commands().is_empty());
+    }
+
+    #[test]
+    fn pins_follow_points_through_late_inserts_and_eviction() {
+        let mut source = Series::from_iter_points(
+            "telemetry",
+            (0..40).map(|x| Point::new(f64::from(x), 0.0)),
+            SeriesKind::Line(Default::default()),
+        )
+        .with_append_policy(AppendPolicy::SortOnInsert)
+        .with_max_points(64);
+        let mut plot = Plot::builder().series(&source).build();
+        let pin = Pin {
+            series_id: source.id(),
+            point_id: source.point_id(30).expect("point"),
+        };
+        plot.pins_mut().push(pin);
+        plot.set_pin_note(pin, Some("spike".to_string()));
+
+        let _ = source.extend_points([Point::new(10.5, 1.0), Point::new(20.5, 1.0)]);
+        assert_eq!(source.point_index(pin.point_id), Some(32));
+        let _ = source.extend_points((40..90).map(|x| Point::new(f64::from(x), 0.0)));
+        plot.sync_pins();
+        assert_eq!(plot.pins(), &[pin]);
+        assert_eq!(plot.pin_note(pin), Some("spike"));
+        assert_eq!(
+            source.point_by_id(pin.point_id).map(|point| point.x),
+            Some(30.0)
+        );
+    }
+
+    #[test]
+    fn sync_pins_follows_truncation_and_clear() {
+        let mut source = Series::line("run");
+        let _ = source.extend_y((0..10).map(f64::from));
+        let mut plot = Plot::new();
+        plot.add_series(&source);
+        let series_id = plot.series()[0].id();
+        for index in [2, 7] {
+            plot.pins_mut().push(Pin {
+                series_id,
+                point_id: source.point_id(index).expect("point"),
+            });
+        }
+        let kept = plot.pins()[1];
+
+        assert_eq!(source.truncate_before_x(5.0), 5);
+        plot.sync_pins();
+        assert_eq!(plot.pins(), &[kept]);
+        assert_eq!(source.point_index(kept.point_id), Some(2));
+
+        source.clear();
+        plot.sync_pins();
+        assert!(plot.pins().is_empty());
+    }
+
+    #[test]
+    fn infers_shared_units_and_flags_mixed() {
+        let mut plot = Plot::new();
+        plot.add_series(&Series::line("a").with_y_units("V"));
+        plot.add_series(&Series::line("b"));
+        plot.add_series(&Series::line("c").with_y_units("V"));
+        assert_eq!(plot.inferred_y_units(), AxisUnits::Uniform("V".to_string()));
+        assert_eq!(plot.inferred_x_units(), AxisUnits::Unknown);
+
+        plot.add_series(&Series::line("d").with_y_units("A"));
+        assert!(plot.inferred_y_units().is_mixed());
+    }
 }
@@ -0,0 +1,335 @@
+//! Recording and replay of append streams.
+//!
+//! A [`Recorder`] logs appended values with timestamps to any writer (usually
+//! a file). A [`Replayer`] reads the log back and re-appends the values to
+//! series at the original or a scaled speed, which makes live sessions
+//! reproducible offline.
+//!
+//! The log is line-oriented text: a header line followed by one
+//! tab-separated record per append.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::datasource::AppendError;
+use crate::geom::Point;
+use crate::series::Series;
+
+const HEADER: &str = "# gpui-liveplot recording v1";
+
+/// A value recorded for one append.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordedValue {
+    /// A Y value appended to an indexed series.
+    Y(f64),
+    /// An explicit point.
+    Point(Point),
+}
+
+/// A single recorded append.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedSample {
+    /// Time since the recording started.
+    pub offset: Duration,
+    /// Name of the series the value was appended to.
+    pub series: String,
+    /// Appended value.
+    pub value: RecordedValue,
+}
+
+/// Log appended values with timestamps.
+///
+/// Use the `push_*` helpers to append to a series and record the value in one
+/// step, or [`Recorder::record`] to log values appended elsewhere.
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    writer: W,
+    start: Instant,
+}
+
+impl Recorder<BufWriter<File>> {
+    /// Create a recorder writing to a new file at `path`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    /// Create a recorder writing to `writer`.
+    ///
+    /// The header records the wall-clock start time in Unix milliseconds.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        let start_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis());
+        writeln!(writer, "{HEADER} start_unix_ms={start_ms}")?;
+        Ok(Self {
+            writer,
+            start: Instant::now(),
+        })
+    }
+
+    /// Record a value appended to the series named `series`.
+    pub fn record(&mut self, series: &str, value: RecordedValue) -> io::Result<()> {
+        let offset = self.start.elapsed().as_micros();
+        let name = escape(series);
+        match value {
+            RecordedValue::Y(y) => writeln!(self.writer, "{offset}\t{name}\ty\t{y}"),
+            RecordedValue::Point(point) => {
+                writeln!(self.writer, "{offset}\t{name}\tp\t{}\t{}", point.x, point.y)
+            }
+        }
+    }
+
+    /// Append a Y value to an indexed series and record it.
+    ///
+    /// Values rejected by the series are not recorded.
+    pub fn push_y(
+        &mut self,
+        series: &mut Series,
+        y: f64,
+    ) -> io::Result<Result<usize, AppendError>> {
+        let result = series.push_y(y);
+        if result.is_ok() {
+            self.record(series.name(), RecordedValue::Y(y))?;
+        }
+        Ok(result)
+    }
+
+    /// Append an explicit point to a series and record it.
+    ///
+    /// Points stored by the series, including non-monotonic ones, are
+    /// recorded; rejected points are not.
+    pub fn push_point(
+        &mut self,
+        series: &mut Series,
+        point: Point,
+    ) -> io::Result<Result<usize, AppendError>> {
+        let result = series.push_point(point);
+        if matches!(result, Ok(_) | Err(AppendError::NonMonotonicX)) {
+            self.record(series.name(), RecordedValue::Point(point))?;
+        }
+        Ok(result)
+    }
+
+    /// Flush buffered records to the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Re-append recorded values at their original or a scaled speed.
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    samples: Vec<RecordedSample>,
+    cursor: usize,
+    speed: f64,
+    start: Option<Instant>,
+}
+
+impl Replayer {
+    /// Load a recording from a file.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Load a recording from a buffered reader.
+    pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+        let mut samples = Vec::new();
+        for (line_index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let sample = parse_line(&line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid recording line {}", line_index + 1),
+                )
+            })?;
+            samples.push(sample);
+        }
+        Ok(Self {
+            samples,
+            cursor: 0,
+            speed: 1.0,
+            start: None,
+        })
+    }
+
+    /// Set the playback speed multiplier (2.0 plays twice as fast).
+    ///
+    /// Non-positive or non-finite values are ignored.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        if speed.is_finite() && speed > 0.0 {
+            self.speed = speed;
+        }
+        self
+    }
+
+    /// Access all recorded samples.
+    pub fn samples(&self) -> &[RecordedSample] {
+        &self.samples
+    }
+
+    /// Check whether every sample has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.samples.len()
+    }
+
+    /// Restart playback from the beginning.
+    pub fn rewind(&mut self) {
+        self.cursor = 0;
+        self.start = None;
+    }
+
+    /// Append every sample that is due by wall-clock time.
+    ///
+    /// The clock starts on the first call. Returns the number of samples
+    /// appended; samples for series not present in `series` are skipped.
+    pub fn advance(&mut self, series: &mut [Series]) -> usize {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        self.advance_to(start.elapsed(), series)
+    }
+
+    /// Append every sample due at `elapsed` playback time.
+    pub fn advance_to(&mut self, elapsed: Duration, series: &mut [Series]) -> usize {
+        let mut appended = 0;
+        while let Some(sample) = self.samples.get(self.cursor) {
+            if sample.offset.as_secs_f64() / self.speed > elapsed.as_secs_f64() {
+                break;
+            }
+            self.cursor += 1;
+            let Some(target) = series.iter_mut().find(|s| s.name() == sample.series) else {
+                continue;
+            };
+            let _ = match sample.value {
+                RecordedValue::Y(y) => target.push_y(y),
+                RecordedValue::Point(point) => target.push_point(point),
+            };
+            appended += 1;
+        }
+        appended
+    }
+}
+
+fn parse_line(line: &str) -> Option<RecordedSample> {
+    let mut fields = line.split('\t');
+    let offset = Duration::from_micros(fields.next()?.parse().ok()?);
+    let series = unescape(fields.next()?);
+    let value = match fields.next()? {
+        "y" => RecordedValue::Y(fields.next()?.parse().ok()?),
+        "p" => {
+            let x = fields.next()?.parse().ok()?;
+            let y = fields.next()?.parse().ok()?;
+            RecordedValue::Point(Point::new(x, y))
+        }
+        _ => return None,
+    };
+    Some(RecordedSample {
+        offset,
+        series,
+        value,
+    })
+}
+
+fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::AppendPolicy;
+    use crate::render::{LineStyle, MarkerStyle};
+    use crate::series::SeriesKind;
+
+    #[test]
+    fn recording_replays_into_matching_series() {
+        let mut line = Series::line("a\tb");
+        let mut scatter = Series::from_iter_points(
+            "events",
+            std::iter::empty(),
+            SeriesKind::Scatter(MarkerStyle::default()),
+        );
+        let mut recorder = Recorder::new(Vec::new()).expect("recorder");
+        let _ = recorder.push_y(&mut line, 1.5).expect("record");
+        let _ = recorder
+            .push_point(&mut scatter, Point::new(2.0, 3.0))
+            .expect("record");
+        let bytes = recorder.into_inner().expect("flush");
+
+        let mut replayer = Replayer::from_reader(bytes.as_slice()).expect("parse");
+        assert_eq!(replayer.samples().len(), 2);
+        let mut targets = vec![
+            Series::line("a\tb"),
+            Series::from_iter_points(
+                "events",
+                std::iter::empty(),
+                SeriesKind::Line(LineStyle::default()),
+            ),
+        ];
+        assert_eq!(
+            replayer.advance_to(Duration::from_secs(60), &mut targets),
+            2
+        );
+        assert!(replayer.is_finished());
+        assert_eq!(targets[0].bounds(), line.bounds());
+        assert_eq!(targets[1].bounds(), scatter.bounds());
+    }
+
+    #[test]
+    fn recorder_skips_points_rejected_by_strict_series() {
+        let mut strict = Series::from_iter_points(
+            "strict",
+            [Point::new(2.0, 0.0)],
+            SeriesKind::Line(LineStyle::default()),
+        )
+        .with_append_policy(AppendPolicy::Strict);
+        let mut recorder = Recorder::new(Vec::new()).expect("recorder");
+        let rejected = recorder
+            .push_point(&mut strict, Point::new(1.0, 1.0))
+            .expect("record");
+        assert_eq!(rejected, Err(AppendError::OutOfOrder));
+        let kept = recorder
+            .push_point(&mut strict, Point::new(3.0, 1.0))
+            .expect("record");
+        assert_eq!(kept, Ok(1));
+        let bytes = recorder.into_inner().expect("flush");
+
+        let replayer = Replayer::from_reader(bytes.as_slice()).expect("parse");
+        assert_eq!(replayer.samples().len(), 1);
+        assert_eq!(
+            replayer.samples()[0].value,
+            RecordedValue::Point(Point::new(3.0, 1.0))
+        );
+    }
+}
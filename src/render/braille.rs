@@ -0,0 +1,241 @@
+//! Braille-dot rasterization of render lists for text terminals.
+
+use std::fmt;
+
+use crate::geom::{ScreenPoint, ScreenRect};
+use crate::render::{ClipStack, Color, RenderCommand, RenderList};
+
+const BRAILLE_BASE: u32 = 0x2800;
+
+/// Grid of braille cells, each holding a 2x4 block of dots.
+///
+/// Render lists are expected in dot coordinates; build them at
+/// [`BrailleCanvas::dot_size`], for example with
+/// [`Plot::render_viewport`](crate::plot::Plot::render_viewport). Lines,
+/// markers and rectangle outlines become dots, text is written into whole
+/// cells on top, and fills and images are skipped. Each cell keeps the color
+/// of the last dot or character drawn into it.
+#[derive(Debug, Clone)]
+pub struct BrailleCanvas {
+    columns: usize,
+    rows: usize,
+    dots: Vec<u8>,
+    colors: Vec<Option<Color>>,
+    text: Vec<Option<char>>,
+}
+
+impl BrailleCanvas {
+    /// Create an empty canvas with the given number of cells.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        let cells = columns * rows;
+        Self {
+            columns,
+            rows,
+            dots: vec![0; cells],
+            colors: vec![None; cells],
+            text: vec![None; cells],
+        }
+    }
+
+    /// Canvas size in cells as `(columns, rows)`.
+    pub fn size(&self) -> (usize, usize) {
+        (self.columns, self.rows)
+    }
+
+    /// Canvas size in dots as `(width, height)`.
+    pub fn dot_size(&self) -> (u32, u32) {
+        ((self.columns * 2) as u32, (self.rows * 4) as u32)
+    }
+
+    /// Draw a render list given in dot coordinates.
+    pub fn draw(&mut self, list: &RenderList) {
+        let mut clips = ClipStack::default();
+        for command in list.commands() {
+            let clip = clips.current();
+            match command {
+                RenderCommand::ClipRect(rect) => clips.push(*rect),
+                RenderCommand::ClipEnd => clips.pop(),
+                RenderCommand::LineSegments { segments, style } => {
+                    for segment in segments {
+                        self.line(segment.start, segment.end, style.color, clip);
+                    }
+                }
+                RenderCommand::Points { points, style } => {
+                    for point in points {
+                        self.dot(*point, style.color, clip);
+                    }
+                }
+                RenderCommand::ErrorBars {
+                    whiskers, style, ..
+                } => {
+                    for whisker in whiskers {
+                        self.line(whisker.start, whisker.end, style.color, clip);
+                    }
+                }
+                RenderCommand::Rect { rect, style } => {
+                    if style.stroke_width > 0.0 && style.stroke.a > 0.0 {
+                        let corners = rect.corners();
+                        for index in 0..4 {
+                            let next = corners[(index + 1) % 4];
+                            self.line(corners[index], next, style.stroke, clip);
+                        }
+                    }
+                }
+                RenderCommand::Text {
+                    position,
+                    text,
+                    style,
+                } => self.write_text(*position, text, style.color),
+                RenderCommand::FillBetween { .. }
+                | RenderCommand::Image { .. }
+                | RenderCommand::Blend(_)
+                | RenderCommand::Role(_) => {}
+            }
+        }
+    }
+
+    /// Character and color of a cell.
+    ///
+    /// Empty cells are the blank braille pattern with no color.
+    pub fn cell(&self, column: usize, row: usize) -> Option<(char, Option<Color>)> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+        let index = row * self.columns + column;
+        let ch = self.text[index].unwrap_or_else(|| {
+            char::from_u32(BRAILLE_BASE + self.dots[index] as u32).unwrap_or(' ')
+        });
+        Some((ch, self.colors[index]))
+    }
+
+    fn dot(&mut self, point: ScreenPoint, color: Color, clip: Option<ScreenRect>) {
+        if !point.x.is_finite() || !point.y.is_finite() {
+            return;
+        }
+        let clipped = clip.is_some_and(|clip| {
+            point.x < clip.min.x
+                || point.x > clip.max.x
+                || point.y < clip.min.y
+                || point.y > clip.max.y
+        });
+        if clipped {
+            return;
+        }
+        let (width, height) = self.dot_size();
+        let (x, y) = (point.x.floor(), point.y.floor());
+        if x < 0.0 || y < 0.0 || x >= width as f32 || y >= height as f32 {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let index = (y / 4) * self.columns + x / 2;
+        let bit = match (x % 2, y % 4) {
+            (0, 0) => 0x01,
+            (0, 1) => 0x02,
+            (0, 2) => 0x04,
+            (0, _) => 0x40,
+            (_, 0) => 0x08,
+            (_, 1) => 0x10,
+            (_, 2) => 0x20,
+            _ => 0x80,
+        };
+        self.dots[index] |= bit;
+        self.colors[index] = Some(color);
+    }
+
+    fn line(
+        &mut self,
+        start: ScreenPoint,
+        end: ScreenPoint,
+        color: Color,
+        clip: Option<ScreenRect>,
+    ) {
+        let dx = end.x - start.x;
+        let dy = end.y - start.y;
+        let steps = dx.abs().max(dy.abs()).ceil();
+        if !steps.is_finite() {
+            return;
+        }
+        let steps = steps.max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f32 / steps as f32;
+            self.dot(
+                ScreenPoint::new(start.x + dx * t, start.y + dy * t),
+                color,
+                clip,
+            );
+        }
+    }
+
+    fn write_text(&mut self, position: ScreenPoint, text: &str, color: Color) {
+        if !position.x.is_finite() || !position.y.is_finite() || position.y < 0.0 {
+            return;
+        }
+        let row = (position.y / 4.0) as usize;
+        if row >= self.rows {
+            return;
+        }
+        let start = (position.x.max(0.0) / 2.0) as usize;
+        for (offset, ch) in text.chars().enumerate() {
+            let column = start + offset;
+            if column >= self.columns {
+                break;
+            }
+            let index = row * self.columns + column;
+            self.text[index] = Some(ch);
+            self.colors[index] = Some(color);
+        }
+    }
+}
+
+impl fmt::Display for BrailleCanvas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for column in 0..self.columns {
+                let (ch, _) = self.cell(column, row).unwrap_or((' ', None));
+                write!(f, "{ch}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{LineSegment, LineStyle, TextStyle};
+
+    #[test]
+    fn draws_lines_as_braille_dots() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        let mut list = RenderList::new();
+        list.push(RenderCommand::LineSegments {
+            segments: vec![LineSegment::new(
+                ScreenPoint::new(0.0, 0.0),
+                ScreenPoint::new(3.0, 0.0),
+            )],
+            style: LineStyle::default(),
+        });
+        canvas.draw(&list);
+        assert_eq!(canvas.to_string(), "\u{2809}\u{2809}");
+        assert_eq!(canvas.cell(0, 0), Some(('\u{2809}', Some(Color::BLACK))));
+    }
+
+    #[test]
+    fn text_overrides_cells() {
+        let mut canvas = BrailleCanvas::new(4, 2);
+        let mut list = RenderList::new();
+        list.push(RenderCommand::Text {
+            position: ScreenPoint::new(2.0, 4.0),
+            text: "hi".to_string(),
+            style: TextStyle::default(),
+        });
+        canvas.draw(&list);
+        assert_eq!(
+            canvas.to_string(),
+            "\u{2800}\u{2800}\u{2800}\u{2800}\n\u{2800}hi\u{2800}"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Iso-contour extraction for gridded data (marching squares).
+
+use crate::datasource::Grid;
+use crate::geom::Point;
+use crate::view::Viewport;
+
+/// Contour segments for a single level in data space.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ContourLevel {
+    /// Iso value.
+    pub level: f64,
+    /// Unordered line segments along the iso line.
+    pub segments: Vec<(Point, Point)>,
+}
+
+/// Compute contour segments for each level.
+///
+/// Grid values are sampled at cell centers placed over `extent` (row 0 at
+/// `extent.y.max`). Cells touching non-finite values are skipped.
+pub(crate) fn contour_levels(grid: &Grid, extent: Viewport, levels: &[f64]) -> Vec<ContourLevel> {
+    levels
+        .iter()
+        .filter(|level| level.is_finite())
+        .map(|level| ContourLevel {
+            level: *level,
+            segments: contour_segments(grid, extent, *level),
+        })
+        .collect()
+}
+
+fn contour_segments(grid: &Grid, extent: Viewport, level: f64) -> Vec<(Point, Point)> {
+    let mut out = Vec::new();
+    if grid.width() < 2 || grid.height() < 2 {
+        return out;
+    }
+    let cell_w = extent.x.span() / grid.width() as f64;
+    let cell_h = extent.y.span() / grid.height() as f64;
+    let center = |column: usize, row: usize| {
+        Point::new(
+            extent.x.min + (column as f64 + 0.5) * cell_w,
+            extent.y.max - (row as f64 + 0.5) * cell_h,
+        )
+    };
+    let value = |column: usize, row: usize| grid.value(column, row).map(f64::from);
+
+    for row in 0..grid.height() - 1 {
+        for column in 0..grid.width() - 1 {
+            let (Some(tl), Some(tr), Some(br), Some(bl)) = (
+                value(column, row),
+                value(column + 1, row),
+                value(column + 1, row + 1),
+                value(column, row + 1),
+            ) else {
+                continue;
+            };
+            if !(tl.is_finite() && tr.is_finite() && br.is_finite() && bl.is_finite()) {
+                continue;
+            }
+            let case = (usize::from(tl >= level) << 3)
+                | (usize::from(tr >= level) << 2)
+                | (usize::from(br >= level) << 1)
+                | usize::from(bl >= level);
+            if case == 0 || case == 15 {
+                continue;
+            }
+
+            let p_tl = center(column, row);
+            let p_tr = center(column + 1, row);
+            let p_br = center(column + 1, row + 1);
+            let p_bl = center(column, row + 1);
+            let top = interpolate(p_tl, tl, p_tr, tr, level);
+            let right = interpolate(p_tr, tr, p_br, br, level);
+            let bottom = interpolate(p_bl, bl, p_br, br, level);
+            let left = interpolate(p_tl, tl, p_bl, bl, level);
+
+            match case {
+                1 | 14 => out.push((left, bottom)),
+                2 | 13 => out.push((bottom, right)),
+                3 | 12 => out.push((left, right)),
+                4 | 11 => out.push((top, right)),
+                6 | 9 => out.push((top, bottom)),
+                7 | 8 => out.push((left, top)),
+                5 => {
+                    out.push((top, right));
+                    out.push((left, bottom));
+                }
+                10 => {
+                    out.push((left, top));
+                    out.push((bottom, right));
+                }
+                _ => {}
+            }
+        }
+    }
+    out
+}
+
+fn interpolate(a: Point, va: f64, b: Point, vb: f64, level: f64) -> Point {
+    let denom = vb - va;
+    let t = if denom.abs() > f64::EPSILON {
+        ((level - va) / denom).clamp(0.0, 1.0)
+    } else {
+        0.5
+    };
+    Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::Range;
+
+    #[test]
+    fn single_peak_produces_closed_diamond() {
+        let grid = Grid::from_fn(
+            3,
+            3,
+            |column, row| {
+                if column == 1 && row == 1 { 1.0 } else { 0.0 }
+            },
+        );
+        let extent = Viewport::new(Range::new(0.0, 3.0), Range::new(0.0, 3.0));
+        let levels = contour_levels(&grid, extent, &[0.5]);
+        assert_eq!(levels.len(), 1);
+        let segments = &levels[0].segments;
+        assert_eq!(segments.len(), 4);
+        for (start, end) in segments {
+            for point in [start, end] {
+                let dist = (point.x - 1.5).abs() + (point.y - 1.5).abs();
+                assert!((dist - 0.5).abs() < 1e-9);
+            }
+        }
+    }
+}
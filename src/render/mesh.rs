@@ -0,0 +1,281 @@
+//! Triangle tessellation of render lists for GPU backends.
+
+use crate::geom::{ScreenPoint, ScreenRect};
+use crate::render::{
+    BlendMode, ClipStack, Color, LineSegment, MarkerShape, RenderCommand, RenderList,
+};
+
+const CIRCLE_SEGMENTS: usize = 12;
+
+/// Vertex of a tessellated render list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshVertex {
+    /// Position in logical screen pixels.
+    pub position: ScreenPoint,
+    /// Vertex color.
+    pub color: Color,
+}
+
+/// Range of mesh indices drawn with a single clip rectangle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshBatch {
+    /// Active clip rectangle, if any.
+    pub clip: Option<ScreenRect>,
+    /// Blend mode for every triangle in the batch.
+    pub blend: BlendMode,
+    /// First index in [`Mesh::indices`].
+    pub first_index: u32,
+    /// Number of indices in the batch.
+    pub index_count: u32,
+}
+
+/// Indexed triangle list built from a [`RenderList`].
+///
+/// Lines, markers, rectangles and fills are expanded into triangles in
+/// painter's order and grouped into batches by clip rectangle and blend mode.
+/// Text and image commands are skipped; backends that need them should draw
+/// them separately.
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    vertices: Vec<MeshVertex>,
+    indices: Vec<u32>,
+    batches: Vec<MeshBatch>,
+}
+
+impl Mesh {
+    /// Tessellate a render list.
+    pub fn from_render_list(list: &RenderList) -> Self {
+        let mut mesh = Self::default();
+        let mut clips = ClipStack::default();
+        let mut blend = BlendMode::Normal;
+        for command in list.commands() {
+            let clip = clips.current();
+            match command {
+                RenderCommand::ClipRect(rect) => clips.push(*rect),
+                RenderCommand::ClipEnd => clips.pop(),
+                RenderCommand::Blend(mode) => blend = *mode,
+                RenderCommand::LineSegments { segments, style } => {
+                    mesh.begin(clip, blend);
+                    for segment in segments {
+                        mesh.line(*segment, style.width.max(0.5), style.color);
+                    }
+                    mesh.end();
+                }
+                RenderCommand::Points { points, style } => {
+                    mesh.begin(clip, blend);
+                    let half = style.size.max(2.0) * 0.5;
+                    for point in points {
+                        mesh.marker(*point, half, style.shape, style.color);
+                    }
+                    mesh.end();
+                }
+                RenderCommand::Rect { rect, style } => {
+                    mesh.begin(clip, blend);
+                    if style.fill.a > 0.0 {
+                        mesh.quad(rect.corners(), style.fill);
+                    }
+                    if style.stroke_width > 0.0 && style.stroke.a > 0.0 {
+                        let corners = rect.corners();
+                        for index in 0..4 {
+                            let edge = LineSegment::new(corners[index], corners[(index + 1) % 4]);
+                            mesh.line(edge, style.stroke_width, style.stroke);
+                        }
+                    }
+                    mesh.end();
+                }
+                RenderCommand::FillBetween {
+                    upper,
+                    lower,
+                    color,
+                } => {
+                    mesh.begin(clip, blend);
+                    for (top, bottom) in upper.windows(2).zip(lower.windows(2)) {
+                        mesh.quad([top[0], top[1], bottom[1], bottom[0]], *color);
+                    }
+                    mesh.end();
+                }
+                RenderCommand::ErrorBars {
+                    whiskers,
+                    style,
+                    cap_width,
+                } => {
+                    mesh.begin(clip, blend);
+                    for whisker in whiskers {
+                        for segment in whisker.with_caps(*cap_width) {
+                            mesh.line(segment, style.width.max(0.5), style.color);
+                        }
+                    }
+                    mesh.end();
+                }
+                RenderCommand::Role(_)
+                | RenderCommand::Image { .. }
+                | RenderCommand::Text { .. } => {}
+            }
+        }
+        mesh.batches.retain(|batch| batch.index_count > 0);
+        mesh
+    }
+
+    /// Mesh vertices.
+    pub fn vertices(&self) -> &[MeshVertex] {
+        &self.vertices
+    }
+
+    /// Triangle indices into [`Mesh::vertices`].
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Draw batches in painter's order.
+    pub fn batches(&self) -> &[MeshBatch] {
+        &self.batches
+    }
+
+    /// Check whether the mesh has no triangles.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    fn begin(&mut self, clip: Option<ScreenRect>, blend: BlendMode) {
+        if self
+            .batches
+            .last()
+            .is_some_and(|batch| batch.clip == clip && batch.blend == blend)
+        {
+            return;
+        }
+        self.batches.push(MeshBatch {
+            clip,
+            blend,
+            first_index: self.indices.len() as u32,
+            index_count: 0,
+        });
+    }
+
+    fn end(&mut self) {
+        let total = self.indices.len() as u32;
+        if let Some(batch) = self.batches.last_mut() {
+            batch.index_count = total - batch.first_index;
+        }
+    }
+
+    fn quad(&mut self, corners: [ScreenPoint; 4], color: Color) {
+        let base = self.vertices.len() as u32;
+        self.vertices
+            .extend(corners.iter().map(|position| MeshVertex {
+                position: *position,
+                color,
+            }));
+        self.indices
+            .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+
+    fn line(&mut self, segment: LineSegment, width: f32, color: Color) {
+        if segment.start == segment.end {
+            return;
+        }
+        self.quad(segment.to_quad(width), color);
+    }
+
+    fn marker(&mut self, center: ScreenPoint, half: f32, shape: MarkerShape, color: Color) {
+        match shape {
+            MarkerShape::Circle => {
+                let base = self.vertices.len() as u32;
+                self.vertices.push(MeshVertex {
+                    position: center,
+                    color,
+                });
+                for step in 0..CIRCLE_SEGMENTS {
+                    let angle = step as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+                    self.vertices.push(MeshVertex {
+                        position: ScreenPoint::new(
+                            center.x + half * angle.cos(),
+                            center.y + half * angle.sin(),
+                        ),
+                        color,
+                    });
+                }
+                for step in 0..CIRCLE_SEGMENTS as u32 {
+                    let next = (step + 1) % CIRCLE_SEGMENTS as u32;
+                    self.indices
+                        .extend_from_slice(&[base, base + 1 + step, base + 1 + next]);
+                }
+            }
+            MarkerShape::Square => {
+                let rect = ScreenRect::new(
+                    ScreenPoint::new(center.x - half, center.y - half),
+                    ScreenPoint::new(center.x + half, center.y + half),
+                );
+                self.quad(rect.corners(), color);
+            }
+            MarkerShape::Cross => {
+                self.line(
+                    LineSegment::new(
+                        ScreenPoint::new(center.x - half, center.y),
+                        ScreenPoint::new(center.x + half, center.y),
+                    ),
+                    1.0,
+                    color,
+                );
+                self.line(
+                    LineSegment::new(
+                        ScreenPoint::new(center.x, center.y - half),
+                        ScreenPoint::new(center.x, center.y + half),
+                    ),
+                    1.0,
+                    color,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{LineStyle, RectStyle};
+
+    #[test]
+    fn tessellates_commands_into_clip_batches() {
+        let clip = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(50.0, 50.0));
+        let mut list = RenderList::new();
+        list.push(RenderCommand::Rect {
+            rect: clip,
+            style: RectStyle {
+                fill: Color::WHITE,
+                stroke: Color::BLACK,
+                stroke_width: 0.0,
+            },
+        });
+        list.push(RenderCommand::ClipRect(clip));
+        list.push(RenderCommand::LineSegments {
+            segments: vec![
+                LineSegment::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(10.0, 10.0)),
+                LineSegment::new(ScreenPoint::new(10.0, 10.0), ScreenPoint::new(20.0, 0.0)),
+            ],
+            style: LineStyle::default(),
+        });
+        list.push(RenderCommand::Text {
+            position: ScreenPoint::new(1.0, 1.0),
+            text: "skipped".to_string(),
+            style: Default::default(),
+        });
+        list.push(RenderCommand::ClipEnd);
+        list.push(RenderCommand::Blend(BlendMode::Additive));
+        list.push(RenderCommand::Rect {
+            rect: clip,
+            style: RectStyle::default(),
+        });
+
+        let mesh = Mesh::from_render_list(&list);
+        assert_eq!(mesh.vertices().len(), 7 * 4);
+        assert_eq!(mesh.indices().len(), 7 * 6);
+        assert_eq!(mesh.batches().len(), 3);
+        assert_eq!(mesh.batches()[0].clip, None);
+        assert_eq!(mesh.batches()[1].clip, Some(clip));
+        assert_eq!(mesh.batches()[1].first_index, 6);
+        assert_eq!(mesh.batches()[1].index_count, 12);
+        assert_eq!(mesh.batches()[2].blend, BlendMode::Additive);
+        assert_eq!(mesh.batches()[2].index_count, 24);
+    }
+}
@@ -3,11 +3,36 @@
 //! These types are backend-agnostic and are used by render backends (such as the
 //! GPUI backend) to describe how plots should be drawn.
 
+mod braille;
+mod contour;
+mod font;
+mod mesh;
+mod raster;
+mod software;
+mod spline;
+
+use std::sync::Arc;
+
+use crate::datasource::ErrorBar;
 use crate::geom::{Point, ScreenPoint, ScreenRect};
 use crate::transform::Transform;
-use crate::view::Viewport;
+use crate::view::{Range, Viewport};
+
+pub use braille::BrailleCanvas;
+pub(crate) use contour::{ContourLevel, contour_levels};
+pub(crate) use font::BitmapFont;
+pub use mesh::{Mesh, MeshBatch, MeshVertex};
+pub(crate) use raster::{ScreenImage, fit_image, rasterize_grid, rasterize_heatmap};
+pub(crate) use spline::interpolate_points;
+pub use spline::{Interpolation, StepMode};
 
-/// RGBA color in linear space.
+/// RGBA color with sRGB-encoded channels.
+///
+/// Channels use the same encoding as CSS hex colors and `gpui::Rgba`, so a
+/// color built with [`Color::from_hex`] matches other UI elements using the
+/// same hex value. Alpha is linear. Use [`Color::to_linear`] and
+/// [`Color::from_linear`] when blending or when targeting a surface that
+/// expects linear values.
 ///
 /// All components are expected to be in the 0.0..=1.0 range.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -32,6 +57,197 @@ impl Color {
     pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
     /// Opaque white.
     pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+
+    /// Create an opaque color from 8-bit sRGB channels.
+    pub const fn from_rgb8(r: u8, g: u8, b: u8) -> Self {
+        Self::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0)
+    }
+
+    /// Parse a hex color such as `"#2196f3"`.
+    ///
+    /// Accepts `rgb`, `rgba`, `rrggbb` and `rrggbbaa` digits with an optional
+    /// leading `#`. Returns `None` for any other input.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        // `from_str_radix` would also accept a sign such as `+f`.
+        if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |index: usize, width: usize| -> Option<f32> {
+            let value = u8::from_str_radix(&digits[index * width..(index + 1) * width], 16).ok()?;
+            let value = if width == 1 { value * 17 } else { value };
+            Some(value as f32 / 255.0)
+        };
+        let (width, count) = match digits.len() {
+            3 => (1, 3),
+            4 => (1, 4),
+            6 => (2, 3),
+            8 => (2, 4),
+            _ => return None,
+        };
+        let alpha = if count == 4 { channel(3, width)? } else { 1.0 };
+        Some(Self::new(
+            channel(0, width)?,
+            channel(1, width)?,
+            channel(2, width)?,
+            alpha,
+        ))
+    }
+
+    /// Format as `#rrggbb`, or `#rrggbbaa` when not fully opaque.
+    pub fn to_hex(self) -> String {
+        let byte = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let (r, g, b, a) = (byte(self.r), byte(self.g), byte(self.b), byte(self.a));
+        if a == 255 {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        }
+    }
+
+    /// Categorical palette used by [`Color::palette`].
+    pub const PALETTE: [Self; 10] = [
+        Self::from_rgb8(0x4e, 0x79, 0xa7),
+        Self::from_rgb8(0xf2, 0x8e, 0x2b),
+        Self::from_rgb8(0xe1, 0x57, 0x59),
+        Self::from_rgb8(0x76, 0xb7, 0xb2),
+        Self::from_rgb8(0x59, 0xa1, 0x4f),
+        Self::from_rgb8(0xed, 0xc9, 0x48),
+        Self::from_rgb8(0xb0, 0x7a, 0xa1),
+        Self::from_rgb8(0xff, 0x9d, 0xa7),
+        Self::from_rgb8(0x9c, 0x75, 0x5f),
+        Self::from_rgb8(0xba, 0xb0, 0xac),
+    ];
+
+    /// Pick the `index`-th categorical color, cycling through
+    /// [`Color::PALETTE`].
+    ///
+    /// The palette stays readable on both the light and the dark theme.
+    pub const fn palette(index: usize) -> Self {
+        Self::PALETTE[index % Self::PALETTE.len()]
+    }
+
+    /// Convert sRGB-encoded channels to linear light, keeping alpha.
+    pub fn to_linear(self) -> Self {
+        Self::new(
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+            self.a,
+        )
+    }
+
+    /// Create a color from linear-light channels, keeping alpha.
+    pub fn from_linear(linear: Self) -> Self {
+        Self::new(
+            linear_to_srgb(linear.r),
+            linear_to_srgb(linear.g),
+            linear_to_srgb(linear.b),
+            linear.a,
+        )
+    }
+
+    /// Components as `[r, g, b, a]`, ready for a vertex or uniform buffer.
+    pub fn to_array(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// RGBA bitmap used for backdrops and image-like overlays.
+///
+/// Pixels are stored row-major with row 0 at the top of the image. Cloning is
+/// cheap because the pixel buffer is shared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RasterImage {
+    width: usize,
+    height: usize,
+    pixels: Arc<[Color]>,
+}
+
+impl RasterImage {
+    /// Create an image from row-major pixels.
+    ///
+    /// Returns `None` when the image is empty or `pixels.len()` does not match
+    /// `width * height`.
+    pub fn new(width: usize, height: usize, pixels: Vec<Color>) -> Option<Self> {
+        if width == 0 || height == 0 || width.checked_mul(height)? != pixels.len() {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            pixels: pixels.into(),
+        })
+    }
+
+    /// Create an image from tightly packed 8-bit RGBA bytes.
+    ///
+    /// Returns `None` when the byte count does not match `width * height * 4`.
+    pub fn from_rgba8(width: usize, height: usize, bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != width.checked_mul(height)?.checked_mul(4)? {
+            return None;
+        }
+        let pixels = bytes
+            .chunks_exact(4)
+            .map(|px| {
+                Color::new(
+                    px[0] as f32 / 255.0,
+                    px[1] as f32 / 255.0,
+                    px[2] as f32 / 255.0,
+                    px[3] as f32 / 255.0,
+                )
+            })
+            .collect();
+        Self::new(width, height, pixels)
+    }
+
+    /// Image width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Image height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Access a pixel by column and row.
+    pub fn pixel(&self, column: usize, row: usize) -> Option<Color> {
+        if column >= self.width || row >= self.height {
+            return None;
+        }
+        self.pixels.get(row * self.width + column).copied()
+    }
+
+    /// Whether both images share one pixel buffer.
+    pub(crate) fn shares_pixels(&self, other: &RasterImage) -> bool {
+        Arc::ptr_eq(&self.pixels, &other.pixels)
+    }
+
+    /// Pack the pixels into row-major 8-bit RGBA bytes.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        let byte = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        self.pixels
+            .iter()
+            .flat_map(|color| [byte(color.r), byte(color.g), byte(color.b), byte(color.a)])
+            .collect()
+    }
 }
 
 /// Line stroke styling.
@@ -43,6 +259,18 @@ pub struct LineStyle {
     pub color: Color,
     /// Stroke width in pixels.
     pub width: f32,
+    /// Dash pattern, or `None` for a solid stroke.
+    pub dash: Option<LineDash>,
+}
+
+impl LineStyle {
+    /// Copy the style with the default dash pattern.
+    pub fn dashed(self) -> Self {
+        Self {
+            dash: Some(LineDash::default()),
+            ..self
+        }
+    }
 }
 
 impl Default for LineStyle {
@@ -50,12 +278,32 @@ impl Default for LineStyle {
         Self {
             color: Color::BLACK,
             width: 1.0,
+            dash: None,
         }
     }
 }
 
+/// Dash pattern for a stroke, in logical pixels.
+///
+/// The pattern continues across the joints of a polyline, so dashes keep
+/// their length on curved data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineDash {
+    /// Length of each drawn dash.
+    pub on: f32,
+    /// Length of each gap.
+    pub off: f32,
+}
+
+impl Default for LineDash {
+    fn default() -> Self {
+        Self { on: 6.0, off: 4.0 }
+    }
+}
+
 /// Marker shape for scatter plots.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MarkerShape {
     /// Circle marker.
     Circle,
@@ -88,9 +336,313 @@ impl Default for MarkerStyle {
     }
 }
 
+/// Mapping from normalized scalar values to colors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Colormap {
+    /// Black to white.
+    Grayscale,
+    /// Perceptually uniform blue-green-yellow map.
+    Viridis,
+    /// Perceptually uniform blue-magenta-yellow map.
+    Plasma,
+    /// Evenly spaced color stops, interpolated linearly.
+    ///
+    /// An empty list maps every value to transparent.
+    Stops(Vec<Color>),
+}
+
+impl Colormap {
+    const VIRIDIS: [Color; 5] = [
+        Color::new(0.267, 0.005, 0.329, 1.0),
+        Color::new(0.229, 0.322, 0.546, 1.0),
+        Color::new(0.128, 0.567, 0.551, 1.0),
+        Color::new(0.369, 0.789, 0.383, 1.0),
+        Color::new(0.993, 0.906, 0.144, 1.0),
+    ];
+    const PLASMA: [Color; 5] = [
+        Color::new(0.050, 0.030, 0.528, 1.0),
+        Color::new(0.494, 0.012, 0.658, 1.0),
+        Color::new(0.798, 0.280, 0.470, 1.0),
+        Color::new(0.973, 0.585, 0.254, 1.0),
+        Color::new(0.940, 0.975, 0.131, 1.0),
+    ];
+
+    /// Sample the colormap at `t` in `0.0..=1.0` (values are clamped).
+    pub fn sample(&self, t: f32) -> Color {
+        let t = if t.is_finite() {
+            t.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        match self {
+            Self::Grayscale => Color::new(t, t, t, 1.0),
+            Self::Viridis => sample_stops(&Self::VIRIDIS, t),
+            Self::Plasma => sample_stops(&Self::PLASMA, t),
+            Self::Stops(stops) => sample_stops(stops, t),
+        }
+    }
+}
+
+fn sample_stops(stops: &[Color], t: f32) -> Color {
+    match stops {
+        [] => Color::new(0.0, 0.0, 0.0, 0.0),
+        [only] => *only,
+        _ => {
+            let scaled = t * (stops.len() - 1) as f32;
+            let index = (scaled.floor() as usize).min(stops.len() - 2);
+            let local = scaled - index as f32;
+            let a = stops[index];
+            let b = stops[index + 1];
+            Color::new(
+                a.r + (b.r - a.r) * local,
+                a.g + (b.g - a.g) * local,
+                a.b + (b.b - a.b) * local,
+                a.a + (b.a - a.a) * local,
+            )
+        }
+    }
+}
+
+/// Sampling method used when rendering gridded data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sampling {
+    /// Each grid cell is drawn as a solid block.
+    #[default]
+    Nearest,
+    /// Values are interpolated between neighboring cell centers.
+    Bilinear,
+}
+
+/// Styling for image and heatmap series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageStyle {
+    /// Colormap applied to normalized values.
+    pub colormap: Colormap,
+    /// Value range mapped onto the colormap; `None` uses the grid's finite range.
+    pub value_range: Option<Range>,
+    /// Sampling method.
+    pub sampling: Sampling,
+    /// Overall opacity in `0.0..=1.0`.
+    pub opacity: f32,
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        Self {
+            colormap: Colormap::Viridis,
+            value_range: None,
+            sampling: Sampling::Nearest,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// Arrow length scaling for vector-field series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrowScale {
+    /// Arrows span `vector * factor` in data units.
+    Data(f64),
+    /// Arrows span `magnitude * factor` pixels in the vector's screen direction.
+    Pixels(f32),
+}
+
+/// Styling for vector-field (quiver) series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuiverStyle {
+    /// Arrow color when not colored by magnitude.
+    pub color: Color,
+    /// Stroke width in pixels.
+    pub width: f32,
+    /// Arrow length scaling.
+    pub scale: ArrowScale,
+    /// Arrow head length in pixels.
+    pub head_size: f32,
+    /// Color arrows by magnitude using this colormap.
+    pub magnitude_colormap: Option<Colormap>,
+    /// Magnitude range mapped onto the colormap; `None` uses the visible range.
+    pub magnitude_range: Option<Range>,
+}
+
+impl Default for QuiverStyle {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            width: 1.0,
+            scale: ArrowScale::Data(1.0),
+            head_size: 6.0,
+            magnitude_colormap: None,
+            magnitude_range: None,
+        }
+    }
+}
+
+/// Styling for error whiskers drawn around series points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorBarStyle {
+    /// Whisker color, or `None` to use the series color.
+    pub color: Option<Color>,
+    /// Whisker stroke width in pixels.
+    pub width: f32,
+    /// Length of the caps across each whisker end in pixels; 0 draws bare
+    /// whiskers.
+    pub cap_width: f32,
+}
+
+impl Default for ErrorBarStyle {
+    fn default() -> Self {
+        Self {
+            color: None,
+            width: 1.0,
+            cap_width: 6.0,
+        }
+    }
+}
+
+/// Styling for stem series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StemStyle {
+    /// Stroke styling for the stems.
+    pub line: LineStyle,
+    /// Marker drawn on top of each stem.
+    pub marker: MarkerStyle,
+    /// Y value the stems start from.
+    pub baseline: f64,
+}
+
+impl Default for StemStyle {
+    fn default() -> Self {
+        Self {
+            line: LineStyle::default(),
+            marker: MarkerStyle::default(),
+            baseline: 0.0,
+        }
+    }
+}
+
+/// Styling for area series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AreaStyle {
+    /// Stroke styling for the line on top of the fill.
+    pub line: LineStyle,
+    /// Fill color between the line and the baseline.
+    pub fill: Color,
+    /// Y value the fill extends to.
+    ///
+    /// The baseline is not part of the series bounds, so auto-fit views
+    /// clip the fill at the plot edge when it lies outside the data.
+    pub baseline: f64,
+}
+
+impl Default for AreaStyle {
+    fn default() -> Self {
+        Self {
+            line: LineStyle::default(),
+            fill: Color::new(0.6, 0.6, 0.6, 0.35),
+            baseline: 0.0,
+        }
+    }
+}
+
+/// Styling for band series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandStyle {
+    /// Fill color between the lower and upper values.
+    pub fill: Color,
+    /// Stroke styling for the lower and upper edges, if drawn.
+    pub edges: Option<LineStyle>,
+}
+
+impl Default for BandStyle {
+    fn default() -> Self {
+        Self {
+            fill: Color::new(0.6, 0.6, 0.6, 0.35),
+            edges: None,
+        }
+    }
+}
+
+/// Styling for box-plot series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxStyle {
+    /// Stroke styling for outlines, whiskers and the median.
+    pub line: LineStyle,
+    /// Box fill color.
+    pub fill: Color,
+    /// Glyph width in data units along the X axis.
+    pub box_width: f64,
+    /// Draw a violin outline when density samples are available.
+    pub violin: bool,
+}
+
+impl Default for BoxStyle {
+    fn default() -> Self {
+        Self {
+            line: LineStyle::default(),
+            fill: Color::new(0.6, 0.6, 0.6, 0.35),
+            box_width: 0.6,
+            violin: false,
+        }
+    }
+}
+
+/// Styling for candlestick series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandleStyle {
+    /// Body and wick color of candles that closed at or above their open.
+    pub up: Color,
+    /// Body and wick color of candles that closed below their open.
+    pub down: Color,
+    /// Body width in data units along the X axis.
+    pub candle_width: f64,
+    /// Wick stroke width in pixels.
+    pub wick_width: f32,
+}
+
+impl Default for CandleStyle {
+    fn default() -> Self {
+        Self {
+            up: Color::new(0.18, 0.62, 0.36, 1.0),
+            down: Color::new(0.84, 0.26, 0.24, 1.0),
+            candle_width: 0.6,
+            wick_width: 1.0,
+        }
+    }
+}
+
+/// Styling for contour series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContourStyle {
+    /// Iso values at which contour lines are drawn.
+    pub levels: Vec<f64>,
+    /// Stroke styling for contour lines.
+    pub line: LineStyle,
+    /// Draw level labels along the contours.
+    pub show_labels: bool,
+}
+
+impl ContourStyle {
+    /// Create a contour style for the given levels.
+    pub fn new(levels: impl Into<Vec<f64>>) -> Self {
+        Self {
+            levels: levels.into(),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ContourStyle {
+    fn default() -> Self {
+        Self {
+            levels: Vec::new(),
+            line: LineStyle::default(),
+            show_labels: true,
+        }
+    }
+}
+
 /// Rectangle styling.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) struct RectStyle {
+pub struct RectStyle {
     /// Fill color.
     pub fill: Color,
     /// Stroke color.
@@ -111,7 +663,7 @@ impl Default for RectStyle {
 
 /// Text styling.
 #[derive(Debug, Clone, PartialEq)]
-pub(crate) struct TextStyle {
+pub struct TextStyle {
     /// Text color.
     pub color: Color,
     /// Font size in pixels.
@@ -129,7 +681,7 @@ impl Default for TextStyle {
 
 /// A line segment in screen space.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) struct LineSegment {
+pub struct LineSegment {
     /// Segment start.
     pub start: ScreenPoint,
     /// Segment end.
@@ -138,18 +690,70 @@ pub(crate) struct LineSegment {
 
 impl LineSegment {
     /// Create a new line segment.
-    pub(crate) fn new(start: ScreenPoint, end: ScreenPoint) -> Self {
+    pub fn new(start: ScreenPoint, end: ScreenPoint) -> Self {
         Self { start, end }
     }
+
+    /// Expand the segment into a quad of the given stroke width.
+    ///
+    /// Corners are returned as `[start_left, start_right, end_right, end_left]`
+    /// so they can be drawn as two triangles. Degenerate segments return a
+    /// collapsed quad at `start`.
+    pub fn to_quad(&self, width: f32) -> [ScreenPoint; 4] {
+        let dx = self.end.x - self.start.x;
+        let dy = self.end.y - self.start.y;
+        let length = dx.hypot(dy);
+        if length <= f32::EPSILON {
+            return [self.start; 4];
+        }
+        let nx = -dy / length * width * 0.5;
+        let ny = dx / length * width * 0.5;
+        [
+            ScreenPoint::new(self.start.x + nx, self.start.y + ny),
+            ScreenPoint::new(self.start.x - nx, self.start.y - ny),
+            ScreenPoint::new(self.end.x - nx, self.end.y - ny),
+            ScreenPoint::new(self.end.x + nx, self.end.y + ny),
+        ]
+    }
+
+    /// The segment followed by caps of `cap_width` across its start and end.
+    ///
+    /// Degenerate segments get collapsed caps at `start`.
+    pub fn with_caps(&self, cap_width: f32) -> [LineSegment; 3] {
+        let [start_left, start_right, end_right, end_left] = self.to_quad(cap_width);
+        [
+            *self,
+            LineSegment::new(start_left, start_right),
+            LineSegment::new(end_left, end_right),
+        ]
+    }
 }
 
-/// Render command list.
+/// Backend-agnostic drawing command in screen space.
+///
+/// Commands are intended for custom GPU pipelines. New variants may be added
+/// in minor releases, so matches need a wildcard arm; unknown commands can be
+/// skipped safely except that clip pushes and pops must stay balanced.
 #[derive(Debug, Clone)]
-pub(crate) enum RenderCommand {
+#[non_exhaustive]
+pub enum RenderCommand {
     /// Start clipping to a rectangle.
+    ///
+    /// Nested clips apply the intersection with every enclosing clip.
     ClipRect(ScreenRect),
     /// End clipping.
     ClipEnd,
+    /// Set the blend mode for the following draw commands.
+    ///
+    /// Backends without additive blending, including GPUI, draw everything
+    /// with [`BlendMode::Normal`].
+    Blend(BlendMode),
+    /// Set what the following draw commands show.
+    ///
+    /// Lists start in [`RenderRole::Chrome`]. Backends draw every role the
+    /// same way; exports use roles to restyle the plot without touching
+    /// series colors.
+    Role(RenderRole),
     /// Draw line segments.
     LineSegments {
         /// Segments to draw.
@@ -171,6 +775,37 @@ pub(crate) enum RenderCommand {
         /// Rectangle styling.
         style: RectStyle,
     },
+    /// Fill the polygon between two edges sampled at shared X positions.
+    ///
+    /// `upper[i]` and `lower[i]` have the same X. Each pair of neighboring
+    /// columns bounds one trapezoid; edges only cross at a column, so every
+    /// trapezoid is simple. Builders clip the polygon to the active clip.
+    FillBetween {
+        /// First edge, in increasing X order.
+        upper: Vec<ScreenPoint>,
+        /// Second edge, with the same length and X positions as `upper`.
+        lower: Vec<ScreenPoint>,
+        /// Fill color.
+        color: Color,
+    },
+    /// Draw error whiskers with caps across both ends.
+    ///
+    /// See [`LineSegment::with_caps`] for the cap geometry.
+    ErrorBars {
+        /// Whiskers from the lower to the upper error of a point.
+        whiskers: Vec<LineSegment>,
+        /// Stroke styling for whiskers and caps.
+        style: LineStyle,
+        /// Cap length in pixels; 0 draws bare whiskers.
+        cap_width: f32,
+    },
+    /// Draw a bitmap stretched over a screen rectangle.
+    Image {
+        /// Destination rectangle (may extend beyond the clip).
+        rect: ScreenRect,
+        /// Image pixels.
+        image: RasterImage,
+    },
     /// Draw text.
     Text {
         /// Text position.
@@ -182,27 +817,103 @@ pub(crate) enum RenderCommand {
     },
 }
 
-/// Aggregated render commands.
+/// What a run of draw commands shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderRole {
+    /// Backgrounds, legend, readouts and other overlays in theme colors.
+    #[default]
+    Chrome,
+    /// Grid lines.
+    Grid,
+    /// Axis lines, ticks, tick labels and axis titles.
+    Axis,
+    /// Series, backdrops, shading and annotations in their own colors.
+    Series,
+}
+
+/// How draw commands are composited over what is already drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Standard alpha blending.
+    #[default]
+    Normal,
+    /// Add the alpha-weighted color to the destination, so dense overlapping
+    /// traces brighten where they overlap instead of hiding each other.
+    Additive,
+}
+
+/// Ordered list of render commands.
+///
+/// Produced by render backends, by
+/// [`Plot::render_thumbnail`](crate::plot::Plot::render_thumbnail), and by
+/// `GpuiPlotView::last_render` (with the `gpui` feature).
+/// Commands are in painter's order and use logical pixels; see
+/// [`ScreenPoint::to_ndc`] for converting to clip space.
 #[derive(Debug, Default, Clone)]
-pub(crate) struct RenderList {
+pub struct RenderList {
     commands: Vec<RenderCommand>,
 }
 
 impl RenderList {
     /// Create an empty render list.
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self::default()
     }
 
     /// Push a render command.
-    pub(crate) fn push(&mut self, command: RenderCommand) {
+    pub fn push(&mut self, command: RenderCommand) {
         self.commands.push(command);
     }
 
     /// Access all render commands.
-    pub(crate) fn commands(&self) -> &[RenderCommand] {
+    pub fn commands(&self) -> &[RenderCommand] {
         &self.commands
     }
+
+    /// Number of commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Check whether the list has no commands.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Consume the list and return its commands.
+    pub fn into_commands(self) -> Vec<RenderCommand> {
+        self.commands
+    }
+}
+
+/// Active clip rectangles while walking a render list.
+///
+/// Each pushed rectangle is intersected with the enclosing clip, so the
+/// current clip never extends outside any of its parents.
+#[derive(Debug, Default)]
+pub(crate) struct ClipStack {
+    rects: Vec<ScreenRect>,
+}
+
+impl ClipStack {
+    /// Enter a clip rectangle.
+    pub(crate) fn push(&mut self, rect: ScreenRect) {
+        let rect = match self.current() {
+            Some(parent) => parent.intersect(rect),
+            None => rect,
+        };
+        self.rects.push(rect);
+    }
+
+    /// Leave the innermost clip rectangle.
+    pub(crate) fn pop(&mut self) {
+        self.rects.pop();
+    }
+
+    /// Resolved clip rectangle, if any clip is active.
+    pub(crate) fn current(&self) -> Option<ScreenRect> {
+        self.rects.last().copied()
+    }
 }
 
 /// Cache key for rendered series data.
@@ -240,6 +951,66 @@ pub(crate) fn build_line_segments(
     }
 }
 
+/// Build clipped vertical stems from a baseline to each data point.
+pub(crate) fn build_stem_segments(
+    points: &[Point],
+    baseline: f64,
+    transform: &Transform,
+    clip: ScreenRect,
+    out: &mut Vec<LineSegment>,
+) {
+    out.clear();
+    for point in points {
+        let Some(start) = transform.data_to_screen(Point::new(point.x, baseline)) else {
+            continue;
+        };
+        let Some(end) = transform.data_to_screen(*point) else {
+            continue;
+        };
+        if let Some((clipped_start, clipped_end)) = clip_segment(start, end, clip) {
+            out.push(LineSegment::new(clipped_start, clipped_end));
+        }
+    }
+}
+
+/// Build error whiskers for points with attached errors.
+///
+/// Each error adds a vertical whisker for a nonzero Y extent and a
+/// horizontal one for a nonzero X extent. Whiskers are left unclipped so
+/// their caps stay at the true error ends; draw them inside a clip rect.
+pub(crate) fn build_error_whiskers(
+    samples: &[(Point, ErrorBar)],
+    transform: &Transform,
+    out: &mut Vec<LineSegment>,
+) {
+    out.clear();
+    for (point, error) in samples {
+        let extent = error.extent(*point);
+        let whiskers = [
+            (extent.y.span() > 0.0).then(|| {
+                (
+                    Point::new(point.x, extent.y.min),
+                    Point::new(point.x, extent.y.max),
+                )
+            }),
+            (extent.x.span() > 0.0).then(|| {
+                (
+                    Point::new(extent.x.min, point.y),
+                    Point::new(extent.x.max, point.y),
+                )
+            }),
+        ];
+        for (start, end) in whiskers.into_iter().flatten() {
+            if let (Some(start), Some(end)) = (
+                transform.data_to_screen(start),
+                transform.data_to_screen(end),
+            ) {
+                out.push(LineSegment::new(start, end));
+            }
+        }
+    }
+}
+
 /// Build clipped scatter points from data points.
 pub(crate) fn build_scatter_points(
     points: &[Point],
@@ -262,6 +1033,159 @@ pub(crate) fn build_scatter_points(
     }
 }
 
+/// Build clipped fill polygons between two curves sampled at shared X
+/// positions.
+///
+/// Each column is `(x, y_upper, y_lower)` in data space. Non-finite columns
+/// split the fill, so each returned `(upper, lower)` pair is one polygon for
+/// [`RenderCommand::FillBetween`].
+pub(crate) fn build_fill_between(
+    columns: &[(f64, f64, f64)],
+    transform: &Transform,
+    clip: ScreenRect,
+    out: &mut Vec<(Vec<ScreenPoint>, Vec<ScreenPoint>)>,
+) {
+    out.clear();
+    let mut run: Vec<(f32, f32, f32)> = Vec::new();
+    for &(x, upper, lower) in columns {
+        let screen = transform
+            .data_to_screen(Point::new(x, upper))
+            .zip(transform.data_to_screen(Point::new(x, lower)))
+            .filter(|_| x.is_finite() && upper.is_finite() && lower.is_finite());
+        match screen {
+            Some((upper, lower)) => run.push((upper.x, upper.y, lower.y)),
+            None => {
+                push_fill_run(&run, clip, out);
+                run.clear();
+            }
+        }
+    }
+    push_fill_run(&run, clip, out);
+}
+
+fn push_fill_run(
+    run: &[(f32, f32, f32)],
+    clip: ScreenRect,
+    out: &mut Vec<(Vec<ScreenPoint>, Vec<ScreenPoint>)>,
+) {
+    let (mut upper, mut lower) = (Vec::new(), Vec::new());
+    clip_fill_run(run, clip, &mut upper, &mut lower);
+    if upper.len() >= 2 {
+        out.push((upper, lower));
+    }
+}
+
+/// Clip a run of screen-space columns to `clip`.
+///
+/// Columns are added where either edge leaves the clip vertically and where
+/// the edges cross, so clamping the Y values between columns gives the exact
+/// intersection of the polygon with the clip.
+fn clip_fill_run(
+    run: &[(f32, f32, f32)],
+    clip: ScreenRect,
+    upper: &mut Vec<ScreenPoint>,
+    lower: &mut Vec<ScreenPoint>,
+) {
+    let mut push = |(x, y_upper, y_lower): (f32, f32, f32)| {
+        let top = ScreenPoint::new(x, y_upper.clamp(clip.min.y, clip.max.y));
+        let bottom = ScreenPoint::new(x, y_lower.clamp(clip.min.y, clip.max.y));
+        if upper.last() != Some(&top) || lower.last() != Some(&bottom) {
+            upper.push(top);
+            lower.push(bottom);
+        }
+    };
+    for pair in run.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if a.0.max(b.0) < clip.min.x || a.0.min(b.0) > clip.max.x {
+            continue;
+        }
+        let dx = b.0 - a.0;
+        let (t0, t1) = if dx == 0.0 {
+            (0.0, 1.0)
+        } else {
+            let enter = ((clip.min.x - a.0) / dx).clamp(0.0, 1.0);
+            let exit = ((clip.max.x - a.0) / dx).clamp(0.0, 1.0);
+            (enter.min(exit), enter.max(exit))
+        };
+        let mut ts = vec![t0, t1];
+        for (from, to) in [(a.1, b.1), (a.2, b.2)] {
+            for level in [clip.min.y, clip.max.y] {
+                if (from - level) * (to - level) < 0.0 {
+                    ts.push((level - from) / (to - from));
+                }
+            }
+        }
+        let (gap_a, gap_b) = (a.1 - a.2, b.1 - b.2);
+        if gap_a * gap_b < 0.0 {
+            ts.push(gap_a / (gap_a - gap_b));
+        }
+        ts.retain(|t| *t >= t0 && *t <= t1);
+        ts.sort_by(f32::total_cmp);
+        for t in ts {
+            push(lerp_column(a, b, t));
+        }
+    }
+}
+
+fn lerp_column(a: (f32, f32, f32), b: (f32, f32, f32), t: f32) -> (f32, f32, f32) {
+    if t <= 0.0 {
+        return a;
+    }
+    if t >= 1.0 {
+        return b;
+    }
+    (
+        a.0 + (b.0 - a.0) * t,
+        a.1 + (b.1 - a.1) * t,
+        a.2 + (b.2 - a.2) * t,
+    )
+}
+
+/// Split connected segments into dashes.
+///
+/// The dash phase carries over between consecutive segments that share an
+/// endpoint and restarts after a gap.
+pub(crate) fn dash_segments(segments: &[LineSegment], dash: LineDash) -> Vec<LineSegment> {
+    let period = dash.on + dash.off;
+    if !(dash.on > 0.0 && dash.off >= 0.0 && period.is_finite()) {
+        return segments.to_vec();
+    }
+    let mut out = Vec::new();
+    let mut phase = 0.0_f32;
+    let mut previous_end = None;
+    for segment in segments {
+        if previous_end != Some(segment.start) {
+            phase = 0.0;
+        }
+        previous_end = Some(segment.end);
+        let dx = segment.end.x - segment.start.x;
+        let dy = segment.end.y - segment.start.y;
+        let length = (dx * dx + dy * dy).sqrt();
+        if length <= 0.0 {
+            continue;
+        }
+        let at = |distance: f32| {
+            let t = distance / length;
+            ScreenPoint::new(segment.start.x + dx * t, segment.start.y + dy * t)
+        };
+        let mut distance = 0.0;
+        while distance < length {
+            let step = if phase < dash.on {
+                dash.on - phase
+            } else {
+                period - phase
+            };
+            let next = (distance + step).min(length);
+            if phase < dash.on {
+                out.push(LineSegment::new(at(distance), at(next)));
+            }
+            phase = (phase + next - distance) % period;
+            distance = next;
+        }
+    }
+    out
+}
+
 fn clip_segment(
     mut start: ScreenPoint,
     mut end: ScreenPoint,
@@ -340,6 +1264,72 @@ mod tests {
     use crate::view::Range;
     use crate::view::Viewport;
 
+    #[test]
+    fn hex_colors_round_trip() {
+        let color = Color::from_hex("#2196f3").expect("valid hex");
+        assert_eq!(color, Color::from_rgb8(0x21, 0x96, 0xf3));
+        assert_eq!(color.to_hex(), "#2196f3");
+        assert_eq!(
+            Color::from_hex("f00").map(Color::to_hex).as_deref(),
+            Some("#ff0000")
+        );
+        assert_eq!(
+            Color::from_hex("#00000080").map(|color| color.a),
+            Some(128.0 / 255.0)
+        );
+        assert_eq!(Color::from_hex("#12345"), None);
+        assert_eq!(Color::from_hex("#gggggg"), None);
+        assert_eq!(Color::from_hex("+f0000"), None);
+        assert_eq!(Color::from_hex("#+f0"), None);
+
+        let back = Color::from_linear(color.to_linear());
+        assert!((back.g - color.g).abs() < 1e-5);
+        assert!(color.to_linear().g < color.g);
+    }
+
+    #[test]
+    fn nested_clips_intersect() {
+        let mut clips = ClipStack::default();
+        clips.push(ScreenRect::new(
+            ScreenPoint::new(0.0, 0.0),
+            ScreenPoint::new(10.0, 10.0),
+        ));
+        clips.push(ScreenRect::new(
+            ScreenPoint::new(5.0, -5.0),
+            ScreenPoint::new(20.0, 8.0),
+        ));
+        assert_eq!(
+            clips.current(),
+            Some(ScreenRect::new(
+                ScreenPoint::new(5.0, 0.0),
+                ScreenPoint::new(10.0, 8.0)
+            ))
+        );
+        clips.push(ScreenRect::new(
+            ScreenPoint::new(30.0, 30.0),
+            ScreenPoint::new(40.0, 40.0),
+        ));
+        assert!(!clips.current().is_some_and(|clip| clip.is_valid()));
+        clips.pop();
+        clips.pop();
+        assert_eq!(clips.current().map(|clip| clip.width()), Some(10.0));
+    }
+
+    #[test]
+    fn dash_phase_continues_across_joints() {
+        let a = ScreenPoint::new(0.0, 0.0);
+        let b = ScreenPoint::new(5.0, 0.0);
+        let c = ScreenPoint::new(5.0, 8.0);
+        let dash = LineDash { on: 4.0, off: 2.0 };
+        let dashes = dash_segments(&[LineSegment::new(a, b), LineSegment::new(b, c)], dash);
+        let expected = [
+            LineSegment::new(a, ScreenPoint::new(4.0, 0.0)),
+            LineSegment::new(ScreenPoint::new(5.0, 1.0), ScreenPoint::new(5.0, 5.0)),
+            LineSegment::new(ScreenPoint::new(5.0, 7.0), c),
+        ];
+        assert_eq!(dashes, expected);
+    }
+
     #[test]
     fn clip_segment_inside() {
         let rect = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(10.0, 10.0));
@@ -350,6 +1340,16 @@ mod tests {
         assert_eq!(clipped.1, end);
     }
 
+    #[test]
+    fn segment_quad_and_ndc_conversion() {
+        let segment = LineSegment::new(ScreenPoint::new(0.0, 5.0), ScreenPoint::new(10.0, 5.0));
+        let quad = segment.to_quad(2.0);
+        assert_eq!(quad[0], ScreenPoint::new(0.0, 6.0));
+        assert_eq!(quad[2], ScreenPoint::new(10.0, 4.0));
+        assert_eq!(ScreenPoint::new(0.0, 0.0).to_ndc(100.0, 50.0), [-1.0, 1.0]);
+        assert_eq!(ScreenPoint::new(50.0, 25.0).to_ndc(100.0, 50.0), [0.0, 0.0]);
+    }
+
     #[test]
     fn build_segments_with_transform() {
         let viewport = Viewport::new(Range::new(0.0, 1.0), Range::new(0.0, 1.0));
@@ -360,4 +1360,86 @@ mod tests {
         build_line_segments(&points, &transform, rect, &mut out);
         assert_eq!(out.len(), 1);
     }
+
+    #[test]
+    fn stems_start_at_baseline() {
+        let viewport = Viewport::new(Range::new(0.0, 2.0), Range::new(-1.0, 1.0));
+        let rect = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(20.0, 20.0));
+        let transform = Transform::new(viewport, rect).expect("valid transform");
+        let points = [Point::new(1.0, 1.0), Point::new(2.0, -1.0)];
+        let mut out = Vec::new();
+        build_stem_segments(&points, 0.0, &transform, rect, &mut out);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].start, ScreenPoint::new(10.0, 10.0));
+        assert_eq!(out[0].end, ScreenPoint::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn error_whiskers_get_caps_at_both_ends() {
+        let viewport = Viewport::new(Range::new(0.0, 2.0), Range::new(0.0, 2.0));
+        let rect = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(20.0, 20.0));
+        let transform = Transform::new(viewport, rect).expect("valid transform");
+        let samples = [
+            (Point::new(1.0, 1.0), ErrorBar::asymmetric(0.5, 1.0)),
+            (Point::new(0.5, 0.5), ErrorBar::default().with_x(0.5, 0.0)),
+        ];
+        let mut out = Vec::new();
+        build_error_whiskers(&samples, &transform, &mut out);
+        assert_eq!(
+            out,
+            vec![
+                LineSegment::new(ScreenPoint::new(10.0, 15.0), ScreenPoint::new(10.0, 0.0)),
+                LineSegment::new(ScreenPoint::new(0.0, 15.0), ScreenPoint::new(5.0, 15.0)),
+            ]
+        );
+        let [_, start_cap, end_cap] = out[0].with_caps(4.0);
+        assert_eq!(start_cap.start.y, 15.0);
+        assert_eq!((start_cap.start.x - start_cap.end.x).abs(), 4.0);
+        assert_eq!(end_cap.end.y, 0.0);
+    }
+
+    #[test]
+    fn fill_between_clips_to_rect_and_splits_at_crossings() {
+        let viewport = Viewport::new(Range::new(0.0, 4.0), Range::new(0.0, 4.0));
+        let rect = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(40.0, 40.0));
+        let transform = Transform::new(viewport, rect).expect("valid transform");
+        let clip = ScreenRect::new(ScreenPoint::new(5.0, 0.0), ScreenPoint::new(40.0, 30.0));
+        // The upper edge dips below the lower edge and then below the clip.
+        let columns = [
+            (0.0, 3.0, 2.0),
+            (2.0, 1.0, 2.0),
+            (f64::NAN, 0.0, 0.0),
+            (3.0, 2.0, 1.0),
+            (4.0, 0.0, 1.0),
+        ];
+        let mut out = Vec::new();
+        build_fill_between(&columns, &transform, clip, &mut out);
+        assert_eq!(out.len(), 2);
+
+        let (upper, lower) = &out[0];
+        let xs: Vec<f32> = upper.iter().map(|point| point.x).collect();
+        assert_eq!(xs, vec![5.0, 10.0, 20.0]);
+        assert_eq!(upper[1].y, lower[1].y);
+        assert!(upper.iter().chain(lower).all(|point| point.y <= 30.0));
+
+        let (upper, _) = &out[1];
+        assert_eq!(upper.first().map(|point| point.x), Some(30.0));
+        assert_eq!(upper.last().map(|point| point.y), Some(30.0));
+    }
+
+    #[test]
+    fn colormap_stops_interpolate() {
+        let map = Colormap::Stops(vec![Color::BLACK, Color::WHITE]);
+        assert_eq!(map.sample(0.5), Color::new(0.5, 0.5, 0.5, 1.0));
+        assert_eq!(map.sample(2.0), Color::WHITE);
+    }
+
+    #[test]
+    fn raster_image_validates_pixel_count() {
+        assert!(RasterImage::new(2, 2, vec![Color::WHITE; 3]).is_none());
+        let image =
+            RasterImage::from_rgba8(1, 2, &[255, 0, 0, 255, 0, 0, 255, 255]).expect("valid image");
+        assert_eq!(image.pixel(0, 1), Some(Color::new(0.0, 0.0, 1.0, 1.0)));
+        assert_eq!(image.pixel(1, 0), None);
+    }
 }
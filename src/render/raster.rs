@@ -0,0 +1,297 @@
+//! Rasterization of gridded data into bitmaps.
+
+use crate::datasource::Grid;
+use crate::geom::{ScreenPoint, ScreenRect};
+use crate::render::{Color, ImageStyle, RasterImage, Sampling};
+
+/// Upsampling factor applied per axis for bilinear sampling.
+const BILINEAR_UPSAMPLE: usize = 4;
+/// Maximum raster size per axis produced by upsampling.
+const MAX_UPSAMPLED_DIM: usize = 256;
+
+/// Convert a grid into a colored bitmap using the image style.
+///
+/// Nearest sampling yields one pixel per cell. Bilinear sampling upsamples the
+/// grid (bounded by [`MAX_UPSAMPLED_DIM`]) and interpolates between cell centers.
+/// Non-finite values are rendered transparent.
+pub(crate) fn rasterize_grid(grid: &Grid, style: &ImageStyle) -> Option<RasterImage> {
+    let color_for = value_colors(grid, style)?;
+
+    match style.sampling {
+        Sampling::Nearest => {
+            let pixels = grid
+                .values()
+                .iter()
+                .map(|value| color_for(*value))
+                .collect();
+            RasterImage::new(grid.width(), grid.height(), pixels)
+        }
+        Sampling::Bilinear => {
+            let out_w = upsampled_dim(grid.width());
+            let out_h = upsampled_dim(grid.height());
+            let scale_x = grid.width() as f32 / out_w as f32;
+            let scale_y = grid.height() as f32 / out_h as f32;
+            let mut pixels = Vec::with_capacity(out_w * out_h);
+            for row in 0..out_h {
+                let gy = (row as f32 + 0.5) * scale_y;
+                for column in 0..out_w {
+                    let gx = (column as f32 + 0.5) * scale_x;
+                    pixels.push(color_for(sample_bilinear(grid, gx, gy)));
+                }
+            }
+            RasterImage::new(out_w, out_h, pixels)
+        }
+    }
+}
+
+/// Convert the heatmap rows in `rows` into a `width` x `height` bitmap.
+///
+/// Grid rows run left to right and grid columns (Y bins) bottom to top. Each
+/// pixel shows the largest finite value of the rows and bins it covers, so
+/// narrow peaks survive downsampling; a pixel without finite values is
+/// transparent. Sizes are clamped to the number of rows and bins.
+pub(crate) fn rasterize_heatmap(
+    grid: &Grid,
+    rows: std::ops::Range<usize>,
+    width: usize,
+    height: usize,
+    style: &ImageStyle,
+) -> Option<RasterImage> {
+    let rows = rows.start..rows.end.min(grid.height());
+    if rows.is_empty() {
+        return None;
+    }
+    let color_for = value_colors(grid, style)?;
+    let bins = grid.width();
+    let width = width.clamp(1, rows.len());
+    let height = height.clamp(1, bins);
+    let values = grid.values();
+    let mut pixels = vec![Color::new(0.0, 0.0, 0.0, 0.0); width * height];
+    for column in 0..width {
+        let first = rows.start + column * rows.len() / width;
+        let last = rows.start + (column + 1) * rows.len() / width;
+        for pixel_row in 0..height {
+            let top = bins - pixel_row * bins / height;
+            let bottom = bins - (pixel_row + 1) * bins / height;
+            let peak = (first..last)
+                .flat_map(|row| &values[row * bins + bottom..row * bins + top])
+                .copied()
+                .filter(|value| value.is_finite())
+                .reduce(f32::max);
+            if let Some(peak) = peak {
+                pixels[pixel_row * width + column] = color_for(peak);
+            }
+        }
+    }
+    RasterImage::new(width, height, pixels)
+}
+
+/// Colormap lookup for the style's value range, or the grid's finite range.
+fn value_colors(grid: &Grid, style: &ImageStyle) -> Option<impl Fn(f32) -> Color> {
+    let range = style.value_range.or_else(|| grid.value_range())?;
+    let span = range.span();
+    let opacity = style.opacity.clamp(0.0, 1.0);
+    Some(move |value: f32| -> Color {
+        if !value.is_finite() {
+            return Color::new(0.0, 0.0, 0.0, 0.0);
+        }
+        let t = if span > 0.0 {
+            ((value as f64 - range.min) / span) as f32
+        } else {
+            0.5
+        };
+        let mut color = style.colormap.sample(t);
+        color.a *= opacity;
+        color
+    })
+}
+
+fn upsampled_dim(dim: usize) -> usize {
+    dim.max((dim * BILINEAR_UPSAMPLE).min(MAX_UPSAMPLED_DIM))
+}
+
+/// Sample a grid at continuous cell coordinates (cell centers at `n + 0.5`).
+pub(crate) fn sample_bilinear(grid: &Grid, gx: f32, gy: f32) -> f32 {
+    let max_x = (grid.width() - 1) as f32;
+    let max_y = (grid.height() - 1) as f32;
+    let x = (gx - 0.5).clamp(0.0, max_x);
+    let y = (gy - 0.5).clamp(0.0, max_y);
+    let x0 = x.floor() as usize;
+    let y0 = y.floor() as usize;
+    let x1 = (x0 + 1).min(grid.width() - 1);
+    let y1 = (y0 + 1).min(grid.height() - 1);
+    let tx = x - x0 as f32;
+    let ty = y - y0 as f32;
+    let value = |column, row| grid.value(column, row).unwrap_or(f32::NAN);
+    let top = value(x0, y0) * (1.0 - tx) + value(x1, y0) * tx;
+    let bottom = value(x0, y1) * (1.0 - tx) + value(x1, y1) * tx;
+    top * (1.0 - ty) + bottom * ty
+}
+
+/// Part of an image fitted to the screen by [`fit_image`].
+#[derive(Debug, Clone)]
+pub(crate) struct ScreenImage {
+    source: RasterImage,
+    /// Source columns and rows kept, then the output width and height.
+    window: [usize; 6],
+    image: RasterImage,
+}
+
+impl ScreenImage {
+    /// Approximate heap usage of the fitted pixels in bytes.
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.image.width() * self.image.height() * size_of::<Color>()
+    }
+}
+
+/// Crop `image` drawn over `rect` to the part inside `clip` and reduce it
+/// to at most one pixel per screen pixel.
+///
+/// Returns the screen rectangle of the kept source pixels and the image to
+/// draw there. Reuses `cache` while the source and the kept window are
+/// unchanged, so panning within a source pixel costs nothing.
+pub(crate) fn fit_image(
+    image: &RasterImage,
+    rect: ScreenRect,
+    clip: ScreenRect,
+    cache: &mut Option<ScreenImage>,
+) -> Option<(ScreenRect, RasterImage)> {
+    let cell_w = rect.width() / image.width() as f32;
+    let cell_h = rect.height() / image.height() as f32;
+    if !(cell_w > 0.0 && cell_h > 0.0) {
+        return None;
+    }
+    let visible = rect.intersect(clip);
+    let span = |min: f32, max: f32, origin: f32, cell: f32, count: usize| {
+        let first = ((min - origin) / cell).floor().clamp(0.0, count as f32) as usize;
+        let last = ((max - origin) / cell).ceil().clamp(0.0, count as f32) as usize;
+        (first, last)
+    };
+    let (col_start, col_end) = span(
+        visible.min.x,
+        visible.max.x,
+        rect.min.x,
+        cell_w,
+        image.width(),
+    );
+    let (row_start, row_end) = span(
+        visible.min.y,
+        visible.max.y,
+        rect.min.y,
+        cell_h,
+        image.height(),
+    );
+    if col_start >= col_end || row_start >= row_end {
+        return None;
+    }
+    let out_w = (col_end - col_start).min(visible.width().ceil().max(1.0) as usize);
+    let out_h = (row_end - row_start).min(visible.height().ceil().max(1.0) as usize);
+    let window = [col_start, col_end, row_start, row_end, out_w, out_h];
+    let screen = ScreenRect::new(
+        ScreenPoint::new(
+            rect.min.x + col_start as f32 * cell_w,
+            rect.min.y + row_start as f32 * cell_h,
+        ),
+        ScreenPoint::new(
+            rect.min.x + col_end as f32 * cell_w,
+            rect.min.y + row_end as f32 * cell_h,
+        ),
+    );
+    if let Some(cached) = cache
+        && cached.window == window
+        && cached.source.shares_pixels(image)
+    {
+        return Some((screen, cached.image.clone()));
+    }
+    let fitted = if window
+        == [
+            0,
+            image.width(),
+            0,
+            image.height(),
+            image.width(),
+            image.height(),
+        ] {
+        image.clone()
+    } else {
+        let (columns, rows) = (col_end - col_start, row_end - row_start);
+        let mut pixels = Vec::with_capacity(out_w * out_h);
+        for y in 0..out_h {
+            let row = row_start + (2 * y + 1) * rows / (2 * out_h);
+            for x in 0..out_w {
+                let column = col_start + (2 * x + 1) * columns / (2 * out_w);
+                pixels.push(image.pixel(column, row)?);
+            }
+        }
+        RasterImage::new(out_w, out_h, pixels)?
+    };
+    *cache = Some(ScreenImage {
+        source: image.clone(),
+        window,
+        image: fitted.clone(),
+    });
+    Some((screen, fitted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Colormap;
+
+    #[test]
+    fn bilinear_upsamples_and_interpolates() {
+        let grid = Grid::new(2, 1, vec![0.0, 1.0]).expect("grid");
+        let style = ImageStyle {
+            colormap: Colormap::Grayscale,
+            sampling: Sampling::Bilinear,
+            ..ImageStyle::default()
+        };
+        let image = rasterize_grid(&grid, &style).expect("raster");
+        assert_eq!(image.width(), 8);
+        assert_eq!(image.height(), 4);
+        let left = image.pixel(0, 0).expect("pixel").r;
+        let mid = image.pixel(4, 0).expect("pixel").r;
+        assert_eq!(left, 0.0);
+        assert!(mid > 0.4 && mid < 0.7);
+    }
+
+    #[test]
+    fn heatmap_keeps_peaks_and_puts_low_bins_at_bottom() {
+        // Four rows of two bins; the peak sits in the upper bin of row 1.
+        let grid = Grid::new(2, 4, vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.5, 0.0]).expect("grid");
+        let style = ImageStyle {
+            colormap: Colormap::Grayscale,
+            ..ImageStyle::default()
+        };
+        let image = rasterize_heatmap(&grid, 0..4, 2, 8, &style).expect("raster");
+        assert_eq!((image.width(), image.height()), (2, 2));
+        let value = |column, row| image.pixel(column, row).expect("pixel").r;
+        assert_eq!(value(0, 0), 1.0);
+        assert_eq!(value(0, 1), 0.0);
+        assert_eq!(value(1, 1), 0.5);
+        assert!(rasterize_heatmap(&grid, 4..4, 2, 2, &style).is_none());
+    }
+
+    #[test]
+    fn fit_image_crops_and_downsamples_to_screen() {
+        let pixels = (0..64 * 64)
+            .map(|index| Color::new((index % 64) as f32 / 63.0, 0.0, 0.0, 1.0))
+            .collect();
+        let image = RasterImage::new(64, 64, pixels).expect("image");
+        let rect = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(32.0, 32.0));
+        let clip = ScreenRect::new(ScreenPoint::new(16.0, 0.0), ScreenPoint::new(48.0, 8.0));
+        let mut cache = None;
+
+        let (screen, fitted) = fit_image(&image, rect, clip, &mut cache).expect("fitted");
+        assert_eq!(screen.min, ScreenPoint::new(16.0, 0.0));
+        assert_eq!(screen.max, ScreenPoint::new(32.0, 8.0));
+        assert_eq!((fitted.width(), fitted.height()), (16, 8));
+        assert!(fitted.pixel(0, 0).expect("pixel").r >= 32.0 / 63.0);
+
+        let (_, again) = fit_image(&image, rect, clip, &mut cache).expect("fitted");
+        assert!(again.shares_pixels(&fitted));
+
+        let (_, whole) = fit_image(&image, rect, rect, &mut None).expect("fitted");
+        assert_eq!((whole.width(), whole.height()), (32, 32));
+    }
+}
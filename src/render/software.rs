@@ -0,0 +1,247 @@
+//! CPU rasterization of render lists for headless export.
+
+#[cfg(feature = "png")]
+use std::io::{self, Write};
+
+use crate::geom::{ScreenPoint, ScreenRect};
+use crate::render::{
+    BitmapFont, BlendMode, ClipStack, Color, Mesh, MeshVertex, RasterImage, RenderCommand,
+    RenderList,
+};
+
+impl RenderList {
+    /// Rasterize the list into a `width` x `height` image without a window.
+    ///
+    /// Shapes are tessellated like [`Mesh`] and filled at pixel centers
+    /// without anti-aliasing; images are sampled nearest-neighbor. Text is
+    /// drawn with a built-in 5x7 bitmap font covering printable ASCII, and
+    /// pixels nothing draws to stay transparent. Returns `None` when either
+    /// size is zero.
+    pub fn rasterize(&self, width: usize, height: usize) -> Option<RasterImage> {
+        let mut canvas = Canvas {
+            width,
+            height,
+            pixels: vec![Color::new(0.0, 0.0, 0.0, 0.0); width.checked_mul(height)?],
+        };
+        let bounds = ScreenRect::new(
+            ScreenPoint::new(0.0, 0.0),
+            ScreenPoint::new(width as f32, height as f32),
+        );
+        let mut clips = ClipStack::default();
+        let mut blend = BlendMode::Normal;
+        for command in self.commands() {
+            let clip = clips
+                .current()
+                .map_or(bounds, |clip| clip.intersect(bounds));
+            match command {
+                RenderCommand::ClipRect(rect) => clips.push(*rect),
+                RenderCommand::ClipEnd => clips.pop(),
+                RenderCommand::Blend(mode) => blend = *mode,
+                RenderCommand::Image { rect, image } => canvas.blit(*rect, image, clip),
+                RenderCommand::Text {
+                    position,
+                    text,
+                    style,
+                } => {
+                    for dot in BitmapFont.glyph_dots(*position, text, style.size) {
+                        canvas.fill_rect(dot, style.color, clip, blend);
+                    }
+                }
+                command => {
+                    // Tessellate one command at a time to keep painter's order
+                    // with the images in between.
+                    let mut single = RenderList::new();
+                    single.push(command.clone());
+                    let mesh = Mesh::from_render_list(&single);
+                    for triangle in mesh.indices().chunks_exact(3) {
+                        let vertex = |index: u32| mesh.vertices()[index as usize];
+                        let triangle = [
+                            vertex(triangle[0]),
+                            vertex(triangle[1]),
+                            vertex(triangle[2]),
+                        ];
+                        canvas.fill_triangle(triangle, clip, blend);
+                    }
+                }
+            }
+        }
+        RasterImage::new(width, height, canvas.pixels)
+    }
+}
+
+#[cfg(feature = "png")]
+impl RasterImage {
+    /// Encode the image as an 8-bit RGBA PNG.
+    pub fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width() as u32, self.height() as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.to_rgba8())
+            .map_err(io::Error::other)
+    }
+}
+
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Canvas {
+    fn fill_triangle(&mut self, [a, b, c]: [MeshVertex; 3], clip: ScreenRect, blend: BlendMode) {
+        let area = edge(a.position, b.position, c.position);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+        let min_x = a
+            .position
+            .x
+            .min(b.position.x)
+            .min(c.position.x)
+            .max(clip.min.x);
+        let max_x = a
+            .position
+            .x
+            .max(b.position.x)
+            .max(c.position.x)
+            .min(clip.max.x);
+        let min_y = a
+            .position
+            .y
+            .min(b.position.y)
+            .min(c.position.y)
+            .max(clip.min.y);
+        let max_y = a
+            .position
+            .y
+            .max(b.position.y)
+            .max(c.position.y)
+            .min(clip.max.y);
+        for (column, row) in self.pixel_span(min_x, max_x, min_y, max_y) {
+            let center = ScreenPoint::new(column as f32 + 0.5, row as f32 + 0.5);
+            let wa = edge(b.position, c.position, center) / area;
+            let wb = edge(c.position, a.position, center) / area;
+            let wc = edge(a.position, b.position, center) / area;
+            if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                continue;
+            }
+            let color = if a.color == b.color && a.color == c.color {
+                a.color
+            } else {
+                let mix = |pick: fn(Color) -> f32| {
+                    pick(a.color) * wa + pick(b.color) * wb + pick(c.color) * wc
+                };
+                Color::new(
+                    mix(|color| color.r),
+                    mix(|color| color.g),
+                    mix(|color| color.b),
+                    mix(|color| color.a),
+                )
+            };
+            self.blend(column, row, color, blend);
+        }
+    }
+
+    fn blit(&mut self, rect: ScreenRect, image: &RasterImage, clip: ScreenRect) {
+        let (width, height) = (rect.max.x - rect.min.x, rect.max.y - rect.min.y);
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        let visible = rect.intersect(clip);
+        for (column, row) in
+            self.pixel_span(visible.min.x, visible.max.x, visible.min.y, visible.max.y)
+        {
+            let u = (column as f32 + 0.5 - rect.min.x) / width;
+            let v = (row as f32 + 0.5 - rect.min.y) / height;
+            let source_column = ((u * image.width() as f32) as usize).min(image.width() - 1);
+            let source_row = ((v * image.height() as f32) as usize).min(image.height() - 1);
+            if let Some(color) = image.pixel(source_column, source_row) {
+                self.blend(column, row, color, BlendMode::Normal);
+            }
+        }
+    }
+
+    fn fill_rect(&mut self, rect: ScreenRect, color: Color, clip: ScreenRect, blend: BlendMode) {
+        let visible = rect.intersect(clip);
+        for (column, row) in
+            self.pixel_span(visible.min.x, visible.max.x, visible.min.y, visible.max.y)
+        {
+            self.blend(column, row, color, blend);
+        }
+    }
+
+    /// Column and row of every pixel whose center falls inside the span.
+    fn pixel_span(
+        &self,
+        min_x: f32,
+        max_x: f32,
+        min_y: f32,
+        max_y: f32,
+    ) -> impl Iterator<Item = (usize, usize)> + use<> {
+        let first = |min: f32| (min - 0.5).ceil().max(0.0) as usize;
+        let end = |max: f32, size: usize| (max - 0.5).floor().clamp(-1.0, size as f32 - 1.0) + 1.0;
+        let columns = first(min_x)..end(max_x, self.width) as usize;
+        let rows = first(min_y)..end(max_y, self.height) as usize;
+        rows.flat_map(move |row| columns.clone().map(move |column| (column, row)))
+    }
+
+    fn blend(&mut self, column: usize, row: usize, source: Color, mode: BlendMode) {
+        let Some(target) = self.pixels.get_mut(row * self.width + column) else {
+            return;
+        };
+        let alpha = source.a.clamp(0.0, 1.0);
+        *target = match mode {
+            BlendMode::Normal => Color::new(
+                source.r * alpha + target.r * (1.0 - alpha),
+                source.g * alpha + target.g * (1.0 - alpha),
+                source.b * alpha + target.b * (1.0 - alpha),
+                alpha + target.a * (1.0 - alpha),
+            ),
+            BlendMode::Additive => Color::new(
+                (target.r + source.r * alpha).min(1.0),
+                (target.g + source.g * alpha).min(1.0),
+                (target.b + source.b * alpha).min(1.0),
+                (target.a + alpha).min(1.0),
+            ),
+        };
+    }
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`.
+fn edge(a: ScreenPoint, b: ScreenPoint, p: ScreenPoint) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::RectStyle;
+
+    #[test]
+    fn rasterize_fills_rects_inside_clip() {
+        let mut list = RenderList::new();
+        list.push(RenderCommand::ClipRect(ScreenRect::new(
+            ScreenPoint::new(0.0, 0.0),
+            ScreenPoint::new(2.0, 4.0),
+        )));
+        list.push(RenderCommand::Rect {
+            rect: ScreenRect::new(ScreenPoint::new(1.0, 1.0), ScreenPoint::new(4.0, 3.0)),
+            style: RectStyle {
+                fill: Color::WHITE,
+                stroke: Color::WHITE,
+                stroke_width: 0.0,
+            },
+        });
+        list.push(RenderCommand::ClipEnd);
+
+        let image = list.rasterize(4, 4).expect("image");
+        let filled: Vec<(usize, usize)> = (0..4)
+            .flat_map(|row| (0..4).map(move |column| (column, row)))
+            .filter(|&(column, row)| image.pixel(column, row) == Some(Color::WHITE))
+            .collect();
+        assert_eq!(filled, vec![(1, 1), (1, 2)]);
+        assert_eq!(image.pixel(0, 0).map(|color| color.a), Some(0.0));
+    }
+}
@@ -0,0 +1,252 @@
+//! Smooth and step interpolation for line series.
+
+use crate::geom::Point;
+
+/// Interpolation used to draw line series between data points.
+///
+/// Interpolation only affects rendering; hover and pins always use the
+/// exact data points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// Straight segments between points.
+    #[default]
+    Linear,
+    /// Monotone cubic (Fritsch-Carlson) curve that never overshoots the data.
+    ///
+    /// Falls back to straight segments when X values are not increasing.
+    MonotoneCubic,
+    /// Uniform Catmull-Rom spline through every point.
+    CatmullRom,
+    /// Staircase of horizontal runs joined by vertical jumps, for digital
+    /// signals and state changes.
+    ///
+    /// Unlike the smooth modes this applies at every zoom level. Steps are
+    /// drawn after decimation, which keeps the low and high point of every
+    /// pixel column, so each transition still shows as a jump.
+    Step(StepMode),
+}
+
+/// Where the vertical jump of a [`Interpolation::Step`] line sits between
+/// two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepMode {
+    /// Jump at the previous point, so each value covers the span before it.
+    Before,
+    /// Jump at the next point, holding each value until the next sample
+    /// (zero-order hold).
+    #[default]
+    After,
+    /// Jump halfway between the two points.
+    Mid,
+}
+
+/// Densify `points` along a smooth curve with `steps` samples per span.
+///
+/// Step modes ignore `steps` and insert one corner per span. Non-finite
+/// points split the input into runs that are interpolated independently;
+/// the break points are copied through unchanged.
+pub(crate) fn interpolate_points(
+    points: &[Point],
+    mode: Interpolation,
+    steps: usize,
+    out: &mut Vec<Point>,
+) {
+    out.clear();
+    let smooth = !matches!(mode, Interpolation::Linear | Interpolation::Step(_));
+    if mode == Interpolation::Linear || (smooth && steps < 2) {
+        out.extend_from_slice(points);
+        return;
+    }
+    let mut start = 0;
+    for (index, point) in points.iter().enumerate() {
+        if point.x.is_finite() && point.y.is_finite() {
+            continue;
+        }
+        interpolate_run(&points[start..index], mode, steps, out);
+        out.push(*point);
+        start = index + 1;
+    }
+    interpolate_run(&points[start..], mode, steps, out);
+}
+
+fn interpolate_run(run: &[Point], mode: Interpolation, steps: usize, out: &mut Vec<Point>) {
+    if let Interpolation::Step(step) = mode {
+        step_run(run, step, out);
+        return;
+    }
+    if run.len() < 3 {
+        out.extend_from_slice(run);
+        return;
+    }
+    match mode {
+        Interpolation::Linear => out.extend_from_slice(run),
+        Interpolation::MonotoneCubic => monotone_cubic(run, steps, out),
+        Interpolation::CatmullRom => catmull_rom(run, steps, out),
+        Interpolation::Step(step) => step_run(run, step, out),
+    }
+}
+
+fn step_run(run: &[Point], mode: StepMode, out: &mut Vec<Point>) {
+    let Some(first) = run.first() else {
+        return;
+    };
+    out.push(*first);
+    for pair in run.windows(2) {
+        let (p0, p1) = (pair[0], pair[1]);
+        match mode {
+            StepMode::Before => out.push(Point::new(p0.x, p1.y)),
+            StepMode::After => out.push(Point::new(p1.x, p0.y)),
+            StepMode::Mid => {
+                let mid = (p0.x + p1.x) * 0.5;
+                out.push(Point::new(mid, p0.y));
+                out.push(Point::new(mid, p1.y));
+            }
+        }
+        out.push(p1);
+    }
+}
+
+fn monotone_cubic(run: &[Point], steps: usize, out: &mut Vec<Point>) {
+    if run.windows(2).any(|pair| pair[1].x <= pair[0].x) {
+        out.extend_from_slice(run);
+        return;
+    }
+    let secants: Vec<f64> = run
+        .windows(2)
+        .map(|pair| (pair[1].y - pair[0].y) / (pair[1].x - pair[0].x))
+        .collect();
+    let mut tangents = Vec::with_capacity(run.len());
+    tangents.push(secants[0]);
+    for pair in secants.windows(2) {
+        if pair[0] * pair[1] <= 0.0 {
+            tangents.push(0.0);
+        } else {
+            tangents.push((pair[0] + pair[1]) * 0.5);
+        }
+    }
+    tangents.push(secants[secants.len() - 1]);
+
+    for (index, secant) in secants.iter().enumerate() {
+        if *secant == 0.0 {
+            tangents[index] = 0.0;
+            tangents[index + 1] = 0.0;
+            continue;
+        }
+        let a = tangents[index] / secant;
+        let b = tangents[index + 1] / secant;
+        let h = a * a + b * b;
+        if h > 9.0 {
+            let t = 3.0 / h.sqrt();
+            tangents[index] = t * a * secant;
+            tangents[index + 1] = t * b * secant;
+        }
+    }
+
+    for index in 0..run.len() - 1 {
+        let (p0, p1) = (run[index], run[index + 1]);
+        let (m0, m1) = (tangents[index], tangents[index + 1]);
+        let h = p1.x - p0.x;
+        out.push(p0);
+        for step in 1..steps {
+            let t = step as f64 / steps as f64;
+            let t2 = t * t;
+            let t3 = t2 * t;
+            let y = (2.0 * t3 - 3.0 * t2 + 1.0) * p0.y
+                + (t3 - 2.0 * t2 + t) * h * m0
+                + (-2.0 * t3 + 3.0 * t2) * p1.y
+                + (t3 - t2) * h * m1;
+            out.push(Point::new(p0.x + t * h, y));
+        }
+    }
+    out.push(run[run.len() - 1]);
+}
+
+fn catmull_rom(run: &[Point], steps: usize, out: &mut Vec<Point>) {
+    let last = run.len() - 1;
+    for index in 0..last {
+        let p0 = run[index.saturating_sub(1)];
+        let p1 = run[index];
+        let p2 = run[index + 1];
+        let p3 = run[(index + 2).min(last)];
+        out.push(p1);
+        for step in 1..steps {
+            let t = step as f64 / steps as f64;
+            let eval = |a: f64, b: f64, c: f64, d: f64| {
+                0.5 * (2.0 * b
+                    + (c - a) * t
+                    + (2.0 * a - 5.0 * b + 4.0 * c - d) * t * t
+                    + (3.0 * b - a - 3.0 * c + d) * t * t * t)
+            };
+            out.push(Point::new(
+                eval(p0.x, p1.x, p2.x, p3.x),
+                eval(p0.y, p1.y, p2.y, p3.y),
+            ));
+        }
+    }
+    out.push(run[last]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monotone_cubic_does_not_overshoot() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(3.0, 1.0),
+        ];
+        let mut out = Vec::new();
+        interpolate_points(&points, Interpolation::MonotoneCubic, 8, &mut out);
+        assert_eq!(out.len(), 3 * 8 + 1);
+        assert!(out.iter().all(|point| (0.0..=1.0).contains(&point.y)));
+        assert!(out.windows(2).all(|pair| pair[1].y >= pair[0].y));
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_points_and_keeps_breaks() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 2.0),
+            Point::new(2.0, 1.0),
+            Point::new(f64::NAN, f64::NAN),
+            Point::new(3.0, 0.0),
+            Point::new(4.0, 1.0),
+        ];
+        let mut out = Vec::new();
+        interpolate_points(&points, Interpolation::CatmullRom, 4, &mut out);
+        assert_eq!(out.len(), 2 * 4 + 1 + 1 + 2);
+        assert_eq!(out[4], points[1]);
+        assert!(out[9].x.is_nan());
+        assert_eq!(&out[10..], &points[4..]);
+    }
+
+    #[test]
+    fn steps_keep_transitions_of_decimated_square_wave() {
+        use crate::datasource::DecimationScratch;
+        use crate::series::Series;
+        use crate::view::Range;
+
+        let wave = Series::auto("wave", (0..1_000).map(|index| ((index / 100) % 2) as f64));
+        let mut scratch = DecimationScratch::default();
+        let mut out = Vec::new();
+        wave.with_store(|store| {
+            let decimated = store.decimate(Range::new(0.0, 999.0), 64, &mut scratch);
+            assert!(decimated.len() < 200);
+            interpolate_points(decimated, Interpolation::Step(StepMode::After), 0, &mut out);
+        });
+        let jumps = out
+            .windows(2)
+            .filter(|pair| pair[0].x == pair[1].x && pair[0].y != pair[1].y)
+            .count();
+        assert_eq!(jumps, 9);
+        assert!(
+            out.windows(2)
+                .all(|pair| pair[0].x == pair[1].x || pair[0].y == pair[1].y)
+        );
+    }
+}
@@ -1,19 +1,27 @@
 //! Data series configuration and storage.
 
+use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
-use crate::datasource::{AppendError, AppendOnlyData, SeriesStore};
-use crate::geom::Point;
-use crate::render::{LineStyle, MarkerStyle};
-use crate::view::Viewport;
+use crate::datasource::{
+    AppendError, AppendOnlyData, AppendPolicy, BoxStats, DataWarning, DecimationScratch, ErrorBar,
+    Grid, GridStore, Ohlc, PointId, Retention, SegmentStats, SeriesStore,
+};
+use crate::error::RecoverLock;
+use crate::geom::{Point, Vector};
+use crate::render::{
+    AreaStyle, BandStyle, BlendMode, BoxStyle, CandleStyle, Color, ContourStyle, ErrorBarStyle,
+    ImageStyle, Interpolation, LineStyle, MarkerStyle, QuiverStyle, StemStyle,
+};
+use crate::view::{Range, Viewport};
 
 static SERIES_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
 /// Unique identifier for a series.
 ///
 /// Series IDs are stable within a process and are used to bind pins to
-/// specific series and point indices.
+/// specific series and points.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SeriesId(u64);
 
@@ -23,6 +31,16 @@ impl SeriesId {
     }
 }
 
+/// Interpolation used when resampling a series onto new X values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResampleMethod {
+    /// Linear interpolation between neighbouring samples.
+    #[default]
+    Linear,
+    /// Hold the most recent sample value (zero-order hold).
+    ZeroOrderHold,
+}
+
 /// Series rendering kind.
 ///
 /// A series always has exactly one rendering kind.
@@ -32,6 +50,92 @@ pub enum SeriesKind {
     Line(LineStyle),
     /// Scatter series with styling.
     Scatter(MarkerStyle),
+    /// Image series rendering the series grid as a colored bitmap.
+    ///
+    /// See [`Series::image`].
+    Image(ImageStyle),
+    /// Heatmap fed row by row, such as a streaming spectrogram.
+    ///
+    /// Each row is one X step with a value per Y bin; only the visible rows
+    /// are drawn, reduced to the plot's pixel size. See [`Series::heatmap`].
+    Heatmap(ImageStyle),
+    /// Iso-contour lines computed from the series grid.
+    ///
+    /// See [`Series::contour`].
+    Contour(ContourStyle),
+    /// Vector field drawn as arrows at each point.
+    ///
+    /// See [`Series::quiver`].
+    Quiver(QuiverStyle),
+    /// Box-and-whisker (or violin) glyphs per category or time bucket.
+    ///
+    /// See [`Series::box_plot`].
+    Box(BoxStyle),
+    /// Vertical stems from a baseline with a marker on each point.
+    Stem(StemStyle),
+    /// Line with the region between it and a baseline filled.
+    Area(AreaStyle),
+    /// Filled region between a low and a high value at each X, such as a
+    /// min/max or ±σ envelope.
+    ///
+    /// See [`Series::band`].
+    Band(BandStyle),
+    /// Open/high/low/close candles per X bucket, merged when zoomed out.
+    ///
+    /// See [`Series::candlestick`].
+    Candlestick(CandleStyle),
+}
+
+/// Sample accepted by [`Series::auto`].
+///
+/// Bare Y values (`f64`, `f32`) get implicit X indices; `(x, y)` pairs,
+/// `[x, y]` arrays and [`Point`]s carry their own X.
+pub trait AutoSample {
+    /// Whether X values come from the sample position.
+    const INDEXED: bool;
+
+    /// Convert into a data point at position `index`.
+    fn into_point(self, index: usize) -> Point;
+}
+
+impl AutoSample for f64 {
+    const INDEXED: bool = true;
+
+    fn into_point(self, index: usize) -> Point {
+        Point::new(index as f64, self)
+    }
+}
+
+impl AutoSample for f32 {
+    const INDEXED: bool = true;
+
+    fn into_point(self, index: usize) -> Point {
+        Point::new(index as f64, self.into())
+    }
+}
+
+impl AutoSample for (f64, f64) {
+    const INDEXED: bool = false;
+
+    fn into_point(self, _index: usize) -> Point {
+        Point::new(self.0, self.1)
+    }
+}
+
+impl AutoSample for [f64; 2] {
+    const INDEXED: bool = false;
+
+    fn into_point(self, _index: usize) -> Point {
+        Point::new(self[0], self[1])
+    }
+}
+
+impl AutoSample for Point {
+    const INDEXED: bool = false;
+
+    fn into_point(self, _index: usize) -> Point {
+        self
+    }
 }
 
 /// Plot series with data storage and styling.
@@ -47,7 +151,32 @@ pub struct Series {
     name: String,
     kind: SeriesKind,
     data: Arc<RwLock<SeriesStore>>,
+    grid: Option<Arc<RwLock<GridStore>>>,
+    x_units: Option<String>,
+    y_units: Option<String>,
+    metadata: BTreeMap<String, String>,
+    interpolation: Interpolation,
+    z_order: i32,
+    blend: BlendMode,
     visible: bool,
+    hover_enabled: bool,
+    error_style: ErrorBarStyle,
+    sampler: Option<LiveSampler>,
+}
+
+/// Function behind a live-sampled series and the X range it covers.
+#[derive(Clone)]
+struct LiveSampler {
+    function: Arc<dyn Fn(f64) -> f64 + Send + Sync>,
+    domain: Range,
+}
+
+impl std::fmt::Debug for LiveSampler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LiveSampler")
+            .field("domain", &self.domain)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Series {
@@ -60,7 +189,17 @@ impl Series {
             name: name.into(),
             kind: SeriesKind::Line(LineStyle::default()),
             data: Arc::new(RwLock::new(SeriesStore::indexed())),
+            grid: None,
+            x_units: None,
+            y_units: None,
+            metadata: BTreeMap::new(),
+            interpolation: Interpolation::Linear,
+            z_order: 0,
+            blend: BlendMode::Normal,
             visible: true,
+            hover_enabled: true,
+            error_style: ErrorBarStyle::default(),
+            sampler: None,
         }
     }
 
@@ -73,7 +212,17 @@ impl Series {
             name: name.into(),
             kind: SeriesKind::Scatter(MarkerStyle::default()),
             data: Arc::new(RwLock::new(SeriesStore::indexed())),
+            grid: None,
+            x_units: None,
+            y_units: None,
+            metadata: BTreeMap::new(),
+            interpolation: Interpolation::Linear,
+            z_order: 0,
+            blend: BlendMode::Normal,
             visible: true,
+            hover_enabled: true,
+            error_style: ErrorBarStyle::default(),
+            sampler: None,
         }
     }
 
@@ -88,7 +237,17 @@ impl Series {
             name: name.into(),
             kind,
             data: Arc::new(RwLock::new(SeriesStore::with_base_chunk(data, 64))),
+            grid: None,
+            x_units: None,
+            y_units: None,
+            metadata: BTreeMap::new(),
+            interpolation: Interpolation::Linear,
+            z_order: 0,
+            blend: BlendMode::Normal,
             visible: true,
+            hover_enabled: true,
+            error_style: ErrorBarStyle::default(),
+            sampler: None,
         }
     }
 
@@ -115,9 +274,49 @@ impl Series {
         Self::with_data(name, data, kind)
     }
 
+    /// Build a series from any samples, picking the kind from the data.
+    ///
+    /// Data with non-decreasing X becomes a 1.5 px line; anything else, such
+    /// as an X/Y trajectory, becomes a scatter. The color is left at the
+    /// default so [`Series::color_by_palette`] or [`Plot::quick`] can pick
+    /// one.
+    ///
+    /// [`Plot::quick`]: crate::plot::Plot::quick
+    pub fn auto<I>(name: impl Into<String>, data: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AutoSample,
+    {
+        let points: Vec<Point> = data
+            .into_iter()
+            .enumerate()
+            .map(|(index, sample)| sample.into_point(index))
+            .collect();
+        let ordered = points
+            .iter()
+            .filter(|point| point.x.is_finite())
+            .map(|point| point.x)
+            .try_fold(f64::NEG_INFINITY, |last, x| (x >= last).then_some(x))
+            .is_some();
+        let kind = if ordered {
+            SeriesKind::Line(LineStyle {
+                width: 1.5,
+                ..LineStyle::default()
+            })
+        } else {
+            SeriesKind::Scatter(MarkerStyle::default())
+        };
+        if I::Item::INDEXED {
+            Self::from_iter_y(name, points.iter().map(|point| point.y), kind)
+        } else {
+            Self::from_iter_points(name, points, kind)
+        }
+    }
+
     /// Build a series by sampling a callback function.
     ///
-    /// The callback is sampled uniformly across `x_range`.
+    /// The callback is sampled uniformly across `x_range`; see
+    /// [`Series::from_adaptive_callback`] for curves with sharp features.
     pub fn from_explicit_callback(
         name: impl Into<String>,
         function: impl Fn(f64) -> f64,
@@ -129,6 +328,242 @@ impl Series {
         Self::with_data(name, data, kind)
     }
 
+    /// Build a series by sampling a callback adaptively.
+    ///
+    /// Samples concentrate where the function bends or changes quickly,
+    /// giving smoother curves than [`Series::from_explicit_callback`] with
+    /// fewer points. At most `max_points` samples are taken; `tolerance` is
+    /// the allowed deviation of a straight segment from the function, as a
+    /// fraction of the Y span (`1e-3` is a good start).
+    pub fn from_adaptive_callback(
+        name: impl Into<String>,
+        function: impl Fn(f64) -> f64,
+        x_range: crate::view::Range,
+        max_points: usize,
+        tolerance: f64,
+        kind: SeriesKind,
+    ) -> Self {
+        let data = AppendOnlyData::from_adaptive_callback(function, x_range, max_points, tolerance);
+        Self::with_data(name, data, kind)
+    }
+
+    /// Build a series that re-samples a function for the current view.
+    ///
+    /// The stored points are an adaptive sampling of `function` across
+    /// `x_range`, used for bounds, hover and export. When drawn, the series
+    /// samples the visible part of `x_range` again at screen resolution, so
+    /// zooming into an analytic curve never shows straight segments. The
+    /// samples are cached until the view or plot size changes.
+    pub fn from_live_callback(
+        name: impl Into<String>,
+        function: impl Fn(f64) -> f64 + Send + Sync + 'static,
+        x_range: Range,
+        kind: SeriesKind,
+    ) -> Self {
+        let data = AppendOnlyData::from_adaptive_callback(&function, x_range, 1024, 1e-3);
+        let mut series = Self::with_data(name, data, kind);
+        series.sampler = Some(LiveSampler {
+            function: Arc::new(function),
+            domain: x_range,
+        });
+        series
+    }
+
+    /// Check whether the series re-samples a function when drawn.
+    pub fn is_live_sampled(&self) -> bool {
+        self.sampler.is_some()
+    }
+
+    /// Sample the series function across `x_range` at `pixel_width`
+    /// resolution into `out`.
+    ///
+    /// One sample past each side keeps lines running to the plot edges.
+    /// Returns `false`, leaving `out` untouched, for series without a
+    /// function.
+    pub(crate) fn sample_live(
+        &self,
+        x_range: Range,
+        pixel_width: usize,
+        out: &mut Vec<Point>,
+    ) -> bool {
+        let Some(sampler) = &self.sampler else {
+            return false;
+        };
+        out.clear();
+        let pixel_width = pixel_width.max(1);
+        let step = x_range.span() / pixel_width as f64;
+        let min = (x_range.min - step).max(sampler.domain.min);
+        let max = (x_range.max + step).min(sampler.domain.max);
+        if max < min || !step.is_finite() || step <= 0.0 {
+            return true;
+        }
+        let samples = (((max - min) / step).ceil() as usize).clamp(1, pixel_width + 2);
+        for i in 0..=samples {
+            let x = if i == samples {
+                max
+            } else {
+                min + (max - min) * i as f64 / samples as f64
+            };
+            out.push(Point::new(x, (sampler.function)(x)));
+        }
+        true
+    }
+
+    /// Build a vector-field series from positions and vectors.
+    ///
+    /// Positions use explicit X values and do not need to be sorted.
+    pub fn quiver<I>(name: impl Into<String>, samples: I, style: QuiverStyle) -> Self
+    where
+        I: IntoIterator<Item = (Point, Vector)>,
+    {
+        let series = Self::with_data(name, AppendOnlyData::explicit(), SeriesKind::Quiver(style));
+        let _ = series.with_store_mut(|data| data.extend_vectors(samples));
+        series
+    }
+
+    /// Build a box-plot series from summaries placed at X positions.
+    ///
+    /// Use [`BoxStats::new`] for precomputed quartiles or
+    /// [`BoxStats::from_samples`] to summarize raw samples.
+    pub fn box_plot<I>(name: impl Into<String>, boxes: I, style: BoxStyle) -> Self
+    where
+        I: IntoIterator<Item = (f64, BoxStats)>,
+    {
+        let series = Self::with_data(name, AppendOnlyData::explicit(), SeriesKind::Box(style));
+        let _ = series.with_store_mut(|data| data.extend_boxes(boxes));
+        series
+    }
+
+    /// Build a band series from `(x, low, high)` samples.
+    ///
+    /// Each band is backed by a point at the center of its range, which is
+    /// what hover and pins report. Draw the mean as a separate line series
+    /// on top for a confidence interval plot.
+    pub fn band<I>(name: impl Into<String>, bands: I, style: BandStyle) -> Self
+    where
+        I: IntoIterator<Item = (f64, f64, f64)>,
+    {
+        let series = Self::with_data(name, AppendOnlyData::explicit(), SeriesKind::Band(style));
+        let _ = series.with_store_mut(|data| {
+            data.extend_bands(
+                bands
+                    .into_iter()
+                    .map(|(x, low, high)| (x, Range::new(low, high))),
+            )
+        });
+        series
+    }
+
+    /// Build a candlestick series from candles placed at X positions.
+    ///
+    /// Each candle is backed by a point at its close, which is what hover
+    /// and pins report. When more candles are visible than fit the plot
+    /// width, neighbors are merged into wider candles.
+    pub fn candlestick<I>(name: impl Into<String>, candles: I, style: CandleStyle) -> Self
+    where
+        I: IntoIterator<Item = (f64, Ohlc)>,
+    {
+        let series = Self::with_data(
+            name,
+            AppendOnlyData::explicit(),
+            SeriesKind::Candlestick(style),
+        );
+        let _ = series.with_store_mut(|data| data.extend_candles(candles));
+        series
+    }
+
+    /// Build an image series from a grid placed over a data-space extent.
+    ///
+    /// Grid row 0 is drawn at the top of the extent (`extent.y.max`).
+    pub fn image(name: impl Into<String>, grid: Grid, extent: Viewport, style: ImageStyle) -> Self {
+        Self::with_grid_data(name, GridStore::new(grid, extent), SeriesKind::Image(style))
+    }
+
+    /// Build a contour series from a grid placed over a data-space extent.
+    ///
+    /// Contours are computed with marching squares at the style's levels.
+    pub fn contour(
+        name: impl Into<String>,
+        grid: Grid,
+        extent: Viewport,
+        style: ContourStyle,
+    ) -> Self {
+        Self::with_grid_data(
+            name,
+            GridStore::new(grid, extent),
+            SeriesKind::Contour(style),
+        )
+    }
+
+    /// Build an empty heatmap series fed row by row with
+    /// [`Series::push_row`], such as a spectrogram.
+    ///
+    /// Each row holds `bins` values spread evenly over `y_range`, lowest Y
+    /// first, and covers the next `x_step` of the X axis, starting at X = 0
+    /// (see [`Series::with_x_origin`]).
+    pub fn heatmap(
+        name: impl Into<String>,
+        bins: usize,
+        y_range: Range,
+        x_step: f64,
+        style: ImageStyle,
+    ) -> Self {
+        Self::with_grid_data(
+            name,
+            GridStore::rows(bins, y_range, x_step),
+            SeriesKind::Heatmap(style),
+        )
+    }
+
+    /// Place the first heatmap row at `x` instead of 0.
+    pub fn with_x_origin(self, x: f64) -> Self {
+        if let Some(grid) = &self.grid {
+            grid.write_unpoisoned().set_x_origin(x);
+        }
+        self
+    }
+
+    /// Keep at most `max_rows` heatmap rows, dropping the oldest so the
+    /// display scrolls.
+    pub fn with_max_rows(self, max_rows: usize) -> Self {
+        if let Some(grid) = &self.grid {
+            grid.write_unpoisoned().set_max_rows(Some(max_rows));
+        }
+        self
+    }
+
+    /// Append a heatmap row with one value per Y bin, lowest Y first.
+    ///
+    /// Fails with [`AppendError::WrongMode`] for series not created by
+    /// [`Series::heatmap`] and with [`AppendError::RowLength`] when the row
+    /// does not match the bin count.
+    pub fn push_row(&mut self, values: &[f32]) -> Result<(), AppendError> {
+        match &self.grid {
+            Some(grid) => grid.write_unpoisoned().push_row(values),
+            None => Err(AppendError::WrongMode),
+        }
+    }
+
+    fn with_grid_data(name: impl Into<String>, grid: GridStore, kind: SeriesKind) -> Self {
+        Self {
+            id: SeriesId::next(),
+            name: name.into(),
+            kind,
+            data: Arc::new(RwLock::new(SeriesStore::indexed())),
+            grid: Some(Arc::new(RwLock::new(grid))),
+            x_units: None,
+            y_units: None,
+            metadata: BTreeMap::new(),
+            interpolation: Interpolation::Linear,
+            z_order: 0,
+            blend: BlendMode::Normal,
+            visible: true,
+            hover_enabled: true,
+            error_style: ErrorBarStyle::default(),
+            sampler: None,
+        }
+    }
+
     /// Access the series identifier.
     pub fn id(&self) -> SeriesId {
         self.id
@@ -146,10 +581,272 @@ impl Series {
 
     /// Replace the series kind.
     pub fn with_kind(mut self, kind: SeriesKind) -> Self {
+        self.set_kind(kind);
+        self
+    }
+
+    /// Switch the series kind in place.
+    ///
+    /// The series keeps its id, data, and pins; render backends drop any
+    /// kind-specific caches on the next frame.
+    pub fn set_kind(&mut self, kind: SeriesKind) {
         self.kind = kind;
+    }
+
+    /// Recolor the series.
+    ///
+    /// Sets the stroke, marker or arrow color of the current kind. Fills keep
+    /// their alpha; image and heatmap series keep their colormap and
+    /// candlestick series their up and down colors.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.set_color(color);
+        self
+    }
+
+    /// Recolor the series in place.
+    ///
+    /// See [`Series::with_color`].
+    pub fn set_color(&mut self, color: Color) {
+        let fill = |fill: Color| Color { a: fill.a, ..color };
+        match &mut self.kind {
+            SeriesKind::Line(style) => style.color = color,
+            SeriesKind::Scatter(style) => style.color = color,
+            SeriesKind::Image(_) | SeriesKind::Heatmap(_) | SeriesKind::Candlestick(_) => {}
+            SeriesKind::Contour(style) => style.line.color = color,
+            SeriesKind::Quiver(style) => style.color = color,
+            SeriesKind::Box(style) => {
+                style.line.color = color;
+                style.fill = fill(style.fill);
+            }
+            SeriesKind::Stem(style) => {
+                style.line.color = color;
+                style.marker.color = color;
+            }
+            SeriesKind::Area(style) => {
+                style.line.color = color;
+                style.fill = fill(style.fill);
+            }
+            SeriesKind::Band(style) => {
+                style.fill = fill(style.fill);
+                if let Some(edges) = &mut style.edges {
+                    edges.color = color;
+                }
+            }
+        }
+    }
+
+    /// Color the series from [`Color::palette`] by its ID.
+    ///
+    /// Series created one after another get distinct colors.
+    pub fn color_by_palette(self) -> Self {
+        let color = Color::palette(self.id.0.saturating_sub(1) as usize);
+        self.with_color(color)
+    }
+
+    /// Draw the series' lines with the default dash pattern.
+    ///
+    /// Applies to line, area, stem and band edge strokes; other kinds are
+    /// unchanged.
+    pub fn dashed(mut self) -> Self {
+        match &mut self.kind {
+            SeriesKind::Line(style) => *style = style.dashed(),
+            SeriesKind::Area(style) => style.line = style.line.dashed(),
+            SeriesKind::Stem(style) => style.line = style.line.dashed(),
+            SeriesKind::Band(style) => style.edges = style.edges.map(LineStyle::dashed),
+            _ => {}
+        }
+        self
+    }
+
+    /// Stroke, marker or arrow color of the current kind, if it has one.
+    pub(crate) fn primary_color(&self) -> Option<Color> {
+        match &self.kind {
+            SeriesKind::Line(style) => Some(style.color),
+            SeriesKind::Scatter(style) => Some(style.color),
+            SeriesKind::Image(_) | SeriesKind::Heatmap(_) | SeriesKind::Candlestick(_) => None,
+            SeriesKind::Contour(style) => Some(style.line.color),
+            SeriesKind::Quiver(style) => Some(style.color),
+            SeriesKind::Box(style) => Some(style.line.color),
+            SeriesKind::Stem(style) => Some(style.line.color),
+            SeriesKind::Area(style) => Some(style.line.color),
+            SeriesKind::Band(style) => style.edges.map(|edges| edges.color),
+        }
+    }
+
+    /// Set the interpolation used to draw line series.
+    ///
+    /// Smoothing is applied only while visible points are sparse; step modes
+    /// apply at every zoom level.
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Access the line interpolation.
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Replace the line interpolation.
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    /// Set the draw order relative to other series.
+    ///
+    /// Lower values are drawn first, underneath higher ones, and equal values
+    /// keep insertion order. The default is 0, so a negative value keeps a
+    /// filled series below lines regardless of when it was added.
+    pub fn with_z_order(mut self, z_order: i32) -> Self {
+        self.z_order = z_order;
+        self
+    }
+
+    /// Access the draw order.
+    pub fn z_order(&self) -> i32 {
+        self.z_order
+    }
+
+    /// Replace the draw order.
+    pub fn set_z_order(&mut self, z_order: i32) {
+        self.z_order = z_order;
+    }
+
+    /// Set how the series is composited over series drawn before it.
+    pub fn with_blend_mode(mut self, blend: BlendMode) -> Self {
+        self.blend = blend;
+        self
+    }
+
+    /// Access the blend mode.
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend
+    }
+
+    /// Replace the blend mode.
+    pub fn set_blend_mode(&mut self, blend: BlendMode) {
+        self.blend = blend;
+    }
+
+    /// Set how explicit-X appends handle out-of-order points.
+    pub fn with_append_policy(self, policy: AppendPolicy) -> Self {
+        self.set_append_policy(policy);
+        self
+    }
+
+    /// Access the append policy.
+    pub fn append_policy(&self) -> AppendPolicy {
+        self.with_store(|store| store.data().policy())
+    }
+
+    /// Replace the append policy.
+    ///
+    /// The policy is part of the shared data, so every handle observes it.
+    pub fn set_append_policy(&self, policy: AppendPolicy) {
+        self.with_store_mut(|store| store.set_append_policy(policy));
+    }
+
+    /// Keep at most `max_points` points, evicting the oldest ones.
+    ///
+    /// Useful for long-running telemetry where memory must stay bounded.
+    /// Pins follow their points as older points are evicted.
+    pub fn with_max_points(self, max_points: usize) -> Self {
+        let retention = Retention {
+            max_points: Some(max_points),
+            ..self.retention()
+        };
+        self.set_retention(retention);
+        self
+    }
+
+    /// Keep only points within `span` of the newest X value, evicting older
+    /// ones.
+    pub fn with_max_x_span(self, span: f64) -> Self {
+        let retention = Retention {
+            max_x_span: Some(span),
+            ..self.retention()
+        };
+        self.set_retention(retention);
+        self
+    }
+
+    /// Access the retention limits.
+    pub fn retention(&self) -> Retention {
+        self.with_store(SeriesStore::retention)
+    }
+
+    /// Replace the retention limits, evicting points beyond them right away.
+    ///
+    /// Returns the number of evicted points. Like the append policy, the
+    /// limits are part of the shared data, so every handle observes them.
+    pub fn set_retention(&self, retention: Retention) -> usize {
+        self.with_store_mut(|store| store.set_retention(retention))
+    }
+
+    /// Set the unit of the series X values.
+    pub fn with_x_units(mut self, units: impl Into<String>) -> Self {
+        self.x_units = Some(units.into());
+        self
+    }
+
+    /// Set the unit of the series Y values.
+    pub fn with_y_units(mut self, units: impl Into<String>) -> Self {
+        self.y_units = Some(units.into());
+        self
+    }
+
+    /// Access the unit of the series X values.
+    pub fn x_units(&self) -> Option<&str> {
+        self.x_units.as_deref()
+    }
+
+    /// Access the unit of the series Y values.
+    pub fn y_units(&self) -> Option<&str> {
+        self.y_units.as_deref()
+    }
+
+    /// Replace the unit of the series X values.
+    pub fn set_x_units(&mut self, units: Option<String>) {
+        self.x_units = units;
+    }
+
+    /// Replace the unit of the series Y values.
+    pub fn set_y_units(&mut self, units: Option<String>) {
+        self.y_units = units;
+    }
+
+    /// Attach a metadata entry, such as a device id or calibration note.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
         self
     }
 
+    /// Access the metadata map.
+    ///
+    /// Entries are ordered by key.
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Access a single metadata value.
+    pub fn metadata_value(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Insert or replace a metadata entry, returning the previous value.
+    pub fn set_metadata(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        self.metadata.insert(key.into(), value.into())
+    }
+
+    /// Remove a metadata entry, returning its value.
+    pub fn remove_metadata(&mut self, key: &str) -> Option<String> {
+        self.metadata.remove(key)
+    }
+
     /// Create another series handle that shares the same append-only data.
     ///
     /// The returned series receives a new [`SeriesId`], so it can coexist with
@@ -161,13 +858,34 @@ impl Series {
             name: self.name.clone(),
             kind: self.kind.clone(),
             data: Arc::clone(&self.data),
+            grid: self.grid.clone(),
+            x_units: self.x_units.clone(),
+            y_units: self.y_units.clone(),
+            metadata: self.metadata.clone(),
+            interpolation: self.interpolation,
+            z_order: self.z_order,
+            blend: self.blend,
             visible: self.visible,
+            hover_enabled: self.hover_enabled,
+            error_style: self.error_style,
+            sampler: self.sampler.clone(),
         }
     }
 
+    /// Copy the series into an independent series with a new [`SeriesId`].
+    ///
+    /// Unlike [`Series::share`], appends to either series are not visible in
+    /// the other. Plain [`Clone`] also copies the data but keeps the ID.
+    pub fn deep_clone(&self) -> Self {
+        let mut series = self.clone();
+        series.id = SeriesId::next();
+        series
+    }
+
     /// Access the underlying series store.
     pub(crate) fn with_store<R>(&self, f: impl FnOnce(&SeriesStore) -> R) -> R {
-        let data = self.data.read().expect("series data lock");
+        self.flush_pending();
+        let data = self.data.read_unpoisoned();
         f(&data)
     }
 
@@ -194,9 +912,11 @@ impl Series {
 
     /// Append multiple explicit points to a series.
     ///
-    /// Returns the number of appended points when X values stay monotonic.
-    /// If any new point has a smaller X than the previous point, all points are
-    /// still appended and [`AppendError::NonMonotonicX`] is returned.
+    /// Returns the number of appended points. Points that break X ordering are
+    /// handled by the series [`AppendPolicy`]: by default they are still
+    /// appended and [`AppendError::NonMonotonicX`] is returned, while
+    /// [`AppendPolicy::Strict`] rejects the batch with
+    /// [`AppendError::OutOfOrder`].
     pub fn extend_points<I>(&mut self, points: I) -> Result<usize, AppendError>
     where
         I: IntoIterator<Item = Point>,
@@ -204,16 +924,496 @@ impl Series {
         self.with_store_mut(|data| data.extend_points(points))
     }
 
+    /// Insert a late point into an explicit series at its sorted X position.
+    ///
+    /// Out-of-order points are buffered and merged on the next read, so the
+    /// series keeps monotonic fast paths and valid summaries.
+    pub fn insert_point(&mut self, point: Point) -> Result<(), AppendError> {
+        self.with_store_mut(|data| data.insert_point(point))
+    }
+
+    /// Replace the most recent points with corrected values.
+    ///
+    /// Replaces as many trailing points as `points` yields. Indexed series
+    /// keep their implicit X values and only take the new Y values. Returns
+    /// [`AppendError::OutOfRange`] if the series has fewer points.
+    pub fn replace_last_n<I>(&mut self, points: I) -> Result<usize, AppendError>
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        self.with_store_mut(|data| data.replace_last_n(points))
+    }
+
+    /// Replace the Y value of the point at `index`.
+    pub fn update_point(&mut self, index: usize, y: f64) -> Result<(), AppendError> {
+        self.with_store_mut(|data| data.update_point(index, y))
+    }
+
+    /// Remove all points, for example when restarting a test run.
+    ///
+    /// Indexed series restart at X = 0. Plots drop pins on the cleared points
+    /// on their next [`Plot::sync_pins`](crate::plot::Plot::sync_pins).
+    pub fn clear(&mut self) {
+        self.with_store_mut(SeriesStore::clear);
+    }
+
+    /// Remove the leading points with X below `x`.
+    ///
+    /// Returns the number of removed points. Indexed series keep the X values
+    /// of the remaining points. Pins are remapped by
+    /// [`Plot::sync_pins`](crate::plot::Plot::sync_pins).
+    pub fn truncate_before_x(&mut self, x: f64) -> usize {
+        self.with_store_mut(|data| data.truncate_before_x(x))
+    }
+
+    /// Start a new segment (for example a new test run) at the next appended
+    /// point.
+    ///
+    /// Lines are drawn with a break between segments, without inserting NaN
+    /// placeholders into the data.
+    pub fn begin_segment(&mut self) {
+        self.with_store_mut(SeriesStore::begin_segment);
+    }
+
+    /// Number of segments; a non-empty series without breaks has one.
+    pub fn segment_count(&self) -> usize {
+        self.with_store(|store| store.segments().len())
+    }
+
+    /// Per-segment summaries (index range, bounds and mean).
+    pub fn segment_stats(&self) -> Vec<SegmentStats> {
+        self.with_store(SeriesStore::segment_stats)
+    }
+
+    /// Copy the points with X inside `x_range` for off-thread analysis.
+    ///
+    /// The data lock is held only while copying, so long-running analysis on
+    /// the returned slice never blocks appends. The copy is a consistent view
+    /// of the series at one instant and is cheap to clone between threads.
+    pub fn snapshot_range(&self, x_range: Range) -> Arc<[Point]> {
+        self.with_store(|store| {
+            let data = store.data();
+            data.points()[data.range_by_x(x_range)]
+                .iter()
+                .filter(|point| point.x >= x_range.min && point.x <= x_range.max)
+                .copied()
+                .collect()
+        })
+    }
+
+    /// Copy the points of one segment, for export or per-run analysis.
+    pub fn segment_points(&self, segment: usize) -> Option<Vec<Point>> {
+        self.with_store(|store| {
+            let range = store.segments().into_iter().nth(segment)?;
+            Some(store.data().points()[range].to_vec())
+        })
+    }
+
+    /// Stable id of the point currently at `index`.
+    pub fn point_id(&self, index: usize) -> Option<PointId> {
+        self.with_store(|store| store.point_id(index))
+    }
+
+    /// Current index of the point with the given id.
+    ///
+    /// Returns `None` once the point was cleared, truncated or evicted.
+    pub fn point_index(&self, id: PointId) -> Option<usize> {
+        self.with_store(|store| store.point_index(id))
+    }
+
+    /// Look up the point with the given id.
+    pub fn point_by_id(&self, id: PointId) -> Option<Point> {
+        self.with_store(|store| store.data().point(store.point_index(id)?))
+    }
+
+    /// Total number of points removed from the front of the data.
+    pub(crate) fn trimmed_count(&self) -> u64 {
+        self.with_store(SeriesStore::trimmed)
+    }
+
+    /// Replace the gridded data and its data-space extent.
+    ///
+    /// Series created without a grid gain one. Shared handles observe the new
+    /// grid immediately. Every call advances [`Series::generation`].
+    pub fn set_grid(&mut self, grid: Grid, extent: Viewport) {
+        match &self.grid {
+            Some(store) => store.write_unpoisoned().replace(grid, extent),
+            None => {
+                let mut store = GridStore::new(grid, extent);
+                store.mark_changed();
+                self.grid = Some(Arc::new(RwLock::new(store)));
+            }
+        }
+    }
+
+    /// Access the data-space extent of the series grid, if any.
+    pub fn grid_extent(&self) -> Option<Viewport> {
+        self.with_grid(GridStore::extent)
+    }
+
+    /// Access the series grid store, if any.
+    pub(crate) fn with_grid<R>(&self, f: impl FnOnce(&GridStore) -> R) -> Option<R> {
+        let grid = self.grid.as_ref()?.read_unpoisoned();
+        Some(f(&grid))
+    }
+
+    /// Append a point with an attached vector (quiver series).
+    ///
+    /// Like [`Series::extend_points`], out-of-order points follow the series
+    /// [`AppendPolicy`].
+    pub fn push_vector(&mut self, position: Point, vector: Vector) -> Result<usize, AppendError> {
+        self.with_store_mut(|data| {
+            data.push_sample(position.x, |data| data.extend_vectors([(position, vector)]))
+        })
+    }
+
+    /// Append a box summary at an X position (box-plot series).
+    pub fn push_box(&mut self, position: f64, stats: BoxStats) -> Result<usize, AppendError> {
+        self.with_store_mut(|data| {
+            data.push_sample(position, |data| data.extend_boxes([(position, stats)]))
+        })
+    }
+
+    /// Append a low/high range at an X position (band series).
+    pub fn push_band(&mut self, x: f64, low: f64, high: f64) -> Result<usize, AppendError> {
+        self.with_store_mut(|data| {
+            data.push_sample(x, |data| data.extend_bands([(x, Range::new(low, high))]))
+        })
+    }
+
+    /// Append a point with an attached error (line or scatter series).
+    ///
+    /// Like [`Series::extend_points`], out-of-order points follow the series
+    /// [`AppendPolicy`].
+    pub fn push_point_with_error(
+        &mut self,
+        point: Point,
+        error: ErrorBar,
+    ) -> Result<usize, AppendError> {
+        self.with_store_mut(|data| {
+            data.push_sample(point.x, |data| data.extend_errors([(point, error)]))
+        })
+    }
+
+    /// Attach errors to the series' points in order, starting at the first.
+    ///
+    /// Extra errors beyond the last point are ignored.
+    pub fn with_errors<I>(mut self, errors: I) -> Self
+    where
+        I: IntoIterator<Item = ErrorBar>,
+    {
+        for (index, error) in errors.into_iter().enumerate() {
+            if !self.set_error(index, error) {
+                break;
+            }
+        }
+        self
+    }
+
+    /// Attach an error to an existing point, replacing any previous one.
+    ///
+    /// Errors are drawn as whiskers with caps on line and scatter series and
+    /// extend the series bounds. Returns `false` when the index is out of
+    /// range.
+    pub fn set_error(&mut self, index: usize, error: ErrorBar) -> bool {
+        self.with_store_mut(|data| data.set_error(index, error))
+    }
+
+    /// Access the error attached to a point index, if any.
+    pub fn error(&self, index: usize) -> Option<ErrorBar> {
+        self.with_store(|data| data.error(index))
+    }
+
+    /// Set the styling of error whiskers.
+    pub fn with_error_style(mut self, style: ErrorBarStyle) -> Self {
+        self.error_style = style;
+        self
+    }
+
+    /// Access the styling of error whiskers.
+    pub fn error_style(&self) -> ErrorBarStyle {
+        self.error_style
+    }
+
+    /// Append a candle at an X position (candlestick series).
+    pub fn push_ohlc(&mut self, x: f64, candle: Ohlc) -> Result<usize, AppendError> {
+        self.with_store_mut(|data| data.push_sample(x, |data| data.extend_candles([(x, candle)])))
+    }
+
+    /// Resample the series onto a uniform X grid with spacing `dt`.
+    ///
+    /// Grid points are aligned to multiples of `dt` so series resampled with
+    /// the same spacing line up exactly. The result is an independent series
+    /// with the same name and kind; it is empty when `dt` is not positive and
+    /// finite or the series has no finite points.
+    pub fn resample(&self, dt: f64, method: ResampleMethod) -> Series {
+        let points = self.sorted_finite_points();
+        let mut resampled = Vec::new();
+        if dt.is_finite()
+            && dt > 0.0
+            && let (Some(first), Some(last)) = (points.first(), points.last())
+        {
+            let start = (first.x / dt).ceil() as i64;
+            let end = (last.x / dt).floor() as i64;
+            let mut cursor = 0;
+            for step in start..=end {
+                let x = step as f64 * dt;
+                if let Some(y) = sample_at(&points, &mut cursor, x, method) {
+                    resampled.push(Point::new(x, y));
+                }
+            }
+        }
+        let mut series = Self::from_iter_points(self.name.clone(), resampled, self.kind.clone());
+        series.x_units = self.x_units.clone();
+        series.y_units = self.y_units.clone();
+        series
+    }
+
+    /// Sample the series value at `x`.
+    ///
+    /// Interpolates between the points around `x` with `method`. Returns
+    /// `None` outside the series X range or across a gap.
+    pub fn value_at(&self, x: f64, method: ResampleMethod) -> Option<f64> {
+        self.with_store(|store| {
+            let data = store.data();
+            let points = data.points();
+            let index = data.nearest_index_by_x(x)?;
+            let start = if points[index].x > x {
+                index.checked_sub(1)?
+            } else {
+                index
+            };
+            let window = &points[start..(start + 2).min(points.len())];
+            sample_at(window, &mut 0, x, method).filter(|y| y.is_finite())
+        })
+    }
+
+    /// Copy the series with every X value shifted by `offset`.
+    ///
+    /// The result is an independent explicit-X series that keeps the name,
+    /// kind, units and metadata.
+    pub fn shift_x(&self, offset: f64) -> Series {
+        let points = self.with_store(|store| {
+            store
+                .data()
+                .points()
+                .iter()
+                .map(|point| Point::new(point.x + offset, point.y))
+                .collect::<Vec<_>>()
+        });
+        let mut series = Self::from_iter_points(self.name.clone(), points, self.kind.clone());
+        series.x_units = self.x_units.clone();
+        series.y_units = self.y_units.clone();
+        series.metadata = self.metadata.clone();
+        series
+    }
+
+    /// Copy the points with X inside `x_range` into an independent series.
+    ///
+    /// The copy keeps the name, kind, units, metadata and draw settings but
+    /// gets a new [`SeriesId`]. Quiver vectors, box summaries, bands and
+    /// candles travel with their points; gridded series are copied whole.
+    pub fn slice_x(&self, x_range: Range) -> Series {
+        self.slice_x_with_indices(x_range).0
+    }
+
+    /// Slice by X and report the source index of every copied point.
+    pub(crate) fn slice_x_with_indices(&self, x_range: Range) -> (Series, Vec<usize>) {
+        if self.grid.is_some() {
+            let series = self.deep_clone();
+            let indices = (0..self.with_store(|store| store.data().len())).collect();
+            return (series, indices);
+        }
+        let (mut series, indices) = self.with_store(|store| {
+            let data = store.data();
+            let indices: Vec<usize> = data
+                .range_by_x(x_range)
+                .filter(|index| {
+                    data.point(*index)
+                        .is_some_and(|point| point.x >= x_range.min && point.x <= x_range.max)
+                })
+                .collect();
+            let points = indices.iter().filter_map(|index| data.point(*index));
+            let series = match &self.kind {
+                SeriesKind::Quiver(style) => Self::quiver(
+                    self.name.clone(),
+                    indices
+                        .iter()
+                        .filter_map(|index| Some((data.point(*index)?, store.vector(*index)?))),
+                    style.clone(),
+                ),
+                SeriesKind::Box(style) => Self::box_plot(
+                    self.name.clone(),
+                    indices.iter().filter_map(|index| {
+                        Some((data.point(*index)?.x, store.box_stats(*index)?.clone()))
+                    }),
+                    *style,
+                ),
+                SeriesKind::Band(style) => Self::band(
+                    self.name.clone(),
+                    indices.iter().filter_map(|index| {
+                        let band = store.band(*index)?;
+                        Some((data.point(*index)?.x, band.min, band.max))
+                    }),
+                    *style,
+                ),
+                SeriesKind::Candlestick(style) => Self::candlestick(
+                    self.name.clone(),
+                    indices
+                        .iter()
+                        .filter_map(|index| Some((data.point(*index)?.x, store.candle(*index)?))),
+                    *style,
+                ),
+                kind => Self::from_iter_points(self.name.clone(), points, kind.clone()),
+            };
+            (series, indices)
+        });
+        series.x_units = self.x_units.clone();
+        series.y_units = self.y_units.clone();
+        series.metadata = self.metadata.clone();
+        series.interpolation = self.interpolation;
+        series.z_order = self.z_order;
+        series.blend = self.blend;
+        series.visible = self.visible;
+        series.hover_enabled = self.hover_enabled;
+        series.error_style = self.error_style;
+        series.sampler = self.sampler.as_ref().and_then(|sampler| {
+            let min = sampler.domain.min.max(x_range.min);
+            let max = sampler.domain.max.min(x_range.max);
+            (min <= max).then(|| LiveSampler {
+                function: Arc::clone(&sampler.function),
+                domain: Range::new(min, max),
+            })
+        });
+        self.with_store(|store| {
+            if store.has_errors() {
+                series.with_store_mut(|copy| {
+                    for (copy_index, index) in indices.iter().enumerate() {
+                        if let Some(error) = store.error(*index) {
+                            copy.set_error(copy_index, error);
+                        }
+                    }
+                });
+            }
+        });
+        (series, indices)
+    }
+
+    /// Combine two series point-wise, aligned by X.
+    ///
+    /// The result uses this series' X values where they overlap `other`, with
+    /// `other` linearly interpolated at each X. Call it again after appends to
+    /// refresh a derived view of two live streams. Only the X unit carries
+    /// over, since `f` may change the Y unit.
+    pub fn zip_with(&self, other: &Series, f: impl Fn(f64, f64) -> f64) -> Series {
+        let points = self.sorted_finite_points();
+        let other_points = other.sorted_finite_points();
+        let mut cursor = 0;
+        let zipped = points.iter().filter_map(|point| {
+            let other_y = sample_at(&other_points, &mut cursor, point.x, ResampleMethod::Linear)?;
+            Some(Point::new(point.x, f(point.y, other_y)))
+        });
+        let mut series = Self::from_iter_points(self.name.clone(), zipped, self.kind.clone());
+        series.x_units = self.x_units.clone();
+        series
+    }
+
+    /// Collect finite points sorted by X.
+    fn sorted_finite_points(&self) -> Vec<Point> {
+        self.with_store(|store| {
+            let data = store.data();
+            let mut points: Vec<Point> = data
+                .points()
+                .iter()
+                .copied()
+                .filter(|point| point.x.is_finite() && point.y.is_finite())
+                .collect();
+            if !data.is_monotonic() {
+                points.sort_by(|a, b| a.x.total_cmp(&b.x));
+            }
+            points
+        })
+    }
+
+    /// Check whether two handles share the same underlying data.
+    pub(crate) fn shares_data_with(&self, other: &Series) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+    }
+
+    /// Render the points within `x_range` as a unicode sparkline.
+    ///
+    /// The range is split into `width` columns using the same decimation as
+    /// the plot view; each column shows the mean of its values scaled between
+    /// the lowest and highest column. Columns without data are spaces.
+    /// Returns an empty string when `width` is zero.
+    pub fn sparkline(&self, x_range: Range, width: usize) -> String {
+        let mut sums = vec![(0.0_f64, 0_usize); width];
+        if width > 0 && x_range.is_valid() {
+            let mut scratch = DecimationScratch::default();
+            self.with_store(|store| {
+                for point in store.decimate(x_range, width, &mut scratch) {
+                    if !point.x.is_finite() || !point.y.is_finite() {
+                        continue;
+                    }
+                    let t = (point.x - x_range.min) / x_range.span();
+                    if !(0.0..=1.0).contains(&t) {
+                        continue;
+                    }
+                    let column = ((t * width as f64) as usize).min(width - 1);
+                    sums[column].0 += point.y;
+                    sums[column].1 += 1;
+                }
+            });
+        }
+        let means: Vec<Option<f64>> = sums
+            .iter()
+            .map(|(sum, count)| (*count > 0).then(|| sum / *count as f64))
+            .collect();
+        let (low, high) = means
+            .iter()
+            .flatten()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| {
+                (low.min(*value), high.max(*value))
+            });
+        means
+            .iter()
+            .map(|mean| match mean {
+                Some(value) if high > low => {
+                    let level = ((value - low) / (high - low) * 7.0).round() as usize;
+                    SPARKLINE_LEVELS[level.min(7)]
+                }
+                Some(_) => SPARKLINE_LEVELS[3],
+                None => ' ',
+            })
+            .collect()
+    }
+
     /// Access the series bounds.
+    ///
+    /// Bounds cover both appended points and the grid extent.
     pub fn bounds(&self) -> Option<Viewport> {
-        self.with_store(SeriesStore::bounds)
+        let points = self.with_store(SeriesStore::bounds);
+        match (points, self.with_grid(GridStore::bounds).flatten()) {
+            (Some(points), Some(extent)) => Some(Viewport::new(
+                Range::union(points.x, extent.x)?,
+                Range::union(points.y, extent.y)?,
+            )),
+            (points, extent) => points.or(extent),
+        }
     }
 
     /// Access the series generation.
     ///
     /// This monotonically increasing value is used for render cache invalidation.
     pub fn generation(&self) -> u64 {
+        let grid_generation = self.with_grid(GridStore::generation).unwrap_or(0);
         self.with_store(SeriesStore::generation)
+            .wrapping_add(grid_generation)
+    }
+
+    /// Collect data quality warnings, such as non-finite values.
+    pub fn data_warnings(&self) -> Vec<DataWarning> {
+        self.with_store(|store| store.data().warnings())
     }
 
     /// Check if the series is visible.
@@ -226,21 +1426,94 @@ impl Series {
         self.visible = visible;
     }
 
+    /// Let the series capture hover, or disable it for reference and overlay
+    /// series such as limits or fitted curves.
+    ///
+    /// Series with hover disabled are still drawn but are skipped by the
+    /// hover readout, point pinning and pin slices.
+    pub fn with_hover_enabled(mut self, enabled: bool) -> Self {
+        self.hover_enabled = enabled;
+        self
+    }
+
+    /// Check whether the series captures hover and pins.
+    pub fn is_hover_enabled(&self) -> bool {
+        self.hover_enabled
+    }
+
+    /// Enable or disable hover and pinning for the series.
+    pub fn set_hover_enabled(&mut self, enabled: bool) {
+        self.hover_enabled = enabled;
+    }
+
+    fn flush_pending(&self) {
+        let pending = self.data.read_unpoisoned().has_pending();
+        if pending {
+            self.data.write_unpoisoned().flush_pending();
+        }
+    }
+
     fn with_store_mut<R>(&self, f: impl FnOnce(&mut SeriesStore) -> R) -> R {
-        let mut data = self.data.write().expect("series data lock");
+        let mut data = self.data.write_unpoisoned();
         f(&mut data)
     }
 }
 
 impl Clone for Series {
     fn clone(&self) -> Self {
-        let data = self.data.read().expect("series data lock").clone();
+        self.flush_pending();
+        let data = self.data.read_unpoisoned().clone();
+        let grid = self.grid.as_ref().map(|grid| {
+            let grid = grid.read_unpoisoned().clone();
+            Arc::new(RwLock::new(grid))
+        });
         Self {
             id: self.id,
             name: self.name.clone(),
             kind: self.kind.clone(),
             data: Arc::new(RwLock::new(data)),
+            grid,
+            x_units: self.x_units.clone(),
+            y_units: self.y_units.clone(),
+            metadata: self.metadata.clone(),
+            interpolation: self.interpolation,
+            z_order: self.z_order,
+            blend: self.blend,
             visible: self.visible,
+            hover_enabled: self.hover_enabled,
+            error_style: self.error_style,
+            sampler: self.sampler.clone(),
+        }
+    }
+}
+
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Sample sorted points at `x`, advancing `cursor` for monotonic queries.
+///
+/// Returns `None` outside the X range covered by `points`.
+fn sample_at(points: &[Point], cursor: &mut usize, x: f64, method: ResampleMethod) -> Option<f64> {
+    let first = points.first()?;
+    let last = points.last()?;
+    if x < first.x || x > last.x {
+        return None;
+    }
+    while *cursor + 1 < points.len() && points[*cursor + 1].x <= x {
+        *cursor += 1;
+    }
+    let left = points[*cursor];
+    let Some(right) = points.get(*cursor + 1) else {
+        return Some(left.y);
+    };
+    match method {
+        ResampleMethod::ZeroOrderHold => Some(left.y),
+        ResampleMethod::Linear => {
+            let span = right.x - left.x;
+            if span <= 0.0 {
+                return Some(left.y);
+            }
+            let t = (x - left.x) / span;
+            Some(left.y + (right.y - left.y) * t)
         }
     }
 }
@@ -249,6 +1522,29 @@ impl Clone for Series {
 mod tests {
     use super::*;
 
+    #[test]
+    fn live_series_resample_the_visible_range() {
+        let series = Series::from_live_callback(
+            "sine",
+            f64::sin,
+            Range::new(0.0, 100.0),
+            SeriesKind::Line(LineStyle::default()),
+        );
+        assert!(series.is_live_sampled());
+        assert!(series.with_store(|store| store.data().len()) <= 1024);
+
+        let mut points = Vec::new();
+        assert!(series.sample_live(Range::new(1.0, 1.001), 100, &mut points));
+        assert_eq!(points.len(), 103);
+        assert!(points.iter().all(|point| point.y == point.x.sin()));
+        assert!(points.windows(2).all(|pair| pair[0].x < pair[1].x));
+
+        let slice = series.slice_x(Range::new(90.0, 200.0));
+        assert!(slice.sample_live(Range::new(95.0, 105.0), 10, &mut points));
+        assert_eq!(points.last().map(|point| point.x), Some(100.0));
+        assert!(!Series::line("plain").sample_live(Range::new(0.0, 1.0), 10, &mut points));
+    }
+
     #[test]
     fn share_observes_appends_from_source() {
         let mut source = Series::line("shared");
@@ -262,6 +1558,45 @@ mod tests {
         assert_eq!(source.bounds(), shared.bounds());
     }
 
+    #[test]
+    fn snapshot_range_is_detached_from_appends() {
+        let mut series = Series::line("live");
+        let _ = series.extend_y([0.0, 1.0, 2.0, 3.0]);
+        let snapshot = series.snapshot_range(Range::new(1.0, 5.0));
+        let worker = std::thread::spawn({
+            let snapshot = Arc::clone(&snapshot);
+            move || snapshot.iter().map(|point| point.y).sum::<f64>()
+        });
+        let _ = series.extend_y([4.0, 5.0]);
+        assert_eq!(worker.join().expect("worker"), 6.0);
+        assert_eq!(snapshot.len(), 3);
+        assert_eq!(series.snapshot_range(Range::new(1.0, 5.0)).len(), 5);
+    }
+
+    #[test]
+    fn set_kind_keeps_identity_and_data() {
+        let mut series = Series::line("trace");
+        let _ = series.extend_y([1.0, 2.0]);
+        let id = series.id();
+
+        series.set_kind(SeriesKind::Scatter(MarkerStyle::default()));
+        assert!(matches!(series.kind(), SeriesKind::Scatter(_)));
+        assert_eq!(series.id(), id);
+        assert_eq!(series.generation(), 2);
+    }
+
+    #[test]
+    fn sparkline_scales_columns() {
+        let series = Series::from_iter_y(
+            "ramp",
+            [0.0, 1.0, 2.0, 3.0],
+            SeriesKind::Line(LineStyle::default()),
+        );
+        assert_eq!(series.sparkline(Range::new(0.0, 3.0), 4), "▁▃▆█");
+        assert_eq!(series.sparkline(Range::new(0.0, 7.0), 8), "▁▃▆█    ");
+        assert_eq!(series.sparkline(Range::new(0.0, 3.0), 0), "");
+    }
+
     #[test]
     fn clone_is_independent_copy() {
         let mut source = Series::line("sensor");
@@ -275,4 +1610,216 @@ mod tests {
         assert_eq!(source.generation(), 1);
         assert_eq!(cloned.generation(), 1);
     }
+
+    #[test]
+    fn metadata_travels_with_shared_handles() {
+        let mut series = Series::line("probe").with_metadata("device", "adc-3");
+        assert_eq!(series.set_metadata("channel", "7"), None);
+        let shared = series.share();
+        assert_eq!(shared.metadata_value("device"), Some("adc-3"));
+        assert_eq!(shared.metadata().len(), 2);
+        assert_eq!(series.remove_metadata("device").as_deref(), Some("adc-3"));
+    }
+
+    #[test]
+    fn resample_aligns_to_uniform_grid() {
+        let series = Series::from_iter_points(
+            "sensor",
+            [Point::new(0.1, 0.0), Point::new(1.1, 10.0)],
+            SeriesKind::Line(LineStyle::default()),
+        );
+        let linear = series.resample(0.5, ResampleMethod::Linear);
+        let held = series.resample(0.5, ResampleMethod::ZeroOrderHold);
+        linear.with_store(|store| {
+            assert_eq!(
+                store.data().points(),
+                &[Point::new(0.5, 4.0), Point::new(1.0, 9.0)]
+            );
+        });
+        held.with_store(|store| {
+            assert_eq!(store.data().points()[1], Point::new(1.0, 0.0));
+        });
+        assert_eq!(series.resample(0.0, ResampleMethod::Linear).bounds(), None);
+    }
+
+    #[test]
+    fn zip_with_interpolates_other_series() {
+        let a = Series::from_iter_y("a", [1.0, 1.0, 1.0], SeriesKind::Line(LineStyle::default()));
+        let b = Series::from_iter_points(
+            "b",
+            [Point::new(0.5, 0.0), Point::new(2.5, 4.0)],
+            SeriesKind::Line(LineStyle::default()),
+        );
+        let diff = a.zip_with(&b, |a, b| a - b);
+        diff.with_store(|store| {
+            assert_eq!(
+                store.data().points(),
+                &[Point::new(1.0, 0.0), Point::new(2.0, -2.0)]
+            );
+        });
+    }
+
+    #[test]
+    fn image_series_bounds_follow_grid_extent() {
+        let extent = Viewport::new(Range::new(-1.0, 1.0), Range::new(0.0, 4.0));
+        let grid = Grid::from_fn(4, 4, |column, row| (column * row) as f32);
+        let mut series = Series::image("frame", grid.clone(), extent, ImageStyle::default());
+        assert_eq!(series.bounds(), Some(extent));
+
+        let moved = Viewport::new(Range::new(0.0, 2.0), Range::new(0.0, 2.0));
+        series.set_grid(grid, moved);
+        assert_eq!(series.bounds(), Some(moved));
+        assert_eq!(series.generation(), 1);
+    }
+
+    #[test]
+    fn set_grid_on_series_without_grid_advances_generation() {
+        let extent = Viewport::new(Range::new(0.0, 4.0), Range::new(0.0, 4.0));
+        let mut series = Series::line("line");
+        assert_eq!(series.generation(), 0);
+        series.set_grid(Grid::from_fn(4, 4, |column, _| column as f32), extent);
+        assert_eq!(series.bounds(), Some(extent));
+        assert_eq!(series.generation(), 1);
+    }
+
+    #[test]
+    fn heatmap_rows_scroll_and_extend_bounds() {
+        let spectrum = Range::new(0.0, 500.0);
+        let mut series = Series::heatmap("spectrum", 3, spectrum, 0.5, ImageStyle::default())
+            .with_x_origin(10.0)
+            .with_max_rows(2);
+        assert_eq!(series.bounds(), None);
+        assert_eq!(
+            series.push_row(&[1.0, 2.0]),
+            Err(AppendError::RowLength {
+                expected: 3,
+                found: 2
+            })
+        );
+        for row in 0..3 {
+            series.push_row(&[row as f32; 3]).expect("row");
+        }
+        assert_eq!(
+            series.bounds(),
+            Some(Viewport::new(Range::new(10.5, 11.5), spectrum))
+        );
+        assert_eq!(
+            series.with_grid(|store| store.grid().values().to_vec()),
+            Some(vec![1.0, 1.0, 1.0, 2.0, 2.0, 2.0])
+        );
+        assert_eq!(
+            Series::line("trace").push_row(&[1.0]),
+            Err(AppendError::WrongMode)
+        );
+    }
+
+    #[test]
+    fn errors_extend_bounds_and_follow_their_points() {
+        let mut series = Series::from_iter_points(
+            "fit",
+            [Point::new(0.0, 1.0), Point::new(2.0, 3.0)],
+            SeriesKind::Scatter(MarkerStyle::default()),
+        )
+        .with_append_policy(AppendPolicy::SortOnInsert)
+        .with_errors([ErrorBar::asymmetric(2.0, 0.5)]);
+        assert_eq!(series.error(0), Some(ErrorBar::asymmetric(2.0, 0.5)));
+        assert_eq!(series.error(1), None);
+
+        let late = ErrorBar::symmetric(1.0).with_x(0.25, 0.25);
+        assert_eq!(
+            series.push_point_with_error(Point::new(1.0, 4.0), late),
+            Ok(1)
+        );
+        assert_eq!(series.error(0), Some(ErrorBar::asymmetric(2.0, 0.5)));
+        assert_eq!(series.error(1), Some(late));
+        assert_eq!(
+            series.bounds(),
+            Some(Viewport::new(Range::new(0.0, 2.0), Range::new(-1.0, 5.0)))
+        );
+
+        let slice = series.slice_x(Range::new(0.5, 2.0));
+        assert_eq!(slice.error(0), Some(late));
+        assert_eq!(slice.error(1), None);
+    }
+
+    #[test]
+    fn auto_picks_kind_from_x_order() {
+        let mut trace = Series::auto("trace", [1.0, 3.0, 2.0]);
+        assert!(matches!(trace.kind(), SeriesKind::Line(style) if style.width == 1.5));
+        assert_eq!(trace.push_y(4.0), Ok(3));
+
+        let path = Series::auto("path", [(0.0, 0.0), (1.0, 1.0), (0.5, 2.0)]).dashed();
+        assert!(matches!(path.kind(), SeriesKind::Scatter(_)));
+
+        let plot = crate::plot::Plot::quick([trace, Series::auto("b", [0.0_f32]).dashed(), path]);
+        let colors: Vec<_> = plot.series().iter().map(Series::primary_color).collect();
+        let expected: Vec<_> = (0..3).map(|index| Some(Color::palette(index))).collect();
+        assert_eq!(colors, expected);
+        assert!(matches!(plot.series()[1].kind(), SeriesKind::Line(style) if style.dash.is_some()));
+    }
+
+    /// Push 200 samples through `push` into a series keeping 32 points and
+    /// check each returned index against the stored sample.
+    fn check_retained_push_indices(
+        series: Series,
+        mut push: impl FnMut(&mut Series, f64) -> Result<usize, AppendError>,
+        attached: impl Fn(&SeriesStore, usize) -> Option<f64>,
+    ) {
+        let mut series = series.with_max_points(32);
+        for step in 0..200 {
+            let x = step as f64;
+            let index = push(&mut series, x).expect("push");
+            series.with_store(|store| {
+                assert_eq!(store.data().point(index).map(|point| point.x), Some(x));
+                assert_eq!(attached(store, index), Some(x));
+            });
+        }
+    }
+
+    #[test]
+    fn push_vector_index_survives_retention() {
+        check_retained_push_indices(
+            Series::quiver("q", [], QuiverStyle::default()),
+            |series, x| series.push_vector(Point::new(x, 0.0), Vector::new(x, 1.0)),
+            |store, index| store.vector(index).map(|vector| vector.dx),
+        );
+    }
+
+    #[test]
+    fn push_box_index_survives_retention() {
+        check_retained_push_indices(
+            Series::box_plot("b", [], BoxStyle::default()),
+            |series, x| series.push_box(x, BoxStats::new(0.0, x, x, x, 2.0 * x)),
+            |store, index| store.box_stats(index).map(|stats| stats.q1),
+        );
+    }
+
+    #[test]
+    fn push_band_index_survives_retention() {
+        check_retained_push_indices(
+            Series::band("b", [], BandStyle::default()),
+            |series, x| series.push_band(x, x, x + 1.0),
+            |store, index| store.band(index).map(|band| band.min),
+        );
+    }
+
+    #[test]
+    fn push_point_with_error_index_survives_retention() {
+        check_retained_push_indices(
+            Series::from_iter_points("e", [], SeriesKind::Line(LineStyle::default())),
+            |series, x| {
+                series.push_point_with_error(Point::new(x, 0.0), ErrorBar::symmetric(x + 1.0))
+            },
+            |store, index| store.error(index).map(|error| error.y_plus - 1.0),
+        );
+    }
+
+    #[test]
+    fn push_ohlc_index_survives_retention() {
+        check_retained_push_indices(
+            Series::candlestick("c", [], CandleStyle::default()),
+            |series, x| series.push_ohlc(x, Ohlc::new(x, x + 1.0, x - 1.0, x)),
+            |store, index| store.candle(index).map(|candle| candle.open),
+        );
+    }
 }
@@ -0,0 +1,429 @@
+//! Declarative plot specifications.
+//!
+//! A [`PlotSpec`] describes a plot's theme, axes, view mode, series styling
+//! and shaded annotations as plain data. Specs can be written in code or,
+//! with the `serde` feature, parsed from JSON ([`PlotSpec::from_json`]) and,
+//! with the `toml` feature, from TOML ([`PlotSpec::from_toml`]), so
+//! dashboards can be configured from files without recompiling.
+//!
+//! [`PlotSpec::build`] materializes the spec into a [`Plot`] with empty
+//! series. Feed them by name through [`Plot::series`] and [`Series::share`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::axis::{AxisConfig, TimeTicks};
+use crate::plot::Plot;
+use crate::render::{Color, Interpolation, LineStyle, MarkerShape, MarkerStyle};
+use crate::series::{Series, SeriesKind};
+use crate::style::{ShadedRegion, ShadingProvider, Theme};
+use crate::view::{Headroom, Range, View};
+
+/// Declarative description of a plot.
+///
+/// Every field has a default, so a spec file only needs the parts it
+/// changes. Colors are hex strings accepted by [`Color::from_hex`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PlotSpec {
+    /// Base theme palette.
+    pub theme: ThemeSpec,
+    /// Initial view mode.
+    pub view: View,
+    /// Y headroom used by [`View::FollowLastNXY`].
+    pub follow_headroom: Option<Headroom>,
+    /// X axis settings.
+    pub x_axis: AxisSpec,
+    /// Y axis settings.
+    pub y_axis: AxisSpec,
+    /// Series in insertion order.
+    pub series: Vec<SeriesSpec>,
+    /// Shaded annotations drawn behind the data.
+    pub annotations: Vec<AnnotationSpec>,
+}
+
+impl PlotSpec {
+    /// Parse a spec from JSON.
+    #[cfg(feature = "serde")]
+    pub fn from_json(text: &str) -> Result<Self, SpecError> {
+        serde_json::from_str(text).map_err(|err| SpecError::Parse(err.to_string()))
+    }
+
+    /// Parse a spec from TOML.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(text: &str) -> Result<Self, SpecError> {
+        toml::from_str(text).map_err(|err| SpecError::Parse(err.to_string()))
+    }
+
+    /// Materialize the spec into a plot with one empty series per entry.
+    ///
+    /// All colors are validated before anything is built.
+    pub fn build(&self) -> Result<Plot, SpecError> {
+        let mut builder = Plot::builder()
+            .theme(self.theme.theme())
+            .view(self.view)
+            .x_axis(self.x_axis.config())
+            .y_axis(self.y_axis.config());
+        if let Some(headroom) = self.follow_headroom {
+            builder = builder.follow_headroom(headroom);
+        }
+        for annotation in &self.annotations {
+            builder = builder.shading(annotation.provider()?);
+        }
+        let series = self
+            .series
+            .iter()
+            .map(SeriesSpec::series)
+            .collect::<Result<Vec<_>, _>>()?;
+        for series in &series {
+            builder = builder.series(series);
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Base theme palette named in a [`PlotSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ThemeSpec {
+    /// [`Theme::dark`].
+    #[default]
+    Dark,
+    /// [`Theme::light`].
+    Light,
+    /// [`Theme::print`].
+    Print,
+}
+
+impl ThemeSpec {
+    fn theme(self) -> Theme {
+        match self {
+            ThemeSpec::Dark => Theme::dark(),
+            ThemeSpec::Light => Theme::light(),
+            ThemeSpec::Print => Theme::print(),
+        }
+    }
+}
+
+/// Axis settings in a [`PlotSpec`].
+///
+/// Unset options keep the [`AxisConfig`] defaults.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AxisSpec {
+    /// Axis title.
+    pub title: Option<String>,
+    /// Axis units.
+    pub units: Option<String>,
+    /// Treat values as Unix timestamps in seconds and use calendar ticks.
+    pub time: bool,
+    /// Show major grid lines.
+    pub grid: Option<bool>,
+    /// Show minor grid lines.
+    pub minor_grid: Option<bool>,
+    /// Show the zero line.
+    pub zero_line: Option<bool>,
+    /// Show the axis border.
+    pub border: Option<bool>,
+    /// Tick label font size.
+    pub label_size: Option<f32>,
+}
+
+impl AxisSpec {
+    fn config(&self) -> AxisConfig {
+        let mut builder = AxisConfig::builder();
+        if self.time {
+            builder = builder.time_ticks(TimeTicks::new());
+        }
+        if let Some(title) = &self.title {
+            builder = builder.title(title.clone());
+        }
+        if let Some(units) = &self.units {
+            builder = builder.units(units.clone());
+        }
+        if let Some(grid) = self.grid {
+            builder = builder.grid(grid);
+        }
+        if let Some(minor_grid) = self.minor_grid {
+            builder = builder.minor_grid(minor_grid);
+        }
+        if let Some(zero_line) = self.zero_line {
+            builder = builder.zero_line(zero_line);
+        }
+        if let Some(border) = self.border {
+            builder = builder.border(border);
+        }
+        if let Some(size) = self.label_size {
+            builder = builder.label_size(size);
+        }
+        builder.build()
+    }
+}
+
+/// Series kind named in a [`SeriesSpec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SeriesKindSpec {
+    /// Line series.
+    #[default]
+    Line,
+    /// Scatter series.
+    Scatter,
+}
+
+/// Series styling in a [`PlotSpec`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SeriesSpec {
+    /// Series name, used to find the series after building.
+    pub name: String,
+    /// Series kind.
+    pub kind: SeriesKindSpec,
+    /// Stroke or marker color as a hex string.
+    pub color: Option<String>,
+    /// Line width in pixels.
+    pub width: Option<f32>,
+    /// Marker size in pixels.
+    pub marker_size: Option<f32>,
+    /// Marker shape.
+    pub marker_shape: Option<MarkerShape>,
+    /// Line interpolation.
+    pub interpolation: Interpolation,
+    /// Draw order; higher values draw on top.
+    pub z_order: i32,
+    /// Start hidden.
+    pub hidden: bool,
+    /// X units.
+    pub x_units: Option<String>,
+    /// Y units.
+    pub y_units: Option<String>,
+    /// Host metadata.
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl SeriesSpec {
+    fn series(&self) -> Result<Series, SpecError> {
+        let color = self
+            .color
+            .as_deref()
+            .map(|hex| parse_color(hex, &self.name))
+            .transpose()?;
+        let kind = match self.kind {
+            SeriesKindSpec::Line => {
+                let mut style = LineStyle::default();
+                style.color = color.unwrap_or(style.color);
+                style.width = self.width.unwrap_or(style.width);
+                SeriesKind::Line(style)
+            }
+            SeriesKindSpec::Scatter => {
+                let mut style = MarkerStyle::default();
+                style.color = color.unwrap_or(style.color);
+                style.size = self.marker_size.unwrap_or(style.size);
+                style.shape = self.marker_shape.unwrap_or(style.shape);
+                SeriesKind::Scatter(style)
+            }
+        };
+        let mut series = Series::line(self.name.clone())
+            .with_kind(kind)
+            .with_interpolation(self.interpolation)
+            .with_z_order(self.z_order);
+        series.set_x_units(self.x_units.clone());
+        series.set_y_units(self.y_units.clone());
+        for (key, value) in &self.metadata {
+            series = series.with_metadata(key.clone(), value.clone());
+        }
+        series.set_visible(!self.hidden);
+        Ok(series)
+    }
+}
+
+/// Shaded annotation in a [`PlotSpec`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum AnnotationSpec {
+    /// Fixed X span, such as a maintenance window.
+    Region {
+        /// Start of the span.
+        x_min: f64,
+        /// End of the span.
+        x_max: f64,
+        /// Fill color as a hex string.
+        color: String,
+    },
+    /// Weekends on a time X axis, see [`ShadingProvider::Weekends`].
+    Weekends {
+        /// Offset of local time from UTC in seconds.
+        #[cfg_attr(feature = "serde", serde(default))]
+        utc_offset_secs: i32,
+        /// Fill color as a hex string; a translucent gray when unset.
+        #[cfg_attr(feature = "serde", serde(default))]
+        color: Option<String>,
+    },
+}
+
+impl AnnotationSpec {
+    fn provider(&self) -> Result<ShadingProvider, SpecError> {
+        match self {
+            AnnotationSpec::Region {
+                x_min,
+                x_max,
+                color,
+            } => Ok(ShadingProvider::Regions(vec![ShadedRegion::new(
+                Range::new(*x_min, *x_max),
+                parse_color(color, "region")?,
+            )])),
+            AnnotationSpec::Weekends {
+                utc_offset_secs,
+                color,
+            } => {
+                let mut provider = ShadingProvider::weekends();
+                if let ShadingProvider::Weekends {
+                    utc_offset_secs: offset,
+                    color: fill,
+                } = &mut provider
+                {
+                    *offset = *utc_offset_secs;
+                    if let Some(hex) = color {
+                        *fill = parse_color(hex, "weekends")?;
+                    }
+                }
+                Ok(provider)
+            }
+        }
+    }
+}
+
+fn parse_color(hex: &str, owner: &str) -> Result<Color, SpecError> {
+    Color::from_hex(hex).ok_or_else(|| SpecError::InvalidColor {
+        owner: owner.to_string(),
+        value: hex.to_string(),
+    })
+}
+
+/// Error returned when parsing or building a [`PlotSpec`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecError {
+    /// The spec text could not be parsed.
+    Parse(String),
+    /// A color string is not a valid hex color.
+    InvalidColor {
+        /// Series name or annotation type the color belongs to.
+        owner: String,
+        /// Offending color string.
+        value: String,
+    },
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::Parse(message) => write!(f, "invalid plot spec: {message}"),
+            SpecError::InvalidColor { owner, value } => {
+                write!(f, "invalid color {value:?} for {owner}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_applies_series_styling_and_annotations() {
+        let spec = PlotSpec {
+            theme: ThemeSpec::Light,
+            view: View::FollowLastN { points: 500 },
+            x_axis: AxisSpec {
+                title: Some("Time".to_string()),
+                time: true,
+                ..AxisSpec::default()
+            },
+            series: vec![SeriesSpec {
+                name: "temp".to_string(),
+                kind: SeriesKindSpec::Scatter,
+                color: Some("#ff0000".to_string()),
+                marker_size: Some(6.0),
+                hidden: true,
+                ..SeriesSpec::default()
+            }],
+            annotations: vec![AnnotationSpec::Region {
+                x_min: 10.0,
+                x_max: 20.0,
+                color: "#00ff0040".to_string(),
+            }],
+            ..PlotSpec::default()
+        };
+        let plot = spec.build().expect("valid spec");
+        assert_eq!(plot.theme(), &Theme::light());
+        assert_eq!(plot.view(), View::FollowLastN { points: 500 });
+        assert_eq!(plot.x_axis().title(), Some("Time"));
+        assert!(plot.x_axis().time_ticks().is_some());
+        assert_eq!(plot.shading().len(), 1);
+        let series = &plot.series()[0];
+        assert_eq!(series.name(), "temp");
+        assert!(!series.is_visible());
+        let SeriesKind::Scatter(style) = series.kind() else {
+            panic!("expected scatter series");
+        };
+        assert_eq!(style.color, Color::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(style.size, 6.0);
+    }
+
+    #[test]
+    fn build_rejects_invalid_color() {
+        let spec = PlotSpec {
+            series: vec![SeriesSpec {
+                name: "bad".to_string(),
+                color: Some("blue".to_string()),
+                ..SeriesSpec::default()
+            }],
+            ..PlotSpec::default()
+        };
+        assert_eq!(
+            spec.build().unwrap_err(),
+            SpecError::InvalidColor {
+                owner: "bad".to_string(),
+                value: "blue".to_string(),
+            }
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_spec_parses() {
+        let spec = PlotSpec::from_toml(
+            r##"
+            theme = "light"
+
+            [y_axis]
+            title = "Voltage"
+            units = "V"
+
+            [[series]]
+            name = "ch1"
+            color = "#2196f3"
+            width = 2.0
+
+            [[annotations]]
+            type = "weekends"
+            "##,
+        )
+        .expect("valid toml");
+        assert_eq!(spec.theme, ThemeSpec::Light);
+        assert_eq!(spec.y_axis.units.as_deref(), Some("V"));
+        assert_eq!(spec.series[0].width, Some(2.0));
+        assert!(spec.build().is_ok());
+    }
+}
@@ -1,8 +1,22 @@
 //! Style and theming configuration.
 //!
 //! Themes describe plot-level colors (background, grid, axes, overlays).
+//! Export styles restyle rendered frames for print without touching the
+//! on-screen theme.
+//! Backdrops describe optional content painted behind the data region.
+//! Shading providers mark X spans such as weekends or maintenance windows.
 
-use crate::render::Color;
+use std::sync::Arc;
+
+use crate::axis::days_from_civil;
+use crate::render::{Color, RasterImage, RenderCommand, RenderList, RenderRole};
+use crate::view::{Range, Viewport};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+/// Weekends listed before a weekend provider gives up when zoomed out.
+const MAX_WEEKENDS: f64 = 2_000.0;
+/// Shifts listed before a shift provider gives up when zoomed out.
+const MAX_SHIFTS: f64 = 2_000.0;
 
 /// Visual theme for plot-level elements such as axes, grid, and overlays.
 ///
@@ -33,6 +47,8 @@ pub struct Theme {
     pub legend_bg: Color,
     /// Legend border color.
     pub legend_border: Color,
+    /// Data warning badge color.
+    pub warning: Color,
 }
 
 impl Theme {
@@ -56,6 +72,7 @@ impl Theme {
             selection_border: Color::new(0.1, 0.4, 0.9, 0.9),
             legend_bg: Color::new(1.0, 1.0, 1.0, 0.85),
             legend_border: Color::new(0.2, 0.2, 0.2, 0.6),
+            warning: Color::new(0.85, 0.5, 0.0, 1.0),
         }
     }
 
@@ -74,6 +91,7 @@ impl Theme {
             selection_border: Color::new(0.3, 0.6, 1.0, 0.9),
             legend_bg: Color::new(0.12, 0.12, 0.13, 0.9),
             legend_border: Color::new(0.5, 0.5, 0.5, 0.7),
+            warning: Color::new(1.0, 0.7, 0.2, 1.0),
         }
     }
 }
@@ -83,3 +101,629 @@ impl Default for Theme {
         Self::dark()
     }
 }
+
+impl Theme {
+    /// Create a print-friendly palette: white background, black axes and
+    /// faint grid lines.
+    pub fn print() -> Self {
+        Self {
+            background: Color::WHITE,
+            axis: Color::BLACK,
+            grid_major: Color::new(0.82, 0.82, 0.82, 1.0),
+            grid_minor: Color::new(0.92, 0.92, 0.92, 1.0),
+            hover_bg: Color::WHITE,
+            hover_border: Color::BLACK,
+            pin_bg: Color::WHITE,
+            pin_border: Color::BLACK,
+            selection_fill: Color::new(0.0, 0.0, 0.0, 0.08),
+            selection_border: Color::BLACK,
+            legend_bg: Color::WHITE,
+            legend_border: Color::BLACK,
+            warning: Color::new(0.75, 0.35, 0.0, 1.0),
+        }
+    }
+
+    fn colors(&self) -> [Color; 13] {
+        [
+            self.background,
+            self.axis,
+            self.grid_major,
+            self.grid_minor,
+            self.hover_bg,
+            self.hover_border,
+            self.pin_bg,
+            self.pin_border,
+            self.selection_fill,
+            self.selection_border,
+            self.legend_bg,
+            self.legend_border,
+            self.warning,
+        ]
+    }
+}
+
+/// Styling overrides applied to exported frames.
+///
+/// The style is applied to a finished [`RenderList`], so the plot's theme
+/// and series styles stay as they are on screen. Outside of
+/// [`RenderRole::Series`] runs, colors of the on-screen theme are swapped
+/// for the matching colors of `theme`, including translucent variants. Line, stroke and
+/// marker sizes are multiplied by `line_scale`, and text sizes by
+/// `font_scale`. Series colors are kept even when they equal a theme color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportStyle {
+    /// Theme whose colors replace the on-screen theme colors.
+    pub theme: Theme,
+    /// Multiplier for line and stroke widths and marker sizes.
+    pub line_scale: f32,
+    /// Multiplier for text sizes.
+    pub font_scale: f32,
+}
+
+impl ExportStyle {
+    /// Print-friendly style with [`Theme::print`], lines 1.5x as thick and
+    /// text 1.25x as large.
+    pub fn print() -> Self {
+        Self {
+            theme: Theme::print(),
+            line_scale: 1.5,
+            font_scale: 1.25,
+        }
+    }
+
+    /// Restyle `list`, which was drawn with the `screen` theme, for export.
+    pub fn apply(&self, list: &RenderList, screen: &Theme) -> RenderList {
+        let pairs: Vec<(Color, Color)> = screen
+            .colors()
+            .into_iter()
+            .zip(self.theme.colors())
+            .collect();
+        let mut role = RenderRole::default();
+        let recolor = |role: RenderRole, color: Color| {
+            if role == RenderRole::Series {
+                return color;
+            }
+            pairs
+                .iter()
+                .find(|(from, _)| (from.r, from.g, from.b) == (color.r, color.g, color.b))
+                .map_or(color, |(from, to)| {
+                    let alpha = if from.a > 0.0 { color.a / from.a } else { 1.0 };
+                    Color {
+                        a: (to.a * alpha).clamp(0.0, 1.0),
+                        ..*to
+                    }
+                })
+        };
+        let mut out = RenderList::new();
+        for command in list.commands() {
+            let mut command = command.clone();
+            match &mut command {
+                RenderCommand::Role(next) => role = *next,
+                RenderCommand::LineSegments { style, .. } => {
+                    style.color = recolor(role, style.color);
+                    style.width *= self.line_scale;
+                }
+                RenderCommand::Points { style, .. } => {
+                    style.color = recolor(role, style.color);
+                    style.size *= self.line_scale;
+                }
+                RenderCommand::Rect { style, .. } => {
+                    style.fill = recolor(role, style.fill);
+                    style.stroke = recolor(role, style.stroke);
+                    style.stroke_width *= self.line_scale;
+                }
+                RenderCommand::ErrorBars { style, .. } => {
+                    style.color = recolor(role, style.color);
+                    style.width *= self.line_scale;
+                }
+                RenderCommand::FillBetween { color, .. } => *color = recolor(role, *color),
+                RenderCommand::Text { style, .. } => {
+                    style.color = recolor(role, style.color);
+                    style.size *= self.font_scale;
+                }
+                _ => {}
+            }
+            out.push(command);
+        }
+        out
+    }
+}
+
+impl Default for ExportStyle {
+    fn default() -> Self {
+        Self::print()
+    }
+}
+
+/// Content painted behind the data region of a plot.
+///
+/// The backdrop is placed in data space, so it pans and zooms together with
+/// the series (for example a track map or floor plan under position data).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backdrop {
+    /// Data-space rectangle covered by the backdrop.
+    pub extent: Viewport,
+    /// Backdrop content.
+    pub fill: BackdropFill,
+}
+
+impl Backdrop {
+    /// Create an image backdrop stretched over a data-space extent.
+    ///
+    /// Image row 0 is drawn at the top (`extent.y.max`).
+    pub fn image(extent: Viewport, image: RasterImage) -> Self {
+        Self {
+            extent,
+            fill: BackdropFill::Image(image),
+        }
+    }
+
+    /// Create a two-stop linear gradient over a data-space extent.
+    pub fn gradient(
+        extent: Viewport,
+        start: Color,
+        end: Color,
+        direction: GradientDirection,
+    ) -> Self {
+        Self {
+            extent,
+            fill: BackdropFill::Gradient {
+                start,
+                end,
+                direction,
+            },
+        }
+    }
+}
+
+/// Backdrop content variants.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackdropFill {
+    /// Bitmap stretched over the extent.
+    Image(RasterImage),
+    /// Two-stop linear gradient.
+    Gradient {
+        /// Color at the left (horizontal) or bottom (vertical) edge.
+        start: Color,
+        /// Color at the right (horizontal) or top (vertical) edge.
+        end: Color,
+        /// Gradient direction.
+        direction: GradientDirection,
+    },
+}
+
+/// X span shaded behind the data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShadedRegion {
+    /// Shaded X range.
+    pub x: Range,
+    /// Fill color, usually translucent.
+    pub color: Color,
+}
+
+impl ShadedRegion {
+    /// Create a shaded region.
+    pub fn new(x: Range, color: Color) -> Self {
+        Self { x, color }
+    }
+}
+
+/// Source of [`ShadedRegion`]s for the visible X range.
+///
+/// Providers are queried every frame with the visible X range, so they can
+/// generate repeating regions such as shifts without storing each one.
+///
+/// The calendar providers ([`Weekends`](Self::Weekends),
+/// [`Shifts`](Self::Shifts) and [`Holidays`](Self::Holidays)) expect an X
+/// axis holding Unix timestamps in seconds. They place days in local time
+/// with a fixed UTC offset, so they do not follow daylight saving changes;
+/// use [`ShadingProvider::custom`] with a time zone library for that.
+#[derive(Clone)]
+pub enum ShadingProvider {
+    /// Weekends, from Saturday to Monday midnight local time.
+    Weekends {
+        /// Offset of local time from UTC in seconds.
+        utc_offset_secs: i32,
+        /// Fill color.
+        color: Color,
+    },
+    /// A shift repeating every day, such as a night shift.
+    Shifts {
+        /// Start of the shift in seconds after local midnight.
+        start_secs: u32,
+        /// Length of the shift in seconds, up to a day. Shifts may run past
+        /// midnight.
+        duration_secs: u32,
+        /// Offset of local time from UTC in seconds.
+        utc_offset_secs: i32,
+        /// Fill color.
+        color: Color,
+    },
+    /// Whole days, such as public holidays, from local midnight to the next.
+    Holidays {
+        /// Days since 1970-01-01.
+        days: Vec<i64>,
+        /// Offset of local time from UTC in seconds.
+        utc_offset_secs: i32,
+        /// Fill color.
+        color: Color,
+    },
+    /// Fixed list of regions, such as maintenance windows.
+    Regions(Vec<ShadedRegion>),
+    /// Custom provider callback.
+    Custom(Arc<dyn Fn(Range) -> Vec<ShadedRegion> + Send + Sync>),
+}
+
+impl ShadingProvider {
+    /// Shade weekends in UTC with a translucent gray.
+    pub fn weekends() -> Self {
+        Self::Weekends {
+            utc_offset_secs: 0,
+            color: Color::new(0.5, 0.5, 0.5, 0.12),
+        }
+    }
+
+    /// Shade a daily shift in UTC, starting `start_secs` after midnight and
+    /// lasting `duration_secs`, with a translucent blue.
+    pub fn shifts(start_secs: u32, duration_secs: u32) -> Self {
+        Self::Shifts {
+            start_secs,
+            duration_secs,
+            utc_offset_secs: 0,
+            color: Color::new(0.3, 0.5, 0.9, 0.12),
+        }
+    }
+
+    /// Shade `(year, month, day)` dates in UTC with a translucent orange.
+    ///
+    /// Dates with a month outside `1..=12` or a day outside `1..=31` are
+    /// skipped.
+    pub fn holidays(dates: impl IntoIterator<Item = (i64, u32, u32)>) -> Self {
+        let days = dates
+            .into_iter()
+            .filter(|(_, month, day)| (1..=12).contains(month) && (1..=31).contains(day))
+            .map(|(year, month, day)| days_from_civil(year, month, day))
+            .collect();
+        Self::Holidays {
+            days,
+            utc_offset_secs: 0,
+            color: Color::new(0.9, 0.6, 0.2, 0.15),
+        }
+    }
+
+    /// Wrap a callback that returns regions for the visible X range.
+    pub fn custom(provider: impl Fn(Range) -> Vec<ShadedRegion> + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(provider))
+    }
+
+    /// Regions overlapping `x_range`.
+    pub fn regions(&self, x_range: Range) -> Vec<ShadedRegion> {
+        match self {
+            Self::Weekends {
+                utc_offset_secs,
+                color,
+            } => weekends(x_range, *utc_offset_secs, *color),
+            Self::Shifts {
+                start_secs,
+                duration_secs,
+                utc_offset_secs,
+                color,
+            } => shifts(
+                x_range,
+                f64::from(*start_secs),
+                f64::from(*duration_secs).min(SECONDS_PER_DAY),
+                *utc_offset_secs,
+                *color,
+            ),
+            Self::Holidays {
+                days,
+                utc_offset_secs,
+                color,
+            } => days
+                .iter()
+                .map(|day| {
+                    let start = *day as f64 * SECONDS_PER_DAY - f64::from(*utc_offset_secs);
+                    ShadedRegion::new(Range::new(start, start + SECONDS_PER_DAY), *color)
+                })
+                .filter(|region| region.x.max > x_range.min && region.x.min < x_range.max)
+                .collect(),
+            Self::Regions(regions) => regions
+                .iter()
+                .filter(|region| region.x.max >= x_range.min && region.x.min <= x_range.max)
+                .cloned()
+                .collect(),
+            Self::Custom(provider) => provider(x_range),
+        }
+    }
+}
+
+impl std::fmt::Debug for ShadingProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Weekends {
+                utc_offset_secs,
+                color,
+            } => f
+                .debug_struct("ShadingProvider::Weekends")
+                .field("utc_offset_secs", utc_offset_secs)
+                .field("color", color)
+                .finish(),
+            Self::Shifts {
+                start_secs,
+                duration_secs,
+                utc_offset_secs,
+                color,
+            } => f
+                .debug_struct("ShadingProvider::Shifts")
+                .field("start_secs", start_secs)
+                .field("duration_secs", duration_secs)
+                .field("utc_offset_secs", utc_offset_secs)
+                .field("color", color)
+                .finish(),
+            Self::Holidays {
+                days,
+                utc_offset_secs,
+                color,
+            } => f
+                .debug_struct("ShadingProvider::Holidays")
+                .field("days", days)
+                .field("utc_offset_secs", utc_offset_secs)
+                .field("color", color)
+                .finish(),
+            Self::Regions(regions) => f
+                .debug_tuple("ShadingProvider::Regions")
+                .field(regions)
+                .finish(),
+            Self::Custom(_) => write!(f, "ShadingProvider::Custom(..)"),
+        }
+    }
+}
+
+fn weekends(x_range: Range, utc_offset_secs: i32, color: Color) -> Vec<ShadedRegion> {
+    let week = 7.0 * SECONDS_PER_DAY;
+    if !x_range.is_finite() || x_range.span() / week > MAX_WEEKENDS {
+        return Vec::new();
+    }
+    // The Unix epoch was a Thursday, so the first Saturday starts on day 2.
+    let origin = 2.0 * SECONDS_PER_DAY - f64::from(utc_offset_secs);
+    let first = ((x_range.min - origin) / week).floor() as i64;
+    let last = ((x_range.max - origin) / week).floor() as i64;
+    (first..=last)
+        .map(|index| {
+            let start = origin + index as f64 * week;
+            ShadedRegion::new(Range::new(start, start + 2.0 * SECONDS_PER_DAY), color)
+        })
+        .filter(|region| region.x.max > x_range.min)
+        .collect()
+}
+
+fn shifts(
+    x_range: Range,
+    start: f64,
+    duration: f64,
+    utc_offset_secs: i32,
+    color: Color,
+) -> Vec<ShadedRegion> {
+    if !x_range.is_finite() || duration <= 0.0 || x_range.span() / SECONDS_PER_DAY > MAX_SHIFTS {
+        return Vec::new();
+    }
+    let origin = start - f64::from(utc_offset_secs);
+    // Start a day early to catch a shift running past midnight into view.
+    let first = ((x_range.min - origin) / SECONDS_PER_DAY).floor() as i64 - 1;
+    let last = ((x_range.max - origin) / SECONDS_PER_DAY).floor() as i64;
+    (first..=last)
+        .map(|index| {
+            let start = origin + index as f64 * SECONDS_PER_DAY;
+            ShadedRegion::new(Range::new(start, start + duration), color)
+        })
+        .filter(|region| region.x.max > x_range.min && region.x.min < x_range.max)
+        .collect()
+}
+
+/// Direction of a backdrop gradient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// Interpolate along the X axis.
+    Horizontal,
+    /// Interpolate along the Y axis.
+    Vertical,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weekend_provider_covers_saturday_and_sunday() {
+        // Tuesday 2023-11-14 22:13:20 UTC through the following Tuesday.
+        let range = Range::new(1_700_000_000.0, 1_700_000_000.0 + 7.0 * SECONDS_PER_DAY);
+        let regions = ShadingProvider::weekends().regions(range);
+        assert_eq!(regions.len(), 1);
+        // Saturday 2023-11-18 00:00 UTC to Monday 2023-11-20 00:00 UTC.
+        assert_eq!(regions[0].x, Range::new(1_700_265_600.0, 1_700_438_400.0));
+
+        let window = ShadedRegion::new(Range::new(10.0, 20.0), Color::WHITE);
+        let fixed = ShadingProvider::Regions(vec![window.clone()]);
+        assert!(fixed.regions(Range::new(0.0, 5.0)).is_empty());
+        assert_eq!(fixed.regions(Range::new(15.0, 30.0)), vec![window]);
+    }
+
+    #[test]
+    fn shift_and_holiday_providers_use_local_days() {
+        // 2023-11-14 00:00 UTC, a Tuesday.
+        let midnight = 1_699_920_000.0;
+        let hour = 3600.0;
+        let night = ShadingProvider::Shifts {
+            start_secs: 22 * 3600,
+            duration_secs: 8 * 3600,
+            utc_offset_secs: 3600,
+            color: Color::WHITE,
+        };
+        // 22:00 local at UTC+1 is 21:00 UTC; the shift from the day before
+        // is still running at local midnight.
+        let regions = night.regions(Range::new(midnight, midnight + SECONDS_PER_DAY));
+        let spans: Vec<Range> = regions.iter().map(|region| region.x).collect();
+        assert_eq!(
+            spans,
+            vec![
+                Range::new(midnight - 3.0 * hour, midnight + 5.0 * hour),
+                Range::new(midnight + 21.0 * hour, midnight + 29.0 * hour),
+            ]
+        );
+
+        let holidays = ShadingProvider::holidays([(2023, 11, 14), (2023, 12, 25), (2023, 13, 1)]);
+        let ShadingProvider::Holidays { days, .. } = &holidays else {
+            panic!("expected a holiday provider");
+        };
+        assert_eq!(days.len(), 2);
+        let regions = holidays.regions(Range::new(midnight - hour, midnight + hour));
+        assert_eq!(regions.len(), 1);
+        assert_eq!(
+            regions[0].x,
+            Range::new(midnight, midnight + SECONDS_PER_DAY)
+        );
+    }
+
+    #[test]
+    fn export_style_swaps_theme_colors_only() {
+        use crate::geom::{ScreenPoint, ScreenRect};
+        use crate::render::{LineStyle, RectStyle, TextStyle};
+
+        let screen = Theme::dark();
+        let series = Color::from_rgb8(0x4e, 0x79, 0xa7);
+        let mut list = RenderList::new();
+        let rect = ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(10.0, 10.0));
+        list.push(RenderCommand::Rect {
+            rect,
+            style: RectStyle {
+                fill: screen.background,
+                stroke: screen.axis,
+                stroke_width: 1.0,
+            },
+        });
+        list.push(RenderCommand::Role(RenderRole::Series));
+        list.push(RenderCommand::LineSegments {
+            segments: Vec::new(),
+            style: LineStyle {
+                color: series,
+                width: 2.0,
+                dash: None,
+            },
+        });
+        list.push(RenderCommand::Role(RenderRole::Axis));
+        list.push(RenderCommand::Text {
+            position: ScreenPoint::new(0.0, 0.0),
+            text: "1.0".to_string(),
+            style: TextStyle {
+                color: screen.axis,
+                size: 12.0,
+            },
+        });
+
+        let exported = ExportStyle::print().apply(&list, &screen);
+        let commands = exported.commands();
+        assert!(matches!(
+            &commands[0],
+            RenderCommand::Rect { style, .. }
+                if style.fill == Color::WHITE && style.stroke == Color::BLACK
+                    && style.stroke_width == 1.5
+        ));
+        assert!(matches!(
+            &commands[2],
+            RenderCommand::LineSegments { style, .. } if style.color == series && style.width == 3.0
+        ));
+        assert!(matches!(
+            &commands[4],
+            RenderCommand::Text { style, .. } if style.color == Color::BLACK && style.size == 15.0
+        ));
+        assert_eq!(list.commands().len(), 5);
+    }
+
+    #[test]
+    fn export_style_restyles_frame_by_role() {
+        use crate::frame::{PlotUiState, PlotViewConfig, build_frame};
+        use crate::geom::{ScreenPoint, ScreenRect};
+        use crate::plot::Plot;
+        use crate::render::{BitmapFont, LineStyle, MarkerShape, MarkerStyle};
+        use crate::series::{Series, SeriesKind};
+
+        let screen = Theme::dark();
+        let line = Series::from_iter_y(
+            "line",
+            [0.0, 1.0, 0.5],
+            SeriesKind::Line(LineStyle {
+                color: screen.axis,
+                width: 2.0,
+                dash: None,
+            }),
+        );
+        let markers = Series::from_iter_y(
+            "markers",
+            [0.5, 0.0, 1.0],
+            SeriesKind::Scatter(MarkerStyle {
+                color: screen.axis,
+                size: 4.0,
+                shape: MarkerShape::Circle,
+            }),
+        );
+        let mut plot = Plot::builder()
+            .theme(screen.clone())
+            .series(&line)
+            .series(&markers)
+            .build();
+        let frame = build_frame(
+            &mut plot,
+            &mut PlotUiState::default(),
+            &PlotViewConfig::default(),
+            ScreenRect::new(ScreenPoint::new(0.0, 0.0), ScreenPoint::new(240.0, 160.0)),
+            &BitmapFont,
+            None,
+        );
+        let print = ExportStyle::print();
+        let exported = print.apply(&frame.render, &screen);
+
+        let mut role = RenderRole::default();
+        let (mut series_lines, mut series_markers, mut axis_lines, mut axis_text) = (0, 0, 0, 0);
+        for (before, after) in frame.render.commands().iter().zip(exported.commands()) {
+            match (before, after) {
+                (_, RenderCommand::Role(next)) => role = *next,
+                (
+                    RenderCommand::LineSegments { style: old, .. },
+                    RenderCommand::LineSegments { style: new, .. },
+                ) => {
+                    assert_eq!(new.width, old.width * 1.5);
+                    if role == RenderRole::Series {
+                        assert_eq!(new.color, screen.axis);
+                        series_lines += 1;
+                    } else if role == RenderRole::Axis {
+                        assert_eq!(new.color, print.theme.axis);
+                        axis_lines += 1;
+                    }
+                }
+                (
+                    RenderCommand::Points { style: old, .. },
+                    RenderCommand::Points { style: new, .. },
+                ) => {
+                    assert_eq!(new.size, old.size * 1.5);
+                    if role == RenderRole::Series {
+                        assert_eq!(new.color, screen.axis);
+                        series_markers += 1;
+                    }
+                }
+                (
+                    RenderCommand::Text { style: old, .. },
+                    RenderCommand::Text { style: new, .. },
+                ) => {
+                    assert_eq!(new.size, old.size * 1.25);
+                    if role == RenderRole::Axis {
+                        assert_eq!(new.color, print.theme.axis);
+                        axis_text += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        assert!(series_lines > 0 && series_markers > 0);
+        assert!(axis_lines > 0 && axis_text > 0);
+    }
+}
@@ -35,6 +35,7 @@ impl Transform {
     }
 
     /// Access the screen rectangle.
+    #[cfg(feature = "gpui")]
     pub(crate) fn screen(&self) -> ScreenRect {
         self.screen
     }
@@ -62,7 +63,7 @@ impl Transform {
 }
 
 fn map_range(range: Range) -> Option<Range> {
-    if !range.is_finite() {
+    if !range.is_finite() || !range.span().is_finite() {
         return None;
     }
     Some(range)
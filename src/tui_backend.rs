@@ -0,0 +1,101 @@
+//! Terminal backend for gpui_liveplot built on ratatui.
+//!
+//! [`TuiPlot`] is a ratatui widget that draws a coarse braille approximation
+//! of a [`Plot`] using the same viewport-aware decimation as the GPUI backend.
+//! It is meant for quick inspection over SSH on headless devices.
+//!
+//! Enable with the `tui` feature.
+//!
+//! ```rust,no_run
+//! use gpui_liveplot::{Plot, TuiPlot};
+//!
+//! fn draw(frame: &mut ratatui::Frame<'_>, plot: &Plot) {
+//!     frame.render_widget(TuiPlot::new(plot), frame.area());
+//! }
+//! ```
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color as TuiColor, Style};
+use ratatui::widgets::Widget;
+
+use crate::plot::Plot;
+use crate::render::{BrailleCanvas, Color};
+
+/// Ratatui widget that renders a plot as braille dots.
+///
+/// The widget draws the plot's current viewport, or the full data bounds when
+/// no viewport has been computed yet. Axis range labels are drawn in the
+/// corners unless disabled.
+#[derive(Debug, Clone, Copy)]
+pub struct TuiPlot<'a> {
+    plot: &'a Plot,
+    show_ranges: bool,
+}
+
+impl<'a> TuiPlot<'a> {
+    /// Create a widget for the given plot.
+    pub fn new(plot: &'a Plot) -> Self {
+        Self {
+            plot,
+            show_ranges: true,
+        }
+    }
+
+    /// Show or hide the axis range labels.
+    pub fn show_ranges(mut self, show: bool) -> Self {
+        self.show_ranges = show;
+        self
+    }
+}
+
+impl Widget for TuiPlot<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let Some(viewport) = self.plot.viewport().or_else(|| self.plot.data_bounds()) else {
+            return;
+        };
+
+        let mut canvas = BrailleCanvas::new(area.width as usize, area.height as usize);
+        let (width, height) = canvas.dot_size();
+        canvas.draw(&self.plot.render_viewport(viewport, width, height));
+        for row in 0..area.height {
+            for column in 0..area.width {
+                let Some((ch, Some(color))) = canvas.cell(column as usize, row as usize) else {
+                    continue;
+                };
+                if let Some(cell) = buf.cell_mut((area.x + column, area.y + row)) {
+                    cell.set_char(ch).set_fg(to_tui_color(color));
+                }
+            }
+        }
+
+        if !self.show_ranges {
+            return;
+        }
+        let style = Style::default().fg(to_tui_color(self.plot.theme().axis));
+        let x_axis = self.plot.x_axis();
+        let y_axis = self.plot.y_axis();
+        let bottom = area.y + area.height - 1;
+        buf.set_string(area.x, area.y, y_axis.format_value(viewport.y.max), style);
+        if area.height > 1 {
+            buf.set_string(area.x, bottom, y_axis.format_value(viewport.y.min), style);
+        }
+        let x_label = format!(
+            "{} .. {}",
+            x_axis.format_value(viewport.x.min),
+            x_axis.format_value(viewport.x.max)
+        );
+        let x_width = x_label.chars().count() as u16;
+        if x_width < area.width {
+            buf.set_string(area.x + area.width - x_width, bottom, x_label, style);
+        }
+    }
+}
+
+fn to_tui_color(color: Color) -> TuiColor {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    TuiColor::Rgb(channel(color.r), channel(color.g), channel(color.b))
+}
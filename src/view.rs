@@ -8,6 +8,7 @@
 /// `Range` is used for axis limits, data bounds, and viewport calculations. The
 /// constructor automatically swaps bounds to maintain `min <= max`.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Range {
     /// Minimum value.
     pub min: f64,
@@ -103,6 +104,7 @@ impl Range {
 /// interactions. Any explicit interaction typically switches the plot to
 /// [`View::Manual`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum View {
     /// Automatically show the full data range (default).
     AutoAll {
@@ -134,11 +136,47 @@ impl Default for View {
     }
 }
 
+/// Extra Y space added around the data in [`View::FollowLastNXY`].
+///
+/// Headroom is separate from the auto-fit padding so live traces can keep
+/// clear of the plot edges without changing how [`View::AutoAll`] fits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Headroom {
+    /// Fraction of the visible Y span added above and below the data.
+    Fraction(f64),
+    /// Fixed amount in data units added above and below the data.
+    Absolute(f64),
+}
+
+impl Headroom {
+    /// Expand a Y range by this headroom.
+    ///
+    /// Negative or non-finite amounts leave the range unchanged.
+    pub fn apply(&self, range: Range) -> Range {
+        let amount = match *self {
+            Headroom::Fraction(frac) => range.span().abs() * frac,
+            Headroom::Absolute(amount) => amount,
+        };
+        if !amount.is_finite() || amount <= 0.0 {
+            return range;
+        }
+        Range::new(range.min - amount, range.max + amount)
+    }
+}
+
+impl Default for Headroom {
+    fn default() -> Self {
+        Self::Fraction(0.0)
+    }
+}
+
 /// Visible data ranges on both axes.
 ///
 /// A `Viewport` is the canonical input to coordinate transforms and decimation
 /// decisions.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Viewport {
     /// X axis range.
     pub x: Range,
@@ -157,6 +195,16 @@ impl Viewport {
         self.x.is_valid() && self.y.is_valid()
     }
 
+    /// Check whether both axes have finite bounds and finite spans.
+    ///
+    /// Ranges near `f64::MAX` can have finite bounds but an infinite span,
+    /// which no transform can map to the screen.
+    pub fn is_finite(&self) -> bool {
+        [self.x, self.y]
+            .iter()
+            .all(|range| range.is_finite() && range.span().is_finite())
+    }
+
     /// Apply padding to both axes.
     pub fn padded(&self, frac: f64, min_padding: f64) -> Self {
         Self {
@@ -0,0 +1,279 @@
+//! Standalone wgpu backend for gpui_liveplot.
+//!
+//! [`WgpuRenderer`] draws a [`RenderList`] into any wgpu texture view, such as
+//! an offscreen texture or a window surface, without GPUI. Lines, markers,
+//! rectangles and fills are drawn from a [`Mesh`], honoring additive
+//! blending; text and image commands are skipped.
+//!
+//! Enable with the `wgpu` feature.
+
+use wgpu::util::DeviceExt;
+
+use crate::render::{BlendMode, Color, Mesh, RenderList};
+
+const SHADER: &str = r#"
+struct VertexOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@location(0) position: vec2<f32>, @location(1) color: vec4<f32>) -> VertexOut {
+    var out: VertexOut;
+    out.position = vec4<f32>(position, 0.0, 1.0);
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOut) -> @location(0) vec4<f32> {
+    return in.color;
+}
+"#;
+
+const VERTEX_ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+    wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x4];
+const VERTEX_STRIDE: u64 = 6 * 4;
+const ADDITIVE_BLENDING: wgpu::BlendState = wgpu::BlendState {
+    color: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::SrcAlpha,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    },
+    alpha: wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    },
+};
+
+/// Target surface for a render pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WgpuTarget {
+    /// Target size in physical pixels.
+    pub width: u32,
+    /// Target size in physical pixels.
+    pub height: u32,
+    /// Physical pixels per logical pixel of the render list.
+    pub scale_factor: f32,
+}
+
+/// Renders plot command lists with wgpu.
+#[derive(Debug)]
+pub struct WgpuRenderer {
+    pipeline: wgpu::RenderPipeline,
+    additive_pipeline: wgpu::RenderPipeline,
+    format: wgpu::TextureFormat,
+}
+
+impl WgpuRenderer {
+    /// Create a renderer for targets of the given texture format.
+    ///
+    /// Plot colors are sRGB-encoded. They are converted to linear values for
+    /// sRGB target formats and passed through unchanged otherwise, so both
+    /// kinds of target match the GPUI backend.
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("gpui_liveplot shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("gpui_liveplot layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+        let create_pipeline = |blend: wgpu::BlendState| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("gpui_liveplot pipeline"),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    compilation_options: Default::default(),
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: VERTEX_STRIDE,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &VERTEX_ATTRIBUTES,
+                    }],
+                },
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(blend),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                multiview: None,
+                cache: None,
+            })
+        };
+        Self {
+            pipeline: create_pipeline(wgpu::BlendState::ALPHA_BLENDING),
+            additive_pipeline: create_pipeline(ADDITIVE_BLENDING),
+            format,
+        }
+    }
+
+    /// Texture format this renderer was created for.
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// Record draw commands for `list` into `encoder`.
+    ///
+    /// The view is cleared to `clear` first when provided; otherwise the plot
+    /// is drawn over the existing contents.
+    pub fn encode(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        view: &wgpu::TextureView,
+        target: WgpuTarget,
+        list: &RenderList,
+        clear: Option<Color>,
+    ) {
+        let mesh = Mesh::from_render_list(list);
+        let load = match clear.map(|color| self.target_color(color)) {
+            Some(color) => wgpu::LoadOp::Clear(wgpu::Color {
+                r: color.r as f64,
+                g: color.g as f64,
+                b: color.b as f64,
+                a: color.a as f64,
+            }),
+            None => wgpu::LoadOp::Load,
+        };
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("gpui_liveplot pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        if mesh.is_empty() || target.width == 0 || target.height == 0 {
+            return;
+        }
+
+        let vertices = vertex_bytes(&mesh, target, |color| self.target_color(color));
+        let indices: Vec<u8> = mesh
+            .indices()
+            .iter()
+            .flat_map(|index| index.to_ne_bytes())
+            .collect();
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("gpui_liveplot vertices"),
+            contents: &vertices,
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("gpui_liveplot indices"),
+            contents: &indices,
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        for batch in mesh.batches() {
+            let (x, y, width, height) = match batch.clip {
+                Some(clip) => {
+                    let scale = target.scale_factor;
+                    let min_x = (clip.min.x * scale).floor().clamp(0.0, target.width as f32);
+                    let min_y = (clip.min.y * scale)
+                        .floor()
+                        .clamp(0.0, target.height as f32);
+                    let max_x = (clip.max.x * scale).ceil().clamp(0.0, target.width as f32);
+                    let max_y = (clip.max.y * scale).ceil().clamp(0.0, target.height as f32);
+                    (
+                        min_x as u32,
+                        min_y as u32,
+                        (max_x - min_x) as u32,
+                        (max_y - min_y) as u32,
+                    )
+                }
+                None => (0, 0, target.width, target.height),
+            };
+            if width == 0 || height == 0 {
+                continue;
+            }
+            pass.set_pipeline(match batch.blend {
+                BlendMode::Normal => &self.pipeline,
+                BlendMode::Additive => &self.additive_pipeline,
+            });
+            pass.set_scissor_rect(x, y, width, height);
+            let end = batch.first_index + batch.index_count;
+            pass.draw_indexed(batch.first_index..end, 0, 0..1);
+        }
+    }
+
+    /// Convert a plot color to the encoding expected by the target format.
+    fn target_color(&self, color: Color) -> Color {
+        if self.format.is_srgb() {
+            color.to_linear()
+        } else {
+            color
+        }
+    }
+
+    /// Render `list` into a new texture and submit the work.
+    ///
+    /// The texture uses this renderer's format and can be copied out or
+    /// sampled, which is useful for headless services.
+    pub fn render_to_texture(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: WgpuTarget,
+        list: &RenderList,
+        clear: Option<Color>,
+    ) -> wgpu::Texture {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("gpui_liveplot texture"),
+            size: wgpu::Extent3d {
+                width: target.width.max(1),
+                height: target.height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("gpui_liveplot encoder"),
+        });
+        self.encode(device, &mut encoder, &view, target, list, clear);
+        queue.submit(std::iter::once(encoder.finish()));
+        texture
+    }
+}
+
+/// Pack mesh vertices as `[x, y, r, g, b, a]` in clip space.
+fn vertex_bytes(mesh: &Mesh, target: WgpuTarget, encode: impl Fn(Color) -> Color) -> Vec<u8> {
+    let width = target.width as f32 / target.scale_factor;
+    let height = target.height as f32 / target.scale_factor;
+    let mut bytes = Vec::with_capacity(mesh.vertices().len() * VERTEX_STRIDE as usize);
+    for vertex in mesh.vertices() {
+        let [x, y] = vertex.position.to_ndc(width, height);
+        for value in [x, y].into_iter().chain(encode(vertex.color).to_array()) {
+            bytes.extend_from_slice(&value.to_ne_bytes());
+        }
+    }
+    bytes
+}